@@ -1,4 +1,5 @@
 use clap::{Parser, Subcommand, ValueEnum};
+use serde::{Deserialize, Serialize};
 
 /// dead_symbols の出力スコープ。`--git/--diff/--diff-file` 指定時のみ意味を持つ。
 ///
@@ -6,12 +7,51 @@ use clap::{Parser, Subcommand, ValueEnum};
 /// - `touched-symbols`: 宣言行が今回の diff hunk と重なる dead だけを返す。`review --hook`
 ///   のデフォルトに採用し、stop hook が「changed file 内に元からあった dead」で毎回
 ///   ノイズを出す UX 問題を解消する (Issue: zod-inferred-types-pre-existing-dead)。
-#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum DeadScope {
     All,
     TouchedSymbols,
 }
 
+/// dead-code の `--scope`。どの可視性のシンボルを dead 判定の対象にするか。
+///
+/// - `exported`: 公開シンボルをワークスペース全体の参照で判定する (デフォルト、従来挙動)
+/// - `private`: 非公開の関数 / メソッド / 定数を、可視範囲 (ファイル / Rust モジュール /
+///   Go パッケージ) 内の参照で判定する
+/// - `all`: 両方
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DeadCodeScope {
+    Exported,
+    Private,
+    All,
+}
+
+/// dead-code の `--sort`。
+///
+/// - `location`: 検出順 (ファイル / 宣言行) のまま返す (デフォルト)
+/// - `age`: `--blame` の最終変更日時が古い順に並べる。blame を引けなかったシンボルは末尾
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DeadCodeSort {
+    Location,
+    Age,
+}
+
+/// affected-tests の `--runner`。指定するとテストランナーにそのまま渡せる
+/// フィルタ文字列 1 行を出力する (JSON ではなくプレーンテキスト)。
+///
+/// - `cargo`: `cargo test -- <filter...>` 用の空白区切りテスト関数名
+/// - `pytest`: `pytest -k "<expr>"` 用の `or` 連結式
+/// - `jest`: `jest --testPathPattern '<regex>'` 用のファイルパス正規表現
+/// - `go`: `go test -run '<regex>'` 用の `^(TestA|TestB)$`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum TestRunner {
+    Cargo,
+    Pytest,
+    Jest,
+    Go,
+}
+
 #[derive(Parser)]
 #[command(
     name = "astro-sight",
@@ -163,6 +203,37 @@ pub enum Commands {
         /// Glob pattern to filter files (e.g. "**/*.rs")
         #[arg(short, long)]
         glob: Option<String>,
+
+        /// Report searched files that contain parse errors (ERROR / MISSING nodes)
+        #[arg(long)]
+        parse_warnings: bool,
+    },
+
+    /// Fetch a symbol's full definition text (with leading docs and attributes) by name
+    Source {
+        /// Path to the source file
+        #[arg(short, long, required_unless_present = "dir")]
+        path: Option<String>,
+
+        /// Directory to search the workspace in (instead of a single file)
+        #[arg(short, long, conflicts_with = "path")]
+        dir: Option<String>,
+
+        /// Glob pattern to filter files when searching a directory (e.g. "**/*.rs")
+        #[arg(short, long, requires = "dir")]
+        glob: Option<String>,
+
+        /// Symbol name whose definition to return
+        #[arg(short, long)]
+        name: String,
+
+        /// Container (class / struct / trait / impl type) name to disambiguate same-named symbols
+        #[arg(long)]
+        container: Option<String>,
+
+        /// Maximum bytes of source returned per definition (longer definitions are truncated)
+        #[arg(long, default_value_t = crate::engine::source::DEFAULT_MAX_SOURCE_BYTES)]
+        max_bytes: usize,
     },
 
     /// Smart context: analyze diff impact
@@ -202,6 +273,11 @@ pub enum Commands {
         /// 例: --exclude-glob '**/openssl_*1.1.1*/**'
         #[arg(long = "exclude-glob", value_name = "PATTERN", num_args = 0..)]
         exclude_globs: Vec<String>,
+
+        /// Follow impacted callers through exported wrappers up to N levels
+        /// (reported as `transitive_callers`; 0 disables)
+        #[arg(long, value_name = "N", default_value_t = 0)]
+        transitive_depth: usize,
     },
 
     /// Detect unresolved change impacts (for stop hooks)
@@ -318,9 +394,10 @@ pub enum Commands {
         paths_file: Option<String>,
 
         /// Minimum confidence threshold (0.0 to 1.0), applied to the raw
-        /// `co_changes / denominator` ratio. Default: 0.3.
-        #[arg(short, long, default_value = "0.3")]
-        min_confidence: f64,
+        /// `co_changes / denominator` ratio. Default: 0.3 (or `cochange.min_confidence`
+        /// in the project / user config).
+        #[arg(short, long)]
+        min_confidence: Option<f64>,
 
         /// Minimum smoothed `score` required per pair (0.0 = disabled, the default).
         /// `score` is a shrinkage estimate used for ranking; gate on it only when you
@@ -331,8 +408,8 @@ pub enum Commands {
         min_score: f64,
 
         /// Minimum shared commit count required per pair (default: 2)
-        #[arg(long, default_value = "2")]
-        min_samples: usize,
+        #[arg(long)]
+        min_samples: Option<usize>,
 
         /// Skip co-change counting for commits touching more files than this
         /// threshold (default: 100; hard cap, the size weighting below
@@ -467,8 +544,8 @@ pub enum Commands {
         /// Minimum cochange confidence threshold (0.0 to 1.0). Default 0.3 to match
         /// blame-mode score semantics; use lower values to surface more
         /// `missing_cochanges` candidates, higher to be stricter.
-        #[arg(long, default_value = "0.3")]
-        min_confidence: f64,
+        #[arg(long)]
+        min_confidence: Option<f64>,
 
         /// Append triage hint for AI agent hooks
         #[arg(long)]
@@ -476,7 +553,11 @@ pub enum Commands {
 
         /// Framework preset を指定して dead_symbols からフレームワーク規約の
         /// エントリポイントを除外する。現在対応: "laravel" (database/migrations,
-        /// app/Http/Controllers, app/Http/Middleware, app/Providers 等)
+        /// app/Http/Controllers, app/Http/Middleware, app/Providers 等), "nextjs",
+        /// "rails" (db/migrate, config, routes.rb のルーティング先・コールバック等),
+        /// "spring" (@RestController ハンドラ, @Bean, @Scheduled, @Autowired 注入型等)。
+        /// それ以外の名前は `.astro-sight/presets/<name>.yaml` (またはユーザー設定
+        /// ディレクトリの `presets/`) のユーザー定義プリセットを使う
         #[arg(long)]
         framework: Option<String>,
 
@@ -493,6 +574,11 @@ pub enum Commands {
         #[arg(long = "exclude-glob", value_name = "PATTERN", num_args = 0..)]
         exclude_globs: Vec<String>,
 
+        /// Annotation or decorator name (without `@`) whose declarations count as framework
+        /// entry points in dead-code detection. Repeatable; added to the `spring` preset list.
+        #[arg(long = "entry-annotation", value_name = "NAME", num_args = 0..)]
+        entry_annotations: Vec<String>,
+
         /// dead_symbols のスコープ。`touched-symbols` は宣言行が diff hunk と重なる
         /// dead だけを返す。未指定時は `--hook` 有なら `touched-symbols`、無なら `all`。
         /// `dead-code --dir .` で全 dead を再確認するときは `--dead-scope all` を指定。
@@ -502,8 +588,21 @@ pub enum Commands {
         /// `pub const` / 非 mut `pub static` / `export const` の値 (initializer) のみ変更を
         /// 厳格に扱う。指定時は api.const_value を Stop hook の blocking 対象に昇格する。
         /// デフォルトでは値のみの変更はコンパイル互換性を壊さないため informational (非 blocking)。
-        #[arg(long = "strict-public-const-values")]
-        strict_public_const_values: bool,
+        #[arg(
+            long = "strict-public-const-values",
+            num_args = 0..=1,
+            require_equals = true,
+            default_missing_value = "true",
+            overrides_with = "no_strict_public_const_values"
+        )]
+        strict_public_const_values: Option<bool>,
+
+        /// Turn off `strict_public_const_values` enabled in `.astro-sight.toml` / config.toml
+        #[arg(
+            long = "no-strict-public-const-values",
+            overrides_with = "strict_public_const_values"
+        )]
+        no_strict_public_const_values: bool,
 
         /// `--hook` のとき、同一 diff で新規 export された (= `api_changes.added` に挙がる)
         /// シンボルも dead 警告に含める。`--hook` 既定では多段実装中の WIP ノイズ (consumer
@@ -511,8 +610,61 @@ pub enum Commands {
         /// dead から除外する。本フラグは `--hook` 経路にのみ作用し、`--hook` 無しの通常
         /// `review` JSON は従来どおり全 dead を返す (= レビュアーが api.added と dead の
         /// 両方を見て総合判断する想定)。Issue 2026-06-25-wip-dead-symbol-during-incremental-impl 対応。
-        #[arg(long = "include-wip-dead")]
-        include_wip_dead: bool,
+        #[arg(
+            long = "include-wip-dead",
+            num_args = 0..=1,
+            require_equals = true,
+            default_missing_value = "true",
+            overrides_with = "no_include_wip_dead"
+        )]
+        include_wip_dead: Option<bool>,
+
+        /// Turn off `include_wip_dead` enabled in `.astro-sight.toml` / config.toml
+        #[arg(long = "no-include-wip-dead", overrides_with = "include_wip_dead")]
+        no_include_wip_dead: bool,
+
+        /// Report added code that duplicates existing code (`duplicated_code`).
+        /// On by default, off with --hook unless enabled (it hashes every same-language file)
+        #[arg(
+            long = "dupes",
+            num_args = 0..=1,
+            require_equals = true,
+            default_missing_value = "true",
+            overrides_with = "no_dupes"
+        )]
+        dupes: Option<bool>,
+
+        /// Turn off duplicated-code detection (including `dupes` enabled in `.astro-sight.toml` / config.toml)
+        #[arg(long = "no-dupes", overrides_with = "dupes")]
+        no_dupes: bool,
+
+        /// With --hook, block (exit 1) when the review risk score reaches this value
+        #[arg(long, value_name = "SCORE", requires = "hook")]
+        risk_threshold: Option<f64>,
+
+        /// Follow impacted callers through exported wrappers up to N levels
+        /// (reported as `transitive_callers`, never blocking; 0 disables)
+        #[arg(long, value_name = "N", default_value_t = 0)]
+        transitive_depth: usize,
+
+        /// Review a commit range `A..B` on a temporary checkout of B instead of the working tree
+        #[arg(
+            long,
+            value_name = "A..B",
+            conflicts_with_all = ["diff", "diff_file", "git", "base", "staged", "hook"]
+        )]
+        range: Option<String>,
+
+        /// With --range, review each commit against its parent (NDJSON: one record per
+        /// commit, then a summary of issues still unresolved at B)
+        #[arg(long, requires = "range")]
+        per_commit: bool,
+
+        /// With --format markdown, link locations to `<URL>/<path>#L<line>`
+        /// (e.g. https://github.com/org/repo/blob/<sha>). Defaults to the current commit
+        /// when GITHUB_SERVER_URL, GITHUB_REPOSITORY and GITHUB_SHA are set
+        #[arg(long, value_name = "URL")]
+        link_base: Option<String>,
     },
 
     /// Detect dead (unreferenced) exported symbols
@@ -561,7 +713,11 @@ pub enum Commands {
         include_build: bool,
 
         /// Framework preset を指定してフレームワーク規約のエントリポイントを除外する。
-        /// 現在対応: "laravel" (database/migrations, app/Http/Controllers 等)
+        /// 現在対応: "laravel" (database/migrations, app/Http/Controllers 等), "nextjs",
+        /// "rails" (db/migrate, config, routes.rb のルーティング先・コールバック等),
+        /// "spring" (@RestController ハンドラ, @Bean, @Scheduled, @Autowired 注入型等)。
+        /// それ以外の名前は `.astro-sight/presets/<name>.yaml` (またはユーザー設定
+        /// ディレクトリの `presets/`) のユーザー定義プリセットを使う
         #[arg(long)]
         framework: Option<String>,
 
@@ -576,11 +732,274 @@ pub enum Commands {
         #[arg(long = "exclude-glob", value_name = "PATTERN", num_args = 0..)]
         exclude_globs: Vec<String>,
 
+        /// Annotation or decorator name (without `@`) whose declarations count as framework
+        /// entry points in dead-code detection. Repeatable; added to the `spring` preset list.
+        #[arg(long = "entry-annotation", value_name = "NAME", num_args = 0..)]
+        entry_annotations: Vec<String>,
+
         /// dead_symbols のスコープ。`--git/--diff/--diff-file` 指定時のみ意味を持つ。
         /// 既定は `all` (changed file 内の全 dead を返す)。`touched-symbols` を指定
         /// すると宣言行が diff hunk と重なる dead のみ返す。
-        #[arg(long = "dead-scope", value_enum, default_value_t = DeadScope::All)]
-        dead_scope: DeadScope,
+        #[arg(long = "dead-scope", value_enum)]
+        dead_scope: Option<DeadScope>,
+
+        /// Which symbols to check: exported (default), private (unused within their
+        /// file / module / package), or all
+        #[arg(long, value_enum, default_value = "exported")]
+        scope: DeadCodeScope,
+
+        /// Report symbols unreachable from entry points (main, exported API, tests,
+        /// framework conventions, --entry) over the workspace reference graph,
+        /// grouped into connected dead clusters
+        #[arg(long, conflicts_with = "scope")]
+        reachability: bool,
+
+        /// Extra entry point for --reachability: NAME_GLOB or FILE_GLOB:NAME_GLOB,
+        /// matched against the symbol name or `Container.name` (repeatable)
+        #[arg(long = "entry", value_name = "PATTERN", requires = "reachability")]
+        entries: Vec<String>,
+
+        /// Also report function / method parameters never referenced in the body
+        /// (trait / interface implementations, overrides and `_`-prefixed names are skipped)
+        #[arg(long)]
+        params: bool,
+
+        /// Also report Cargo `[features]` never referenced by `cfg(feature = "...")` and
+        /// `cfg(feature = "...")` naming a feature the crate does not declare
+        #[arg(long)]
+        cargo_features: bool,
+
+        /// Report workspace files that contain parse errors (ERROR / MISSING nodes);
+        /// symbols in or referenced from those files may be misreported
+        #[arg(long)]
+        parse_warnings: bool,
+
+        /// Print a unified diff that deletes each dead symbol (with its doc comments,
+        /// attributes and decorators) and the imports / re-exports it leaves unused,
+        /// instead of the JSON report. Same-name and test-only symbols are left alone.
+        /// Imports are cleaned up across the whole workspace, regardless of --glob.
+        /// Cannot be combined with --format
+        #[arg(long, conflicts_with = "format")]
+        fix_diff: bool,
+
+        /// Annotate each dead symbol with the last commit that touched its declaration
+        /// (sha, author, date) and, when found, the commit that removed its last reference
+        #[arg(long)]
+        blame: bool,
+
+        /// Order of dead_symbols / test_only_symbols: location (default) or age
+        /// (oldest --blame date first)
+        #[arg(
+            long,
+            value_enum,
+            default_value = "location",
+            requires_if("age", "blame")
+        )]
+        sort: DeadCodeSort,
+    },
+
+    /// Detect duplicate (copy-pasted) code via normalized AST subtree hashing
+    Dupes {
+        /// Workspace / project root directory
+        #[arg(short, long, default_value = ".")]
+        dir: String,
+
+        /// Glob pattern to filter files (e.g. "**/*.rs")
+        #[arg(short, long)]
+        glob: Option<String>,
+
+        /// 報告する clone の最小トークン数 (コメントを除く葉ノード数)
+        #[arg(long, default_value_t = crate::engine::dupes::DEFAULT_MIN_TOKENS)]
+        min_tokens: usize,
+
+        /// Include tests / Tests / __tests__ / spec / testdata ディレクトリ配下
+        /// (既定: 除外)
+        #[arg(long)]
+        include_tests: bool,
+
+        /// 追加で除外するディレクトリ名 (完全一致、複数指定可)。
+        #[arg(long = "exclude-dir", value_name = "NAME", num_args = 0..)]
+        exclude_dirs: Vec<String>,
+
+        /// 追加で除外する glob パターン (ワークスペース相対、複数指定可)。
+        #[arg(long = "exclude-glob", value_name = "PATTERN", num_args = 0..)]
+        exclude_globs: Vec<String>,
+    },
+
+    /// Structural symbol diff between two git revisions (including private symbols)
+    Symdiff {
+        /// Workspace / project root directory
+        #[arg(short, long, default_value = ".")]
+        dir: String,
+
+        /// 比較元 revision (tag / branch / commit)
+        #[arg(long)]
+        from: String,
+
+        /// 比較先 revision (既定: HEAD)
+        #[arg(long, default_value = "HEAD")]
+        to: String,
+    },
+
+    /// Report documentation coverage of public (exported) symbols
+    DocCoverage {
+        /// Workspace / project root directory
+        #[arg(short, long, default_value = ".")]
+        dir: String,
+
+        /// Glob pattern to filter files (e.g. "**/*.rs")
+        #[arg(short, long)]
+        glob: Option<String>,
+
+        /// Inline diff string (fail on undocumented symbols added or modified by the diff)
+        #[arg(long)]
+        diff: Option<String>,
+
+        /// Path to a diff file (fail on undocumented symbols added or modified by the diff)
+        #[arg(long, conflicts_with = "diff")]
+        diff_file: Option<String>,
+
+        /// Auto-run git diff (fail on undocumented symbols added or modified by the diff)
+        #[arg(long, conflicts_with_all = ["diff", "diff_file"])]
+        git: bool,
+
+        /// Base ref for git diff (default: HEAD)
+        #[arg(long, default_value = "HEAD")]
+        base: String,
+
+        /// Use staged changes (git diff --cached)
+        #[arg(long)]
+        staged: bool,
+
+        /// 追加で除外するディレクトリ名 (完全一致、複数指定可)。
+        #[arg(long = "exclude-dir", value_name = "NAME", num_args = 0..)]
+        exclude_dirs: Vec<String>,
+
+        /// 追加で除外する glob パターン (ワークスペース相対、複数指定可)。
+        #[arg(long = "exclude-glob", value_name = "PATTERN", num_args = 0..)]
+        exclude_globs: Vec<String>,
+    },
+
+    /// Report imported names that are never referenced in the importing file
+    UnusedImports {
+        /// Workspace / project root directory
+        #[arg(short, long, default_value = ".")]
+        dir: String,
+
+        /// Check a single source file (inside --dir) instead of the whole directory
+        #[arg(short, long, conflicts_with_all = ["glob", "diff", "diff_file", "git"])]
+        path: Option<String>,
+
+        /// Glob pattern to filter files (e.g. "**/*.ts")
+        #[arg(short, long)]
+        glob: Option<String>,
+
+        /// Inline diff string (check only the changed files)
+        #[arg(long)]
+        diff: Option<String>,
+
+        /// Path to a diff file (check only the changed files)
+        #[arg(long, conflicts_with = "diff")]
+        diff_file: Option<String>,
+
+        /// Auto-run git diff (check only the changed files)
+        #[arg(long, conflicts_with_all = ["diff", "diff_file"])]
+        git: bool,
+
+        /// Base ref for git diff (default: HEAD)
+        #[arg(long, default_value = "HEAD")]
+        base: String,
+
+        /// Use staged changes (git diff --cached)
+        #[arg(long)]
+        staged: bool,
+
+        /// 追加で除外するディレクトリ名 (完全一致、複数指定可)。
+        #[arg(long = "exclude-dir", value_name = "NAME", num_args = 0..)]
+        exclude_dirs: Vec<String>,
+
+        /// 追加で除外する glob パターン (ワークスペース相対、複数指定可)。
+        #[arg(long = "exclude-glob", value_name = "PATTERN", num_args = 0..)]
+        exclude_globs: Vec<String>,
+    },
+
+    /// Select the tests that transitively reference symbols changed in a diff
+    AffectedTests {
+        /// Workspace directory
+        #[arg(short, long, default_value = ".")]
+        dir: String,
+
+        /// Inline diff string
+        #[arg(long)]
+        diff: Option<String>,
+
+        /// Path to a diff file
+        #[arg(long, conflicts_with = "diff")]
+        diff_file: Option<String>,
+
+        /// Auto-run git diff
+        #[arg(long, conflicts_with_all = ["diff", "diff_file"])]
+        git: bool,
+
+        /// Base ref for git diff (default: HEAD)
+        #[arg(long, default_value = "HEAD")]
+        base: String,
+
+        /// Use staged changes (git diff --cached)
+        #[arg(long)]
+        staged: bool,
+
+        /// Maximum number of reference hops followed from the changed symbols
+        #[arg(long, default_value_t = 5)]
+        max_depth: usize,
+
+        /// Print a filter for the given test runner instead of the JSON report
+        #[arg(long, value_enum)]
+        runner: Option<TestRunner>,
+
+        /// 追加で除外するディレクトリ名 (完全一致、複数指定可)。
+        #[arg(long = "exclude-dir", value_name = "NAME", num_args = 0..)]
+        exclude_dirs: Vec<String>,
+
+        /// 追加で除外する glob パターン (ワークスペース相対、複数指定可)。
+        #[arg(long = "exclude-glob", value_name = "PATTERN", num_args = 0..)]
+        exclude_globs: Vec<String>,
+    },
+
+    /// Recommend a SemVer bump from public API changes and check the manifest version
+    Semver {
+        /// Workspace directory
+        #[arg(short, long, default_value = ".")]
+        dir: String,
+
+        /// Base ref to compare against (usually the last release tag)
+        #[arg(long)]
+        base: String,
+    },
+
+    /// Write or verify a public API snapshot file (works without git history)
+    Api {
+        #[command(subcommand)]
+        action: ApiAction,
+    },
+
+    /// Report files whose parse trees contain ERROR / MISSING nodes, grouped by language
+    ParseHealth {
+        /// Workspace / project root directory
+        #[arg(short, long, default_value = ".")]
+        dir: String,
+
+        /// Glob pattern to filter files (e.g. "**/*.swift")
+        #[arg(short, long)]
+        glob: Option<String>,
+
+        /// 追加で除外するディレクトリ名 (完全一致、複数指定可)。
+        #[arg(long = "exclude-dir", value_name = "NAME", num_args = 0..)]
+        exclude_dirs: Vec<String>,
+
+        /// 追加で除外する glob パターン (ワークスペース相対、複数指定可)。
+        #[arg(long = "exclude-glob", value_name = "PATTERN", num_args = 0..)]
+        exclude_globs: Vec<String>,
     },
 
     /// Check tool availability and language support
@@ -592,6 +1011,12 @@ pub enum Commands {
     /// Start MCP (Model Context Protocol) server over stdio
     Mcp,
 
+    /// Show the loaded user / project configuration
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+
     /// Generate default configuration file
     Init {
         /// Path to write the configuration file (default: ~/.config/astro-sight/config.toml)
@@ -605,3 +1030,62 @@ pub enum Commands {
         target: String,
     },
 }
+
+#[derive(Subcommand, Debug)]
+pub enum ApiAction {
+    /// Print a sorted listing of every exported symbol with its normalized signature
+    Dump {
+        /// Workspace / project root directory
+        #[arg(short, long, default_value = ".")]
+        dir: String,
+
+        /// Glob pattern to filter files (e.g. "src/**/*.rs")
+        #[arg(short, long)]
+        glob: Option<String>,
+
+        /// 追加で除外するディレクトリ名 (完全一致、複数指定可)。
+        #[arg(long = "exclude-dir", value_name = "NAME", num_args = 0..)]
+        exclude_dirs: Vec<String>,
+
+        /// 追加で除外する glob パターン (ワークスペース相対、複数指定可)。
+        #[arg(long = "exclude-glob", value_name = "PATTERN", num_args = 0..)]
+        exclude_globs: Vec<String>,
+    },
+
+    /// Report additions, removals and signature changes against a snapshot from `api dump`
+    Check {
+        /// Workspace / project root directory
+        #[arg(short, long, default_value = ".")]
+        dir: String,
+
+        /// Snapshot file written by `api dump`
+        #[arg(long)]
+        snapshot: String,
+
+        /// Glob pattern to filter files (must match the one used for `api dump`)
+        #[arg(short, long)]
+        glob: Option<String>,
+
+        /// 追加で除外するディレクトリ名 (完全一致、複数指定可)。
+        #[arg(long = "exclude-dir", value_name = "NAME", num_args = 0..)]
+        exclude_dirs: Vec<String>,
+
+        /// 追加で除外する glob パターン (ワークスペース相対、複数指定可)。
+        #[arg(long = "exclude-glob", value_name = "PATTERN", num_args = 0..)]
+        exclude_globs: Vec<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigAction {
+    /// Print the user config and the project config (`.astro-sight.toml`) found for a directory
+    Show {
+        /// project 設定 (`.astro-sight.toml`) を探し始めるディレクトリ
+        #[arg(short, long, default_value = ".")]
+        dir: String,
+
+        /// Print the merged values (CLI defaults < user < project) and where each one came from
+        #[arg(long)]
+        effective: bool,
+    },
+}
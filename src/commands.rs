@@ -6,7 +6,7 @@ use crate::doctor;
 use crate::engine::parser;
 use crate::models::cochange::{CoChangeOptions, CoChangeResult};
 use crate::models::skip::SkipInfo;
-use crate::service::{AppService, AstParams};
+use crate::service::{AppService, AstParams, SourceParams};
 
 mod common;
 
@@ -16,6 +16,15 @@ pub(crate) use common::read_bytes_limited_and_drain;
 pub(crate) use common::{ChangedFileSet, cache_hash_for_path, log_phase, read_to_string_limited};
 pub use common::{MAX_INPUT_SIZE, classify_error, read_paths_file_limited};
 
+/// 結果を stdout に出し終えたチェック系コマンド (doc-coverage / semver / api check) を
+/// `message` を stderr に出して `code` で終了させる。
+/// `process::exit` は stdout のバッファを flush しないため、先に flush して結果 JSON を確実に残す。
+pub(crate) fn exit_with_status(code: i32, message: &str) -> Result<()> {
+    std::io::Write::flush(&mut std::io::stdout())?;
+    eprintln!("{message}");
+    std::process::exit(code);
+}
+
 // ---------------------------------------------------------------------------
 // 単一ファイル系コマンド（キャッシュ・出力フォーマット対応）
 // ---------------------------------------------------------------------------
@@ -252,7 +261,11 @@ pub fn cmd_lint(
     output: OutputOptions,
 ) -> Result<()> {
     let result = service.lint_file(path, rules)?;
-    let text = serialize_cli_document(&result, output)?;
+    let text = if output.format() == OutputFormat::Codeclimate {
+        crate::output::codeclimate::render(&lint_codeclimate_issues(path, &result), output)?
+    } else {
+        serialize_cli_document(&result, output)?
+    };
     info!(
         command = "lint",
         path = path,
@@ -264,6 +277,35 @@ pub fn cmd_lint(
     Ok(())
 }
 
+/// lint 結果を Code Climate の issue にする。ルールはユーザ定義なので category は
+/// 一律 Style とし、重大度だけをルールの `severity` から写す。
+/// `LintResult` はパスを持たないため、入力に渡されたパスをそのまま location に使う。
+pub(crate) fn lint_codeclimate_issues(
+    path: &str,
+    result: &crate::models::lint::LintResult,
+) -> Vec<crate::output::codeclimate::Issue> {
+    use crate::models::lint::Severity as LintSeverity;
+    use crate::output::codeclimate::{Category, Issue, Severity};
+
+    result
+        .matches
+        .iter()
+        .map(|m| Issue {
+            check_name: format!("astro-sight/lint/{}", m.rule_id),
+            description: m.message.clone(),
+            category: Category::Style,
+            severity: match m.severity {
+                LintSeverity::Error => Severity::Major,
+                LintSeverity::Warning => Severity::Minor,
+                LintSeverity::Info => Severity::Info,
+            },
+            path: path.to_string(),
+            line: Some(m.line),
+            identity: m.matched_text.clone(),
+        })
+        .collect()
+}
+
 pub fn cmd_sequence(
     service: &AppService,
     path: &str,
@@ -282,20 +324,47 @@ pub fn cmd_refs(
     name: &str,
     dir: &str,
     glob: Option<&str>,
+    parse_warnings: bool,
     output: OutputOptions,
 ) -> Result<()> {
-    let result = service.find_references(name, dir, glob)?;
+    let mut result = service.find_references(name, dir, glob)?;
+    if parse_warnings {
+        let names = [name.to_string()];
+        result.parse_warnings = service
+            .parse_warnings_for_names(&names, dir, glob)?
+            .pop()
+            .unwrap_or_default();
+    }
     let text = serialize_cli_document(&result, output)?;
     info!(command = "refs", name = name, dir = dir, glob = ?glob, output_bytes = text.len(), "command completed");
     print!("{text}");
     Ok(())
 }
 
+pub fn cmd_source(
+    service: &AppService,
+    params: &SourceParams<'_>,
+    output: OutputOptions,
+) -> Result<()> {
+    let result = service.extract_source(params)?;
+    let text = serialize_cli_document(&result, output)?;
+    info!(
+        command = "source",
+        name = params.name,
+        matches = result.matches.len(),
+        output_bytes = text.len(),
+        "command completed"
+    );
+    print!("{text}");
+    Ok(())
+}
+
 pub fn cmd_refs_batch(
     service: &AppService,
     names: &[String],
     dir: &str,
     glob: Option<&str>,
+    parse_warnings: bool,
     output: OutputOptions,
 ) -> Result<()> {
     use std::io::Write;
@@ -307,7 +376,13 @@ pub fn cmd_refs_batch(
     // 集約するため、ここでは全名を 1 回で渡す（以前は呼び出し側で chunk 分割していたが
     // chunk 毎に walk し直していた）。service は入力順を保った `Vec<RefsResult>` を返すので
     // NDJSON 出力も names 順を維持する。
-    let results = service.find_references_batch(names, dir, glob)?;
+    let mut results = service.find_references_batch(names, dir, glob)?;
+    if parse_warnings {
+        let warnings = service.parse_warnings_for_names(names, dir, glob)?;
+        for (result, warnings) in results.iter_mut().zip(warnings) {
+            result.parse_warnings = warnings;
+        }
+    }
     for result in &results {
         total_refs += result.references.len();
     }
@@ -318,7 +393,7 @@ pub fn cmd_refs_batch(
     // auto も全件を持っているぶん近似が要らず、NDJSON 全体と TOON ドキュメントを
     // 実際に組み立てて短い方を選べる。
     match output.format() {
-        OutputFormat::Json => {
+        OutputFormat::Json | OutputFormat::Markdown | OutputFormat::Codeclimate => {
             for result in &results {
                 let line = serde_json::to_string(result)?;
                 writeln!(out, "{line}")?;
@@ -417,6 +492,8 @@ pub struct CmdContextOpts<'a> {
     pub output: OutputOptions,
     pub exclude_dirs: &'a [String],
     pub exclude_globs: &'a [String],
+    /// 公開ラッパー経由で `transitive_callers` を辿る段数 (0 は無効)。
+    pub transitive_depth: usize,
 }
 
 pub fn cmd_context(service: &AppService, opts: &CmdContextOpts<'_>) -> Result<()> {
@@ -431,6 +508,7 @@ pub fn cmd_context(service: &AppService, opts: &CmdContextOpts<'_>) -> Result<()
         output,
         exclude_dirs,
         exclude_globs,
+        transitive_depth,
     } = opts;
     let (diff_input, truncations) =
         match resolve_diff_source(dir, diff, diff_file, git, base, staged)? {
@@ -457,6 +535,7 @@ pub fn cmd_context(service: &AppService, opts: &CmdContextOpts<'_>) -> Result<()
     let options = crate::models::impact::ContextAnalysisOptions {
         exclude_dirs: exclude_dirs.to_vec(),
         exclude_globs: exclude_globs.to_vec(),
+        transitive_depth,
     };
 
     // 逐次出力できるのは compact JSON だけ。pretty は整形が要り、TOON はルート配列の
@@ -577,6 +656,7 @@ pub fn cmd_impact(service: &AppService, opts: &CmdImpactOpts<'_>) -> Result<()>
     let options = crate::models::impact::ContextAnalysisOptions {
         exclude_dirs: exclude_dirs.to_vec(),
         exclude_globs: exclude_globs.to_vec(),
+        transitive_depth: 0,
     };
     let result = service.analyze_context(&diff_input, dir, &options)?;
 
@@ -699,16 +779,22 @@ pub fn cmd_mcp(output: OutputOptions) -> Result<()> {
 mod review;
 
 #[cfg(test)]
-pub(crate) use review::hook::build_review_hook_json;
+pub(crate) use review::hook::{apply_risk_threshold, build_review_hook_json};
 pub use review::{CmdReviewOpts, cmd_review};
 
 mod api_changes;
 mod dead_code;
+mod dead_code_blame;
+mod dead_code_features;
+mod dead_code_fix;
 mod dead_code_member_liveness;
+mod dead_code_params;
+mod dead_code_private;
+mod dead_code_reachability;
 
 #[cfg(test)]
 pub(crate) use api_changes::*;
-pub use dead_code::cmd_dead_code;
+pub use dead_code::{CmdDeadCodeOpts, cmd_dead_code};
 // tests.rs が `use super::*` 経由で参照する dead_code 内部シンボル。
 // (production 側の唯一の利用者だった cmd_review は review モジュールへ移動し、
 //  そこから `super::dead_code::…` を直接参照している)
@@ -718,6 +804,44 @@ pub(crate) use dead_code::{
     filter_dead_by_wip_added, filter_diff_files_for_dead_code, resolve_dead_code_excludes,
     resolve_framework_globs_with_auto_detect,
 };
+#[cfg(test)]
+pub(crate) use dead_code_params::detect_unused_parameters;
+#[cfg(test)]
+pub(crate) use dead_code_private::detect_private_dead_symbols;
+#[cfg(test)]
+pub(crate) use dead_code_reachability::detect_unreachable_clusters;
+
+mod dupes;
+
+pub use dupes::{CmdDupesOpts, cmd_dupes};
+
+mod symdiff;
+
+pub use symdiff::{CmdSymdiffOpts, cmd_symdiff};
+
+mod doc_coverage;
+
+pub use doc_coverage::{CmdDocCoverageOpts, cmd_doc_coverage};
+
+mod unused_imports;
+
+pub use unused_imports::{CmdUnusedImportsOpts, cmd_unused_imports};
+
+mod parse_health;
+
+pub use parse_health::{CmdParseHealthOpts, cmd_parse_health};
+
+mod affected_tests;
+
+pub use affected_tests::{CmdAffectedTestsOpts, cmd_affected_tests};
+
+mod semver;
+
+pub use semver::{CmdSemverOpts, cmd_semver};
+
+mod api_snapshot;
+
+pub use api_snapshot::{CmdApiCheckOpts, CmdApiDumpOpts, cmd_api_check, cmd_api_dump};
 
 mod batch;
 mod session_handler;
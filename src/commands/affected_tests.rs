@@ -0,0 +1,488 @@
+use anyhow::Result;
+use camino::Utf8Path;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use tracing::info;
+
+use crate::cli::TestRunner;
+use crate::engine::functions::{FunctionSpan, enclosing, load_functions};
+use crate::engine::impact::test_context::{
+    has_attribute_text, is_in_test_context, is_test_file_path,
+};
+use crate::error::{AstroError, ErrorCode};
+use crate::language::LangId;
+use crate::models::affected_tests::{AffectedTestFile, AffectedTestsResult};
+use crate::models::impact::ContextAnalysisOptions;
+use crate::models::reference::RefKind;
+use crate::models::symbol::Symbol;
+use crate::models::truncation::TruncationInfo;
+use crate::output::{OutputOptions, serialize_cli_document};
+use crate::service::AppService;
+
+use super::common::{MAX_INPUT_SIZE, read_to_string_limited};
+use super::git_input::{DiffSourceResolution, resolve_diff_source};
+
+/// `cmd_affected_tests` の引数一式。
+pub struct CmdAffectedTestsOpts<'a> {
+    pub dir: &'a str,
+    pub diff: Option<&'a str>,
+    pub diff_file: Option<&'a str>,
+    pub git: bool,
+    pub base: &'a str,
+    pub staged: bool,
+    pub max_depth: usize,
+    pub runner: Option<TestRunner>,
+    pub exclude_dirs: &'a [String],
+    pub exclude_globs: &'a [String],
+    pub output: OutputOptions,
+}
+
+/// diff で変更されたシンボルを推移的に参照しているテストファイル / テスト関数を選ぶ。
+///
+/// 起点は `context` の affected symbols。各段で `refs` を引き、参照元がテスト
+/// (パス規約または `is_in_test_context`) ならテストとして記録し、プロダクションコードなら
+/// 参照を含む関数を次の段の起点にする。テスト内のヘルパー関数も次の段に回すので、
+/// ヘルパー経由で呼ぶテストまで届く。`--runner` 指定時はランナー用フィルタ 1 行だけを出す。
+pub fn cmd_affected_tests(service: &AppService, opts: &CmdAffectedTestsOpts<'_>) -> Result<()> {
+    if opts.max_depth == 0 {
+        return Err(AstroError::new(
+            ErrorCode::InvalidRequest,
+            "--max-depth must be at least 1".to_string(),
+        )
+        .into());
+    }
+
+    let diff_input = match resolve_diff_source(
+        opts.dir,
+        opts.diff,
+        opts.diff_file,
+        opts.git,
+        opts.base,
+        opts.staged,
+    )? {
+        DiffSourceResolution::Diff { diff, .. } => diff,
+        DiffSourceResolution::Skipped(skip) => {
+            let result = AffectedTestsResult {
+                skipped: Some(skip),
+                ..Default::default()
+            };
+            return print_result(&result, opts);
+        }
+        DiffSourceResolution::NotRequested => {
+            let stdin = std::io::stdin();
+            read_to_string_limited(stdin.lock(), MAX_INPUT_SIZE, "stdin input")?
+        }
+    };
+
+    let options = ContextAnalysisOptions {
+        exclude_dirs: opts.exclude_dirs.to_vec(),
+        exclude_globs: opts.exclude_globs.to_vec(),
+        transitive_depth: 0,
+    };
+    let context = service.analyze_context(&diff_input, opts.dir, &options)?;
+    let canonical_dir = std::fs::canonicalize(opts.dir)?;
+
+    let mut collector = TestCollector::default();
+    let mut files: HashMap<String, Option<FileFunctions>> = HashMap::new();
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut frontier: BTreeSet<String> = BTreeSet::new();
+    for change in &context.changes {
+        let functions = files
+            .entry(change.path.clone())
+            .or_insert_with(|| FileFunctions::load(&canonical_dir, &change.path))
+            .as_ref();
+        let test_file = is_test_file_path(&change.path);
+        if test_file {
+            // diff がテストファイル自体を変えた: ファイルごと対象 (段数 0)。
+            let Some(lang) = lang_of(&change.path) else {
+                continue;
+            };
+            collector.add_file(&change.path, lang, 0);
+        }
+        for sym in &change.affected_symbols {
+            // 変更されたテスト関数そのもの (`#[cfg(test)]` mod 内の `#[test]` fn 等) は段数 0。
+            if let Some(functions) = functions
+                && functions.is_test_case(&sym.name)
+            {
+                collector.add_file(&change.path, functions.lang, 0);
+                collector.add_test(&change.path, &sym.name);
+                continue;
+            }
+            if !test_file && seen.insert(sym.name.clone()) {
+                frontier.insert(sym.name.clone());
+            }
+        }
+    }
+    let changed_symbols: Vec<String> = frontier.iter().cloned().collect();
+
+    let mut truncations = Vec::new();
+    let mut depth = 0;
+    while !frontier.is_empty() {
+        if depth == opts.max_depth {
+            truncations.push(TruncationInfo::depth_limit_reached(
+                opts.max_depth,
+                frontier.len(),
+            ));
+            break;
+        }
+        depth += 1;
+
+        let names: Vec<String> = frontier.into_iter().collect();
+        let results = service.find_references_batch(&names, opts.dir, None)?;
+        let mut next: BTreeSet<String> = BTreeSet::new();
+        for reference in results.iter().flat_map(|r| &r.references) {
+            if reference.kind == Some(RefKind::Definition) {
+                continue;
+            }
+            let functions = files
+                .entry(reference.path.clone())
+                .or_insert_with(|| FileFunctions::load(&canonical_dir, &reference.path));
+            let Some(functions) = functions else {
+                continue;
+            };
+            let enclosing = functions.enclosing(reference.line);
+            let in_test =
+                functions.test_file || enclosing.is_some_and(|function| function.info.test_context);
+            if in_test {
+                collector.add_file(&reference.path, functions.lang, depth);
+            }
+            match enclosing {
+                Some(function) if in_test && function.info.test_case => {
+                    collector.add_test(&reference.path, &function.name);
+                }
+                Some(function) => {
+                    // プロダクションの関数、またはテスト内のヘルパー: 呼び出し元を次の段で追う。
+                    if seen.insert(function.name.clone()) {
+                        next.insert(function.name.clone());
+                    }
+                }
+                None => {}
+            }
+        }
+        frontier = next;
+    }
+
+    let result = AffectedTestsResult {
+        changed_symbols,
+        test_files: collector.into_files(),
+        skipped: None,
+        truncations,
+    };
+    print_result(&result, opts)
+}
+
+fn print_result(result: &AffectedTestsResult, opts: &CmdAffectedTestsOpts<'_>) -> Result<()> {
+    let text = match opts.runner {
+        Some(runner) => {
+            let canonical_dir = std::fs::canonicalize(opts.dir)?;
+            let file_tests = |file: &AffectedTestFile| {
+                FileFunctions::load(&canonical_dir, &file.path)
+                    .map(|functions| functions.test_cases().map(str::to_string).collect())
+                    .unwrap_or_default()
+            };
+            let filter = runner_filter(runner, &result.test_files, &file_tests);
+            if filter.is_empty() {
+                String::new()
+            } else {
+                format!("{filter}\n")
+            }
+        }
+        None => serialize_cli_document(result, opts.output)?,
+    };
+    info!(
+        command = "affected-tests",
+        dir = opts.dir,
+        test_files = result.test_files.len(),
+        runner = ?opts.runner,
+        "command completed"
+    );
+    print!("{text}");
+    Ok(())
+}
+
+fn lang_of(path: &str) -> Option<LangId> {
+    LangId::from_path(Utf8Path::new(path))
+        .ok()
+        .filter(|lang| !lang.is_lexer_only())
+}
+
+/// ランナーが個別に選択できるテスト関数の命名規約。Rust は名前ではなく
+/// `#[test]` 系 attribute で判定する (`FileFunctions::load`) ため、ここでは `false`。
+fn is_test_case_name(lang: LangId, name: &str) -> bool {
+    match lang {
+        LangId::Python => name.starts_with("test"),
+        LangId::Go => name.starts_with("Test"),
+        _ => false,
+    }
+}
+
+/// 1 ファイル内の関数 / メソッドと、そのテスト上の扱い。
+/// review のリスクスコア (変更シンボルのテスト参照有無) からも使う。
+pub(crate) struct FileFunctions {
+    lang: LangId,
+    pub(crate) test_file: bool,
+    functions: Vec<FunctionSpan<TestRole>>,
+}
+
+pub(crate) struct TestRole {
+    /// テストコンテキスト内 (`#[cfg(test)]` mod、`TestCase` サブクラス等)。
+    pub(crate) test_context: bool,
+    /// ランナーが名前で選択できるテスト関数そのもの。
+    test_case: bool,
+}
+
+impl FileFunctions {
+    /// 読めない / 非対応言語のファイルは `None` (参照元として扱わない)。
+    pub(crate) fn load(dir: &std::path::Path, relative: &str) -> Option<Self> {
+        let (lang, functions) = load_functions(&dir.join(relative), |root, source, lang, sym| {
+            let test_context = is_in_test_context(root, source, &sym.range, lang, relative);
+            let test_case = test_context
+                && match lang {
+                    LangId::Rust => is_rust_test_fn(root, source, sym),
+                    _ => is_test_case_name(lang, &sym.name),
+                };
+            TestRole {
+                test_context,
+                test_case,
+            }
+        })?;
+        Some(Self {
+            lang,
+            test_file: is_test_file_path(relative),
+            functions,
+        })
+    }
+
+    /// `name` がランナーで選択できるテスト関数か。
+    fn is_test_case(&self, name: &str) -> bool {
+        self.functions
+            .iter()
+            .any(|f| f.info.test_case && f.name == name)
+    }
+
+    /// ファイル内のテスト関数名。
+    fn test_cases(&self) -> impl Iterator<Item = &str> {
+        self.functions
+            .iter()
+            .filter(|f| f.info.test_case)
+            .map(|f| f.name.as_str())
+    }
+
+    /// `line` を含む最も内側の関数。
+    pub(crate) fn enclosing(&self, line: usize) -> Option<&FunctionSpan<TestRole>> {
+        enclosing(&self.functions, line)
+    }
+}
+
+/// Rust: `#[test]` / `#[tokio::test]` 等が付いた関数か。
+fn is_rust_test_fn(root: tree_sitter::Node<'_>, source: &[u8], sym: &Symbol) -> bool {
+    let start = tree_sitter::Point {
+        row: sym.range.start.line,
+        column: sym.range.start.column,
+    };
+    let end = tree_sitter::Point {
+        row: sym.range.end.line,
+        column: sym.range.end.column,
+    };
+    let mut current = root.named_descendant_for_point_range(start, end);
+    while let Some(node) = current {
+        if node.kind() == "function_item" {
+            return has_attribute_text(node, source, "test]");
+        }
+        current = node.parent();
+    }
+    false
+}
+
+/// テストファイルをパス順に集める。同じファイルに複数段で到達したら最小段数を残す。
+#[derive(Default)]
+struct TestCollector {
+    files: BTreeMap<String, (LangId, usize, BTreeSet<String>)>,
+}
+
+impl TestCollector {
+    fn add_file(&mut self, path: &str, lang: LangId, depth: usize) {
+        let entry = self
+            .files
+            .entry(path.to_string())
+            .or_insert_with(|| (lang, depth, BTreeSet::new()));
+        entry.1 = entry.1.min(depth);
+    }
+
+    fn add_test(&mut self, path: &str, name: &str) {
+        if let Some(entry) = self.files.get_mut(path) {
+            entry.2.insert(name.to_string());
+        }
+    }
+
+    fn into_files(self) -> Vec<AffectedTestFile> {
+        self.files
+            .into_iter()
+            .map(|(path, (lang, depth, tests))| AffectedTestFile {
+                path,
+                lang,
+                tests: tests.into_iter().collect(),
+                depth,
+            })
+            .collect()
+    }
+}
+
+/// `--runner` 用のフィルタ文字列。対象が無ければ空文字 (呼び出し側は空なら全件実行に倒す想定)。
+///
+/// cargo / go / pytest は名前でしか選べないため、関数名が取れないファイル (テスト内ヘルパーの
+/// 変更等) は `file_tests` でファイル内の全テスト関数に展開する。展開できないファイル
+/// (`conftest.py` の fixture 等) が 1 つでもあれば取りこぼさないよう空文字 (全件実行) を返す。
+fn runner_filter(
+    runner: TestRunner,
+    files: &[AffectedTestFile],
+    file_tests: &dyn Fn(&AffectedTestFile) -> Vec<String>,
+) -> String {
+    let tests_of = |lang: LangId| -> Option<BTreeSet<String>> {
+        let mut tests = BTreeSet::new();
+        for file in files.iter().filter(|file| file.lang == lang) {
+            if file.tests.is_empty() {
+                let expanded = file_tests(file);
+                if expanded.is_empty() {
+                    return None;
+                }
+                tests.extend(expanded);
+            } else {
+                tests.extend(file.tests.iter().cloned());
+            }
+        }
+        Some(tests)
+    };
+    match runner {
+        TestRunner::Cargo => tests_of(LangId::Rust)
+            .map(|tests| tests.into_iter().collect::<Vec<_>>().join(" "))
+            .unwrap_or_default(),
+        TestRunner::Pytest => {
+            // conftest.py の fixture は同じディレクトリ以下の全テストに効くため、関数名では選べない。
+            let conftest = files.iter().any(|file| {
+                file.lang == LangId::Python
+                    && Utf8Path::new(&file.path).file_name() == Some("conftest.py")
+            });
+            if conftest {
+                return String::new();
+            }
+            tests_of(LangId::Python)
+                .map(|tests| tests.into_iter().collect::<Vec<_>>().join(" or "))
+                .unwrap_or_default()
+        }
+        TestRunner::Jest => files
+            .iter()
+            .filter(|file| {
+                matches!(
+                    file.lang,
+                    LangId::Javascript | LangId::Typescript | LangId::Tsx
+                )
+            })
+            .map(|file| regex_escape(&file.path))
+            .collect::<Vec<_>>()
+            .join("|"),
+        TestRunner::Go => match tests_of(LangId::Go) {
+            Some(tests) if !tests.is_empty() => {
+                format!("^({})$", tests.into_iter().collect::<Vec<_>>().join("|"))
+            }
+            _ => String::new(),
+        },
+    }
+}
+
+fn regex_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if "\\.^$|?*+()[]{}".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str, lang: LangId, tests: &[&str]) -> AffectedTestFile {
+        AffectedTestFile {
+            path: path.to_string(),
+            lang,
+            tests: tests.iter().map(|t| t.to_string()).collect(),
+            depth: 1,
+        }
+    }
+
+    #[test]
+    fn runner_filters_per_runner() {
+        let files = vec![
+            file("src/lib.rs", LangId::Rust, &["adds", "subtracts"]),
+            file("tests/test_api.py", LangId::Python, &["test_get"]),
+            file("tests/conftest.py", LangId::Python, &[]),
+            file("src/app.test.ts", LangId::Typescript, &[]),
+            file("pkg/calc_test.go", LangId::Go, &["TestAdd", "TestSub"]),
+        ];
+        let none = |_: &AffectedTestFile| Vec::new();
+        assert_eq!(
+            runner_filter(TestRunner::Cargo, &files, &none),
+            "adds subtracts"
+        );
+        // conftest.py の変更はテスト名で絞れないため全件実行 (空文字)。
+        assert_eq!(runner_filter(TestRunner::Pytest, &files, &none), "");
+        assert_eq!(
+            runner_filter(TestRunner::Pytest, &files[..2], &none),
+            "test_get"
+        );
+        assert_eq!(
+            runner_filter(TestRunner::Jest, &files, &none),
+            "src/app\\.test\\.ts"
+        );
+        assert_eq!(
+            runner_filter(TestRunner::Go, &files, &none),
+            "^(TestAdd|TestSub)$"
+        );
+    }
+
+    #[test]
+    fn runner_filter_is_empty_without_matching_tests() {
+        let files = vec![file("src/app.test.ts", LangId::Typescript, &[])];
+        let none = |_: &AffectedTestFile| Vec::new();
+        assert_eq!(runner_filter(TestRunner::Go, &files, &none), "");
+        assert_eq!(runner_filter(TestRunner::Cargo, &files, &none), "");
+    }
+
+    #[test]
+    fn runner_filter_expands_file_only_entries_to_their_tests() {
+        let files = vec![
+            file("src/lib.rs", LangId::Rust, &["adds"]),
+            file("tests/helpers.rs", LangId::Rust, &[]),
+            file("pkg/util_test.go", LangId::Go, &[]),
+            file("tests/test_api.py", LangId::Python, &["test_get"]),
+            file("tests/test_db.py", LangId::Python, &[]),
+        ];
+        let expand = |file: &AffectedTestFile| match file.path.as_str() {
+            "tests/helpers.rs" => vec!["uses_helper".to_string()],
+            "pkg/util_test.go" => vec!["TestUtil".to_string()],
+            "tests/test_db.py" => vec!["test_connect".to_string()],
+            _ => Vec::new(),
+        };
+        assert_eq!(
+            runner_filter(TestRunner::Cargo, &files, &expand),
+            "adds uses_helper"
+        );
+        assert_eq!(
+            runner_filter(TestRunner::Go, &files, &expand),
+            "^(TestUtil)$"
+        );
+        assert_eq!(
+            runner_filter(TestRunner::Pytest, &files, &expand),
+            "test_connect or test_get"
+        );
+
+        // 展開できないファイルがあれば取りこぼさないよう全件実行 (空文字) に倒す。
+        let none = |_: &AffectedTestFile| Vec::new();
+        assert_eq!(runner_filter(TestRunner::Cargo, &files, &none), "");
+        assert_eq!(runner_filter(TestRunner::Go, &files, &none), "");
+        assert_eq!(runner_filter(TestRunner::Pytest, &files, &none), "");
+    }
+}
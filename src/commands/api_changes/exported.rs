@@ -589,6 +589,236 @@ pub(crate) fn filter_exported_symbols(
     result
 }
 
+/// dead-code `--scope private` の候補: 非公開の関数 / メソッド / 定数を `(qualname, kind, 宣言行)` で返す。
+///
+/// 公開判定だけを反転させ、trait 実装・override・実行時入口・テスト規約の除外は
+/// `filter_exported_symbols` (dead-code 経路) と同じ規則を通す。加えて、本体を持たない
+/// 宣言 (interface / trait / abstract)、関数内ローカル、テストコンテキスト、言語既定の
+/// 入口 (`main` / Go `init` / Python dunder) も除く。C/C++ は private メソッドの定義が
+/// 宣言と別ファイルに置かれるためスコープを決められず、Swift は明示 `private` /
+/// `fileprivate` 以外 (既定 internal はモジュール全体) を対象外にする。`protected`
+/// (PHP 等) は別ファイルのサブクラスから呼べるため、ファイル単位では判定しない。
+pub(crate) fn filter_private_symbols(
+    syms: &[Symbol],
+    root: tree_sitter::Node<'_>,
+    source: &[u8],
+    lang_id: crate::language::LangId,
+    file_path: &str,
+) -> Vec<(String, String, usize)> {
+    use crate::language::LangId;
+
+    if matches!(lang_id, LangId::C | LangId::Cpp) {
+        return Vec::new();
+    }
+    let context =
+        ExportSurfaceContext::new(syms, root, source, lang_id, true, true, Some(file_path));
+    let mut result = Vec::new();
+    for sym in syms {
+        if !matches!(
+            sym.kind,
+            SymbolKind::Function | SymbolKind::Method | SymbolKind::Constant
+        ) || is_language_entry_name(lang_id, &sym.name)
+            || crate::engine::symbols::is_symbol_exported(root, source, lang_id, &sym.range)
+            || crate::engine::symbols::is_local_scope_symbol(root, source, lang_id, &sym.range)
+            || crate::engine::impact::test_context::is_in_test_context(
+                root, source, &sym.range, lang_id, file_path,
+            )
+        {
+            continue;
+        }
+        let decl_line = context
+            .lines
+            .get(sym.range.start.line)
+            .unwrap_or(&"")
+            .trim();
+        if decl_line.contains("abstract ")
+            || decl_line.split_whitespace().any(|word| word == "protected")
+            || (lang_id == LangId::Swift && !decl_line.contains("private"))
+            || (lang_id == LangId::Rust && has_rust_linkage(&context.lines, sym.range.start.line))
+        {
+            continue;
+        }
+        if enclosing_container(sym, &context.containers)
+            .is_some_and(|c| matches!(c.kind, SymbolKind::Interface | SymbolKind::Trait))
+        {
+            continue;
+        }
+        if context.is_non_api_item(sym) || context.is_runtime_entrypoint(sym) {
+            continue;
+        }
+        let qualname = context.qualname(sym);
+        if context.is_excluded_by_qualname(sym, &qualname) {
+            continue;
+        }
+        result.push((
+            qualname,
+            format!("{:?}", sym.kind).to_lowercase(),
+            sym.range.start.line,
+        ));
+    }
+    result
+}
+
+/// dead-code `--reachability` の参照グラフに載せるシンボル。
+pub(crate) struct ReachabilityCandidate<'a> {
+    pub(crate) symbol: &'a Symbol,
+    pub(crate) qualname: String,
+    /// 公開 API か (`filter_exported_symbols` と同じ公開判定)。
+    pub(crate) exported: bool,
+    /// 参照が無くても到達可能とみなす起点か。
+    pub(crate) entry: bool,
+    /// 報告対象か。Rust の `impl` ブロックは型名で参照を受けて本体の参照を
+    /// 中継するためグラフには載せるが、それ自体は dead として報告しない。
+    pub(crate) reportable: bool,
+}
+
+/// dead-code `--reachability` のノード候補と起点を返す。
+///
+/// 起点は公開 API・言語既定の入口・外部リンケージ・テストコンテキストに加え、
+/// `filter_exported_symbols` (dead-code 経路) が除外する trait 実装 / override /
+/// フレームワーク規約の実行時入口。関数内ローカルと C/C++ の前方宣言は載せない。
+pub(crate) fn reachability_candidates<'a>(
+    syms: &'a [Symbol],
+    root: tree_sitter::Node<'_>,
+    source: &[u8],
+    lang_id: crate::language::LangId,
+    file_path: &str,
+) -> Vec<ReachabilityCandidate<'a>> {
+    use crate::language::LangId;
+
+    let context =
+        ExportSurfaceContext::new(syms, root, source, lang_id, true, true, Some(file_path));
+    let mut result = Vec::new();
+    for sym in syms {
+        if !matches!(
+            sym.kind,
+            SymbolKind::Function
+                | SymbolKind::Method
+                | SymbolKind::Class
+                | SymbolKind::Struct
+                | SymbolKind::Enum
+                | SymbolKind::Interface
+                | SymbolKind::Trait
+                | SymbolKind::Constant
+                | SymbolKind::Type
+        ) || crate::engine::symbols::is_local_scope_symbol(root, source, lang_id, &sym.range)
+        {
+            continue;
+        }
+        if matches!(lang_id, LangId::C | LangId::Cpp)
+            && (crate::engine::symbols::is_cpp_nested_function(root, &sym.range)
+                || crate::engine::symbols::is_cpp_forward_declaration(root, &sym.range))
+        {
+            continue;
+        }
+        let qualname = context.qualname(sym);
+        let exported = !context.is_non_definition(sym);
+        let decl_line = context
+            .lines
+            .get(sym.range.start.line)
+            .unwrap_or(&"")
+            .trim_start();
+        let entry = exported
+            || is_language_entry_name(lang_id, &sym.name)
+            || (lang_id == LangId::Rust && has_rust_linkage(&context.lines, sym.range.start.line))
+            || crate::engine::impact::test_context::is_in_test_context(
+                root, source, &sym.range, lang_id, file_path,
+            )
+            || context.is_non_api_item(sym)
+            || context.is_runtime_entrypoint(sym)
+            || context.is_excluded_by_qualname(sym, &qualname);
+        let reportable = !(lang_id == LangId::Rust
+            && sym.kind == SymbolKind::Type
+            && (decl_line.starts_with("impl") || decl_line.starts_with("unsafe impl")));
+        result.push(ReachabilityCandidate {
+            symbol: sym,
+            qualname,
+            exported,
+            entry,
+            reportable,
+        });
+    }
+    result
+}
+
+/// dead-code `--params` の検査対象になる関数 / メソッドを `(symbol, qualname)` で返す。
+///
+/// 引数の並びを呼び出し側 (trait / interface / 親クラス / フレームワーク / テストランナー /
+/// 言語ランタイム) に決められているものは、使わない引数も削れないため除く。除外規則は
+/// `filter_private_symbols` と同じ述語を公開・非公開を問わず適用する。
+pub(crate) fn parameter_check_candidates<'a>(
+    syms: &'a [Symbol],
+    root: tree_sitter::Node<'_>,
+    source: &[u8],
+    lang_id: crate::language::LangId,
+    file_path: &str,
+) -> Vec<(&'a Symbol, String)> {
+    use crate::language::LangId;
+
+    let context =
+        ExportSurfaceContext::new(syms, root, source, lang_id, true, true, Some(file_path));
+    let mut result = Vec::new();
+    for sym in syms {
+        if !matches!(sym.kind, SymbolKind::Function | SymbolKind::Method)
+            || is_language_entry_name(lang_id, &sym.name)
+            || crate::engine::impact::test_context::is_in_test_context(
+                root, source, &sym.range, lang_id, file_path,
+            )
+        {
+            continue;
+        }
+        let decl_line = context
+            .lines
+            .get(sym.range.start.line)
+            .unwrap_or(&"")
+            .trim();
+        if decl_line.contains("abstract ")
+            || (lang_id == LangId::Rust && has_rust_linkage(&context.lines, sym.range.start.line))
+        {
+            continue;
+        }
+        if enclosing_container(sym, &context.containers)
+            .is_some_and(|c| matches!(c.kind, SymbolKind::Interface | SymbolKind::Trait))
+        {
+            continue;
+        }
+        if context.is_non_api_item(sym) || context.is_runtime_entrypoint(sym) {
+            continue;
+        }
+        let qualname = context.qualname(sym);
+        if context.is_excluded_by_qualname(sym, &qualname) {
+            continue;
+        }
+        result.push((sym, qualname));
+    }
+    result
+}
+
+/// 参照が無くてもランタイム / ランナーから呼ばれる言語既定の入口名。
+fn is_language_entry_name(lang_id: crate::language::LangId, name: &str) -> bool {
+    use crate::language::LangId;
+    name == "main"
+        || (lang_id == LangId::Go && name == "init")
+        || (lang_id == LangId::Python && name.starts_with("__") && name.ends_with("__"))
+}
+
+/// Rust: `extern "C"` / `#[no_mangle]` / `#[export_name]` 等、シンボル名で外部から
+/// リンクされる関数か (宣言行と直前の attribute 行を見る)。
+fn has_rust_linkage(lines: &[&str], decl_line: usize) -> bool {
+    if lines
+        .get(decl_line)
+        .is_some_and(|line| line.contains("extern "))
+    {
+        return true;
+    }
+    lines[..decl_line.min(lines.len())]
+        .iter()
+        .rev()
+        .map(|line| line.trim())
+        .take_while(|line| line.starts_with("#[") || line.starts_with("//"))
+        .any(|line| line.contains("no_mangle") || line.contains("export_name"))
+}
+
 /// `qualname` (例: `Class.method` や bare name `foo`) が `callees` に含まれるかを判定する。
 /// Python/Ruby など「obj.method()」形式で呼び出される言語では callee 側は bare name のみ
 /// なので、qualname の末尾 (`.` 区切りの最後) でも判定する。
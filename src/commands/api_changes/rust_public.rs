@@ -169,19 +169,8 @@ pub(crate) fn rust_private_module_info(
     }
     let canonical_dir = std::fs::canonicalize(dir).ok()?;
     let abs = canonical_dir.join(rel);
-    let mut crate_root: Option<PathBuf> = None;
-    let mut anc = abs.parent();
-    while let Some(d) = anc {
-        if d.join("Cargo.toml").is_file() {
-            crate_root = Some(d.to_path_buf());
-            break;
-        }
-        if d == canonical_dir {
-            break;
-        }
-        anc = d.parent();
-    }
-    let crate_root = crate_root?;
+    let manifest = crate::engine::cargo_manifest::nearest_manifest(&canonical_dir, &abs)?;
+    let crate_root = manifest.parent()?;
     let src_dir = crate_root.join("src");
     if !src_dir.join("lib.rs").is_file() {
         return None;
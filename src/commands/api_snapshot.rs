@@ -0,0 +1,332 @@
+//! `api dump` / `api check`: git 履歴に依らない公開 API の snapshot。
+//!
+//! review の API 差分は `--base` の blob を旧側に使うため、履歴の無い tarball ビルドでは
+//! 使えない。公開シンボルの一覧 (API 差分と同じ公開面判定・シグネチャ正規化) を
+//! テキストに書き出してリポジトリに commit しておき、以降はそれを旧側として比較する。
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use rayon::prelude::*;
+use tracing::info;
+
+use crate::engine::paths::relative_path;
+use crate::error::{AstroError, ErrorCode};
+use crate::models::api_snapshot::ApiCheckResult;
+use crate::models::review::{ApiSymbol, ApiSymbolChange};
+use crate::output::{OutputOptions, serialize_cli_document};
+
+use super::api_changes::extract_exported_symbols_from_file_inner_with_lang;
+use super::common::{MAX_INPUT_SIZE, read_file_to_string_limited, timed};
+use super::dead_code::{is_test_path, resolve_dead_code_excludes};
+
+/// snapshot の先頭行。書式を変えるときは版を上げ、旧版の読み込みを拒否できるようにする。
+const SNAPSHOT_HEADER: &str = "# astro-sight api snapshot v1";
+
+/// `cmd_api_dump` の引数一式。
+pub struct CmdApiDumpOpts<'a> {
+    pub dir: &'a str,
+    pub glob: Option<&'a str>,
+    pub extra_exclude_dirs: &'a [String],
+    pub extra_exclude_globs: &'a [String],
+}
+
+/// `cmd_api_check` の引数一式。
+pub struct CmdApiCheckOpts<'a> {
+    pub dir: &'a str,
+    pub snapshot: &'a str,
+    pub glob: Option<&'a str>,
+    pub extra_exclude_dirs: &'a [String],
+    pub extra_exclude_globs: &'a [String],
+    pub output: OutputOptions,
+}
+
+/// snapshot の 1 行。フィールド順がそのまま並び順 (ファイル → 名前 → 種類 → シグネチャ)。
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct SnapshotEntry {
+    file: String,
+    name: String,
+    kind: String,
+    signature: String,
+}
+
+/// 公開シンボルを 1 行 1 件 (`file\tname\tkind\tsignature`) でソートして書き出す。
+///
+/// 出力は `--format` に依らず常にこのテキスト形式 (commit してレビューする前提なので、
+/// 差分が行単位で読めることを優先する)。
+pub fn cmd_api_dump(opts: &CmdApiDumpOpts<'_>) -> Result<()> {
+    let entries = collect_entries(
+        opts.dir,
+        opts.glob,
+        opts.extra_exclude_dirs,
+        opts.extra_exclude_globs,
+    )?;
+    let text = render_snapshot(&entries);
+    info!(
+        command = "api dump",
+        dir = opts.dir,
+        symbols = entries.len(),
+        "command completed"
+    );
+    print!("{text}");
+    Ok(())
+}
+
+/// `api dump` の snapshot と現在の作業ツリーの公開 API を比較する。
+/// 追加・削除・シグネチャ変更のいずれかがあれば結果を出力したうえで exit 1。
+pub fn cmd_api_check(opts: &CmdApiCheckOpts<'_>) -> Result<()> {
+    let snapshot = parse_snapshot(&read_file_to_string_limited(opts.snapshot, MAX_INPUT_SIZE)?)?;
+    let entries = collect_entries(
+        opts.dir,
+        opts.glob,
+        opts.extra_exclude_dirs,
+        opts.extra_exclude_globs,
+    )?;
+
+    let mut result = ApiCheckResult {
+        dir: std::fs::canonicalize(opts.dir)?
+            .to_string_lossy()
+            .to_string(),
+        snapshot: opts.snapshot.to_string(),
+        symbols: entries.len(),
+        ..Default::default()
+    };
+    compare_entries(snapshot, entries, &mut result);
+    result.ok = result.added.is_empty() && result.removed.is_empty() && result.modified.is_empty();
+
+    let text = serialize_cli_document(&result, opts.output)?;
+    info!(
+        command = "api check",
+        dir = opts.dir,
+        added = result.added.len(),
+        removed = result.removed.len(),
+        modified = result.modified.len(),
+        "command completed"
+    );
+    print!("{text}");
+    if !result.ok {
+        return super::exit_with_status(
+            1,
+            &format!(
+                "public API differs from {} ({} added, {} removed, {} modified); \
+                 run `astro-sight api dump` to update the snapshot",
+                opts.snapshot,
+                result.added.len(),
+                result.removed.len(),
+                result.modified.len()
+            ),
+        );
+    }
+    Ok(())
+}
+
+/// 作業ツリーの公開シンボルを集める。公開面の判定とシグネチャは API 差分の新側
+/// (`extract_new_file_facts`) と同じく trait impl メソッドを除き、framework entrypoint は残す。
+/// テストファイルと `.gitattributes` の linguist-generated は対象外。
+fn collect_entries(
+    dir: &str,
+    glob: Option<&str>,
+    extra_exclude_dirs: &[String],
+    extra_exclude_globs: &[String],
+) -> Result<Vec<SnapshotEntry>> {
+    let canonical_dir = std::fs::canonicalize(dir)?;
+    if !canonical_dir.is_dir() {
+        return Err(
+            AstroError::new(ErrorCode::InvalidRequest, format!("Not a directory: {dir}")).into(),
+        );
+    }
+    let default_excludes = resolve_dead_code_excludes(false, false, false);
+    let mut excludes: Vec<&str> = default_excludes.to_vec();
+    excludes.extend(extra_exclude_dirs.iter().map(String::as_str));
+    let globs: Vec<&str> = extra_exclude_globs.iter().map(String::as_str).collect();
+    let files: Vec<PathBuf> =
+        crate::engine::refs::collect_files_with_excludes(&canonical_dir, glob, &excludes, &globs)?;
+    let gitattrs = crate::engine::gitattributes::GitAttributes::load(&canonical_dir);
+
+    let dir_str = canonical_dir.to_string_lossy().to_string();
+    let mut entries: Vec<SnapshotEntry> = timed("api_snapshot", || {
+        files
+            .par_iter()
+            .filter_map(|path| relative_path(&canonical_dir, path))
+            .filter(|rel| !is_test_path(Path::new(rel)) && !gitattrs.is_generated(rel))
+            .flat_map_iter(|rel| {
+                extract_exported_symbols_from_file_inner_with_lang(&dir_str, &rel, true, false)
+                    .map(|(_, symbols)| symbols)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(move |(name, kind, signature)| SnapshotEntry {
+                        file: rel.clone(),
+                        name,
+                        kind,
+                        // fallback の先頭行シグネチャはタブを含み得るため、区切り文字と
+                        // 衝突しないよう空白を 1 つに潰す。
+                        signature: signature.split_whitespace().collect::<Vec<_>>().join(" "),
+                    })
+            })
+            .collect()
+    });
+    entries.sort();
+    entries.dedup();
+    Ok(entries)
+}
+
+fn render_snapshot(entries: &[SnapshotEntry]) -> String {
+    let mut out = String::from(SNAPSHOT_HEADER);
+    out.push('\n');
+    for entry in entries {
+        let _ = writeln!(
+            out,
+            "{}\t{}\t{}\t{}",
+            entry.file, entry.name, entry.kind, entry.signature
+        );
+    }
+    out
+}
+
+/// snapshot を読む。空行と `#` で始まる行 (先頭の版行を含む) は読み飛ばす。
+fn parse_snapshot(text: &str) -> Result<Vec<SnapshotEntry>> {
+    if let Some(header) = text.lines().next()
+        && header.starts_with("# astro-sight api snapshot")
+        && header != SNAPSHOT_HEADER
+    {
+        return Err(AstroError::new(
+            ErrorCode::InvalidRequest,
+            format!("unsupported api snapshot version: {header}"),
+        )
+        .into());
+    }
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+        .map(|(index, line)| {
+            let fields: Vec<&str> = line.splitn(4, '\t').collect();
+            let &[file, name, kind, signature] = fields.as_slice() else {
+                return Err(AstroError::new(
+                    ErrorCode::InvalidRequest,
+                    format!(
+                        "malformed api snapshot line {}: expected file, name, kind and signature separated by tabs",
+                        index + 1
+                    ),
+                )
+                .into());
+            };
+            Ok(SnapshotEntry {
+                file: file.to_string(),
+                name: name.to_string(),
+                kind: kind.to_string(),
+                signature: signature.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// snapshot (旧) と現在 (新) を `(file, name, kind)` 単位で突き合わせる。
+///
+/// 同じキーに 1 件ずつあってシグネチャだけが違えば `modified`、それ以外で片側にしか無い
+/// シグネチャは `removed` / `added`。オーバーロード等で同じキーが複数ある場合は
+/// 対応が定まらないため、一致しないシグネチャを削除と追加の組として出す。
+fn compare_entries(old: Vec<SnapshotEntry>, new: Vec<SnapshotEntry>, result: &mut ApiCheckResult) {
+    type Key = (String, String, String);
+    let mut grouped: BTreeMap<Key, (Vec<String>, Vec<String>)> = BTreeMap::new();
+    for (entries, is_new) in [(old, false), (new, true)] {
+        for entry in entries {
+            let sides = grouped
+                .entry((entry.file, entry.name, entry.kind))
+                .or_default();
+            if is_new {
+                sides.1.push(entry.signature);
+            } else {
+                sides.0.push(entry.signature);
+            }
+        }
+    }
+
+    for ((file, name, kind), (mut old_sigs, mut new_sigs)) in grouped {
+        let common: Vec<String> = old_sigs
+            .iter()
+            .filter(|sig| new_sigs.contains(sig))
+            .cloned()
+            .collect();
+        for sig in &common {
+            if let Some(pos) = old_sigs.iter().position(|s| s == sig) {
+                old_sigs.remove(pos);
+            }
+            if let Some(pos) = new_sigs.iter().position(|s| s == sig) {
+                new_sigs.remove(pos);
+            }
+        }
+        let symbol = || ApiSymbol {
+            name: name.clone(),
+            kind: kind.clone(),
+            file: file.clone(),
+            refs_internal: 0,
+        };
+        if let ([old_sig], [new_sig]) = (old_sigs.as_slice(), new_sigs.as_slice()) {
+            result.modified.push(ApiSymbolChange {
+                name: name.clone(),
+                kind: kind.clone(),
+                file: file.clone(),
+                old_signature: Some(old_sig.clone()),
+                new_signature: Some(new_sig.clone()),
+                no_resolved_internal_callers: false,
+            });
+            continue;
+        }
+        result.removed.extend(old_sigs.iter().map(|_| symbol()));
+        result.added.extend(new_sigs.iter().map(|_| symbol()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(file: &str, name: &str, signature: &str) -> SnapshotEntry {
+        SnapshotEntry {
+            file: file.to_string(),
+            name: name.to_string(),
+            kind: "function".to_string(),
+            signature: signature.to_string(),
+        }
+    }
+
+    #[test]
+    fn snapshot_round_trips_through_text() {
+        let entries = vec![
+            entry("src/lib.rs", "add", "pub fn add(a: i32, b: i32) -> i32"),
+            entry("src/lib.rs", "sub", "pub fn sub(a: i32, b: i32) -> i32"),
+        ];
+        let text = render_snapshot(&entries);
+        assert!(text.starts_with(SNAPSHOT_HEADER));
+        assert_eq!(parse_snapshot(&text).unwrap(), entries);
+        assert!(parse_snapshot("src/lib.rs\tadd\n").is_err());
+        assert!(parse_snapshot("# astro-sight api snapshot v0\n").is_err());
+    }
+
+    #[test]
+    fn compare_reports_added_removed_and_modified() {
+        let old = vec![
+            entry("src/lib.rs", "keep", "pub fn keep()"),
+            entry("src/lib.rs", "gone", "pub fn gone()"),
+            entry("src/lib.rs", "run", "pub fn run(a: u8)"),
+        ];
+        let new = vec![
+            entry("src/lib.rs", "keep", "pub fn keep()"),
+            entry("src/lib.rs", "fresh", "pub fn fresh()"),
+            entry("src/lib.rs", "run", "pub fn run(a: u16)"),
+        ];
+        let mut result = ApiCheckResult::default();
+        compare_entries(old, new, &mut result);
+        assert_eq!(result.added.len(), 1);
+        assert_eq!(result.added[0].name, "fresh");
+        assert_eq!(result.removed.len(), 1);
+        assert_eq!(result.removed[0].name, "gone");
+        assert_eq!(result.modified.len(), 1);
+        assert_eq!(
+            result.modified[0].new_signature.as_deref(),
+            Some("pub fn run(a: u16)")
+        );
+    }
+}
@@ -77,7 +77,10 @@ pub(crate) fn render_batch_record<T: serde::Serialize>(
     output: OutputOptions,
 ) -> BatchRendered {
     match output.format() {
-        OutputFormat::Json => BatchRendered::One(render_json_record(value)),
+        // レポート形式は dispatch 前に JSON へ倒してあるため、ここでは JSON と同じ扱い。
+        OutputFormat::Json | OutputFormat::Markdown | OutputFormat::Codeclimate => {
+            BatchRendered::One(render_json_record(value))
+        }
         OutputFormat::Toon => BatchRendered::One(render_toon_record(value, output)),
         OutputFormat::Auto => BatchRendered::Both {
             json: render_json_record(value),
@@ -99,7 +102,9 @@ fn render_toon_record<T: serde::Serialize>(value: &T, output: OutputOptions) ->
 /// 要素数 `[N]` と実際の item 数が食い違うと strict decoder が落ちるため。
 pub(crate) fn render_batch_error(e: &anyhow::Error, output: OutputOptions) -> BatchRendered {
     match output.format() {
-        OutputFormat::Json => BatchRendered::One(make_error_line(e)),
+        OutputFormat::Json | OutputFormat::Markdown | OutputFormat::Codeclimate => {
+            BatchRendered::One(make_error_line(e))
+        }
         OutputFormat::Toon => BatchRendered::One(toon_error_item(e, output)),
         OutputFormat::Auto => BatchRendered::Both {
             json: make_error_line(e),
@@ -348,6 +353,25 @@ pub fn batch_lint(
     rules: &[crate::models::lint::Rule],
     output: OutputOptions,
 ) -> Result<()> {
+    // Code Climate は「issue 配列 1 個」の文書なのでレコード単位には流せない。
+    // 全ファイルの issue を入力順に集めて 1 度だけ描画する。1 件でも lint できなければ
+    // 「指摘なし」と区別できなくなるため、その場でエラーにする。
+    if output.format() == OutputFormat::Codeclimate {
+        let pool = build_batch_pool()?;
+        let per_file: Vec<Vec<crate::output::codeclimate::Issue>> = pool.install(|| {
+            paths
+                .par_iter()
+                .map(|p| {
+                    service
+                        .lint_file(p, rules)
+                        .map(|result| super::lint_codeclimate_issues(p, &result))
+                })
+                .collect::<Result<Vec<_>>>()
+        })?;
+        let issues: Vec<_> = per_file.into_iter().flatten().collect();
+        print!("{}", crate::output::codeclimate::render(&issues, output)?);
+        return Ok(());
+    }
     batch_ndjson(paths, output, |p, output| {
         match service.lint_file(p, rules) {
             Ok(result) => render_batch_record(&result, output),
@@ -2,25 +2,42 @@ use anyhow::Result;
 use std::collections::HashSet;
 use tracing::info;
 
+use crate::config::{
+    FrameworkPreset, PRESET_DIR, discover_framework_presets, find_framework_preset,
+};
 use crate::engine::parser;
+use crate::engine::preset_refs::{PresetMatcher, PresetRefs};
+use crate::engine::spring_refs::AnnotationRules;
 use crate::error::{AstroError, ErrorCode};
 use crate::models::dead_code::DeadCodeResult;
-use crate::models::review::DeadSymbol;
+use crate::models::parse_health::ParseWarning;
+use crate::models::review::{DeadSymbol, DeadSymbolBlame};
 
 #[cfg(test)]
 use super::api_changes::extract_exported_symbols_from_file_inner;
 use super::api_changes::{
     bare_name, extract_exported_symbols_from_file_inner_with_lang, extract_symbol_lines,
 };
+use super::dead_code_blame::{ReferenceRemovals, attach_blame, sort_by_age};
+use super::dead_code_features::{detect_cargo_feature_issues, touched_feature_manifests};
+use super::dead_code_fix::build_fix_diff;
 use super::dead_code_member_liveness::{JsTsMemberLiveness, MemberStatus, PhpMemberLiveness};
+use super::dead_code_params::detect_unused_parameters;
+use super::dead_code_private::detect_private_dead_symbols;
+use super::dead_code_reachability::detect_unreachable_clusters;
 use super::git_input::{DiffSourceResolution, resolve_diff_source};
-use crate::output::{OutputOptions, serialize_cli_document};
+use super::review::codeclimate::{cargo_feature_issue, dead_symbol_issue, unused_parameter_issue};
+use crate::output::{OutputFormat, OutputOptions, serialize_cli_document};
 
 /// dead-code 検出本体。候補収集 → 名前インデックス構築 → 参照カウント →
 /// アセット参照収集 → 分類の段階パイプラインで (dead_symbols, test_only_symbols) を返す。
+///
+/// `conventions` の annotation が付いた宣言・注入される型・プリセット規約に合う名前は
+/// 参照ありとみなす (`resolve_dead_code_conventions`)。
 pub(crate) fn detect_dead_symbols_from_files(
     dir: &str,
     files: &[std::path::PathBuf],
+    conventions: &DeadCodeConventions,
 ) -> (Vec<DeadSymbol>, Vec<DeadSymbol>) {
     let canonical_dir = match std::fs::canonicalize(dir) {
         Ok(d) => d,
@@ -55,7 +72,7 @@ pub(crate) fn detect_dead_symbols_from_files(
         Err(_) => return (Vec::new(), Vec::new()),
     };
 
-    let asset_refs = collect_framework_asset_refs(&canonical_dir);
+    let asset_refs = collect_framework_asset_refs(&canonical_dir, conventions);
 
     let (mut dead, mut test_only) =
         classify_dead_symbols(&candidates, &index, &counts, &asset_refs);
@@ -282,16 +299,22 @@ fn build_dead_code_name_index(
 }
 
 /// AST の cross-file refs では追跡できないフレームワークアセット由来の参照名集合。
-struct FrameworkAssetRefs {
+pub(crate) struct FrameworkAssetRefs {
     /// AndroidManifest.xml / layout XML から参照されるシンボル名。
     xml: HashSet<String>,
     /// Angular テンプレート (`*.component.html` / inline template) から参照される名前。
     template: HashSet<String>,
+    /// Rails の routes.rb / コールバックのシンボル引数 / ビューテンプレートから参照される名前。
+    rails: HashSet<String>,
+    /// Spring / Jakarta 等の entrypoint annotation が付いた宣言名と注入される型名。
+    annotated: HashSet<String>,
+    /// ユーザー定義プリセットの規約 (基底クラス / メソッド名パターン / 文字列参照)。
+    preset: PresetRefs,
 }
 
 impl FrameworkAssetRefs {
     /// bare name / qualname のいずれかでフレームワークアセットから参照されているか判定する。
-    fn contains_symbol(&self, name: &str) -> bool {
+    pub(crate) fn contains_symbol(&self, name: &str) -> bool {
         // bare name と qualname (Container.method) の両方を突き合わせる。
         // layout XML の `android:onClick="handler"` や Angular template の
         // `(event)="handler()"` は単純名でしか書けないため bare で検索し、
@@ -301,11 +324,19 @@ impl FrameworkAssetRefs {
             || self.xml.contains(name)
             || self.template.contains(bare)
             || self.template.contains(name)
+            || self.rails.contains(bare)
+            || self.rails.contains(name)
+            || self.annotated.contains(bare)
+            || self.preset.contains_symbol(name)
     }
 }
 
-/// Android XML / Angular テンプレートからの参照集合を収集する。
-fn collect_framework_asset_refs(canonical_dir: &std::path::Path) -> FrameworkAssetRefs {
+/// Android XML / Angular テンプレート / Rails 規約 / entrypoint annotation /
+/// ユーザー定義プリセットからの参照集合を収集する。
+pub(crate) fn collect_framework_asset_refs(
+    canonical_dir: &std::path::Path,
+    conventions: &DeadCodeConventions,
+) -> FrameworkAssetRefs {
     // Android プロジェクトでは `AndroidManifest.xml` / layout XML から
     // シンボルが参照されうる（`<activity android:name=".MainActivity"/>` 等）。
     // Kotlin/Java AST のみでは追跡できない Android framework 経由の生存判定を補うため、
@@ -322,7 +353,35 @@ fn collect_framework_asset_refs(canonical_dir: &std::path::Path) -> FrameworkAss
     let template =
         crate::engine::angular_template_refs::collect_angular_template_refs(canonical_dir);
 
-    FrameworkAssetRefs { xml, template }
+    // Rails ではコントローラアクションが routes.rb から、コールバックが
+    // `before_action :authenticate` のシンボル引数から、ヘルパーがビューから呼ばれる。
+    // config/routes.rb も Gemfile の rails gem も無いプロジェクトでは空集合が返り副作用なし。
+    let rails = crate::engine::rails_refs::collect_rails_references(canonical_dir);
+
+    // `@GetMapping` / `@Bean` / `@Scheduled` 等の付いた宣言は DI コンテナがリフレクションで
+    // 呼ぶ。`spring` プリセットも `--entry-annotation` も無ければルールが空で走査しない。
+    let annotated = crate::engine::spring_refs::collect_annotation_references(
+        canonical_dir,
+        &conventions.annotations,
+    );
+
+    // ユーザー定義プリセット (`.astro-sight/presets/*.yaml`) が選ばれていれば、その
+    // `base_classes` / `method_patterns` / `string_refs` に合う名前を live にする。
+    let preset = conventions
+        .preset
+        .as_ref()
+        .map(|matcher| {
+            crate::engine::preset_refs::collect_preset_references(canonical_dir, matcher)
+        })
+        .unwrap_or_default();
+
+    FrameworkAssetRefs {
+        xml,
+        template,
+        rails,
+        annotated,
+        preset,
+    }
 }
 
 /// シンボルの production / test 参照数を返す。
@@ -413,6 +472,8 @@ fn classify_dead_symbols(
                         // 宣言行は attach_declaration_lines で後付けする
                         // (per-file の parse を 1 回に集約するため)。
                         line: None,
+                        visibility: None,
+                        blame: None,
                     });
                     continue;
                 }
@@ -428,6 +489,8 @@ fn classify_dead_symbols(
                         // 宣言行は attach_declaration_lines で後付けする
                         // (per-file の parse を 1 回に集約するため)。
                         line: None,
+                        visibility: None,
+                        blame: None,
                     });
                     continue;
                 }
@@ -450,6 +513,8 @@ fn classify_dead_symbols(
             kind: kind.clone(),
             file: file.clone(),
             line: None,
+            visibility: None,
+            blame: None,
         };
         if test_cnt > 0 {
             // PHPUnit テストクラス内のヘルパーメソッドは test_only からも除外する。
@@ -813,68 +878,94 @@ pub(crate) const NEXTJS_PRESET_EXCLUDE_GLOBS: &[&str] = &[
     "**/instrumentation.{js,ts}",
 ];
 
-/// `resolve_framework_globs` の auto-detect 対応版。
+/// Ruby on Rails の規約プリセット。Rails ランタイムや rake タスクが直接呼ぶファイルを除外する。
 ///
-/// 呼び出し側で `framework` が明示指定されていれば従来通り `resolve_framework_globs` に
-/// 委譲する。未指定の場合は `dir` 直下の `package.json` を読んで `next` 依存を検出し、
-/// 見つかれば `"nextjs"` プリセットを適用する。明示指定が auto detect より常に優先される。
+/// - `db/migrate/**` / `db/seeds*`: `rails db:migrate` / `db:seed` が `change` / `up` 等を呼ぶ
+/// - `config/**`, `config.ru`: 起動時に評価される設定・初期化コード
+/// - `lib/tasks/**`: rake タスク定義
+/// - `app/jobs/**`, `app/channels/**`, `app/mailboxes/**`: ActiveJob / ActionCable /
+///   ActionMailbox が `perform` / `subscribed` / `process` を規約名で呼ぶ
 ///
-/// 自動検出に失敗した場合 (package.json なし、JSON パース失敗、依存不一致) は空 Vec を
+/// コントローラ・モデル・ヘルパーはディレクトリ単位では除外せず、routes.rb の
+/// ルーティング先・コールバックのシンボル引数・ビューからの参照を
+/// [`crate::engine::rails_refs`] で収集して個別に生存扱いにする。
+pub(crate) const RAILS_PRESET_EXCLUDE_GLOBS: &[&str] = &[
+    "**/db/migrate/**",
+    "**/db/seeds.rb",
+    "**/db/seeds/**",
+    "**/config/**",
+    "**/config.ru",
+    "**/lib/tasks/**",
+    "**/app/jobs/**",
+    "**/app/channels/**",
+    "**/app/mailboxes/**",
+];
+
+/// フレームワークプリセットの除外 glob を返す。
+///
+/// 呼び出し側で `framework` が明示指定されていればその組み込みプリセットの glob を返す
+/// (組み込みに無い名前はユーザー定義プリセットの `entry_globs`)。未指定の場合は
+/// ユーザー定義プリセットの marker、`dir` 直下の `package.json` / `Gemfile` 等の順で
+/// フレームワークを検出し (`select_framework`)、見つかればそのプリセットを適用する。
+/// 明示指定が auto detect より常に優先される。
+///
+/// 自動検出に失敗した場合 (マーカーファイルなし、JSON パース失敗、依存不一致) は空 Vec を
 /// 返す。debug ログを出さない (副作用最小化のため、検出結果は呼び出し側の review JSON 等で
 /// 表現する余地を残す)。
 pub(crate) fn resolve_framework_globs_with_auto_detect(
     framework: Option<&str>,
     dir: &str,
 ) -> Result<Vec<String>> {
-    if framework.is_some() {
-        return resolve_framework_globs(framework);
-    }
-    match auto_detect_framework(dir) {
-        Some(name) => resolve_framework_globs(Some(name)),
-        None => Ok(Vec::new()),
+    match select_framework(framework, dir)? {
+        FrameworkSelection::None => Ok(Vec::new()),
+        FrameworkSelection::Builtin(framework) => Ok(framework.exclude_globs()),
+        FrameworkSelection::Preset(preset) => Ok(preset.entry_globs),
     }
 }
 
-/// `dir/package.json` を読んで Next.js プロジェクトかを判定する。
-///
-/// 判定: `package.json` の `dependencies` または `devDependencies` に `next` キーが存在
-/// すること。`peerDependencies` / `optionalDependencies` は Next.js ライブラリやテスト
-/// fixture で誤爆しやすいため対象外。
-///
-/// 失敗時 (ファイル無し / JSON パース失敗 / next 依存なし) は `None` を返す。
-///
-/// モノレポでの workspace 走査は将来対応 (初期実装は root `package.json` のみ)。
-pub(crate) fn auto_detect_framework(dir: &str) -> Option<&'static str> {
-    let pkg_path = std::path::Path::new(dir).join("package.json");
-    let text = std::fs::read_to_string(&pkg_path).ok()?;
-    let value: serde_json::Value = serde_json::from_str(&text).ok()?;
-    let has_next = ["dependencies", "devDependencies"].iter().any(|field| {
-        value
-            .get(field)
-            .and_then(|v| v.as_object())
-            .is_some_and(|deps| deps.contains_key("next"))
-    });
-    if has_next { Some("nextjs") } else { None }
+/// 組み込みフレームワークプリセット。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BuiltinFramework {
+    Laravel,
+    Nextjs,
+    Rails,
+    Spring,
 }
 
-/// フレームワーク名から対応する除外 glob プリセットを返す。
-/// 未知のフレームワーク名はエラー。
-///
-/// `**/app/X/**` / `**/database/X/**` のような app-prefix 付きパターンには、
-/// `**/X/**` という prefix 省略版も自動で追加する。これにより以下が同時にカバーされる:
-/// - `--dir <project>/app` のように `app/` 直下を指した場合の fallback
-/// - `app/` を別名 (例: `core/`) にリネームしている独自レイアウト
-/// - Laravel 配下に複数 module を抱えるモノレポ (`<root>/<sub>/Http/Controllers/...`)
-///
-/// 過剰除外の懸念: `**/Http/**` の類は Laravel 規約以外でも使われ得るが、
-/// 既定除外に `vendor/` / `node_modules/` 等のサードパーティ配下が入っており、
-/// なおかつ `--framework laravel` を指定しているのは Laravel プロジェクトのみという
-/// 前提なので、実用上の誤マッチはほぼ発生しない。
-pub(crate) fn resolve_framework_globs(framework: Option<&str>) -> Result<Vec<String>> {
-    match framework {
-        None => Ok(Vec::new()),
-        Some(name) => match name.to_ascii_lowercase().as_str() {
-            "laravel" => {
+/// `--framework` / 自動検出で受け付ける組み込みプリセット名 (小文字、別名を含む)。
+const BUILTIN_FRAMEWORKS: &[(&str, BuiltinFramework)] = &[
+    ("laravel", BuiltinFramework::Laravel),
+    ("nextjs", BuiltinFramework::Nextjs),
+    ("next", BuiltinFramework::Nextjs),
+    ("rails", BuiltinFramework::Rails),
+    ("spring", BuiltinFramework::Spring),
+    ("jakarta", BuiltinFramework::Spring),
+];
+
+impl BuiltinFramework {
+    /// 大文字小文字を無視して組み込みプリセット名を引く。
+    fn from_name(name: &str) -> Option<Self> {
+        BUILTIN_FRAMEWORKS
+            .iter()
+            .find(|(builtin, _)| builtin.eq_ignore_ascii_case(name))
+            .map(|&(_, framework)| framework)
+    }
+
+    /// dead-code の走査から外すエントリファイルの glob。
+    ///
+    /// `**/app/X/**` / `**/database/X/**` のような app-prefix 付きパターンには、
+    /// `**/X/**` という prefix 省略版も自動で追加する。これにより以下が同時にカバーされる:
+    /// - `--dir <project>/app` のように `app/` 直下を指した場合の fallback
+    /// - `app/` を別名 (例: `core/`) にリネームしている独自レイアウト
+    /// - Laravel 配下に複数 module を抱えるモノレポ (`<root>/<sub>/Http/Controllers/...`)
+    ///
+    /// 過剰除外の懸念: `**/Http/**` の類は Laravel 規約以外でも使われ得るが、
+    /// 既定除外に `vendor/` / `node_modules/` 等のサードパーティ配下が入っており、
+    /// なおかつ `--framework laravel` を指定しているのは Laravel プロジェクトのみという
+    /// 前提なので、実用上の誤マッチはほぼ発生しない。
+    fn exclude_globs(self) -> Vec<String> {
+        match self {
+            Self::Laravel => {
                 let mut globs: Vec<String> =
                     Vec::with_capacity(LARAVEL_PRESET_EXCLUDE_GLOBS.len() * 2);
                 for pat in LARAVEL_PRESET_EXCLUDE_GLOBS {
@@ -888,27 +979,165 @@ pub(crate) fn resolve_framework_globs(framework: Option<&str>) -> Result<Vec<Str
                         globs.push(format!("**/{rest}"));
                     }
                 }
-                Ok(globs)
+                globs
             }
-            "nextjs" | "next" => {
-                // Next.js は `app/` と `pages/` が予約ディレクトリ名で、`src/app/`
-                // / `src/pages/` レイアウトも `**/app/**` / `**/pages/**` グロブで
-                // そのままカバーされるため prefix 省略形は不要。
-                // むしろ `**/pages/**/*.{js,jsx,ts,tsx}` の省略形は
-                // `**/*.{js,jsx,ts,tsx}` となり全 TS/JS ファイルを誤除外するので
-                // Laravel と異なり省略形を生成しない。
-                Ok(NEXTJS_PRESET_EXCLUDE_GLOBS
-                    .iter()
-                    .map(|s| (*s).to_string())
-                    .collect())
+            // Next.js は `app/` と `pages/` が予約ディレクトリ名で、`src/app/`
+            // / `src/pages/` レイアウトも `**/app/**` / `**/pages/**` グロブで
+            // そのままカバーされるため prefix 省略形は不要。
+            // むしろ `**/pages/**/*.{js,jsx,ts,tsx}` の省略形は
+            // `**/*.{js,jsx,ts,tsx}` となり全 TS/JS ファイルを誤除外するので
+            // Laravel と異なり省略形を生成しない。
+            Self::Nextjs => NEXTJS_PRESET_EXCLUDE_GLOBS
+                .iter()
+                .map(|s| (*s).to_string())
+                .collect(),
+            // Rails のディレクトリ名 (`config` / `db` / `lib`) は他エコシステムでも一般的なため
+            // prefix 省略形は生成しない。
+            Self::Rails => RAILS_PRESET_EXCLUDE_GLOBS
+                .iter()
+                .map(|s| (*s).to_string())
+                .collect(),
+            // Spring / Jakarta はファイル配置ではなく annotation で entrypoint が決まるため
+            // glob は追加しない (`resolve_dead_code_conventions` が allowlist を有効にする)。
+            Self::Spring => Vec::new(),
+        }
+    }
+}
+
+/// `--framework` または自動検出で選ばれたフレームワーク。
+enum FrameworkSelection {
+    None,
+    Builtin(BuiltinFramework),
+    /// `.astro-sight/presets/` / ユーザー設定ディレクトリの YAML プリセット。
+    Preset(FrameworkPreset),
+}
+
+/// 明示指定は組み込み名 → ユーザー定義プリセット名の順に引き、どちらにも無ければエラー。
+/// 未指定なら marker が `dir` に存在するユーザー定義プリセットを、無ければ
+/// `auto_detect_framework` の組み込みプリセットを選ぶ (リポジトリ固有の定義を優先する)。
+/// 読めないプリセットファイルは明示指定されたものだけエラーにし、他は警告して飛ばす。
+fn select_framework(framework: Option<&str>, dir: &str) -> Result<FrameworkSelection> {
+    let dir_path = std::path::Path::new(dir);
+    if let Some(name) = framework {
+        if let Some(builtin) = BuiltinFramework::from_name(name) {
+            return Ok(FrameworkSelection::Builtin(builtin));
+        }
+        return find_framework_preset(dir_path, name)?
+            .map(FrameworkSelection::Preset)
+            .ok_or_else(|| unknown_framework_error(name));
+    }
+    if let Some(preset) = discover_framework_presets(dir_path)
+        .into_iter()
+        .find(|preset| preset.matches_markers(dir_path))
+    {
+        return Ok(FrameworkSelection::Preset(preset));
+    }
+    Ok(auto_detect_framework(dir)
+        .and_then(BuiltinFramework::from_name)
+        .map_or(FrameworkSelection::None, FrameworkSelection::Builtin))
+}
+
+fn unknown_framework_error(name: &str) -> anyhow::Error {
+    let supported: Vec<&str> = BUILTIN_FRAMEWORKS
+        .iter()
+        .map(|&(builtin, _)| builtin)
+        .collect();
+    AstroError::new(
+        ErrorCode::InvalidRequest,
+        format!(
+            "Unknown framework preset: {name} (supported: {}, or a preset file in {PRESET_DIR}/)",
+            supported.join(", ")
+        ),
+    )
+    .into()
+}
+
+/// `dir` 直下のマーカーファイルからフレームワークを判定する。
+///
+/// - `"nextjs"`: `package.json` の `dependencies` または `devDependencies` に `next` キーが
+///   存在すること。`peerDependencies` / `optionalDependencies` は Next.js ライブラリやテスト
+///   fixture で誤爆しやすいため対象外。
+/// - `"rails"`: `Gemfile` が `gem 'rails'` (または `railties`) を宣言していること。
+/// - `"spring"`: `pom.xml` / `build.gradle(.kts)` が `org.springframework` または
+///   `jakarta.*` に依存していること。
+///
+/// 複数に該当する場合は上の順で優先する。いずれにも該当しなければ `None` を返す。
+///
+/// モノレポでの workspace 走査は将来対応 (初期実装は root `package.json` のみ)。
+pub(crate) fn auto_detect_framework(dir: &str) -> Option<&'static str> {
+    let dir = std::path::Path::new(dir);
+    if package_json_has_next(dir) {
+        return Some("nextjs");
+    }
+    if crate::engine::rails_refs::gemfile_has_rails(dir) {
+        return Some("rails");
+    }
+    if crate::engine::spring_refs::build_files_have_spring(dir) {
+        return Some("spring");
+    }
+    None
+}
+
+/// `dir/package.json` の `dependencies` / `devDependencies` に `next` があるか。
+/// ファイル無し / JSON パース失敗は `false`。
+fn package_json_has_next(dir: &std::path::Path) -> bool {
+    let Ok(text) = std::fs::read_to_string(dir.join("package.json")) else {
+        return false;
+    };
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(&text) else {
+        return false;
+    };
+    ["dependencies", "devDependencies"].iter().any(|field| {
+        value
+            .get(field)
+            .and_then(|v| v.as_object())
+            .is_some_and(|deps| deps.contains_key("next"))
+    })
+}
+
+/// glob 以外でフレームワークの実行時呼び出しを表す規約 (annotation / プリセット規約)。
+#[derive(Debug, Default)]
+pub(crate) struct DeadCodeConventions {
+    /// 付いている宣言を entrypoint とみなす annotation。
+    annotations: AnnotationRules,
+    /// ユーザー定義プリセットの `base_classes` / `method_patterns` / `string_refs`。
+    preset: Option<PresetMatcher>,
+}
+
+/// dead-code で entrypoint とみなす規約を組み立てる。
+///
+/// `framework` (未指定なら `select_framework` の自動検出結果) が `spring` / `jakarta` なら
+/// 組み込み allowlist (`@RestController` のハンドラ、`@Bean`、`@Scheduled`、
+/// `@EventListener`、`@Configuration` 等と `@Autowired` / `@Inject`) を、ユーザー定義
+/// プリセットならその `annotations` と残りの規約を有効にする。
+/// `extra` (`--entry-annotation` / 設定の `entry_annotations`) はプリセットに関わらず追加する。
+pub(crate) fn resolve_dead_code_conventions(
+    framework: Option<&str>,
+    dir: &str,
+    extra: &[String],
+) -> Result<DeadCodeConventions> {
+    let mut conventions = DeadCodeConventions::default();
+    match select_framework(framework, dir)? {
+        FrameworkSelection::Builtin(BuiltinFramework::Spring) => {
+            conventions.annotations = AnnotationRules::spring();
+        }
+        FrameworkSelection::Preset(preset) => {
+            for name in &preset.annotations {
+                conventions.annotations.add_entry(name);
             }
-            other => Err(AstroError::new(
-                ErrorCode::InvalidRequest,
-                format!("Unknown framework preset: {other} (supported: laravel, nextjs)"),
-            )
-            .into()),
-        },
+            conventions.preset = Some(PresetMatcher::new(
+                &preset.name,
+                &preset.base_classes,
+                &preset.method_patterns,
+                &preset.string_refs,
+            )?);
+        }
+        FrameworkSelection::Builtin(_) | FrameworkSelection::None => {}
+    }
+    for name in extra {
+        conventions.annotations.add_entry(name);
     }
+    Ok(conventions)
 }
 
 /// 指定パスが既定除外対象のディレクトリセグメントを含むかを判定する。
@@ -1226,24 +1455,73 @@ pub(crate) fn is_python_test_symbol(
     false
 }
 
-#[allow(clippy::too_many_arguments)]
-pub fn cmd_dead_code(
-    dir: &str,
-    glob: Option<&str>,
-    diff: Option<&str>,
-    diff_file: Option<&str>,
-    git: bool,
-    base: &str,
-    staged: bool,
-    include_vendor: bool,
-    include_tests: bool,
-    include_build: bool,
-    framework: Option<&str>,
-    extra_exclude_dirs: &[String],
-    extra_exclude_globs: &[String],
-    output: OutputOptions,
-    dead_scope: crate::cli::DeadScope,
-) -> Result<()> {
+/// `cmd_dead_code` の引数一式。
+pub struct CmdDeadCodeOpts<'a> {
+    pub dir: &'a str,
+    pub glob: Option<&'a str>,
+    pub diff: Option<&'a str>,
+    pub diff_file: Option<&'a str>,
+    pub git: bool,
+    pub base: &'a str,
+    pub staged: bool,
+    pub include_vendor: bool,
+    pub include_tests: bool,
+    pub include_build: bool,
+    pub framework: Option<&'a str>,
+    pub extra_exclude_dirs: &'a [String],
+    pub extra_exclude_globs: &'a [String],
+    /// 付いている宣言を entrypoint とみなす追加の annotation 名 (`--entry-annotation`)。
+    pub entry_annotations: &'a [String],
+    pub output: OutputOptions,
+    pub dead_scope: crate::cli::DeadScope,
+    /// 公開 / 非公開 / 両方のどれを dead 判定の対象にするか。
+    pub scope: crate::cli::DeadCodeScope,
+    /// 参照件数ではなく起点からの到達可能性で dead を判定する。
+    pub reachability: bool,
+    /// `--reachability` の追加起点 (`NAME_GLOB` / `FILE_GLOB:NAME_GLOB`)。
+    pub entries: &'a [String],
+    /// 本体で参照されない関数 / メソッド引数も `unused_parameters` に載せる。
+    pub params: bool,
+    /// 使われない Cargo feature と未宣言 feature の参照も `cargo_features` に載せる。
+    pub cargo_features: bool,
+    /// 走査対象に parse error を持つファイルがあれば `parse_warnings` に載せる。
+    pub parse_warnings: bool,
+    /// JSON の代わりに dead シンボルを削除する unified diff を出力する。
+    pub fix_diff: bool,
+    /// 宣言の最終変更コミットと、最後の参照を消したコミットを付ける。
+    pub blame: bool,
+    /// dead_symbols / test_only_symbols の並び順。
+    pub sort: crate::cli::DeadCodeSort,
+}
+
+pub fn cmd_dead_code(opts: &CmdDeadCodeOpts<'_>) -> Result<()> {
+    let &CmdDeadCodeOpts {
+        dir,
+        glob,
+        diff,
+        diff_file,
+        git,
+        base,
+        staged,
+        include_vendor,
+        include_tests,
+        include_build,
+        framework,
+        extra_exclude_dirs,
+        extra_exclude_globs,
+        entry_annotations,
+        output,
+        dead_scope,
+        scope,
+        reachability,
+        entries,
+        params,
+        cargo_features,
+        parse_warnings,
+        fix_diff,
+        blame,
+        sort,
+    } = opts;
     let canonical_dir = std::fs::canonicalize(dir)?;
     if !canonical_dir.is_dir() {
         return Err(
@@ -1287,8 +1565,15 @@ pub fn cmd_dead_code(
                     test_only_symbols: Vec::new(),
                     skipped: None,
                     truncations,
+                    parse_warnings: Vec::new(),
+                    dead_clusters: Vec::new(),
+                    unused_parameters: Vec::new(),
+                    cargo_features: Vec::new(),
                 };
-                print!("{}", serialize_cli_document(&result, output)?);
+                // --fix-diff では削除するものが無い = 空の patch。
+                if !fix_diff {
+                    print!("{}", render_dead_code(&result, output, false)?);
+                }
                 return Ok(());
             }
 
@@ -1304,8 +1589,14 @@ pub fn cmd_dead_code(
                 test_only_symbols: Vec::new(),
                 skipped: Some(skip),
                 truncations: Vec::new(),
+                parse_warnings: Vec::new(),
+                dead_clusters: Vec::new(),
+                unused_parameters: Vec::new(),
+                cargo_features: Vec::new(),
             };
-            print!("{}", serialize_cli_document(&result, output)?);
+            if !fix_diff {
+                print!("{}", render_dead_code(&result, output, false)?);
+            }
             return Ok(());
         }
         DiffSourceResolution::NotRequested => (None, None, Vec::new()),
@@ -1329,7 +1620,69 @@ pub fn cmd_dead_code(
     };
 
     let scanned_files = files.len();
-    let (dead_symbols, test_only_symbols) = detect_dead_symbols_from_files(dir, &files);
+    // annotation / プリセット規約はどの検出経路 (公開 / 非公開 / 到達可能性) にも効かせる。
+    let conventions = resolve_dead_code_conventions(framework, dir, entry_annotations)?;
+    let mut dead_clusters = Vec::new();
+    let (mut dead_symbols, test_only_symbols) = if reachability {
+        // テストは起点なので既定の tests 除外に関わらずグラフへ含める。フレームワーク
+        // プリセットが外すファイルも参照元としては数え、そのシンボルは起点にする。
+        let mut graph_excludes = resolve_dead_code_excludes(include_vendor, true, include_build);
+        graph_excludes.extend(extra_exclude_dirs.iter().map(String::as_str));
+        let user_globs: Vec<&str> = extra_exclude_globs.iter().map(String::as_str).collect();
+        let graph_files = crate::engine::refs::collect_files_with_excludes(
+            &canonical_dir,
+            glob,
+            &graph_excludes,
+            &user_globs,
+        )?;
+        let preset_filtered: HashSet<std::path::PathBuf> =
+            crate::engine::refs::collect_files_with_excludes(
+                &canonical_dir,
+                glob,
+                &graph_excludes,
+                &combined_globs,
+            )?
+            .into_iter()
+            .collect();
+        let convention_files: HashSet<std::path::PathBuf> = graph_files
+            .iter()
+            .filter(|path| !preset_filtered.contains(*path))
+            .cloned()
+            .collect();
+        dead_clusters = detect_unreachable_clusters(
+            dir,
+            &files,
+            &graph_files,
+            &convention_files,
+            entries,
+            &conventions,
+        )?;
+        let flat = dead_clusters
+            .iter()
+            .flat_map(|cluster| cluster.symbols.iter().cloned())
+            .collect();
+        (flat, Vec::new())
+    } else if matches!(scope, crate::cli::DeadCodeScope::Private) {
+        (Vec::new(), Vec::new())
+    } else {
+        detect_dead_symbols_from_files(dir, &files, &conventions)
+    };
+    if !reachability && !matches!(scope, crate::cli::DeadCodeScope::Exported) {
+        // 非公開シンボルの参照元は可視範囲内に限られるが、diff 指定時でも範囲内の
+        // 変更されていないファイルを数える必要があるためワークスペース全体から選ぶ。
+        let workspace_files = crate::engine::refs::collect_files_with_excludes(
+            &canonical_dir,
+            glob,
+            &excludes,
+            &combined_globs,
+        )?;
+        dead_symbols.extend(detect_private_dead_symbols(
+            dir,
+            &files,
+            &workspace_files,
+            &conventions,
+        ));
+    }
 
     // dead-scope=touched-symbols: --git/--diff 指定時のみ意味を持つ。
     // diff の追加行情報が必要なので、has_diff のときだけ適用する。
@@ -1340,6 +1693,84 @@ pub fn cmd_dead_code(
     } else {
         dead_symbols
     };
+    // touched-symbols で落としたシンボルは cluster 側からも外す。
+    for cluster in &mut dead_clusters {
+        cluster.symbols.retain(|sym| {
+            dead_symbols
+                .iter()
+                .any(|d| d.file == sym.file && d.line == sym.line && d.name == sym.name)
+        });
+    }
+    dead_clusters.retain(|cluster| !cluster.symbols.is_empty());
+
+    let mut unused_parameters = if params {
+        detect_unused_parameters(dir, &files)
+    } else {
+        Vec::new()
+    };
+    // touched-symbols では dead と同じく、diff の `+` 行で宣言された引数だけを残す。
+    if matches!(dead_scope, crate::cli::DeadScope::TouchedSymbols)
+        && let Some(diff_input) = diff_input.as_deref()
+    {
+        retain_added_parameters(&mut unused_parameters, diff_input);
+    }
+
+    // feature の宣言と参照は crate 単位で突き合わせるため、diff 指定時は diff が
+    // `Cargo.toml` か feature gate の行に触れた crate だけを残す。
+    let cargo_features = if cargo_features {
+        let mut issues = detect_cargo_feature_issues(&canonical_dir, &excludes);
+        if let (Some(diff_input), Some(diff_files)) = (diff_input.as_deref(), diff_files.as_ref()) {
+            let touched = touched_feature_manifests(&canonical_dir, diff_input, diff_files);
+            issues.retain(|issue| touched.contains(&issue.manifest));
+        }
+        issues
+    } else {
+        Vec::new()
+    };
+
+    // 参照側の取りこぼしも dead の誤検出につながるため、diff 指定時でも
+    // 参照探索の対象になるワークスペース全体を調べる。
+    let parse_warnings = if parse_warnings {
+        let workspace_files = crate::engine::refs::collect_files_with_excludes(
+            &canonical_dir,
+            glob,
+            &excludes,
+            &combined_globs,
+        )?;
+        crate::engine::parse_health::scan_parse_health(&canonical_dir, &workspace_files)
+            .iter()
+            .filter(|health| health.is_unhealthy())
+            .map(ParseWarning::from)
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let (mut dead_symbols, mut test_only_symbols) = (dead_symbols, test_only_symbols);
+    if blame {
+        let mut references = ReferenceRemovals::default();
+        attach_blame(&canonical_dir, &mut dead_symbols, &mut references);
+        attach_blame(&canonical_dir, &mut test_only_symbols, &mut references);
+        // cluster 側は dead_symbols の複製なので、同じシンボルの blame を写す。
+        let blames: std::collections::HashMap<(&str, Option<usize>, &str), &DeadSymbolBlame> =
+            dead_symbols
+                .iter()
+                .filter_map(|d| {
+                    let blame = d.blame.as_ref()?;
+                    Some(((d.file.as_str(), d.line, d.name.as_str()), blame))
+                })
+                .collect();
+        for sym in dead_clusters.iter_mut().flat_map(|c| c.symbols.iter_mut()) {
+            sym.blame = blames
+                .get(&(sym.file.as_str(), sym.line, sym.name.as_str()))
+                .copied()
+                .cloned();
+        }
+    }
+    if matches!(sort, crate::cli::DeadCodeSort::Age) {
+        sort_by_age(&mut dead_symbols);
+        sort_by_age(&mut test_only_symbols);
+    }
 
     let result = DeadCodeResult {
         dir: canonical_dir.to_string_lossy().to_string(),
@@ -1348,9 +1779,34 @@ pub fn cmd_dead_code(
         test_only_symbols,
         skipped: None,
         truncations,
+        parse_warnings,
+        dead_clusters,
+        unused_parameters,
+        cargo_features,
     };
 
-    let text = serialize_cli_document(&result, output)?;
+    // `git apply` にそのまま渡せるよう、--fix-diff では patch だけを出力する。
+    let text = if fix_diff {
+        // --glob の外やテストにある import / 再 export も消さないと patch 適用後に
+        // ビルドが通らないため、後始末はワークスペース全体を対象にする。
+        let mut cleanup_excludes = resolve_dead_code_excludes(include_vendor, true, include_build);
+        cleanup_excludes.extend(extra_exclude_dirs.iter().map(String::as_str));
+        let user_globs: Vec<&str> = extra_exclude_globs.iter().map(String::as_str).collect();
+        let workspace_files = crate::engine::refs::collect_files_with_excludes(
+            &canonical_dir,
+            None,
+            &cleanup_excludes,
+            &user_globs,
+        )?;
+        build_fix_diff(
+            &canonical_dir,
+            &result.dead_symbols,
+            &result.test_only_symbols,
+            &workspace_files,
+        )
+    } else {
+        render_dead_code(&result, output, reachability)?
+    };
     info!(
         command = "dead-code",
         dir = dir,
@@ -1361,3 +1817,42 @@ pub fn cmd_dead_code(
     print!("{text}");
     Ok(())
 }
+
+/// 引数名の行が diff の `+` 行に含まれる未使用引数だけを残す。
+pub(crate) fn retain_added_parameters(
+    params: &mut Vec<crate::models::review::UnusedParameter>,
+    diff_input: &str,
+) {
+    let mut changed_lines: std::collections::HashMap<String, HashSet<usize>> =
+        std::collections::HashMap::new();
+    params.retain(|param| {
+        changed_lines
+            .entry(param.file.clone())
+            .or_insert_with(|| {
+                crate::engine::diff::extract_changed_new_lines(diff_input, &param.file)
+            })
+            .contains(&param.line)
+    });
+}
+
+/// `--format codeclimate` は dead symbol・未使用引数・Cargo feature の食い違いを
+/// GitLab Code Quality の issue 配列にする。
+/// `test_only_symbols` はテスト経由で実利用されている可能性があり「直すべき指摘」と
+/// 断定できないため含めない (review の informational バケットと同じ扱い)。
+fn render_dead_code(
+    result: &DeadCodeResult,
+    output: OutputOptions,
+    reachability: bool,
+) -> Result<String> {
+    if output.format() == OutputFormat::Codeclimate {
+        let issues: Vec<_> = result
+            .dead_symbols
+            .iter()
+            .map(|dead| dead_symbol_issue(dead, reachability))
+            .chain(result.unused_parameters.iter().map(unused_parameter_issue))
+            .chain(result.cargo_features.iter().map(cargo_feature_issue))
+            .collect();
+        return crate::output::codeclimate::render(&issues, output);
+    }
+    serialize_cli_document(result, output)
+}
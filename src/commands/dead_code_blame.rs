@@ -0,0 +1,258 @@
+//! dead-code `--blame`: dead シンボルの宣言を最後に変更したコミットと、最後の参照を
+//! 消したコミットを git の履歴から引く。
+//!
+//! 先週死んだ (うっかり消した可能性が高い) のか、何年も前から死んでいる (安全に消せる)
+//! のかを仕分けるための情報で、git 管理外や履歴に無いシンボルには何も付けない。
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+use std::process::Command;
+
+use camino::Utf8Path;
+use rayon::prelude::*;
+
+use crate::engine::cochange::{BlameInfo, parse_blame_porcelain};
+use crate::language::LangId;
+use crate::models::review::{BlameCommit, DeadSymbol, DeadSymbolBlame};
+
+use super::api_changes::bare_name;
+use super::dead_code_fix::{find_declaration, parse_symbols, read_source};
+
+/// 未コミット行に `git blame` が付ける SHA。
+const UNCOMMITTED_SHA: &str = "0000000000000000000000000000000000000000";
+
+/// 最後の参照を消したコミットを探すときに遡る、名前を含むコミットの上限。
+const REFERENCE_HISTORY_LIMIT: usize = 50;
+
+/// `reference_removal_commit` の結果を (名前, 宣言の言語) ごとに覚えておく。
+/// `git log -S` は履歴全体を舐めて重いため、同じ名前のシンボルや dead_symbols と
+/// test_only_symbols の間で引き直さない。
+#[derive(Default)]
+pub(crate) struct ReferenceRemovals(HashMap<(String, Option<LangId>), Option<BlameCommit>>);
+
+/// `symbols` に `blame` を付ける。`dir` は git 作業ツリー内のディレクトリで、
+/// `DeadSymbol.file` はそこからの相対パス。
+pub(crate) fn attach_blame(
+    dir: &Path,
+    symbols: &mut [DeadSymbol],
+    references: &mut ReferenceRemovals,
+) {
+    let mut by_file: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+    for (index, sym) in symbols.iter().enumerate() {
+        by_file.entry(sym.file.as_str()).or_default().push(index);
+    }
+    let commits: Vec<(usize, BlameCommit)> = by_file
+        .into_par_iter()
+        .flat_map_iter(|(file, indices)| {
+            let parsed = read_source(&dir.join(file))
+                .and_then(|(source, lang)| parse_symbols(&source, lang));
+            indices
+                .into_iter()
+                .filter_map(|index| {
+                    let sym = &symbols[index];
+                    // 宣言範囲が引けなければ宣言行だけを blame する。
+                    let lines = parsed
+                        .as_deref()
+                        .and_then(|syms| find_declaration(syms, &sym.name))
+                        .map(|decl| (decl.range.start.line, decl.range.end.line))
+                        .or_else(|| sym.line.map(|line| (line, line)))?;
+                    Some((index, last_commit_for_lines(dir, file, lines)?))
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    let reference_key = |sym: &DeadSymbol| {
+        (
+            bare_name(&sym.name).to_string(),
+            LangId::from_path(Utf8Path::new(&sym.file)).ok(),
+        )
+    };
+    let missing: HashSet<(String, Option<LangId>)> = commits
+        .iter()
+        .map(|&(index, _)| reference_key(&symbols[index]))
+        .filter(|key| !references.0.contains_key(key))
+        .collect();
+    let found: Vec<_> = missing
+        .into_par_iter()
+        .map(|key| {
+            let commit = reference_removal_commit(dir, &key.0, key.1);
+            (key, commit)
+        })
+        .collect();
+    references.0.extend(found);
+
+    for (index, commit) in commits {
+        let reference_removed_by = references.0[&reference_key(&symbols[index])].clone();
+        symbols[index].blame = Some(DeadSymbolBlame {
+            commit,
+            reference_removed_by,
+        });
+    }
+}
+
+/// `--sort age`: blame の日時が古い順 (同日時は元の順序) に並べ、blame の無いものは末尾に置く。
+pub(crate) fn sort_by_age(symbols: &mut [DeadSymbol]) {
+    symbols.sort_by_key(|sym| {
+        sym.blame
+            .as_ref()
+            .map_or(i64::MAX, |blame| blame.commit.timestamp)
+    });
+}
+
+/// 行範囲 (0-indexed, 両端含む) を最後に変更したコミット。未コミットの行は除く。
+fn last_commit_for_lines(
+    dir: &Path,
+    file: &str,
+    (start, end): (usize, usize),
+) -> Option<BlameCommit> {
+    let output = Command::new("git")
+        .args(["blame", "--line-porcelain", "-L"])
+        .arg(format!("{},{}", start + 1, end + 1))
+        .args(["--", file])
+        .current_dir(dir)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let infos = parse_blame_porcelain(&String::from_utf8_lossy(&output.stdout)).ok()?;
+    infos
+        .into_iter()
+        .filter(|(sha, _)| sha != UNCOMMITTED_SHA)
+        .filter_map(|(sha, info)| blame_commit(sha, &info))
+        .max_by(|a, b| {
+            a.timestamp
+                .cmp(&b.timestamp)
+                .then_with(|| a.sha.cmp(&b.sha))
+        })
+}
+
+fn blame_commit(sha: String, info: &BlameInfo) -> Option<BlameCommit> {
+    let timestamp = info.author_time?;
+    Some(BlameCommit {
+        sha,
+        author: info.author_name.clone().unwrap_or_default(),
+        date: format_timestamp(timestamp)?,
+        timestamp,
+    })
+}
+
+/// `name` の出現数が減った最新のコミット (`git log -S` の候補を新しい順に見て、
+/// 削除行の出現数が追加行を上回る最初のもの)。宣言自体は残っているので、
+/// 減ったのは参照側と推定できる。`lang` (宣言の言語) が分かれば、その定義を参照し得る
+/// 言語のファイルだけを見る (`run` 等のありふれた名前で無関係な言語のコミットを拾わない)。
+fn reference_removal_commit(dir: &Path, name: &str, lang: Option<LangId>) -> Option<BlameCommit> {
+    let pathspecs = lang.map_or_else(|| vec![".".to_string()], LangId::referencing_pathspecs);
+    let output = Command::new("git")
+        .arg("log")
+        .arg(format!("-S{name}"))
+        .arg(format!("-n{REFERENCE_HISTORY_LIMIT}"))
+        .args([
+            "--format=%x01%H%x00%an%x00%at",
+            "-p",
+            "-U0",
+            "--no-color",
+            "--",
+        ])
+        .args(&pathspecs)
+        .current_dir(dir)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    find_reference_removal(&String::from_utf8_lossy(&output.stdout), name)
+}
+
+/// `git log --format=%x01%H%x00%an%x00%at -p -U0` の出力から、`name` を含む削除行が
+/// 追加行より多い最初のコミットを返す。
+fn find_reference_removal(log: &str, name: &str) -> Option<BlameCommit> {
+    for entry in log.split('\x01').filter(|entry| !entry.is_empty()) {
+        let (header, patch) = entry.split_once('\n').unwrap_or((entry, ""));
+        let mut fields = header.split('\0');
+        let (Some(sha), Some(author), Some(time)) = (fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+        let mut removed = 0;
+        let mut added = 0;
+        for line in patch.lines() {
+            if line.starts_with("---") || line.starts_with("+++") {
+                continue;
+            }
+            if let Some(body) = line.strip_prefix('-') {
+                removed += count_identifier(body, name);
+            } else if let Some(body) = line.strip_prefix('+') {
+                added += count_identifier(body, name);
+            }
+        }
+        if removed > added {
+            let timestamp = time.trim().parse().ok()?;
+            return Some(BlameCommit {
+                sha: sha.to_string(),
+                author: author.to_string(),
+                date: format_timestamp(timestamp)?,
+                timestamp,
+            });
+        }
+    }
+    None
+}
+
+/// `text` に識別子として現れる `name` の数 (前後が識別子文字でないもの)。
+fn count_identifier(text: &str, name: &str) -> usize {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_' || c == '$';
+    text.match_indices(name)
+        .filter(|&(at, _)| {
+            !text[..at].chars().next_back().is_some_and(is_ident)
+                && !text[at + name.len()..].chars().next().is_some_and(is_ident)
+        })
+        .count()
+}
+
+/// unix 秒を RFC 3339 (UTC) にする。
+fn format_timestamp(timestamp: i64) -> Option<String> {
+    time::OffsetDateTime::from_unix_timestamp(timestamp)
+        .ok()?
+        .format(&time::format_description::well_known::Rfc3339)
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reference_removal_skips_commits_that_only_add_or_move_references() {
+        let log = "\x01cccc\0Carol\01700000300\n\
+diff --git a/src/a.rs b/src/a.rs\n\
+--- a/src/a.rs\n\
++++ b/src/a.rs\n\
+@@ -1 +1 @@\n\
+-let x = helper_v2();\n\
++let x = helper(1);\n\
+\x01bbbb\0Bob\01700000200\n\
+--- a/src/b.rs\n\
++++ b/src/b.rs\n\
+@@ -3 +2,0 @@\n\
+-    helper(2);\n\
+\x01aaaa\0Alice\01700000100\n\
++++ b/src/a.rs\n\
+@@ -0,0 +1 @@\n\
++pub fn helper(n: u8) {}\n";
+        let commit = find_reference_removal(log, "helper").unwrap();
+        assert_eq!(commit.sha, "bbbb");
+        assert_eq!(commit.author, "Bob");
+        assert_eq!(commit.date, "2023-11-14T22:16:40Z");
+        assert!(find_reference_removal(log, "unrelated").is_none());
+    }
+
+    #[test]
+    fn count_identifier_requires_word_boundaries() {
+        assert_eq!(
+            count_identifier("helper(helper_v2, $helper, helper)", "helper"),
+            2
+        );
+    }
+}
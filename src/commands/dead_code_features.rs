@@ -0,0 +1,606 @@
+//! dead-code `--cargo-features`: `Cargo.toml` の `[features]` と crate ソースの
+//! `cfg(feature = "...")` の食い違いを検出する。
+//!
+//! 参照として数えるのは `#[cfg]` / `#[cfg_attr]` / `#[doc(cfg)]` 属性と `cfg!` マクロの
+//! `feature = "..."`、build script が読む `CARGO_FEATURE_<NAME>` だけ。`cfg_if!` や
+//! `macro_rules!` の本体のように属性として parse されない token tree の中も同じ形を拾う。
+//! コメントや doc comment の例は tree-sitter で除く。crate のソースは `Cargo.toml` のディレクトリ
+//! 配下の `.rs` のうち、より近い `Cargo.toml` を持たないもの (tests / examples / benches /
+//! build.rs を含む)。
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use rayon::prelude::*;
+use tree_sitter::Node;
+
+use crate::engine::cargo_manifest::{MANIFEST_FILE, nearest_manifest, read_manifest_table};
+use crate::engine::diff::extract_changed_line_texts;
+use crate::engine::parser;
+use crate::engine::paths::relative_path;
+use crate::language::LangId;
+use crate::models::impact::DiffFile;
+use crate::models::review::{CargoFeatureIssue, CargoFeatureIssueKind};
+
+/// build script に渡される feature 環境変数の接頭辞 (`CARGO_FEATURE_<NAME>`)。
+const FEATURE_ENV_PREFIX: &str = "CARGO_FEATURE_";
+
+/// 引数に `feature = "..."` を書ける属性。
+const FEATURE_GATE_ATTRIBUTES: &[&str] = &["cfg", "cfg_attr", "doc"];
+
+/// `[package]` を持つ `Cargo.toml` 1 つ分。
+struct CrateManifest {
+    /// `canonical_dir` からの相対パス。
+    rel: String,
+    text: String,
+    /// `[features]` の宣言と、それぞれが有効にする項目。
+    features: Vec<(String, Vec<String>)>,
+    /// optional 依存。暗黙に同名の feature になる。
+    optional_deps: HashSet<String>,
+}
+
+/// 1 ファイル内の feature 参照。
+struct FileFeatureRefs {
+    /// 所属 crate (`manifests` の添字)。
+    owner: usize,
+    rel: String,
+    /// `feature = "name"` の name と行 (0-indexed)。
+    named: Vec<(String, usize)>,
+    /// `CARGO_FEATURE_<NAME>` の `<NAME>`。
+    env: Vec<String>,
+}
+
+/// `canonical_dir` 配下の crate について、使われない feature と未宣言 feature の参照を返す。
+///
+/// `excluded_dir_names` は報告対象の `Cargo.toml` にだけ効かせる (vendor の crate は
+/// 報告しないが、その配下の `.rs` を親 crate の参照と取り違えないよう所属判定には残す)。
+/// feature は `cfg` から参照されなくても、依存 (`dep:x` / `x/feat` / optional 依存名) や
+/// 参照される feature を有効にしていれば使われているとみなす。`default` は対象外。
+pub(crate) fn detect_cargo_feature_issues(
+    canonical_dir: &Path,
+    excluded_dir_names: &[&str],
+) -> Vec<CargoFeatureIssue> {
+    let (manifest_paths, sources) = collect_cargo_files(canonical_dir);
+    let mut owners: HashMap<PathBuf, usize> = HashMap::new();
+    let mut manifests: Vec<Option<CrateManifest>> = Vec::new();
+    for path in &manifest_paths {
+        let Some(rel) = relative_path(canonical_dir, path) else {
+            continue;
+        };
+        let Some(crate_dir) = path.parent() else {
+            continue;
+        };
+        owners.insert(crate_dir.to_path_buf(), manifests.len());
+        let reported = !rel
+            .split('/')
+            .any(|segment| excluded_dir_names.contains(&segment));
+        manifests.push(reported.then(|| read_manifest(path, rel)).flatten());
+    }
+    if manifests.iter().all(Option::is_none) {
+        return Vec::new();
+    }
+
+    let file_refs: Vec<FileFeatureRefs> = sources
+        .par_iter()
+        .filter_map(|path| {
+            let owner = path
+                .ancestors()
+                .skip(1)
+                .take_while(|dir| dir.starts_with(canonical_dir))
+                .find_map(|dir| owners.get(dir).copied())?;
+            manifests[owner].as_ref()?;
+            let rel = relative_path(canonical_dir, path)?;
+            let source = std::fs::read(path).ok()?;
+            let (named, env) = feature_references(&source);
+            Some(FileFeatureRefs {
+                owner,
+                rel,
+                named,
+                env,
+            })
+        })
+        .collect();
+
+    let mut issues = Vec::new();
+    for (index, manifest) in manifests.iter().enumerate() {
+        let Some(manifest) = manifest else {
+            continue;
+        };
+        let crate_refs: Vec<&FileFeatureRefs> = file_refs
+            .iter()
+            .filter(|refs| refs.owner == index)
+            .collect();
+        let referenced: HashSet<&str> = crate_refs
+            .iter()
+            .flat_map(|refs| refs.named.iter().map(|(name, _)| name.as_str()))
+            .collect();
+        let env_referenced: HashSet<&str> = crate_refs
+            .iter()
+            .flat_map(|refs| refs.env.iter().map(String::as_str))
+            .collect();
+        for feature in unused_features(manifest, &referenced, &env_referenced) {
+            issues.push(CargoFeatureIssue {
+                kind: CargoFeatureIssueKind::Unused,
+                feature: feature.to_string(),
+                file: manifest.rel.clone(),
+                line: feature_declaration_line(&manifest.text, feature),
+                manifest: manifest.rel.clone(),
+            });
+        }
+        for refs in &crate_refs {
+            for (name, line) in &refs.named {
+                let declared = manifest.features.iter().any(|(feature, _)| feature == name)
+                    || manifest.optional_deps.contains(name);
+                if !declared {
+                    issues.push(CargoFeatureIssue {
+                        kind: CargoFeatureIssueKind::Undeclared,
+                        feature: name.clone(),
+                        file: refs.rel.clone(),
+                        line: *line,
+                        manifest: manifest.rel.clone(),
+                    });
+                }
+            }
+        }
+    }
+    issues.sort_by(|a, b| {
+        a.file
+            .cmp(&b.file)
+            .then_with(|| a.line.cmp(&b.line))
+            .then_with(|| a.feature.cmp(&b.feature))
+    });
+    issues
+}
+
+/// diff が `Cargo.toml`、または `cfg` / `CARGO_FEATURE_` を含む `.rs` の行 (追加・削除とも) に
+/// 触れた crate の `Cargo.toml` (dir 相対)。
+pub(crate) fn touched_feature_manifests(
+    canonical_dir: &Path,
+    diff_input: &str,
+    diff_files: &[DiffFile],
+) -> HashSet<String> {
+    let mut touched = HashSet::new();
+    let paths: HashSet<&str> = diff_files
+        .iter()
+        .flat_map(|file| [file.old_path.as_str(), file.new_path.as_str()])
+        .filter(|path| *path != "/dev/null")
+        .collect();
+    for path in paths {
+        let gates = if path == MANIFEST_FILE || path.ends_with("/Cargo.toml") {
+            true
+        } else if path.ends_with(".rs") {
+            extract_changed_line_texts(diff_input, path)
+                .iter()
+                .any(|text| text.contains("cfg") || text.contains(FEATURE_ENV_PREFIX))
+        } else {
+            false
+        };
+        if gates
+            && let Some(manifest) = nearest_manifest(canonical_dir, &canonical_dir.join(path))
+            && let Some(rel) = relative_path(canonical_dir, &manifest)
+        {
+            touched.insert(rel);
+        }
+    }
+    touched
+}
+
+/// `Cargo.toml` と `.rs` を集める。`target/` はビルド生成物なので辿らない。
+fn collect_cargo_files(canonical_dir: &Path) -> (Vec<PathBuf>, Vec<PathBuf>) {
+    let mut manifests = Vec::new();
+    let mut sources = Vec::new();
+    let walker = ignore::WalkBuilder::new(canonical_dir)
+        .hidden(true)
+        .git_ignore(true)
+        .filter_entry(|entry| entry.file_name() != "target")
+        .build();
+    for entry in walker.flatten() {
+        if !entry.file_type().is_some_and(|ft| ft.is_file()) {
+            continue;
+        }
+        let path = entry.into_path();
+        if path.file_name().is_some_and(|name| name == MANIFEST_FILE) {
+            manifests.push(path);
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            sources.push(path);
+        }
+    }
+    (manifests, sources)
+}
+
+/// `Cargo.toml` を読む。parse できないものと `[package]` の無い virtual manifest は `None`。
+fn read_manifest(path: &Path, rel: String) -> Option<CrateManifest> {
+    let (text, table) = read_manifest_table(path)?;
+    table.get("package")?;
+    let features = table
+        .get("features")
+        .and_then(toml::Value::as_table)
+        .map(|features| {
+            features
+                .iter()
+                .map(|(name, enables)| {
+                    let enables = enables
+                        .as_array()
+                        .map(|items| {
+                            items
+                                .iter()
+                                .filter_map(|item| item.as_str().map(str::to_string))
+                                .collect()
+                        })
+                        .unwrap_or_default();
+                    (name.clone(), enables)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let mut optional_deps = HashSet::new();
+    collect_optional_deps(&table, &mut optional_deps);
+    if let Some(targets) = table.get("target").and_then(toml::Value::as_table) {
+        for target in targets.values().filter_map(toml::Value::as_table) {
+            collect_optional_deps(target, &mut optional_deps);
+        }
+    }
+    Some(CrateManifest {
+        rel,
+        text,
+        features,
+        optional_deps,
+    })
+}
+
+fn collect_optional_deps(table: &toml::Table, out: &mut HashSet<String>) {
+    for key in ["dependencies", "build-dependencies"] {
+        let Some(deps) = table.get(key).and_then(toml::Value::as_table) else {
+            continue;
+        };
+        for (name, spec) in deps {
+            if spec.get("optional").and_then(toml::Value::as_bool) == Some(true) {
+                out.insert(name.clone());
+            }
+        }
+    }
+}
+
+/// 参照されず、依存も参照される feature も有効にしない feature を宣言順に返す。
+fn unused_features<'a>(
+    manifest: &'a CrateManifest,
+    referenced: &HashSet<&str>,
+    env_referenced: &HashSet<&str>,
+) -> Vec<&'a str> {
+    let declared: HashSet<&str> = manifest
+        .features
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect();
+    let mut live: HashSet<&str> = manifest
+        .features
+        .iter()
+        .filter(|(name, enables)| {
+            name == "default"
+                || referenced.contains(name.as_str())
+                || env_referenced.contains(feature_env_name(name).as_str())
+                // 自 crate の feature 以外 (依存・optional 依存) を有効にするものは
+                // 依存側の挙動を切り替えるので、cfg から参照されなくても使われている。
+                || enables.iter().any(|item| !declared.contains(item.as_str()))
+        })
+        .map(|(name, _)| name.as_str())
+        .collect();
+    // 使われている feature を有効にする feature (`full = ["a", "b"]` 等) も使われている。
+    loop {
+        let before = live.len();
+        for (name, enables) in &manifest.features {
+            if enables.iter().any(|item| live.contains(item.as_str())) {
+                live.insert(name.as_str());
+            }
+        }
+        if live.len() == before {
+            break;
+        }
+    }
+    let mut unused: Vec<&str> = declared
+        .into_iter()
+        .filter(|name| !live.contains(name))
+        .collect();
+    unused.sort_by_key(|name| feature_declaration_line(&manifest.text, name));
+    unused
+}
+
+/// Cargo が build script に渡す環境変数名の `<NAME>` 部分 (大文字化し `-` を `_` に)。
+fn feature_env_name(feature: &str) -> String {
+    feature.to_uppercase().replace('-', "_")
+}
+
+/// `[features]` 内で `name` を宣言している行 (0-indexed)。見つからなければ `[features]` の行。
+fn feature_declaration_line(text: &str, name: &str) -> usize {
+    let mut in_features = false;
+    let mut header = 0;
+    for (index, line) in text.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        if line.starts_with('[') && !line.contains('=') {
+            in_features = line == "[features]";
+            if in_features {
+                header = index;
+            }
+            continue;
+        }
+        if in_features
+            && let Some((key, _)) = line.split_once('=')
+            && key.trim().trim_matches(|c| c == '"' || c == '\'') == name
+        {
+            return index;
+        }
+    }
+    header
+}
+
+/// ソース中の `feature = "name"` 参照 (name, 行) と `CARGO_FEATURE_<NAME>` の `<NAME>`。
+fn feature_references(source: &[u8]) -> (Vec<(String, usize)>, Vec<String>) {
+    let finder = |needle: &str| memchr::memmem::find(source, needle.as_bytes()).is_some();
+    if !finder("feature") && !finder(FEATURE_ENV_PREFIX) {
+        return (Vec::new(), Vec::new());
+    }
+    let Ok(tree) = parser::parse_source(source, LangId::Rust) else {
+        return (Vec::new(), Vec::new());
+    };
+    let mut named = Vec::new();
+    let mut env = Vec::new();
+    let mut stack = vec![tree.root_node()];
+    while let Some(node) = stack.pop() {
+        if let Some(arguments) = feature_gate_arguments(node, source) {
+            let Ok(text) = arguments.utf8_text(source) else {
+                continue;
+            };
+            for (offset, name) in scan_feature_predicates(text) {
+                let at = arguments.start_byte() + offset;
+                let line = source[..at].iter().filter(|&&b| b == b'\n').count();
+                named.push((name.to_string(), line));
+            }
+            continue;
+        }
+        // token tree は入れ子ごと 1 回で拾い、文字列の `CARGO_FEATURE_` を見るために子へは降りる。
+        if node.kind() == "token_tree"
+            && node
+                .parent()
+                .is_none_or(|parent| parent.kind() != "token_tree")
+        {
+            for (at, name) in token_tree_feature_predicates(node, source) {
+                let line = source[..at].iter().filter(|&&b| b == b'\n').count();
+                named.push((name, line));
+            }
+        }
+        if node.kind() == "string_literal"
+            && let Ok(text) = node.utf8_text(source)
+        {
+            for (at, _) in text.match_indices(FEATURE_ENV_PREFIX) {
+                let rest = &text[at + FEATURE_ENV_PREFIX.len()..];
+                let end = rest
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(rest.len());
+                if end > 0 {
+                    env.push(rest[..end].to_string());
+                }
+            }
+            continue;
+        }
+        // 出現順に見るため、子は逆順に積む。
+        let mut cursor = node.walk();
+        let children: Vec<Node<'_>> = node.children(&mut cursor).collect();
+        stack.extend(children.into_iter().rev());
+    }
+    (named, env)
+}
+
+/// feature を参照できる属性 (`#[cfg(...)]` / `#[cfg_attr(...)]` / `#[doc(cfg(...))]`) と
+/// `cfg!(...)` の引数部分。
+fn feature_gate_arguments<'a>(node: Node<'a>, source: &[u8]) -> Option<Node<'a>> {
+    match node.kind() {
+        "attribute" => {
+            let path = node.named_child(0)?.utf8_text(source).ok()?;
+            if !FEATURE_GATE_ATTRIBUTES.contains(&path) {
+                return None;
+            }
+            node.child_by_field_name("arguments")
+        }
+        "macro_invocation" => {
+            let name = node.child_by_field_name("macro")?.utf8_text(source).ok()?;
+            if name != "cfg" {
+                return None;
+            }
+            let mut cursor = node.walk();
+            node.named_children(&mut cursor)
+                .find(|child| child.kind() == "token_tree")
+        }
+        _ => None,
+    }
+}
+
+/// token tree (マクロ本体) 中の `#[cfg(...)]` / `#![cfg_attr(...)]` / `#[doc(...)]` /
+/// `cfg!(...)` にある `feature = "name"` を (source 上の byte offset, name) で返す。
+/// token tree の中身は属性として parse されないため、コメントを空白で潰したテキストから拾う。
+fn token_tree_feature_predicates(node: Node<'_>, source: &[u8]) -> Vec<(usize, String)> {
+    let base = node.start_byte();
+    let mut bytes = source[node.byte_range()].to_vec();
+    let mut stack = vec![node];
+    while let Some(current) = stack.pop() {
+        if current.kind().ends_with("comment") {
+            bytes[current.start_byte() - base..current.end_byte() - base].fill(b' ');
+            continue;
+        }
+        let mut cursor = current.walk();
+        stack.extend(current.children(&mut cursor));
+    }
+    let Ok(text) = String::from_utf8(bytes) else {
+        return Vec::new();
+    };
+
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    let mut found = Vec::new();
+    let mut push_arguments = |start: usize, (open, close): (u8, u8)| {
+        let Some(len) = balanced_len(&text[start..], open, close) else {
+            return;
+        };
+        for (offset, name) in scan_feature_predicates(&text[start..start + len]) {
+            found.push((base + start + offset, name.to_string()));
+        }
+    };
+    for (at, _) in text.match_indices('#') {
+        let rest = text[at + 1..].trim_start();
+        let rest = rest.strip_prefix('!').map_or(rest, str::trim_start);
+        let Some(inner) = rest.strip_prefix('[') else {
+            continue;
+        };
+        let inner = inner.trim_start();
+        let path = &inner[..inner.find(|c: char| !is_ident(c)).unwrap_or(inner.len())];
+        if FEATURE_GATE_ATTRIBUTES.contains(&path) {
+            push_arguments(text.len() - rest.len(), (b'[', b']'));
+        }
+    }
+    for (at, keyword) in text.match_indices("cfg") {
+        if text[..at].chars().next_back().is_some_and(is_ident) {
+            continue;
+        }
+        let Some(rest) = text[at + keyword.len()..].trim_start().strip_prefix('!') else {
+            continue;
+        };
+        let rest = rest.trim_start();
+        if rest.starts_with('(') {
+            push_arguments(text.len() - rest.len(), (b'(', b')'));
+        }
+    }
+    found.sort_by_key(|&(at, _)| at);
+    found
+}
+
+/// `text` 先頭の `open` に対応する `close` までの長さ (`close` を含む)。
+fn balanced_len(text: &str, open: u8, close: u8) -> Option<usize> {
+    let mut depth = 0usize;
+    for (index, byte) in text.bytes().enumerate() {
+        if byte == open {
+            depth += 1;
+        } else if byte == close {
+            depth = depth.checked_sub(1)?;
+            if depth == 0 {
+                return Some(index + 1);
+            }
+        }
+    }
+    None
+}
+
+/// `text` 中の `feature = "name"` を (`feature` の byte offset, name) で返す。
+/// `target_feature = "..."` のように識別子の一部になっているものは除く。
+fn scan_feature_predicates(text: &str) -> Vec<(usize, &str)> {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    let mut found = Vec::new();
+    for (at, keyword) in text.match_indices("feature") {
+        if text[..at].chars().next_back().is_some_and(is_ident) {
+            continue;
+        }
+        let Some(rest) = text[at + keyword.len()..].trim_start().strip_prefix('=') else {
+            continue;
+        };
+        let Some(rest) = rest.trim_start().strip_prefix('"') else {
+            continue;
+        };
+        if let Some(end) = rest.find('"') {
+            found.push((at, &rest[..end]));
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn feature_references_skip_comments_and_other_attributes() {
+        let source = br#"#![cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+// #[cfg(feature = "commented")]
+/// `#[cfg(feature = "documented")]`
+#[cfg(all(feature = "std", not(target_feature = "avx2")))]
+#[serde(feature = "not_a_gate")]
+fn run() {
+    if cfg!(feature = "fast") {
+        let _ = std::env::var("CARGO_FEATURE_SIMD_X86");
+    }
+}
+"#;
+        let (named, env) = feature_references(source);
+        assert_eq!(
+            named,
+            vec![
+                ("serde".to_string(), 0),
+                ("std".to_string(), 3),
+                ("fast".to_string(), 6),
+            ]
+        );
+        assert_eq!(env, vec!["SIMD_X86".to_string()]);
+    }
+
+    #[test]
+    fn feature_references_scan_macro_token_trees() {
+        let source = br#"cfg_if::cfg_if! {
+    if #[cfg(feature = "simd")] {
+        mod simd;
+    } else if #[cfg(all(feature = "std", not(feature = "no_alloc")))] {
+        // #[cfg(feature = "commented")]
+        mod fallback;
+    }
+}
+
+macro_rules! gated {
+    ($item:item) => {
+        #[cfg_attr(feature = "trace", derive(Debug))]
+        $item
+        const ON: bool = cfg!(feature = "fast");
+        #[serde(feature = "not_a_gate")]
+        const ENV: &str = "CARGO_FEATURE_JSON";
+    };
+}
+"#;
+        let (named, env) = feature_references(source);
+        assert_eq!(
+            named,
+            vec![
+                ("simd".to_string(), 1),
+                ("std".to_string(), 3),
+                ("no_alloc".to_string(), 3),
+                ("trace".to_string(), 11),
+                ("fast".to_string(), 13),
+            ]
+        );
+        assert_eq!(env, vec!["JSON".to_string()]);
+    }
+
+    #[test]
+    fn unused_features_keep_forwarding_and_umbrella_features() {
+        let text = "[package]\nname = \"demo\"\n\n[features]\ndefault = [\"std\"]\nstd = []\nserde = [\"dep:serde\"]\nfull = [\"std\", \"extra\"]\nextra = []\nstale = []\nsimd-x86 = []\n\n[dependencies]\nserde = { version = \"1\", optional = true }\n";
+        let manifest = CrateManifest {
+            rel: "Cargo.toml".to_string(),
+            text: text.to_string(),
+            features: [
+                "default", "std", "serde", "full", "extra", "stale", "simd-x86",
+            ]
+            .iter()
+            .map(|name| {
+                let enables = match *name {
+                    "default" => vec!["std".to_string()],
+                    "serde" => vec!["dep:serde".to_string()],
+                    "full" => vec!["std".to_string(), "extra".to_string()],
+                    _ => Vec::new(),
+                };
+                (name.to_string(), enables)
+            })
+            .collect(),
+            optional_deps: HashSet::from(["serde".to_string()]),
+        };
+        let referenced = HashSet::from(["std"]);
+        let env_referenced = HashSet::from(["SIMD_X86"]);
+        assert_eq!(
+            unused_features(&manifest, &referenced, &env_referenced),
+            vec!["extra", "stale"]
+        );
+        assert_eq!(feature_declaration_line(text, "stale"), 9);
+    }
+}
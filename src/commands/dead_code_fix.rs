@@ -0,0 +1,533 @@
+//! dead-code `--fix-diff`: dead シンボルの宣言を削除する unified diff の生成。
+//!
+//! 宣言範囲を直前の doc comment / attribute / decorator ごと行単位で削除し、その削除で
+//! 不要になる import と、削除したシンボルの再 export (`pub use` / `export { }`) を外す。
+//! 同名の宣言がワークスペースに複数あるシンボルと `test_only_symbols` は触らない。
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use rayon::prelude::*;
+
+use crate::engine::parser;
+use crate::engine::paths::relative_path;
+use crate::engine::unused_imports::find_orphaned_imports;
+use crate::language::LangId;
+use crate::models::import::UnusedImport;
+use crate::models::review::DeadSymbol;
+use crate::models::symbol::{Symbol, SymbolKind};
+
+use super::api_changes::bare_name;
+
+/// hunk の前後に付ける文脈行数 (`git diff` の既定と同じ)。
+const CONTEXT_LINES: usize = 3;
+
+/// 宣言と同じ行でその前に置ける語。symbol の range が `export` 等を含まない言語向けで、
+/// これ以外が前にある行 (1 行に複数の宣言がある等) は削除しない。
+const DECLARATION_PREFIX_WORDS: &[&str] = &[
+    "export",
+    "default",
+    "declare",
+    "pub",
+    "public",
+    "private",
+    "protected",
+    "internal",
+    "static",
+    "final",
+    "abstract",
+    "async",
+    "const",
+    "let",
+    "var",
+    "open",
+    "override",
+];
+
+/// 旧ファイルの行 `start..end` (0-indexed) を `replacement` に置き換える編集。
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct LineEdit {
+    start: usize,
+    end: usize,
+    replacement: Vec<String>,
+}
+
+/// 1 ファイル分の宣言削除。
+struct FileRemoval {
+    source: String,
+    lang: LangId,
+    /// 削除する行範囲 (重なりをまとめて昇順)。
+    lines: Vec<Range<usize>>,
+    /// 削除したシンボルの bare name。
+    names: Vec<String>,
+}
+
+/// `dead` の宣言を削除する unified diff を返す。削除できるものが無ければ空文字列。
+///
+/// `workspace_files` は同名宣言の判定と、再 export / import を外すファイルの探索に使う。
+pub(crate) fn build_fix_diff(
+    canonical_dir: &Path,
+    dead: &[DeadSymbol],
+    test_only: &[DeadSymbol],
+    workspace_files: &[PathBuf],
+) -> String {
+    let mut paths: Vec<PathBuf> = workspace_files.to_vec();
+    for sym in dead {
+        let path = canonical_dir.join(&sym.file);
+        if !paths.contains(&path) {
+            paths.push(path);
+        }
+    }
+
+    // bare name が同じ宣言が 2 つ以上あるものは、どれが参照されていないのかを
+    // 名前だけでは決められないため削除しない (dead 判定の同名スキップと同じ考え方)。
+    let declared = declared_name_counts(&paths);
+    let test_only_names: HashSet<&str> = test_only.iter().map(|s| bare_name(&s.name)).collect();
+    let mut by_file: BTreeMap<&str, Vec<&DeadSymbol>> = BTreeMap::new();
+    for sym in dead {
+        let bare = bare_name(&sym.name);
+        if test_only_names.contains(bare) || declared.get(bare).copied() != Some(1) {
+            continue;
+        }
+        by_file.entry(sym.file.as_str()).or_default().push(sym);
+    }
+
+    let removals: BTreeMap<String, FileRemoval> = by_file
+        .into_iter()
+        .filter_map(|(file, syms)| {
+            plan_declaration_removal(&canonical_dir.join(file), &syms)
+                .map(|removal| (file.to_string(), removal))
+        })
+        .collect();
+    if removals.is_empty() {
+        return String::new();
+    }
+    let removed_names: HashSet<&str> = removals
+        .values()
+        .flat_map(|r| r.names.iter().map(String::as_str))
+        .collect();
+
+    let mut diffs: Vec<(String, String)> = paths
+        .par_iter()
+        .filter_map(|path| {
+            let rel = relative_path(canonical_dir, path)?;
+            let removal = removals.get(&rel);
+            let (source, lang) = match removal {
+                Some(removal) => (removal.source.clone(), removal.lang),
+                None => {
+                    let (source, lang) = read_source(path)?;
+                    // 削除する名前を含まないファイルは再 export / import の持ちようがない。
+                    if !removed_names.iter().any(|name| source.contains(name)) {
+                        return None;
+                    }
+                    (source, lang)
+                }
+            };
+            let mut edits: Vec<LineEdit> = removal
+                .map(|r| {
+                    r.lines
+                        .iter()
+                        .map(|lines| LineEdit {
+                            start: lines.start,
+                            end: lines.end,
+                            replacement: Vec::new(),
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            let imports = import_edits(&source, lang, &rel, &edits, &removed_names);
+            edits.extend(imports);
+            if edits.is_empty() {
+                return None;
+            }
+            Some((
+                rel.clone(),
+                render_file_diff(&rel, &source, &without_overlaps(edits)),
+            ))
+        })
+        .collect();
+    diffs.sort_by(|a, b| a.0.cmp(&b.0));
+    diffs.into_iter().map(|(_, diff)| diff).collect()
+}
+
+pub(super) fn read_source(path: &Path) -> Option<(String, LangId)> {
+    let utf8 = camino::Utf8Path::from_path(path)?;
+    let buf = parser::read_file(utf8).ok()?;
+    let lang = parser::detect_lang(utf8, &buf).ok()?;
+    let source = std::str::from_utf8(&buf).ok()?.to_string();
+    Some((source, lang))
+}
+
+/// tree-sitter で解析できるファイルのシンボル。lexer-only 言語は range が粗いため対象外。
+pub(super) fn parse_symbols(source: &str, lang: LangId) -> Option<Vec<Symbol>> {
+    if lang.is_lexer_only() {
+        return None;
+    }
+    let tree = parser::parse_source(source.as_bytes(), lang).ok()?;
+    crate::engine::symbols::extract_symbols(tree.root_node(), source.as_bytes(), lang).ok()
+}
+
+fn is_declaration(sym: &Symbol) -> bool {
+    !matches!(sym.kind, SymbolKind::Import | SymbolKind::Parameter)
+}
+
+fn declared_name_counts(paths: &[PathBuf]) -> HashMap<String, usize> {
+    let names: Vec<Vec<String>> = paths
+        .par_iter()
+        .filter_map(|path| {
+            let (source, lang) = read_source(path)?;
+            let symbols =
+                if let crate::language::DetectedLang::LexerOnly(lexer_lang) = lang.detected() {
+                    crate::engine::lexer::extract_symbols(source.as_bytes(), lexer_lang)
+                } else {
+                    parse_symbols(&source, lang)?
+                };
+            Some(
+                symbols
+                    .into_iter()
+                    .filter(is_declaration)
+                    .map(|sym| sym.name)
+                    .collect(),
+            )
+        })
+        .collect();
+    let mut counts = HashMap::new();
+    for name in names.into_iter().flatten() {
+        *counts.entry(name).or_default() += 1;
+    }
+    counts
+}
+
+/// `path` 内の `syms` の宣言を削除する行範囲を求める。宣言を一意に特定できない
+/// (同じファイルに同名がある / 同じ行に別の宣言がある) シンボルは外す。
+fn plan_declaration_removal(path: &Path, syms: &[&DeadSymbol]) -> Option<FileRemoval> {
+    let (source, lang) = read_source(path)?;
+    let symbols = parse_symbols(&source, lang)?;
+    let lines: Vec<&str> = source.split_inclusive('\n').collect();
+    let hash_comments = matches!(lang, LangId::Python | LangId::Ruby | LangId::Bash);
+
+    let mut ranges = Vec::new();
+    let mut names = Vec::new();
+    for dead in syms {
+        let Some(sym) = find_declaration(&symbols, &dead.name) else {
+            continue;
+        };
+        if let Some(range) = declaration_lines(&lines, sym, hash_comments) {
+            ranges.push(range);
+            names.push(sym.name.clone());
+        }
+    }
+    if ranges.is_empty() {
+        return None;
+    }
+    ranges.sort_by_key(|range| range.start);
+    let mut merged: Vec<Range<usize>> = Vec::new();
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+    Some(FileRemoval {
+        source,
+        lang,
+        lines: merged,
+        names,
+    })
+}
+
+/// qualname (`Container.method`) / bare name に一致する宣言がファイル内に 1 つだけあれば返す。
+pub(super) fn find_declaration<'a>(symbols: &'a [Symbol], qualname: &str) -> Option<&'a Symbol> {
+    let (container, bare) = match qualname.rsplit_once('.') {
+        Some((container, bare)) => (Some(bare_name(container)), bare),
+        None => (None, qualname),
+    };
+    let mut candidates = symbols.iter().filter(|sym| {
+        is_declaration(sym)
+            && sym.name == bare
+            && container.is_none_or(|c| sym.container.as_deref() == Some(c))
+    });
+    match (candidates.next(), candidates.next()) {
+        (Some(sym), None) => Some(sym),
+        _ => None,
+    }
+}
+
+/// 宣言 `sym` を削除する行範囲。直前に連続する doc comment / attribute / decorator を含め、
+/// 宣言の後ろ (無ければ前) の空行を 1 行だけ一緒に消す。
+fn declaration_lines(lines: &[&str], sym: &Symbol, hash_comments: bool) -> Option<Range<usize>> {
+    let range = sym.range;
+    // 末尾の改行まで含むノードは end が次行の 0 桁目になる。
+    let (last, end_column) = if range.end.column == 0 && range.end.line > range.start.line {
+        (range.end.line - 1, lines.get(range.end.line - 1)?.len())
+    } else {
+        (range.end.line, range.end.column)
+    };
+    let prefix = lines.get(range.start.line)?.get(..range.start.column)?;
+    if !prefix
+        .split_whitespace()
+        .all(|word| DECLARATION_PREFIX_WORDS.contains(&word))
+    {
+        return None;
+    }
+    let suffix = lines.get(last)?.get(end_column..)?.trim();
+    if !suffix.trim_start_matches(';').trim().is_empty() {
+        return None;
+    }
+
+    let mut start = range.start.line;
+    while start > 0 && is_attached_line(lines[start - 1], hash_comments) {
+        start -= 1;
+    }
+    let mut end = last + 1;
+    if lines.get(end).is_some_and(|line| line.trim().is_empty()) {
+        end += 1;
+    } else if start > 0 && lines[start - 1].trim().is_empty() {
+        start -= 1;
+    }
+    Some(start..end)
+}
+
+/// 宣言の直前に付く行 (comment / Rust の `#[attr]` / `@decorator` / `@Annotation`) か。
+fn is_attached_line(line: &str, hash_comments: bool) -> bool {
+    let line = line.trim_start();
+    (line.starts_with("//") && !line.starts_with("//!"))
+        || line.starts_with("/*")
+        || line.starts_with('*')
+        || line.starts_with("#[")
+        || line.starts_with('@')
+        || (hash_comments && line.starts_with('#') && !line.starts_with("#!"))
+}
+
+/// 宣言の削除で不要になる import / 再 export を外す編集。
+fn import_edits(
+    source: &str,
+    lang: LangId,
+    rel: &str,
+    removals: &[LineEdit],
+    removed_names: &HashSet<&str>,
+) -> Vec<LineEdit> {
+    if lang.is_lexer_only() {
+        return Vec::new();
+    }
+    let Ok(tree) = parser::parse_source(source.as_bytes(), lang) else {
+        return Vec::new();
+    };
+    let line_starts: Vec<usize> = std::iter::once(0)
+        .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+        .collect();
+    let offset = |point: crate::models::location::Point| line_starts[point.line] + point.column;
+    let removed_bytes: Vec<Range<usize>> = removals
+        .iter()
+        .map(|edit| {
+            line_starts[edit.start]..line_starts.get(edit.end).copied().unwrap_or(source.len())
+        })
+        .collect();
+
+    let orphaned = find_orphaned_imports(
+        tree.root_node(),
+        source.as_bytes(),
+        lang,
+        rel,
+        &removed_bytes,
+        removed_names,
+    );
+    let mut statements: BTreeMap<(usize, usize), Vec<&UnusedImport>> = BTreeMap::new();
+    for import in &orphaned {
+        let start = import.statement_span.start;
+        statements
+            .entry((start.line, start.column))
+            .or_default()
+            .push(import);
+    }
+
+    let lines: Vec<&str> = source.split_inclusive('\n').collect();
+    let mut edits = Vec::new();
+    for imports in statements.values() {
+        let span = imports[0].statement_span;
+        let (first, last) = (span.start.line, span.end.line);
+        if removals
+            .iter()
+            .any(|edit| first < edit.end && edit.start <= last)
+        {
+            continue;
+        }
+        // 行単位で書き換えるため、同じ行に他の文 (別の import 等) がある文は触らない。
+        let alone = lines[first][..span.start.column].trim().is_empty()
+            && lines[last][span.end.column..].trim().is_empty();
+        if !alone {
+            continue;
+        }
+        if imports[0].whole_statement {
+            edits.push(LineEdit {
+                start: first,
+                end: last + 1,
+                replacement: Vec::new(),
+            });
+            continue;
+        }
+        let block_start = line_starts[first];
+        let mut block: String = lines[first..=last].concat();
+        let mut spans: Vec<Range<usize>> = imports
+            .iter()
+            .map(|import| {
+                offset(import.span.start) - block_start..offset(import.span.end) - block_start
+            })
+            .collect();
+        spans.sort_by_key(|span| std::cmp::Reverse(span.start));
+        for span in spans {
+            remove_list_item(&mut block, span);
+        }
+        edits.push(LineEdit {
+            start: first,
+            end: last + 1,
+            replacement: block
+                .split_inclusive('\n')
+                .filter(|line| !line.trim().is_empty())
+                .map(str::to_string)
+                .collect(),
+        });
+    }
+    edits
+}
+
+/// `edits` を start 昇順に並べ、先行する編集と行が重なるものを捨てる
+/// (`render_file_diff` の前提)。
+fn without_overlaps(mut edits: Vec<LineEdit>) -> Vec<LineEdit> {
+    edits.sort_by_key(|edit| (edit.start, edit.end));
+    let mut kept: Vec<LineEdit> = Vec::with_capacity(edits.len());
+    for edit in edits {
+        if kept.last().is_none_or(|prev| prev.end <= edit.start) {
+            kept.push(edit);
+        }
+    }
+    kept
+}
+
+/// `text` から list 要素 `span` を区切りのカンマごと消す。後ろにカンマがあればそれを、
+/// 無ければ前のカンマを消す (`{a, b}` → `{a}`)。
+fn remove_list_item(text: &mut String, span: Range<usize>) {
+    let rest = &text[span.end..];
+    let after = rest.trim_start();
+    let range = if after.starts_with(',') {
+        let comma_end = span.end + (rest.len() - after.len()) + 1;
+        let spaces =
+            text[comma_end..].len() - text[comma_end..].trim_start_matches([' ', '\t']).len();
+        span.start..comma_end + spaces
+    } else {
+        let before = text[..span.start].trim_end();
+        match before.strip_suffix(',') {
+            Some(kept) => kept.len()..span.end,
+            None => span,
+        }
+    };
+    text.replace_range(range, "");
+}
+
+/// 1 ファイル分の unified diff (`--- a/` / `+++ b/` ヘッダ付き)。`edits` は start 昇順で
+/// 重なりが無いこと。
+fn render_file_diff(path: &str, source: &str, edits: &[LineEdit]) -> String {
+    let lines: Vec<&str> = source.split_inclusive('\n').collect();
+    let mut out = format!("--- a/{path}\n+++ b/{path}\n");
+    // ここまでの hunk で増減した行数 (新ファイルの行番号 = 旧行番号 + delta)。
+    let mut delta: isize = 0;
+    let mut i = 0;
+    while i < edits.len() {
+        // 文脈行が重なる編集は 1 つの hunk にまとめる。
+        let mut j = i;
+        while j + 1 < edits.len() && edits[j + 1].start <= edits[j].end + 2 * CONTEXT_LINES {
+            j += 1;
+        }
+        let old_start = edits[i].start.saturating_sub(CONTEXT_LINES);
+        let old_end = (edits[j].end + CONTEXT_LINES).min(lines.len());
+        let mut body = String::new();
+        let mut new_count = 0;
+        let mut cursor = old_start;
+        for edit in &edits[i..=j] {
+            for line in &lines[cursor..edit.start] {
+                push_diff_line(&mut body, ' ', line);
+                new_count += 1;
+            }
+            for line in &lines[edit.start..edit.end] {
+                push_diff_line(&mut body, '-', line);
+            }
+            for line in &edit.replacement {
+                push_diff_line(&mut body, '+', line);
+                new_count += 1;
+            }
+            cursor = edit.end;
+        }
+        for line in &lines[cursor..old_end] {
+            push_diff_line(&mut body, ' ', line);
+            new_count += 1;
+        }
+        let old_count = old_end - old_start;
+        let new_start = old_start.saturating_add_signed(delta);
+        out.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            hunk_range(old_start, old_count),
+            hunk_range(new_start, new_count)
+        ));
+        out.push_str(&body);
+        delta += new_count as isize - old_count as isize;
+        i = j + 1;
+    }
+    out
+}
+
+/// hunk ヘッダの `start,count`。行数 0 の側は直前の行番号を書く (unified diff の規約)。
+fn hunk_range(start: usize, count: usize) -> String {
+    match count {
+        0 => format!("{start},0"),
+        1 => (start + 1).to_string(),
+        _ => format!("{},{count}", start + 1),
+    }
+}
+
+fn push_diff_line(out: &mut String, marker: char, line: &str) {
+    out.push(marker);
+    out.push_str(line);
+    if !line.ends_with('\n') {
+        out.push_str("\n\\ No newline at end of file\n");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn import_edits_skip_statements_sharing_a_line() {
+        let source = "import { deadA, liveA } from \"./a\"; import { deadB, liveB } from \"./b\";\nliveA();\nliveB();\n";
+        let removed: HashSet<&str> = ["deadA", "deadB"].into_iter().collect();
+        let edits = import_edits(source, LangId::Typescript, "main.ts", &[], &removed);
+        assert!(edits.is_empty());
+        assert_eq!(
+            render_file_diff("main.ts", source, &without_overlaps(edits)),
+            "--- a/main.ts\n+++ b/main.ts\n"
+        );
+    }
+
+    #[test]
+    fn import_edits_rewrite_partial_import_on_its_own_line() {
+        let source = "import { deadA, liveA } from \"./a\";\nliveA();\n";
+        let removed: HashSet<&str> = ["deadA"].into_iter().collect();
+        let edits = import_edits(source, LangId::Typescript, "main.ts", &[], &removed);
+        assert_eq!(edits.len(), 1);
+        assert_eq!((edits[0].start, edits[0].end), (0, 1));
+        assert_eq!(edits[0].replacement, ["import { liveA } from \"./a\";\n"]);
+    }
+
+    #[test]
+    fn without_overlaps_drops_edits_on_already_edited_lines() {
+        let edit = |start, end| LineEdit {
+            start,
+            end,
+            replacement: Vec::new(),
+        };
+        let kept = without_overlaps(vec![edit(3, 4), edit(0, 2), edit(1, 2), edit(2, 3)]);
+        let ranges: Vec<(usize, usize)> = kept.iter().map(|e| (e.start, e.end)).collect();
+        assert_eq!(ranges, [(0, 2), (2, 3), (3, 4)]);
+    }
+}
@@ -0,0 +1,416 @@
+//! dead-code `--params`: 関数本体で一度も参照されない引数の検出。
+//!
+//! 判定は関数単位で閉じる (引数は本体の外から参照できない) ため、参照探索の
+//! ワークスペース走査は行わない。本体の非コメント葉ノードを単語に分割した集合に
+//! 引数名が現れなければ未使用とする。文字列内の単語も数えるため、補間
+//! (`format!("{x}")` / f-string 等) で使われる引数は保守的に使用扱いになる。
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use rayon::prelude::*;
+use tree_sitter::Node;
+
+use crate::engine::parser::{self, child_of_kind};
+use crate::engine::paths::relative_path;
+use crate::language::LangId;
+use crate::models::review::UnusedParameter;
+use crate::models::symbol::{Symbol, SymbolKind};
+
+use super::api_changes::parameter_check_candidates;
+
+/// 親クラス / interface の指定を表す子ノード。override 修飾が任意 (Java / TS) か
+/// 存在しない (PHP / C# の interface 実装 / Python) 言語では、これを持つクラスの
+/// メソッドは署名を継承元に決められている可能性があるため判定しない。
+const CLASS_HERITAGE_KINDS: &[&str] = &[
+    "class_heritage",
+    "superclass",
+    "super_interfaces",
+    "base_list",
+    "base_clause",
+    "class_interface_clause",
+];
+
+/// 引数を名前ではなく位置や一覧で読む呼び出し。本体に現れたら全引数を使用扱いにする。
+const DYNAMIC_ARGUMENT_WORDS: &[&str] = &[
+    "arguments",
+    "locals",
+    "vars",
+    "func_get_args",
+    "func_get_arg",
+    "get_defined_vars",
+];
+
+/// 本体だけの stub として扱う Rust マクロ。
+const RUST_STUB_MACROS: &[&str] = &["todo", "unimplemented", "unreachable", "panic"];
+
+/// `files` 内の関数 / メソッドのうち、本体で一度も参照されない引数を返す。
+///
+/// trait / interface 実装・override・フレームワーク規約の入口など署名を外から決められて
+/// いる関数、`_` 始まりの引数、本体を持たない / stub だけの関数は対象外。JS / TS の
+/// 関数式 (callback) は呼び出し側が渡す引数の位置を変えられないため、使われている
+/// 引数より前の未使用引数は報告しない。
+pub(crate) fn detect_unused_parameters(dir: &str, files: &[PathBuf]) -> Vec<UnusedParameter> {
+    let Ok(canonical_dir) = std::fs::canonicalize(dir) else {
+        return Vec::new();
+    };
+    let mut found: Vec<UnusedParameter> = files
+        .par_iter()
+        .filter_map(|path| analyze_file(&canonical_dir, path))
+        .flatten()
+        .collect();
+    found.sort_by(|a, b| {
+        a.file
+            .cmp(&b.file)
+            .then_with(|| a.line.cmp(&b.line))
+            .then_with(|| a.column.cmp(&b.column))
+    });
+    found
+}
+
+fn analyze_file(canonical_dir: &Path, path: &Path) -> Option<Vec<UnusedParameter>> {
+    let canonical_path = std::fs::canonicalize(path).ok()?;
+    let rel = relative_path(canonical_dir, &canonical_path)?;
+    let utf8_path = camino::Utf8Path::from_path(&canonical_path)?;
+    let source = parser::read_file(utf8_path).ok()?;
+    let lang = parser::detect_lang(utf8_path, &source).ok()?;
+    if lang.is_lexer_only() || !is_supported(lang) {
+        return None;
+    }
+    let tree = parser::parse_source(&source, lang).ok()?;
+    let root = tree.root_node();
+    let syms = crate::engine::symbols::extract_symbols(root, &source, lang).ok()?;
+    let mut found = Vec::new();
+    for (sym, qualname) in parameter_check_candidates(&syms, root, &source, lang, &rel) {
+        found.extend(unused_parameters_of(
+            root, &source, lang, sym, &qualname, &rel,
+        ));
+    }
+    Some(found)
+}
+
+/// 引数リストの構造を解釈できる言語。C/C++ は宣言と定義で引数名が食い違い得る上に
+/// コールバック署名の慣習 (未使用引数を名前ごと残す) が強く、Swift は外部引数ラベル、
+/// Ruby はブロック引数の暗黙受け渡しがあり、名前だけでは判定できないため扱わない。
+fn is_supported(lang: LangId) -> bool {
+    matches!(
+        lang,
+        LangId::Rust
+            | LangId::Go
+            | LangId::Python
+            | LangId::Java
+            | LangId::Kotlin
+            | LangId::CSharp
+            | LangId::Php
+            | LangId::Javascript
+            | LangId::Typescript
+            | LangId::Tsx
+    )
+}
+
+fn unused_parameters_of(
+    root: Node<'_>,
+    source: &[u8],
+    lang: LangId,
+    sym: &Symbol,
+    qualname: &str,
+    file: &str,
+) -> Vec<UnusedParameter> {
+    // Go のメソッドは interface を暗黙に満たすため、署名が interface に決められて
+    // いるかを宣言から判別できない。
+    if lang == LangId::Go && sym.kind == SymbolKind::Method {
+        return Vec::new();
+    }
+    let Some(func) = function_node(root, sym) else {
+        return Vec::new();
+    };
+    if is_in_inheriting_class(func, lang) {
+        return Vec::new();
+    }
+    let Some((params, body)) = parameters_and_body(func) else {
+        return Vec::new();
+    };
+    let names = parameter_names(params, source, lang);
+    if names.is_empty() || is_stub_body(body, source, lang) {
+        return Vec::new();
+    }
+    let used = collect_used_words(body, source);
+    if DYNAMIC_ARGUMENT_WORDS
+        .iter()
+        .any(|word| used.contains(word))
+    {
+        return Vec::new();
+    }
+    // `(req, res) => res.send()` の `req` は位置を保つためだけの引数。
+    let first_reportable = if is_callback(func, lang) {
+        names
+            .iter()
+            .rposition(|(name, _)| used.contains(name))
+            .map_or(0, |ix| ix + 1)
+    } else {
+        0
+    };
+    names[first_reportable..]
+        .iter()
+        .filter(|(name, _)| !used.contains(name))
+        .map(|(name, node)| UnusedParameter {
+            name: name.to_string(),
+            function: qualname.to_string(),
+            file: file.to_string(),
+            line: node.start_position().row,
+            column: node.start_position().column,
+        })
+        .collect()
+}
+
+/// symbol range に対応する関数ノード。`const f = (a) => ...` のように宣言子が
+/// symbol になる場合は値側の関数式を返す。
+fn function_node<'tree>(root: Node<'tree>, sym: &Symbol) -> Option<Node<'tree>> {
+    let start = tree_sitter::Point {
+        row: sym.range.start.line,
+        column: sym.range.start.column,
+    };
+    let end = tree_sitter::Point {
+        row: sym.range.end.line,
+        column: sym.range.end.column,
+    };
+    let node = root.descendant_for_point_range(start, end)?;
+    match node.child_by_field_name("value") {
+        Some(value)
+            if matches!(
+                value.kind(),
+                "arrow_function" | "function_expression" | "function" | "generator_function"
+            ) =>
+        {
+            Some(value)
+        }
+        _ => Some(node),
+    }
+}
+
+/// 引数の並びを呼び出し側 (フレームワーク / 高階関数) に決められる JS / TS の関数式。
+fn is_callback(func: Node<'_>, lang: LangId) -> bool {
+    matches!(lang, LangId::Javascript | LangId::Typescript | LangId::Tsx)
+        && matches!(
+            func.kind(),
+            "arrow_function" | "function_expression" | "function" | "generator_function"
+        )
+}
+
+fn parameters_and_body(func: Node<'_>) -> Option<(Node<'_>, Node<'_>)> {
+    let params = func
+        .child_by_field_name("parameters")
+        .or_else(|| func.child_by_field_name("parameter"))
+        .or_else(|| child_of_kind(func, "function_value_parameters"))?;
+    let body = func
+        .child_by_field_name("body")
+        .or_else(|| child_of_kind(func, "function_body"))?;
+    Some((params, body))
+}
+
+fn is_in_inheriting_class(func: Node<'_>, lang: LangId) -> bool {
+    if !matches!(
+        lang,
+        LangId::Python
+            | LangId::Java
+            | LangId::CSharp
+            | LangId::Php
+            | LangId::Javascript
+            | LangId::Typescript
+            | LangId::Tsx
+    ) {
+        return false;
+    }
+    let mut cur = func.parent();
+    while let Some(node) = cur {
+        match node.kind() {
+            "class_definition" => return node.child_by_field_name("superclasses").is_some(),
+            "class_declaration"
+            | "abstract_class_declaration"
+            | "class"
+            | "enum_declaration"
+            | "record_declaration" => {
+                let mut cursor = node.walk();
+                return node
+                    .named_children(&mut cursor)
+                    .any(|child| CLASS_HERITAGE_KINDS.contains(&child.kind()));
+            }
+            _ => {}
+        }
+        cur = node.parent();
+    }
+    false
+}
+
+/// 引数名と、その名前ノード (位置報告用) を宣言順に返す。`_` 始まりの名前、
+/// レシーバ (`self` / `cls` / `this`)、可変長引数、分割代入、TS の parameter property
+/// (`constructor(private x)` はフィールド宣言を兼ねる) は含めない。
+fn parameter_names<'a, 'tree>(
+    params: Node<'tree>,
+    source: &'a [u8],
+    lang: LangId,
+) -> Vec<(&'a str, Node<'tree>)> {
+    let mut nodes = Vec::new();
+    if params.kind() == "identifier" {
+        // JS/TS の括弧なし単一引数 arrow function (`x => ...`)。
+        nodes.push(params);
+    }
+    let mut cursor = params.walk();
+    for param in params.named_children(&mut cursor) {
+        match lang {
+            LangId::Rust => {
+                if param.kind() == "parameter"
+                    && let Some(pattern) = param.child_by_field_name("pattern")
+                {
+                    match pattern.kind() {
+                        "identifier" => nodes.push(pattern),
+                        "mut_pattern" => nodes.extend(child_of_kind(pattern, "identifier")),
+                        _ => {}
+                    }
+                }
+            }
+            LangId::Javascript | LangId::Typescript | LangId::Tsx => match param.kind() {
+                "identifier" => nodes.push(param),
+                "assignment_pattern" => nodes.extend(
+                    param
+                        .child_by_field_name("left")
+                        .filter(|left| left.kind() == "identifier"),
+                ),
+                "required_parameter" | "optional_parameter" => {
+                    let mut inner = param.walk();
+                    let is_property = param.children(&mut inner).any(|child| {
+                        matches!(
+                            child.kind(),
+                            "accessibility_modifier" | "override_modifier" | "readonly"
+                        )
+                    });
+                    if !is_property {
+                        nodes.extend(
+                            param
+                                .child_by_field_name("pattern")
+                                .filter(|pattern| pattern.kind() == "identifier"),
+                        );
+                    }
+                }
+                _ => {}
+            },
+            LangId::Python => match param.kind() {
+                "identifier" => nodes.push(param),
+                "typed_parameter" => nodes.extend(
+                    param
+                        .named_child(0)
+                        .filter(|name| name.kind() == "identifier"),
+                ),
+                "default_parameter" | "typed_default_parameter" => nodes.extend(
+                    param
+                        .child_by_field_name("name")
+                        .filter(|name| name.kind() == "identifier"),
+                ),
+                _ => {}
+            },
+            LangId::Go => {
+                if param.kind() == "parameter_declaration" {
+                    let mut inner = param.walk();
+                    nodes.extend(param.children_by_field_name("name", &mut inner));
+                }
+            }
+            LangId::Java => {
+                if param.kind() == "formal_parameter" {
+                    nodes.extend(param.child_by_field_name("name"));
+                }
+            }
+            LangId::CSharp => {
+                if param.kind() == "parameter" {
+                    nodes.extend(param.child_by_field_name("name"));
+                }
+            }
+            LangId::Php => {
+                if param.kind() == "simple_parameter" {
+                    nodes.extend(
+                        param
+                            .child_by_field_name("name")
+                            .and_then(|var| child_of_kind(var, "name")),
+                    );
+                }
+            }
+            LangId::Kotlin => {
+                if param.kind() == "parameter" {
+                    nodes.extend(child_of_kind(param, "simple_identifier"));
+                }
+            }
+            _ => {}
+        }
+    }
+    nodes
+        .into_iter()
+        .filter_map(|node| {
+            let name = std::str::from_utf8(&source[node.byte_range()]).ok()?;
+            (!name.starts_with('_') && !matches!(name, "self" | "cls" | "this"))
+                .then_some((name, node))
+        })
+        .collect()
+}
+
+/// 空の本体、または `pass` / docstring / `...` / `raise` / `throw` / `todo!()` だけの本体。
+/// 引数を使わないのは未実装だからで、引数自体は将来の実装のために宣言されている。
+fn is_stub_body(body: Node<'_>, source: &[u8], lang: LangId) -> bool {
+    let mut body = body;
+    loop {
+        let statements = non_comment_children(body);
+        match statements.as_slice() {
+            [only] if matches!(only.kind(), "block" | "statements" | "statement_list") => {
+                body = *only
+            }
+            _ => break,
+        }
+    }
+    non_comment_children(body)
+        .into_iter()
+        .all(|stmt| is_stub_statement(stmt, source, lang))
+}
+
+fn is_stub_statement(stmt: Node<'_>, source: &[u8], lang: LangId) -> bool {
+    match stmt.kind() {
+        "pass_statement" | "raise_statement" | "throw_statement" | "throw_expression" => true,
+        "expression_statement" => match stmt.named_child(0) {
+            Some(expr) if stmt.named_child_count() == 1 => {
+                (lang == LangId::Python && matches!(expr.kind(), "string" | "ellipsis"))
+                    || is_stub_statement(expr, source, lang)
+            }
+            _ => false,
+        },
+        "macro_invocation" if lang == LangId::Rust => stmt
+            .child_by_field_name("macro")
+            .and_then(|name| std::str::from_utf8(&source[name.byte_range()]).ok())
+            .is_some_and(|name| RUST_STUB_MACROS.contains(&name)),
+        _ => false,
+    }
+}
+
+fn non_comment_children(node: Node<'_>) -> Vec<Node<'_>> {
+    let mut cursor = node.walk();
+    node.named_children(&mut cursor)
+        .filter(|child| !child.kind().contains("comment"))
+        .collect()
+}
+
+fn collect_used_words<'a>(body: Node<'_>, source: &'a [u8]) -> HashSet<&'a str> {
+    let mut used = HashSet::new();
+    let mut stack = vec![body];
+    while let Some(node) = stack.pop() {
+        if node.kind().contains("comment") {
+            continue;
+        }
+        if node.child_count() == 0 {
+            let text = std::str::from_utf8(&source[node.byte_range()]).unwrap_or("");
+            used.extend(
+                text.split(|c: char| !(c.is_alphanumeric() || c == '_'))
+                    .filter(|word| !word.is_empty()),
+            );
+            continue;
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.children(&mut cursor));
+    }
+    used
+}
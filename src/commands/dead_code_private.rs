@@ -0,0 +1,182 @@
+//! dead-code `--scope private`: 可視範囲内で参照されていない非公開シンボルの検出。
+//!
+//! 公開シンボルの dead 判定はワークスペース全体の参照を数えるが、非公開シンボルは
+//! 可視範囲 (ファイル / Rust のモジュール / Go のパッケージ) の外から参照できないため、
+//! その範囲のファイルだけを数える。
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use crate::engine::parser;
+use crate::engine::paths::relative_path;
+use crate::language::LangId;
+use crate::models::review::{DeadSymbol, DeadSymbolVisibility};
+
+use super::api_changes::{bare_name, filter_private_symbols};
+use super::dead_code::{DeadCodeConventions, collect_framework_asset_refs};
+
+/// 非公開シンボルが参照され得る範囲 (ワークスペース相対パスで表す)。
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum VisibilityScope {
+    /// 宣言ファイルのみ (class private / module-private 関数等)。
+    File(String),
+    /// Go のパッケージ: 同じディレクトリ直下の `.go` ファイル。
+    Package(String),
+    /// Rust のモジュール: 宣言ファイルと、子モジュールを置くディレクトリ (`subtree`) 配下。
+    Module { file: String, subtree: String },
+}
+
+impl VisibilityScope {
+    fn of(rel: &str, lang: LangId) -> Self {
+        let (parent, file_name) = match rel.rsplit_once('/') {
+            Some((parent, name)) => (format!("{parent}/"), name),
+            None => (String::new(), rel),
+        };
+        match lang {
+            LangId::Go => Self::Package(parent),
+            LangId::Rust => {
+                // `mod.rs` / `lib.rs` / `main.rs` はディレクトリ自体がモジュール。
+                // `foo.rs` の子モジュールは `foo/` 配下に置かれる。
+                let subtree = if matches!(file_name, "mod.rs" | "lib.rs" | "main.rs") {
+                    parent
+                } else {
+                    format!("{}/", rel.trim_end_matches(".rs"))
+                };
+                Self::Module {
+                    file: rel.to_string(),
+                    subtree,
+                }
+            }
+            _ => Self::File(rel.to_string()),
+        }
+    }
+
+    fn contains(&self, rel: &str) -> bool {
+        match self {
+            Self::File(file) => rel == file,
+            Self::Package(dir) => {
+                rel.ends_with(".go")
+                    && rel
+                        .strip_prefix(dir.as_str())
+                        .is_some_and(|rest| !rest.contains('/'))
+            }
+            Self::Module { file, subtree } => {
+                rel == file || (rel.ends_with(".rs") && rel.starts_with(subtree.as_str()))
+            }
+        }
+    }
+}
+
+/// `files` 内の非公開シンボルのうち、可視範囲 (`workspace_files` から選ぶ) で
+/// 非 Definition 参照が 0 件のものを返す。各要素の `visibility` は `"private"`。
+/// `conventions` の annotation (`@PostConstruct` 等) やプリセット規約で実行時に呼ばれる
+/// 宣言は、公開シンボルと同じく参照ありとみなす。
+pub(crate) fn detect_private_dead_symbols(
+    dir: &str,
+    files: &[PathBuf],
+    workspace_files: &[PathBuf],
+    conventions: &DeadCodeConventions,
+) -> Vec<DeadSymbol> {
+    let Ok(canonical_dir) = std::fs::canonicalize(dir) else {
+        return Vec::new();
+    };
+    let gitattrs = crate::engine::gitattributes::GitAttributes::load(&canonical_dir);
+    let asset_refs = collect_framework_asset_refs(&canonical_dir, conventions);
+
+    let mut by_scope: BTreeMap<VisibilityScope, Vec<DeadSymbol>> = BTreeMap::new();
+    for path in files {
+        let Ok(canonical_path) = std::fs::canonicalize(path) else {
+            continue;
+        };
+        let Some(rel) = relative_path(&canonical_dir, &canonical_path) else {
+            continue;
+        };
+        if gitattrs.is_generated(&rel)
+            || crate::engine::generated::is_auto_generated(&canonical_path)
+        {
+            continue;
+        }
+        let Some((lang, candidates)) = private_candidates(&canonical_path, &rel) else {
+            continue;
+        };
+        let candidates: Vec<_> = candidates
+            .into_iter()
+            .filter(|(name, _, _)| !asset_refs.contains_symbol(name))
+            .collect();
+        if candidates.is_empty() {
+            continue;
+        }
+        by_scope
+            .entry(VisibilityScope::of(&rel, lang))
+            .or_default()
+            .extend(candidates.into_iter().map(|(name, kind, line)| DeadSymbol {
+                name,
+                kind,
+                file: rel.clone(),
+                line: Some(line),
+                visibility: Some(DeadSymbolVisibility::Private),
+                blame: None,
+            }));
+    }
+
+    let workspace: Vec<(String, &PathBuf)> = workspace_files
+        .iter()
+        .filter_map(|path| Some((relative_path(&canonical_dir, path)?, path)))
+        .collect();
+    let mut dead = Vec::new();
+    for (scope, candidates) in by_scope {
+        let mut scope_files: Vec<PathBuf> = workspace
+            .iter()
+            .filter(|(rel, _)| scope.contains(rel))
+            .map(|(_, path)| (*path).clone())
+            .collect();
+        // 候補ファイル自身は workspace 走査の除外 (hidden dir 等) に関わらず必ず数える。
+        for candidate_file in candidates
+            .iter()
+            .map(|c| c.file.as_str())
+            .collect::<BTreeSet<_>>()
+        {
+            let path = canonical_dir.join(candidate_file);
+            if !scope_files.contains(&path) {
+                scope_files.push(path);
+            }
+        }
+        // qualname (`Class.method`) の参照は bare name で現れる。同じ範囲に同名の
+        // 非公開シンボルが複数あれば件数は合算され、保守的に live 側へ倒れる。
+        let names: Vec<String> = candidates
+            .iter()
+            .map(|c| bare_name(&c.name).to_string())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let Ok(counts) =
+            crate::engine::refs::count_non_definition_refs_in_files(&names, &scope_files)
+        else {
+            continue;
+        };
+        dead.extend(candidates.into_iter().filter(|c| {
+            counts
+                .get(bare_name(&c.name))
+                .is_some_and(|&count| count == 0)
+        }));
+    }
+    dead.sort_by(|a, b| a.file.cmp(&b.file).then_with(|| a.line.cmp(&b.line)));
+    dead
+}
+
+fn private_candidates(path: &Path, rel: &str) -> Option<(LangId, Vec<(String, String, usize)>)> {
+    let utf8_path = camino::Utf8Path::from_path(path)?;
+    let source = parser::read_file(utf8_path).ok()?;
+    let lang = parser::detect_lang(utf8_path, &source).ok()?;
+    // lexer-only 言語は可視性を判定できないため対象外。
+    if lang.is_lexer_only() {
+        return None;
+    }
+    let tree = parser::parse_source(&source, lang).ok()?;
+    let root = tree.root_node();
+    let syms = crate::engine::symbols::extract_symbols(root, &source, lang).ok()?;
+    Some((
+        lang,
+        filter_private_symbols(&syms, root, &source, lang, rel),
+    ))
+}
@@ -0,0 +1,340 @@
+//! dead-code `--reachability`: 起点から参照グラフで辿れないシンボルの検出。
+//!
+//! ワークスペース全体の定義シンボルをノードとし、「参照を含む最も内側のシンボル →
+//! 参照名と同名のシンボル」を辺とするグラフを作る。起点 (`main` / 公開 API / テスト /
+//! フレームワーク規約 / `--entry`) から辿れないシンボルを、互いの参照で繋がった塊ごとに返す。
+//! 参照は名前単位で解決するため、同名シンボルが複数あれば全てに辺を張り、保守的に
+//! 到達可能側へ倒れる。
+
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use crate::engine::gitattributes::Pattern;
+use crate::engine::parser;
+use crate::engine::paths::relative_path;
+use crate::models::dead_code::DeadCluster;
+use crate::models::reference::RefKind;
+use crate::models::review::{DeadSymbol, DeadSymbolVisibility};
+
+use super::api_changes::reachability_candidates;
+use super::dead_code::{DeadCodeConventions, collect_framework_asset_refs};
+
+/// import / use 等の宣言ノード。ここに現れる名前は利用ではないため辺にも起点にもしない。
+const IMPORT_NODE_KINDS: &[&str] = &[
+    "use_declaration",
+    "extern_crate_declaration",
+    "import_statement",
+    "import_from_statement",
+    "import_declaration",
+    "import_header",
+    "import_list",
+    "using_directive",
+    "namespace_use_declaration",
+    "preproc_include",
+];
+
+struct Node {
+    name: String,
+    qualname: String,
+    kind: String,
+    file: String,
+    start_line: usize,
+    end_line: usize,
+    exported: bool,
+    entry: bool,
+    reportable: bool,
+}
+
+impl Node {
+    /// qualname (`Container.name`) の container 部分。
+    fn container(&self) -> Option<&str> {
+        self.qualname
+            .rsplit_once('.')
+            .map(|(container, _)| container)
+    }
+
+    fn is_container_kind(&self) -> bool {
+        matches!(
+            self.kind.as_str(),
+            "class" | "struct" | "enum" | "interface" | "trait" | "type"
+        )
+    }
+}
+
+/// 1 ファイル分のノード (グラフ全体での添字) と import 宣言の行範囲。
+#[derive(Default)]
+struct FileGraph {
+    nodes: Vec<usize>,
+    imports: Vec<(usize, usize)>,
+}
+
+impl FileGraph {
+    /// `line` を含む最も内側のノード。
+    fn enclosing(&self, nodes: &[Node], line: usize) -> Option<usize> {
+        self.nodes
+            .iter()
+            .copied()
+            .filter(|&ix| nodes[ix].start_line <= line && line <= nodes[ix].end_line)
+            .min_by_key(|&ix| nodes[ix].end_line - nodes[ix].start_line)
+    }
+
+    fn is_import_line(&self, line: usize) -> bool {
+        self.imports
+            .iter()
+            .any(|&(start, end)| start <= line && line <= end)
+    }
+}
+
+/// `--entry` の 1 パターン。`FILE_GLOB:NAME_GLOB` または `NAME_GLOB`。
+/// 名前側は bare name と qualname (`Container.name`) のどちらかに一致すればよい。
+struct EntryPattern {
+    file: Option<Pattern>,
+    name: Pattern,
+}
+
+impl EntryPattern {
+    fn parse(raw: &str) -> Self {
+        match raw.split_once(':') {
+            Some((file, name)) => Self {
+                file: Some(Pattern::parse(file)),
+                name: Pattern::parse(name),
+            },
+            None => Self {
+                file: None,
+                name: Pattern::parse(raw),
+            },
+        }
+    }
+
+    fn matches(&self, node: &Node) -> bool {
+        self.file
+            .as_ref()
+            .is_none_or(|file| file.matches(&node.file))
+            && (self.name.matches(&node.qualname) || self.name.matches(&node.name))
+    }
+}
+
+/// `files` 内のシンボルのうち、`graph_files` 全体で作った参照グラフ上で起点から
+/// 辿れないものを連結成分ごとに返す。`convention_files` (フレームワークプリセットで
+/// 除外されるファイル) と生成ファイルのシンボル、`conventions` の annotation /
+/// プリセット規約で実行時に呼ばれるシンボルは起点にする。
+pub(crate) fn detect_unreachable_clusters(
+    dir: &str,
+    files: &[PathBuf],
+    graph_files: &[PathBuf],
+    convention_files: &HashSet<PathBuf>,
+    entry_patterns: &[String],
+    conventions: &DeadCodeConventions,
+) -> Result<Vec<DeadCluster>> {
+    let canonical_dir = std::fs::canonicalize(dir)?;
+    let gitattrs = crate::engine::gitattributes::GitAttributes::load(&canonical_dir);
+    let asset_refs = collect_framework_asset_refs(&canonical_dir, conventions);
+    let patterns: Vec<EntryPattern> = entry_patterns
+        .iter()
+        .map(|raw| EntryPattern::parse(raw))
+        .collect();
+    let report_files: HashSet<String> = files
+        .iter()
+        .filter_map(|path| relative_path(&canonical_dir, &std::fs::canonicalize(path).ok()?))
+        .collect();
+
+    let mut nodes: Vec<Node> = Vec::new();
+    let mut by_file: HashMap<String, FileGraph> = HashMap::new();
+    let mut scan_files: Vec<PathBuf> = Vec::new();
+    // 報告対象ファイル自身は graph 側の除外 (hidden dir 等) に関わらず必ず載せる。
+    for path in graph_files.iter().chain(files) {
+        let Ok(canonical_path) = std::fs::canonicalize(path) else {
+            continue;
+        };
+        let Some(rel) = relative_path(&canonical_dir, &canonical_path) else {
+            continue;
+        };
+        if by_file.contains_key(&rel) {
+            continue;
+        }
+        let conventional = convention_files.contains(path)
+            || gitattrs.is_generated(&rel)
+            || crate::engine::generated::is_auto_generated(&canonical_path);
+        let mut graph = FileGraph::default();
+        if let Some((file_nodes, imports)) = load_file(&canonical_path, &rel) {
+            for mut node in file_nodes {
+                node.entry = node.entry
+                    || conventional
+                    || patterns.iter().any(|pattern| pattern.matches(&node))
+                    || asset_refs.contains_symbol(&node.qualname);
+                graph.nodes.push(nodes.len());
+                nodes.push(node);
+            }
+            graph.imports = imports;
+        }
+        by_file.insert(rel, graph);
+        scan_files.push(canonical_path);
+    }
+
+    let mut name_to_nodes: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (ix, node) in nodes.iter().enumerate() {
+        name_to_nodes
+            .entry(node.name.as_str())
+            .or_default()
+            .push(ix);
+    }
+    let mut names: Vec<String> = name_to_nodes.keys().map(|name| name.to_string()).collect();
+    names.sort();
+    let references = crate::engine::refs::find_references_in_files(&names, &scan_files)?;
+
+    let mut edges: Vec<Vec<usize>> = vec![Vec::new(); nodes.len()];
+    let mut roots: Vec<usize> = (0..nodes.len()).filter(|&ix| nodes[ix].entry).collect();
+    for (name, found) in &references {
+        let Some(targets) = name_to_nodes.get(name.as_str()) else {
+            continue;
+        };
+        for reference in found {
+            if matches!(reference.kind, Some(RefKind::Definition)) {
+                continue;
+            }
+            let Some(graph) = relative_path(&canonical_dir, Path::new(&reference.path))
+                .and_then(|rel| by_file.get(&rel))
+            else {
+                continue;
+            };
+            if graph.is_import_line(reference.line) {
+                continue;
+            }
+            match graph.enclosing(&nodes, reference.line) {
+                Some(from) => edges[from].extend(targets.iter().copied().filter(|&t| t != from)),
+                // シンボル外 (トップレベルの実行コード・登録処理等) からの参照は起点扱い。
+                None => roots.extend(targets.iter().copied()),
+            }
+        }
+    }
+    // メンバーが使われていれば、それを持つ型も使われている。
+    for (ix, node) in nodes.iter().enumerate() {
+        let Some(targets) = node.container().and_then(|c| name_to_nodes.get(c)) else {
+            continue;
+        };
+        edges[ix].extend(
+            targets
+                .iter()
+                .copied()
+                .filter(|&t| t != ix && nodes[t].is_container_kind()),
+        );
+    }
+    for targets in &mut edges {
+        targets.sort_unstable();
+        targets.dedup();
+    }
+
+    let mut reachable = vec![false; nodes.len()];
+    let mut queue: VecDeque<usize> = VecDeque::new();
+    for ix in roots {
+        if !reachable[ix] {
+            reachable[ix] = true;
+            queue.push_back(ix);
+        }
+    }
+    while let Some(ix) = queue.pop_front() {
+        for &target in &edges[ix] {
+            if !reachable[target] {
+                reachable[target] = true;
+                queue.push_back(target);
+            }
+        }
+    }
+
+    // 到達不能ノード同士の辺を無向とみなして連結成分に分ける。
+    let mut parent: Vec<usize> = (0..nodes.len()).collect();
+    for (from, targets) in edges.iter().enumerate() {
+        if reachable[from] {
+            continue;
+        }
+        for &to in targets {
+            let (a, b) = (find_root(&mut parent, from), find_root(&mut parent, to));
+            if a != b {
+                parent[a.max(b)] = a.min(b);
+            }
+        }
+    }
+    let mut components: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for ix in (0..nodes.len()).filter(|&ix| !reachable[ix]) {
+        let root = find_root(&mut parent, ix);
+        components.entry(root).or_default().push(ix);
+    }
+
+    let mut clusters: Vec<DeadCluster> = components
+        .into_values()
+        .filter_map(|members| {
+            let mut symbols: Vec<DeadSymbol> = members
+                .into_iter()
+                .map(|ix| &nodes[ix])
+                .filter(|node| node.reportable && report_files.contains(&node.file))
+                .map(|node| DeadSymbol {
+                    name: node.qualname.clone(),
+                    kind: node.kind.clone(),
+                    file: node.file.clone(),
+                    line: Some(node.start_line),
+                    visibility: (!node.exported).then_some(DeadSymbolVisibility::Private),
+                    blame: None,
+                })
+                .collect();
+            symbols.sort_by(|a, b| a.file.cmp(&b.file).then_with(|| a.line.cmp(&b.line)));
+            (!symbols.is_empty()).then_some(DeadCluster { symbols })
+        })
+        .collect();
+    clusters.sort_by(|a, b| {
+        let (a, b) = (&a.symbols[0], &b.symbols[0]);
+        a.file.cmp(&b.file).then_with(|| a.line.cmp(&b.line))
+    });
+    Ok(clusters)
+}
+
+fn find_root(parent: &mut [usize], mut ix: usize) -> usize {
+    while parent[ix] != ix {
+        parent[ix] = parent[parent[ix]];
+        ix = parent[ix];
+    }
+    ix
+}
+
+fn load_file(path: &Path, rel: &str) -> Option<(Vec<Node>, Vec<(usize, usize)>)> {
+    let utf8_path = camino::Utf8Path::from_path(path)?;
+    let source = parser::read_file(utf8_path).ok()?;
+    let lang = parser::detect_lang(utf8_path, &source).ok()?;
+    // lexer-only 言語は定義範囲を取れないためグラフに載せない (参照元としても数えない)。
+    if lang.is_lexer_only() {
+        return None;
+    }
+    let tree = parser::parse_source(&source, lang).ok()?;
+    let root = tree.root_node();
+    let syms = crate::engine::symbols::extract_symbols(root, &source, lang).ok()?;
+    let nodes = reachability_candidates(&syms, root, &source, lang, rel)
+        .into_iter()
+        .map(|candidate| Node {
+            name: candidate.symbol.name.clone(),
+            qualname: candidate.qualname,
+            kind: format!("{:?}", candidate.symbol.kind).to_lowercase(),
+            file: rel.to_string(),
+            start_line: candidate.symbol.range.start.line,
+            end_line: candidate.symbol.range.end.line,
+            exported: candidate.exported,
+            entry: candidate.entry,
+            reportable: candidate.reportable,
+        })
+        .collect();
+    Some((nodes, import_line_ranges(root)))
+}
+
+fn import_line_ranges(root: tree_sitter::Node<'_>) -> Vec<(usize, usize)> {
+    let mut ranges = Vec::new();
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        if IMPORT_NODE_KINDS.contains(&node.kind()) {
+            ranges.push((node.start_position().row, node.end_position().row));
+            continue;
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.named_children(&mut cursor));
+    }
+    ranges
+}
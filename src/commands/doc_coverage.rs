@@ -0,0 +1,393 @@
+use anyhow::Result;
+use camino::Utf8Path;
+use rayon::prelude::*;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+use crate::engine::parser;
+use crate::engine::paths::relative_path;
+use crate::engine::symbols::{
+    extract_symbols, has_doc_comment, is_local_scope_symbol, is_restricted_visibility_rust,
+    is_symbol_exported, is_trait_impl_method_rust,
+};
+use crate::error::{AstroError, ErrorCode};
+use crate::language::LangId;
+use crate::models::doc_coverage::{
+    DocCoverageResult, FileDocCoverage, PackageDocCoverage, UndocumentedSymbol,
+};
+use crate::models::impact::DiffFile;
+use crate::models::symbol::{Symbol, SymbolKind};
+use crate::output::{OutputOptions, serialize_cli_document};
+
+use super::common::timed;
+use super::dead_code::{filter_diff_files_for_dead_code, is_test_path, resolve_dead_code_excludes};
+use super::git_input::{DiffSourceResolution, resolve_diff_source};
+
+/// パッケージ境界とみなす manifest ファイル名。
+const PACKAGE_MANIFESTS: &[&str] = &[
+    "Cargo.toml",
+    "package.json",
+    "pyproject.toml",
+    "setup.py",
+    "go.mod",
+    "pom.xml",
+    "build.gradle",
+    "build.gradle.kts",
+    "composer.json",
+    "Gemfile",
+    "Package.swift",
+    "build.zig",
+];
+
+/// `cmd_doc_coverage` の引数一式。
+pub struct CmdDocCoverageOpts<'a> {
+    pub dir: &'a str,
+    pub glob: Option<&'a str>,
+    pub diff: Option<&'a str>,
+    pub diff_file: Option<&'a str>,
+    pub git: bool,
+    pub base: &'a str,
+    pub staged: bool,
+    pub extra_exclude_dirs: &'a [String],
+    pub extra_exclude_globs: &'a [String],
+    pub output: OutputOptions,
+}
+
+/// 公開シンボルの doc comment 網羅率をファイル / パッケージ単位で集計する。
+///
+/// 公開判定は `is_symbol_exported` に従い、Rust はさらに `pub(crate)` 等の制限付き可視性と
+/// trait impl のメソッド (doc は trait 側に書く) を除く。diff 指定時は changed file だけを
+/// 集計し、追加・変更された undocumented 公開シンボルがあれば結果を出力したうえで exit 1。
+pub fn cmd_doc_coverage(opts: &CmdDocCoverageOpts<'_>) -> Result<()> {
+    let canonical_dir = std::fs::canonicalize(opts.dir)?;
+    if !canonical_dir.is_dir() {
+        return Err(AstroError::new(
+            ErrorCode::InvalidRequest,
+            format!("Not a directory: {}", opts.dir),
+        )
+        .into());
+    }
+
+    let default_excludes = resolve_dead_code_excludes(false, false, false);
+    let mut excludes: Vec<&str> = default_excludes.to_vec();
+    excludes.extend(opts.extra_exclude_dirs.iter().map(String::as_str));
+    let globs: Vec<&str> = opts
+        .extra_exclude_globs
+        .iter()
+        .map(String::as_str)
+        .collect();
+
+    let mut result = DocCoverageResult {
+        dir: canonical_dir.to_string_lossy().to_string(),
+        ..Default::default()
+    };
+    let (diff_input, diff_files) = match resolve_diff_source(
+        opts.dir,
+        opts.diff,
+        opts.diff_file,
+        opts.git,
+        opts.base,
+        opts.staged,
+    )? {
+        DiffSourceResolution::Diff { diff, truncations } => {
+            result.truncations = truncations;
+            let parsed = crate::engine::diff::parse_unified_diff(&diff);
+            (Some(diff), Some(parsed))
+        }
+        DiffSourceResolution::Skipped(skip) => {
+            result.skipped = Some(skip);
+            print!("{}", serialize_cli_document(&result, opts.output)?);
+            return Ok(());
+        }
+        DiffSourceResolution::NotRequested => (None, None),
+    };
+
+    let files: Vec<PathBuf> = match diff_files.as_deref() {
+        Some(diff_files) => filter_diff_files_for_dead_code(
+            &canonical_dir,
+            diff_files,
+            &excludes,
+            &globs,
+            opts.glob,
+        )?,
+        None => crate::engine::refs::collect_files_with_excludes(
+            &canonical_dir,
+            opts.glob,
+            &excludes,
+            &globs,
+        )?,
+    };
+    let files: Vec<PathBuf> = files
+        .into_iter()
+        .filter(|path| !is_test_path(path.strip_prefix(&canonical_dir).unwrap_or(path)))
+        .collect();
+
+    let mut analyzed: Vec<FileDocSymbols> = timed("doc_coverage", || {
+        files
+            .par_iter()
+            .filter_map(|path| analyze_file(&canonical_dir, path))
+            .collect()
+    });
+    analyzed.sort_by(|a, b| a.file.cmp(&b.file));
+
+    result.scanned_files = files.len();
+    aggregate_coverage(&canonical_dir, &analyzed, &mut result);
+    if let (Some(diff_input), Some(diff_files)) = (diff_input.as_deref(), diff_files.as_deref()) {
+        result.violations = touched_undocumented(&analyzed, diff_input, diff_files);
+    }
+
+    let text = serialize_cli_document(&result, opts.output)?;
+    info!(
+        command = "doc-coverage",
+        dir = opts.dir,
+        scanned_files = result.scanned_files,
+        undocumented = result.undocumented.len(),
+        violations = result.violations.len(),
+        "command completed"
+    );
+    print!("{text}");
+    if !result.violations.is_empty() {
+        return super::exit_with_status(
+            1,
+            &format!(
+                "{} public symbol(s) added or modified without documentation",
+                result.violations.len()
+            ),
+        );
+    }
+    Ok(())
+}
+
+/// 1 ファイル内の doc 対象シンボル。
+struct FileDocSymbols {
+    /// dir 相対パス。
+    file: String,
+    symbols: Vec<DocSymbol>,
+}
+
+struct DocSymbol {
+    name: String,
+    kind: String,
+    start_line: usize,
+    end_line: usize,
+    documented: bool,
+}
+
+fn analyze_file(dir: &Path, path: &Path) -> Option<FileDocSymbols> {
+    let utf8 = Utf8Path::from_path(path)?;
+    let source = parser::read_file(utf8).ok()?;
+    let lang = parser::detect_lang(utf8, &source).ok()?;
+    if lang.is_lexer_only() {
+        return None;
+    }
+    let tree = parser::parse_source(&source, lang).ok()?;
+    let root = tree.root_node();
+    let symbols = extract_symbols(root, &source, lang).ok()?;
+
+    let doc_symbols = symbols
+        .iter()
+        .filter(|sym| requires_doc(root, &source, lang, sym))
+        .map(|sym| DocSymbol {
+            name: match sym.container.as_deref() {
+                Some(container) => format!("{container}.{}", sym.name),
+                None => sym.name.clone(),
+            },
+            kind: format!("{:?}", sym.kind).to_lowercase(),
+            start_line: sym.range.start.line,
+            end_line: sym.range.end.line,
+            documented: has_doc_comment(root, &source, lang, sym),
+        })
+        .collect();
+    Some(FileDocSymbols {
+        file: relative_path(dir, path)?,
+        symbols: doc_symbols,
+    })
+}
+
+/// doc comment を要求する公開 API シンボルか。
+///
+/// 変数・フィールド・引数・import は対象外 (フィールド単位の doc 強制はノイズが多い)。
+fn requires_doc(root: tree_sitter::Node, source: &[u8], lang: LangId, sym: &Symbol) -> bool {
+    if !matches!(
+        sym.kind,
+        SymbolKind::Function
+            | SymbolKind::Method
+            | SymbolKind::Class
+            | SymbolKind::Struct
+            | SymbolKind::Enum
+            | SymbolKind::Interface
+            | SymbolKind::Trait
+            | SymbolKind::Type
+            | SymbolKind::Constant
+    ) {
+        return false;
+    }
+    if is_local_scope_symbol(root, source, lang, &sym.range)
+        || !is_symbol_exported(root, source, lang, &sym.range)
+    {
+        return false;
+    }
+    if lang == LangId::Rust
+        && (is_trait_impl_method_rust(root, &sym.range)
+            || is_restricted_visibility_rust(root, source, &sym.range))
+    {
+        return false;
+    }
+    true
+}
+
+fn aggregate_coverage(dir: &Path, analyzed: &[FileDocSymbols], result: &mut DocCoverageResult) {
+    let mut package_cache: HashMap<PathBuf, String> = HashMap::new();
+    let mut packages: BTreeMap<String, (usize, usize)> = BTreeMap::new();
+
+    for file in analyzed {
+        if file.symbols.is_empty() {
+            continue;
+        }
+        let total = file.symbols.len();
+        let documented = file.symbols.iter().filter(|s| s.documented).count();
+        result.total += total;
+        result.documented += documented;
+        result.files.push(FileDocCoverage {
+            file: file.file.clone(),
+            total,
+            documented,
+            coverage: coverage_percent(documented, total),
+        });
+
+        let package = package_of(dir, &file.file, &mut package_cache);
+        let entry = packages.entry(package).or_default();
+        entry.0 += total;
+        entry.1 += documented;
+
+        result
+            .undocumented
+            .extend(
+                file.symbols
+                    .iter()
+                    .filter(|s| !s.documented)
+                    .map(|s| UndocumentedSymbol {
+                        name: s.name.clone(),
+                        kind: s.kind.clone(),
+                        file: file.file.clone(),
+                        line: s.start_line,
+                    }),
+            );
+    }
+
+    result.coverage = coverage_percent(result.documented, result.total);
+    result.packages = packages
+        .into_iter()
+        .map(|(package, (total, documented))| PackageDocCoverage {
+            package,
+            total,
+            documented,
+            coverage: coverage_percent(documented, total),
+        })
+        .collect();
+}
+
+/// 網羅率 (百分率、小数 1 桁)。対象シンボルが 0 件なら 100.0。
+fn coverage_percent(documented: usize, total: usize) -> f64 {
+    if total == 0 {
+        return 100.0;
+    }
+    (documented as f64 * 1000.0 / total as f64).round() / 10.0
+}
+
+/// `file` (dir 相対) の祖先方向で最初に manifest を持つディレクトリを返す。
+fn package_of(dir: &Path, file: &str, cache: &mut HashMap<PathBuf, String>) -> String {
+    let mut current = Path::new(file).parent();
+    let mut visited: Vec<PathBuf> = Vec::new();
+    let package = loop {
+        let Some(rel) = current else {
+            break ".".to_string();
+        };
+        if let Some(package) = cache.get(rel) {
+            break package.clone();
+        }
+        visited.push(rel.to_path_buf());
+        if PACKAGE_MANIFESTS
+            .iter()
+            .any(|manifest| dir.join(rel).join(manifest).is_file())
+        {
+            break if rel.as_os_str().is_empty() {
+                ".".to_string()
+            } else {
+                rel.to_string_lossy().replace('\\', "/")
+            };
+        }
+        current = rel.parent();
+    };
+    for rel in visited {
+        cache.insert(rel, package.clone());
+    }
+    package
+}
+
+/// diff の追加行が定義範囲に掛かる undocumented シンボル (= 新規 / 変更された公開シンボル)。
+fn touched_undocumented(
+    analyzed: &[FileDocSymbols],
+    diff_input: &str,
+    diff_files: &[DiffFile],
+) -> Vec<UndocumentedSymbol> {
+    let changed_files: HashSet<&str> = diff_files
+        .iter()
+        .filter(|f| f.new_path != "/dev/null")
+        .map(|f| f.new_path.as_str())
+        .collect();
+
+    let mut violations = Vec::new();
+    for file in analyzed {
+        if !changed_files.contains(file.file.as_str()) {
+            continue;
+        }
+        let changed_lines = crate::engine::diff::extract_changed_new_lines(diff_input, &file.file);
+        violations.extend(
+            file.symbols
+                .iter()
+                .filter(|s| !s.documented)
+                .filter(|s| (s.start_line..=s.end_line).any(|line| changed_lines.contains(&line)))
+                .map(|s| UndocumentedSymbol {
+                    name: s.name.clone(),
+                    kind: s.kind.clone(),
+                    file: file.file.clone(),
+                    line: s.start_line,
+                }),
+        );
+    }
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coverage_percent_rounds_to_one_decimal() {
+        assert_eq!(coverage_percent(1, 3), 33.3);
+        assert_eq!(coverage_percent(2, 3), 66.7);
+        assert_eq!(coverage_percent(0, 0), 100.0);
+    }
+
+    #[test]
+    fn package_of_uses_nearest_manifest() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join("crates/core/src/nested")).unwrap();
+        std::fs::write(tmp.path().join("Cargo.toml"), "").unwrap();
+        std::fs::write(tmp.path().join("crates/core/Cargo.toml"), "").unwrap();
+        let mut cache = HashMap::new();
+
+        assert_eq!(
+            package_of(tmp.path(), "crates/core/src/nested/a.rs", &mut cache),
+            "crates/core"
+        );
+        assert_eq!(
+            package_of(tmp.path(), "crates/core/src/b.rs", &mut cache),
+            "crates/core"
+        );
+        assert_eq!(package_of(tmp.path(), "src/main.rs", &mut cache), ".");
+        assert_eq!(package_of(tmp.path(), "lib.rs", &mut cache), ".");
+    }
+}
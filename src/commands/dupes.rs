@@ -0,0 +1,145 @@
+use anyhow::Result;
+use camino::Utf8Path;
+use std::collections::{HashMap, HashSet};
+use tracing::info;
+
+use crate::engine::dupes::{DEFAULT_MIN_TOKENS, find_added_duplicates, find_clone_groups};
+use crate::error::{AstroError, ErrorCode};
+use crate::language::LangId;
+use crate::models::dupes::{DupesResult, DuplicatedCode};
+use crate::models::impact::DiffFile;
+use crate::output::{OutputOptions, serialize_cli_document};
+
+use super::common::timed;
+use super::dead_code::resolve_dead_code_excludes;
+
+/// `cmd_dupes` の引数一式。
+pub struct CmdDupesOpts<'a> {
+    pub dir: &'a str,
+    pub glob: Option<&'a str>,
+    pub min_tokens: usize,
+    pub include_tests: bool,
+    pub extra_exclude_dirs: &'a [String],
+    pub extra_exclude_globs: &'a [String],
+    pub output: OutputOptions,
+}
+
+/// 正規化 AST の部分木ハッシュで type-2 clone group を検出する。
+///
+/// 除外ディレクトリは dead-code と同じ既定 (vendor / build は常に除外、tests は
+/// `--include-tests` で対象に戻せる)。テストの fixture / table は意図的な重複が多いため
+/// 既定では対象外にする。
+pub fn cmd_dupes(opts: &CmdDupesOpts<'_>) -> Result<()> {
+    let canonical_dir = std::fs::canonicalize(opts.dir)?;
+    if !canonical_dir.is_dir() {
+        return Err(AstroError::new(
+            ErrorCode::InvalidRequest,
+            format!("Not a directory: {}", opts.dir),
+        )
+        .into());
+    }
+    if opts.min_tokens == 0 {
+        return Err(AstroError::new(
+            ErrorCode::InvalidRequest,
+            "--min-tokens must be at least 1".to_string(),
+        )
+        .into());
+    }
+
+    let default_excludes = resolve_dead_code_excludes(false, opts.include_tests, false);
+    let mut excludes: Vec<&str> = default_excludes.to_vec();
+    excludes.extend(opts.extra_exclude_dirs.iter().map(String::as_str));
+    let globs: Vec<&str> = opts
+        .extra_exclude_globs
+        .iter()
+        .map(String::as_str)
+        .collect();
+    let files = crate::engine::refs::collect_files_with_excludes(
+        &canonical_dir,
+        opts.glob,
+        &excludes,
+        &globs,
+    )?;
+
+    let groups = timed("dupes", || {
+        find_clone_groups(&canonical_dir, &files, opts.min_tokens)
+    });
+    let result = DupesResult {
+        dir: canonical_dir.to_string_lossy().to_string(),
+        scanned_files: files.len(),
+        min_tokens: opts.min_tokens,
+        groups,
+    };
+
+    let text = serialize_cli_document(&result, opts.output)?;
+    info!(
+        command = "dupes",
+        dir = opts.dir,
+        scanned_files = result.scanned_files,
+        group_count = result.groups.len(),
+        "command completed"
+    );
+    print!("{text}");
+    Ok(())
+}
+
+/// review 用: diff で追加されたコードのうち、diff 外の既存コードと clone になっているもの。
+///
+/// 走査対象は changed file と同じ言語のファイルに絞る (異言語間は AST 種別が異なり
+/// clone になり得ないため、parse コストだけがかかる)。除外は review の dead 検出と同じ
+/// 固定既定 + ユーザ指定。`dir` を canonicalize できない / 収集に失敗した場合は空結果。
+pub(crate) fn review_duplicated_code(
+    dir: &str,
+    diff_input: &str,
+    diff_files: &[DiffFile],
+    extra_exclude_dirs: &[String],
+    extra_exclude_globs: &[String],
+) -> Vec<DuplicatedCode> {
+    let Ok(canonical_dir) = std::fs::canonicalize(dir) else {
+        return Vec::new();
+    };
+
+    let mut added_lines: HashMap<String, HashSet<usize>> = HashMap::new();
+    for file in diff_files {
+        if file.new_path == "/dev/null" {
+            continue;
+        }
+        let lines = crate::engine::diff::extract_changed_new_lines(diff_input, &file.new_path);
+        if !lines.is_empty() {
+            added_lines.insert(file.new_path.clone(), lines);
+        }
+    }
+    if added_lines.is_empty() {
+        return Vec::new();
+    }
+
+    let langs: HashSet<LangId> = added_lines
+        .keys()
+        .filter_map(|path| LangId::from_path(Utf8Path::new(path)).ok())
+        .filter(|lang| !lang.is_lexer_only())
+        .collect();
+    if langs.is_empty() {
+        return Vec::new();
+    }
+
+    let default_excludes = resolve_dead_code_excludes(false, false, false);
+    let mut excludes: Vec<&str> = default_excludes.to_vec();
+    excludes.extend(extra_exclude_dirs.iter().map(String::as_str));
+    let globs: Vec<&str> = extra_exclude_globs.iter().map(String::as_str).collect();
+    let Ok(files) =
+        crate::engine::refs::collect_files_with_excludes(&canonical_dir, None, &excludes, &globs)
+    else {
+        return Vec::new();
+    };
+    let files: Vec<std::path::PathBuf> = files
+        .into_iter()
+        .filter(|path| {
+            Utf8Path::from_path(path)
+                .and_then(|p| LangId::from_path(p).ok())
+                .is_some_and(|lang| langs.contains(&lang))
+        })
+        .collect();
+
+    let groups = find_clone_groups(&canonical_dir, &files, DEFAULT_MIN_TOKENS);
+    find_added_duplicates(&groups, &added_lines)
+}
@@ -192,6 +192,66 @@ fn cwd_relative_git_path(path: &str) -> std::borrow::Cow<'_, str> {
     std::borrow::Cow::Owned(format!("./{path}"))
 }
 
+/// `<from>` → `<to>` の 2 revision 間で変更されたファイルを `(old_path, new_path)` の組で返す。
+///
+/// 表記は `DiffFile` と揃え、追加は old 側、削除は new 側を `/dev/null` にする。rename は
+/// `--find-renames` で旧パス→新パスの 1 組に、copy は新パスの追加として扱う。
+/// `diff.renames` 設定に関わらず rename を組にするため、フラグは明示的に渡す。
+/// `run_git_diff` と同じく `--relative` で `dir` 相対パスに揃える。
+pub(crate) fn git_changed_paths_between(
+    dir: &str,
+    from: &str,
+    to: &str,
+) -> Result<Vec<(String, String)>> {
+    validate_git_revision(from, "--from")?;
+    validate_git_revision(to, "--to")?;
+    let output = std::process::Command::new("git")
+        .args([
+            "-c",
+            "core.quotepath=off",
+            "diff",
+            "--relative",
+            "--find-renames",
+            "--name-status",
+            from,
+            to,
+        ])
+        .current_dir(dir)
+        .output()
+        .map_err(|e| {
+            AstroError::new(ErrorCode::InvalidRequest, format!("Failed to run git: {e}"))
+        })?;
+    if !output.status.success() {
+        return Err(AstroError::new(
+            ErrorCode::InvalidRequest,
+            format!(
+                "git diff failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            ),
+        )
+        .into());
+    }
+
+    const DEV_NULL: &str = "/dev/null";
+    let mut paths = Vec::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let mut fields = line.split('\t');
+        let (Some(status), Some(first)) = (fields.next(), fields.next()) else {
+            continue;
+        };
+        let second = fields.next();
+        let pair = match (status.as_bytes().first(), second) {
+            (Some(b'A'), _) => (DEV_NULL.to_string(), first.to_string()),
+            (Some(b'D'), _) => (first.to_string(), DEV_NULL.to_string()),
+            (Some(b'R'), Some(new)) => (first.to_string(), new.to_string()),
+            (Some(b'C'), Some(new)) => (DEV_NULL.to_string(), new.to_string()),
+            _ => (first.to_string(), first.to_string()),
+        };
+        paths.push(pair);
+    }
+    Ok(paths)
+}
+
 /// `--git` 入力解決の結果。diff が取れたか、git 管理外で skip かを型で表す。
 ///
 /// `Diff` は合成時に対象外にした未追跡ファイルの `truncations` を同時に運ぶ。out-param に
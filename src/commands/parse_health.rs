@@ -0,0 +1,108 @@
+use anyhow::Result;
+use std::collections::BTreeMap;
+use tracing::info;
+
+use crate::engine::parse_health::scan_parse_health;
+use crate::error::{AstroError, ErrorCode};
+use crate::language::LangId;
+use crate::models::parse_health::{FileParseHealth, LanguageParseHealth, ParseHealthResult};
+use crate::output::{OutputOptions, serialize_cli_document};
+
+use super::common::timed;
+use super::dead_code::resolve_dead_code_excludes;
+
+/// `cmd_parse_health` の引数一式。
+pub struct CmdParseHealthOpts<'a> {
+    pub dir: &'a str,
+    pub glob: Option<&'a str>,
+    pub extra_exclude_dirs: &'a [String],
+    pub extra_exclude_globs: &'a [String],
+    pub output: OutputOptions,
+}
+
+/// ワークスペース内で ERROR / MISSING ノードを持つファイルを言語別に一覧する。
+///
+/// テストも grammar 追従漏れの影響を受けるため対象に含める (vendor / build は除外)。
+/// 報告専用で、parse error があっても exit code は 0。
+pub fn cmd_parse_health(opts: &CmdParseHealthOpts<'_>) -> Result<()> {
+    let canonical_dir = std::fs::canonicalize(opts.dir)?;
+    if !canonical_dir.is_dir() {
+        return Err(AstroError::new(
+            ErrorCode::InvalidRequest,
+            format!("Not a directory: {}", opts.dir),
+        )
+        .into());
+    }
+
+    let default_excludes = resolve_dead_code_excludes(false, true, false);
+    let mut excludes: Vec<&str> = default_excludes.to_vec();
+    excludes.extend(opts.extra_exclude_dirs.iter().map(String::as_str));
+    let globs: Vec<&str> = opts
+        .extra_exclude_globs
+        .iter()
+        .map(String::as_str)
+        .collect();
+    let files = crate::engine::refs::collect_files_with_excludes(
+        &canonical_dir,
+        opts.glob,
+        &excludes,
+        &globs,
+    )?;
+
+    let health = timed("parse-health", || scan_parse_health(&canonical_dir, &files));
+    let result = summarize(canonical_dir.to_string_lossy().to_string(), health);
+
+    let text = serialize_cli_document(&result, opts.output)?;
+    info!(
+        command = "parse-health",
+        dir = opts.dir,
+        scanned_files = result.scanned_files,
+        files_with_errors = result.files_with_errors,
+        "command completed"
+    );
+    print!("{text}");
+    Ok(())
+}
+
+/// ファイル単位の結果を言語別に集計する。問題のあるファイルを持つ言語だけを
+/// 該当ファイル数の降順 (同数なら言語名順) に並べる。
+fn summarize(dir: String, health: Vec<FileParseHealth>) -> ParseHealthResult {
+    let scanned_files = health.len();
+    let mut by_lang: BTreeMap<String, LanguageParseHealth> = BTreeMap::new();
+    for file in health {
+        let entry = by_lang
+            .entry(file.lang.to_string())
+            .or_insert_with(|| empty_language(file.lang));
+        entry.scanned_files += 1;
+        if file.is_unhealthy() {
+            entry.files_with_errors += 1;
+            entry.errors += file.errors;
+            entry.missing += file.missing;
+            entry.files.push(file);
+        }
+    }
+
+    let mut languages: Vec<LanguageParseHealth> = by_lang
+        .into_values()
+        .filter(|lang| lang.files_with_errors > 0)
+        .collect();
+    languages.sort_by(|a, b| b.files_with_errors.cmp(&a.files_with_errors));
+
+    ParseHealthResult {
+        dir,
+        scanned_files,
+        files_with_errors: languages.iter().map(|lang| lang.files_with_errors).sum(),
+        languages,
+    }
+}
+
+fn empty_language(lang: LangId) -> LanguageParseHealth {
+    LanguageParseHealth {
+        lang,
+        scanned_files: 0,
+        files_with_errors: 0,
+        errors: 0,
+        missing: 0,
+        files: Vec::new(),
+    }
+}
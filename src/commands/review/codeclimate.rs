@@ -0,0 +1,256 @@
+use crate::models::review::{DeadSymbolVisibility, ReviewResult};
+use crate::output::codeclimate::{Category, Issue, Severity};
+
+/// `review --format codeclimate` の issue 一覧。
+///
+/// blocking 扱いのバケット (影響を受ける呼び出し側 / API 削除・変更 / 変更漏れ候補 /
+/// dead symbol) と重複コードだけを issue にする。informational なバケット
+/// (互換 api.mod / 移動 / dead-code 整理 / api.add) は「直すべき指摘」ではないため出さない。
+pub(crate) fn review_codeclimate_issues(result: &ReviewResult) -> Vec<Issue> {
+    let mut issues = Vec::new();
+
+    for change in &result.impact.changes {
+        for caller in &change.impacted_callers {
+            let symbols = caller.symbols.join(", ");
+            issues.push(Issue {
+                check_name: "astro-sight/impacted-caller".to_string(),
+                description: format!(
+                    "`{}` depends on `{}` changed in {}",
+                    caller.name,
+                    if symbols.is_empty() { "?" } else { &symbols },
+                    change.path
+                ),
+                category: Category::BugRisk,
+                severity: Severity::Major,
+                path: caller.path.clone(),
+                line: Some(caller.line),
+                identity: format!("{}\0{}\0{}", change.path, caller.name, symbols),
+            });
+        }
+    }
+
+    let api = &result.api_changes;
+    for symbol in &api.removed {
+        issues.push(Issue {
+            check_name: "astro-sight/api-removed".to_string(),
+            description: format!("Public {} `{}` was removed", symbol.kind, symbol.name),
+            category: Category::Compatibility,
+            severity: Severity::Critical,
+            path: symbol.file.clone(),
+            line: None,
+            identity: format!("{}\0{}", symbol.kind, symbol.name),
+        });
+    }
+    for change in &api.modified {
+        issues.push(Issue {
+            check_name: "astro-sight/api-modified".to_string(),
+            description: format!(
+                "Signature of public {} `{}` changed: `{}` -> `{}`",
+                change.kind,
+                change.name,
+                change.old_signature.as_deref().unwrap_or("?"),
+                change.new_signature.as_deref().unwrap_or("?")
+            ),
+            category: Category::Compatibility,
+            severity: Severity::Major,
+            path: change.file.clone(),
+            line: None,
+            identity: format!("{}\0{}", change.kind, change.name),
+        });
+    }
+
+    for missing in &result.missing_cochanges {
+        issues.push(Issue {
+            check_name: "astro-sight/missing-cochange".to_string(),
+            description: format!(
+                "`{}` usually changes together with `{}` ({:.0}%), but is not in this diff",
+                missing.file,
+                missing.expected_with,
+                missing.confidence * 100.0
+            ),
+            category: Category::BugRisk,
+            severity: Severity::Minor,
+            path: missing.file.clone(),
+            line: None,
+            identity: missing.expected_with.clone(),
+        });
+    }
+
+    for dead in &result.dead_symbols {
+        issues.push(dead_symbol_issue(dead, false));
+    }
+
+    for param in &result.unused_parameters {
+        issues.push(unused_parameter_issue(param));
+    }
+
+    for feature in &result.cargo_features {
+        issues.push(cargo_feature_issue(feature));
+    }
+
+    for duplicated in &result.duplicated_code {
+        let mut others: Vec<&str> = duplicated
+            .duplicates
+            .iter()
+            .map(|d| d.file.as_str())
+            .collect();
+        others.sort_unstable();
+        others.dedup();
+        issues.push(Issue {
+            check_name: "astro-sight/duplicated-code".to_string(),
+            description: format!(
+                "{} tokens duplicate existing code in {}",
+                duplicated.tokens,
+                others.join(", ")
+            ),
+            category: Category::Duplication,
+            severity: Severity::Info,
+            path: duplicated.file.clone(),
+            line: Some(duplicated.start_line),
+            identity: others.join("\0"),
+        });
+    }
+
+    issues
+}
+
+/// dead symbol 1 件分の issue。`dead-code --format codeclimate` と共有する。
+/// `unreachable` は `dead-code --reachability` の結果 (参照はあっても起点から辿れない) を表す。
+pub(crate) fn dead_symbol_issue(
+    dead: &crate::models::review::DeadSymbol,
+    unreachable: bool,
+) -> Issue {
+    let description = if unreachable {
+        format!(
+            "Unreachable {} `{}` is not reachable from any entry point",
+            dead.kind, dead.name
+        )
+    } else {
+        let scope = match dead.visibility {
+            Some(DeadSymbolVisibility::Private) => "Private",
+            None => "Public",
+        };
+        format!("{scope} {} `{}` is never referenced", dead.kind, dead.name)
+    };
+    Issue {
+        check_name: "astro-sight/dead-symbol".to_string(),
+        description,
+        category: Category::Clarity,
+        severity: Severity::Minor,
+        path: dead.file.clone(),
+        line: dead.line,
+        identity: format!("{}\0{}", dead.kind, dead.name),
+    }
+}
+
+/// 未使用引数 1 件分の issue。`dead-code --params --format codeclimate` と共有する。
+pub(crate) fn unused_parameter_issue(param: &crate::models::review::UnusedParameter) -> Issue {
+    Issue {
+        check_name: "astro-sight/unused-parameter".to_string(),
+        description: format!(
+            "Parameter `{}` of `{}` is never used",
+            param.name, param.function
+        ),
+        category: Category::Clarity,
+        severity: Severity::Info,
+        path: param.file.clone(),
+        line: Some(param.line),
+        identity: format!("{}\0{}", param.function, param.name),
+    }
+}
+
+/// Cargo feature の食い違い 1 件分の issue。`dead-code --cargo-features --format codeclimate`
+/// と共有する。未宣言 feature の参照は typo で `cfg` が常に偽になっている可能性が高いため重くする。
+pub(crate) fn cargo_feature_issue(feature: &crate::models::review::CargoFeatureIssue) -> Issue {
+    use crate::models::review::CargoFeatureIssueKind;
+    let (check_name, description, category, severity) = match feature.kind {
+        CargoFeatureIssueKind::Unused => (
+            "astro-sight/unused-cargo-feature",
+            format!(
+                "Cargo feature `{}` is never referenced by `cfg`",
+                feature.feature
+            ),
+            Category::Clarity,
+            Severity::Info,
+        ),
+        CargoFeatureIssueKind::Undeclared => (
+            "astro-sight/undeclared-cargo-feature",
+            format!(
+                "`cfg(feature = \"{}\")` names a feature not declared in {}",
+                feature.feature, feature.manifest
+            ),
+            Category::BugRisk,
+            Severity::Minor,
+        ),
+    };
+    Issue {
+        check_name: check_name.to_string(),
+        description,
+        category,
+        severity,
+        path: feature.file.clone(),
+        line: Some(feature.line),
+        identity: format!("{}\0{}", feature.manifest, feature.feature),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::review::{ApiSymbol, DeadSymbol};
+
+    #[test]
+    fn maps_blocking_buckets_to_categories() {
+        let mut result = ReviewResult::default();
+        result.api_changes.removed.push(ApiSymbol {
+            name: "old".to_string(),
+            kind: "function".to_string(),
+            file: "src/lib.rs".to_string(),
+            refs_internal: 0,
+        });
+        result.api_changes.added.push(ApiSymbol {
+            name: "new".to_string(),
+            kind: "function".to_string(),
+            file: "src/lib.rs".to_string(),
+            refs_internal: 0,
+        });
+        result.dead_symbols.push(DeadSymbol {
+            name: "unused".to_string(),
+            kind: "function".to_string(),
+            file: "src/util.rs".to_string(),
+            line: Some(3),
+            visibility: None,
+            blame: None,
+        });
+
+        let issues = review_codeclimate_issues(&result);
+        assert_eq!(issues.len(), 2, "api.add is informational: {issues:?}");
+        assert_eq!(issues[0].category, Category::Compatibility);
+        assert_eq!(issues[1].category, Category::Clarity);
+        assert_eq!(issues[1].line, Some(3));
+        assert_eq!(
+            issues[1].description,
+            "Public function `unused` is never referenced"
+        );
+    }
+
+    #[test]
+    fn dead_symbol_description_follows_visibility_and_reachability() {
+        let dead = DeadSymbol {
+            name: "helper".to_string(),
+            kind: "function".to_string(),
+            file: "src/util.rs".to_string(),
+            line: Some(3),
+            visibility: Some(DeadSymbolVisibility::Private),
+            blame: None,
+        };
+        assert_eq!(
+            dead_symbol_issue(&dead, false).description,
+            "Private function `helper` is never referenced"
+        );
+        assert_eq!(
+            dead_symbol_issue(&dead, true).description,
+            "Unreachable function `helper` is not reachable from any entry point"
+        );
+    }
+}
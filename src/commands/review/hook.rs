@@ -3,7 +3,7 @@ use serde::Serialize;
 
 use crate::commands::ChangedFileSet;
 use crate::models::impact::ImpactedCaller;
-use crate::models::review::{ApiChanges, ReviewResult};
+use crate::models::review::{ApiChanges, ReviewResult, ReviewRisk};
 
 /// `--hook` の出力判定結果。
 /// - `value`: stderr に書き出す JSON (何もなければ None)
@@ -110,6 +110,35 @@ struct HookCochange<'a> {
     c: u32,
 }
 
+/// 重複コード (既存コードのコピー&ペースト) の hook 用 DTO。
+/// `f`/`l` が追加側の開始行、`w`/`wl` が複製元 (先頭 1 件) の開始行。
+#[derive(Serialize)]
+struct HookDuplicate<'a> {
+    f: &'a str,
+    l: usize,
+    w: &'a str,
+    wl: usize,
+}
+
+/// 未使用引数の hook 用 DTO。`fn` は引数を持つ関数の qualname、`l` は引数名の行。
+#[derive(Serialize)]
+struct HookUnusedParameter<'a> {
+    n: &'a str,
+    #[serde(rename = "fn")]
+    function: &'a str,
+    f: &'a str,
+    l: usize,
+}
+
+/// Cargo feature の食い違いの hook 用 DTO。`k` は `unused` / `undeclared`、`n` は feature 名。
+#[derive(Serialize)]
+struct HookCargoFeature<'a> {
+    k: crate::models::review::CargoFeatureIssueKind,
+    n: &'a str,
+    f: &'a str,
+    l: usize,
+}
+
 /// 打ち切り (解析対象から外したもの) の hook 用 DTO。
 /// 未追跡の巨大ファイルを除外した場合など「レビュー範囲が欠けた」ことを hook でも伝える。
 /// blocking にはしない (検出ではなく解析範囲の申告) が、沈黙させると「全部見た」と読める。
@@ -120,6 +149,23 @@ struct HookTruncation<'a> {
     r: &'a str,
 }
 
+/// `--risk-threshold` 到達時の hook 用 DTO。`top` はスコア上位のファイル (最大
+/// `HOOK_RISK_TOP_FILES` 件) で、内訳は通常の `review` 出力の `risk` で確認する。
+#[derive(Serialize)]
+struct HookRisk<'a> {
+    score: f64,
+    th: f64,
+    top: Vec<HookRiskFile<'a>>,
+}
+
+#[derive(Serialize)]
+struct HookRiskFile<'a> {
+    f: &'a str,
+    s: f64,
+}
+
+const HOOK_RISK_TOP_FILES: usize = 3;
+
 #[derive(Serialize)]
 struct HookApi<'a> {
     #[serde(rename = "add", skip_serializing_if = "Vec::is_empty")]
@@ -439,6 +485,68 @@ pub(crate) fn build_review_hook_json(
         );
     }
 
+    // dup: [{f,l,w,wl}] — 既存コードの複製。意図的なコピーもあるため情報提供のみ。
+    let duplicates: Vec<HookDuplicate<'_>> = result
+        .duplicated_code
+        .iter()
+        .filter_map(|dup| {
+            let origin = dup.duplicates.first()?;
+            Some(HookDuplicate {
+                f: dup.file.as_str(),
+                l: dup.start_line,
+                w: origin.file.as_str(),
+                wl: origin.start_line,
+            })
+        })
+        .collect();
+    if !duplicates.is_empty() {
+        has_any_output = true;
+        hook_obj.insert(
+            "dup".into(),
+            serde_json::to_value(duplicates).expect("hook duplicate DTO should serialize"),
+        );
+    }
+
+    // unused_params: [{n,fn,f,l}] — diff で追加されたが本体で使われない引数。
+    // コールバック等で署名を合わせているだけの場合もあるため情報提供のみ。
+    if !result.unused_parameters.is_empty() {
+        has_any_output = true;
+        let params: Vec<HookUnusedParameter<'_>> = result
+            .unused_parameters
+            .iter()
+            .map(|param| HookUnusedParameter {
+                n: param.name.as_str(),
+                function: param.function.as_str(),
+                f: param.file.as_str(),
+                l: param.line,
+            })
+            .collect();
+        hook_obj.insert(
+            "unused_params".into(),
+            serde_json::to_value(params).expect("hook unused-parameter DTO should serialize"),
+        );
+    }
+
+    // cargo_features: [{k,n,f,l}] — 使われない feature / 未宣言 feature の参照。
+    // feature は crate の外 (依存側の指定や CI の `--features`) から使われ得るため情報提供のみ。
+    if !result.cargo_features.is_empty() {
+        has_any_output = true;
+        let features: Vec<HookCargoFeature<'_>> = result
+            .cargo_features
+            .iter()
+            .map(|feature| HookCargoFeature {
+                k: feature.kind,
+                n: feature.feature.as_str(),
+                f: feature.file.as_str(),
+                l: feature.line,
+            })
+            .collect();
+        hook_obj.insert(
+            "cargo_features".into(),
+            serde_json::to_value(features).expect("hook cargo-feature DTO should serialize"),
+        );
+    }
+
     // trunc: [{f,r}] — 解析対象から外したものの申告。情報提供のみ (blocking にしない)。
     if !result.truncations.is_empty() {
         has_any_output = true;
@@ -451,6 +559,12 @@ pub(crate) fn build_review_hook_json(
                     crate::models::truncation::TruncationReason::UntrackedFileTooLarge => {
                         "untracked_file_too_large"
                     }
+                    crate::models::truncation::TruncationReason::SourceTooLarge => {
+                        "source_too_large"
+                    }
+                    crate::models::truncation::TruncationReason::DepthLimitReached => {
+                        "depth_limit_reached"
+                    }
                 },
             })
             .collect();
@@ -497,20 +611,75 @@ pub(crate) fn build_review_hook_json(
     }
 }
 
+/// リスクスコアが `threshold` 以上なら hook JSON に `risk` を加えて blocking にする。
+///
+/// 閾値未満 (または閾値未指定) のときは `build` をそのまま返す。他の検出が無く
+/// スコアだけが閾値に達した場合も、`hint` 付きの JSON を新たに作って止める。
+pub(crate) fn apply_risk_threshold(
+    build: HookJsonBuild,
+    risk: Option<&ReviewRisk>,
+    threshold: Option<f64>,
+) -> HookJsonBuild {
+    let (Some(risk), Some(threshold)) = (risk, threshold) else {
+        return build;
+    };
+    if risk.score < threshold {
+        return build;
+    }
+
+    let mut hook_obj = match build.value {
+        Some(serde_json::Value::Object(map)) => map,
+        _ => {
+            let mut map = serde_json::Map::new();
+            map.insert(
+                "hint".into(),
+                serde_json::Value::String("False positives? Run astro-sight-triage skill.".into()),
+            );
+            map
+        }
+    };
+    let dto = HookRisk {
+        score: risk.score,
+        th: threshold,
+        top: risk
+            .files
+            .iter()
+            .take(HOOK_RISK_TOP_FILES)
+            .map(|file| HookRiskFile {
+                f: file.path.as_str(),
+                s: file.score,
+            })
+            .collect(),
+    };
+    hook_obj.insert(
+        "risk".into(),
+        serde_json::to_value(dto).expect("hook risk DTO should serialize"),
+    );
+    HookJsonBuild {
+        value: Some(serde_json::Value::Object(hook_obj)),
+        is_blocking: true,
+    }
+}
+
 /// --hook 時の review 出力: compact JSON を stderr に出力する。
 ///
 /// ここは Stop hook の JSON 契約なので、`OutputOptions` を受け取らず
 /// `serde_json::Value` の `Display` (= compact JSON) で直接書く。
 /// `serialize_document` を通すと `--format auto` で TOON が選ばれ、黙って契約が壊れる
 /// (`OutputOptions::ensure_json_protocol` の不変条件を参照)。
-/// blocking な検出 (impacts / api / dead) があれば exit 1、
+/// blocking な検出 (impacts / api / dead、`--risk-threshold` 到達) があれば exit 1、
 /// cochange のみの informational な出力は exit 0 にして Stop hook を止めない。
 pub(crate) fn review_hook_output(
     result: &ReviewResult,
     dir: &str,
     strict_const_values: bool,
+    risk_threshold: Option<f64>,
 ) -> Result<()> {
-    let build = build_review_hook_json(result, dir, strict_const_values);
+    let build = apply_risk_threshold(
+        build_review_hook_json(result, dir, strict_const_values),
+        result.risk.as_ref(),
+        risk_threshold,
+    );
     let Some(hook_output) = build.value else {
         return Ok(());
     };
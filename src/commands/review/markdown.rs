@@ -0,0 +1,621 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+use crate::models::review::{CargoFeatureIssueKind, ReviewResult};
+
+/// 1 セクションあたりの最大行数。PR コメントは長すぎると読まれない (GitHub の本文上限
+/// 65536 文字にも当たる) ため、超過分は件数だけ「… N more」で示す。
+const MAX_ITEMS_PER_SECTION: usize = 30;
+
+/// `review --format markdown` の本文を組み立てる。
+///
+/// 先頭に要約行、続いて blocking 扱いのバケット (影響を受ける呼び出し側 / API 変更 /
+/// 変更漏れ候補 / dead symbol) を見出し付きで、informational なバケット
+/// (diff 内で追随済みの api.mod / 互換 api.mod / 定数値の変更 / property → field / 移動 /
+/// dead-code 整理 / テストからだけ参照されるシンボル / 未使用引数 / Cargo feature / 複製コード)
+/// は `<details>` に畳んで出す。
+/// 位置は `path:line` (1-indexed) で、GitHub / エディタ上でそのまま辿れる形にする。
+/// `link_base` (例: `https://github.com/org/repo/blob/<sha>`) があれば位置を
+/// `<link_base>/<path>#L<line>` へのリンクにする。
+pub(crate) fn render_review_markdown(result: &ReviewResult, link_base: Option<&str>) -> String {
+    let links = Links {
+        base: link_base.map(|base| base.trim_end_matches('/')),
+    };
+    let mut out = String::from("## astro-sight review\n\n");
+
+    if let Some(skip) = &result.skipped {
+        let _ = writeln!(out, "_Skipped: {}_\n", escape_cell(&skip.message));
+    }
+
+    let impacts = group_impacts(result);
+    let api = &result.api_changes;
+    let caller_count: usize = impacts.values().map(Vec::len).sum();
+    let _ = writeln!(
+        out,
+        "**{}** impacted caller(s) · **{}** removed / **{}** modified / **{}** added API · **{}** missing co-change(s) · **{}** dead symbol(s)\n",
+        caller_count,
+        api.removed.len(),
+        api.modified.len(),
+        api.added.len(),
+        result.missing_cochanges.len(),
+        result.dead_symbols.len(),
+    );
+
+    if let Some(risk) = result.risk.as_ref().filter(|risk| !risk.files.is_empty()) {
+        let _ = writeln!(out, "### Risk: {}\n", risk.score);
+        push_table(
+            &mut out,
+            "By file",
+            &["File", "Score", "Factors"],
+            risk.files.iter().map(|file| {
+                let factors = file
+                    .factors
+                    .iter()
+                    .map(|f| format!("{} +{}", f.factor, f.contribution))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                vec![
+                    links.location(&file.path, None),
+                    file.score.to_string(),
+                    factors,
+                ]
+            }),
+            risk.files.len(),
+        );
+    }
+
+    if !impacts.is_empty() {
+        out.push_str("### Impacted callers\n\n");
+        for (symbol, callers) in impacts.iter().take(MAX_ITEMS_PER_SECTION) {
+            let _ = writeln!(out, "- `{symbol}`");
+            let lines = callers.iter().map(|(path, line, name)| {
+                format!("  - {} in `{name}`", links.location(path, Some(*line)))
+            });
+            push_capped(&mut out, lines, callers.len(), "  ");
+        }
+        if impacts.len() > MAX_ITEMS_PER_SECTION {
+            let _ = writeln!(
+                out,
+                "- … {} more changed symbol(s)",
+                impacts.len() - MAX_ITEMS_PER_SECTION
+            );
+        }
+        out.push('\n');
+    }
+
+    if !api.removed.is_empty() || !api.modified.is_empty() || !api.added.is_empty() {
+        out.push_str("### API changes\n\n");
+        if !api.removed.is_empty() {
+            push_table(
+                &mut out,
+                "Removed",
+                &["Symbol", "Kind", "File"],
+                api.removed
+                    .iter()
+                    .map(|s| vec![code(&s.name), s.kind.clone(), links.location(&s.file, None)]),
+                api.removed.len(),
+            );
+        }
+        if !api.modified.is_empty() {
+            push_table(
+                &mut out,
+                "Modified",
+                &["Symbol", "File", "Old signature", "New signature"],
+                api.modified.iter().map(|m| {
+                    vec![
+                        code(&m.name),
+                        links.location(&m.file, None),
+                        m.old_signature.as_deref().map(code).unwrap_or_default(),
+                        m.new_signature.as_deref().map(code).unwrap_or_default(),
+                    ]
+                }),
+                api.modified.len(),
+            );
+        }
+        if !api.added.is_empty() {
+            push_table(
+                &mut out,
+                "Added",
+                &["Symbol", "Kind", "File"],
+                api.added
+                    .iter()
+                    .map(|s| vec![code(&s.name), s.kind.clone(), links.location(&s.file, None)]),
+                api.added.len(),
+            );
+        }
+    }
+
+    if !result.missing_cochanges.is_empty() {
+        out.push_str("### Missing co-changes\n\n");
+        let lines = result.missing_cochanges.iter().map(|m| {
+            format!(
+                "- `{}` usually changes with `{}` ({:.0}%)",
+                m.file,
+                m.expected_with,
+                m.confidence * 100.0
+            )
+        });
+        push_capped(&mut out, lines, result.missing_cochanges.len(), "");
+        out.push('\n');
+    }
+
+    if !result.owners_to_notify.is_empty() {
+        out.push_str("### Owners to notify\n\n");
+        let lines = result.owners_to_notify.iter().map(|o| {
+            let files = o
+                .files
+                .iter()
+                .map(|file| format!("`{file}`"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("- {}: {files}", o.owner)
+        });
+        push_capped(&mut out, lines, result.owners_to_notify.len(), "");
+        out.push('\n');
+    }
+
+    if !result.dead_symbols.is_empty() {
+        out.push_str("### Dead symbols\n\n");
+        let lines = result.dead_symbols.iter().map(|d| {
+            format!(
+                "- `{}` ({}) at {}",
+                d.name,
+                d.kind,
+                links.location(&d.file, d.line)
+            )
+        });
+        push_capped(&mut out, lines, result.dead_symbols.len(), "");
+        out.push('\n');
+    }
+
+    let transitive: Vec<_> = result
+        .impact
+        .changes
+        .iter()
+        .flat_map(|change| &change.transitive_callers)
+        .collect();
+    if !transitive.is_empty() {
+        push_details(
+            &mut out,
+            "Transitive callers",
+            transitive.iter().map(|c| {
+                let via = c
+                    .chain
+                    .iter()
+                    .map(|name| format!("`{name}`"))
+                    .collect::<Vec<_>>()
+                    .join(" → ");
+                format!("- {} via {via}", links.location(&c.path, Some(c.line)))
+            }),
+            transitive.len(),
+        );
+    }
+    if !api.modified_closed_in_diff.is_empty() {
+        push_details(
+            &mut out,
+            "API modifications with callers updated in this diff",
+            api.modified_closed_in_diff
+                .iter()
+                .map(|m| format!("- `{}` in `{}`", m.name, m.file)),
+            api.modified_closed_in_diff.len(),
+        );
+    }
+    if !api.compatible_modified.is_empty() {
+        push_details(
+            &mut out,
+            "Compatible API modifications",
+            api.compatible_modified
+                .iter()
+                .map(|m| format!("- `{}` in `{}` ({})", m.name, m.file, m.reason)),
+            api.compatible_modified.len(),
+        );
+    }
+    if !api.const_value_changes.is_empty() {
+        push_details(
+            &mut out,
+            "Constant value changes",
+            api.const_value_changes.iter().map(|m| {
+                let old = m.old_signature.as_deref().map(code).unwrap_or_default();
+                let new = m.new_signature.as_deref().map(code).unwrap_or_default();
+                format!("- `{}` in `{}`: {old} → {new}", m.name, m.file)
+            }),
+            api.const_value_changes.len(),
+        );
+    }
+    if !api.property_to_field.is_empty() {
+        push_details(
+            &mut out,
+            "Properties turned into fields",
+            api.property_to_field
+                .iter()
+                .map(|p| format!("- `{}` in `{}`", p.name, p.file)),
+            api.property_to_field.len(),
+        );
+    }
+    if !api.moved.is_empty() {
+        push_details(
+            &mut out,
+            "Moved symbols",
+            api.moved
+                .iter()
+                .map(|m| format!("- `{}`: `{}` → `{}`", m.name, m.from, m.to)),
+            api.moved.len(),
+        );
+    }
+    if !api.removed_dead.is_empty() {
+        push_details(
+            &mut out,
+            "Removed dead code",
+            api.removed_dead
+                .iter()
+                .map(|s| format!("- `{}` ({}) in `{}`", s.name, s.kind, s.file)),
+            api.removed_dead.len(),
+        );
+    }
+    if !result.test_only_symbols.is_empty() {
+        push_details(
+            &mut out,
+            "Symbols used only by tests",
+            result.test_only_symbols.iter().map(|d| {
+                format!(
+                    "- `{}` ({}) at {}",
+                    d.name,
+                    d.kind,
+                    links.location(&d.file, d.line)
+                )
+            }),
+            result.test_only_symbols.len(),
+        );
+    }
+    if !result.unused_parameters.is_empty() {
+        push_details(
+            &mut out,
+            "Unused parameters",
+            result.unused_parameters.iter().map(|p| {
+                format!(
+                    "- `{}` of `{}` at {}",
+                    p.name,
+                    p.function,
+                    links.location(&p.file, Some(p.line))
+                )
+            }),
+            result.unused_parameters.len(),
+        );
+    }
+    if !result.cargo_features.is_empty() {
+        push_details(
+            &mut out,
+            "Cargo features",
+            result.cargo_features.iter().map(|f| match f.kind {
+                CargoFeatureIssueKind::Unused => format!(
+                    "- `{}` is never referenced by `cfg` ({})",
+                    f.feature,
+                    links.location(&f.file, Some(f.line))
+                ),
+                CargoFeatureIssueKind::Undeclared => format!(
+                    "- `{}` is not declared in `{}` ({})",
+                    f.feature,
+                    f.manifest,
+                    links.location(&f.file, Some(f.line))
+                ),
+            }),
+            result.cargo_features.len(),
+        );
+    }
+    if !result.duplicated_code.is_empty() {
+        push_details(
+            &mut out,
+            "Duplicated code",
+            result.duplicated_code.iter().map(|d| {
+                let duplicates = d
+                    .duplicates
+                    .iter()
+                    .map(|dup| links.location(&dup.file, Some(dup.start_line)))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!(
+                    "- {} ({} tokens, {:.0}% similar) duplicates {duplicates}",
+                    links.location(&d.file, Some(d.start_line)),
+                    d.tokens,
+                    d.similarity * 100.0
+                )
+            }),
+            result.duplicated_code.len(),
+        );
+    }
+    if !result.truncations.is_empty() {
+        push_details(
+            &mut out,
+            "Truncations",
+            result.truncations.iter().map(|t| match &t.path {
+                Some(path) => format!("- `{path}`: {}", t.message),
+                None => format!("- {}", t.message),
+            }),
+            result.truncations.len(),
+        );
+    }
+
+    out
+}
+
+/// `impacted_callers` を「変更されたシンボル → 呼び出し側一覧」にまとめ直す。
+/// caller の `symbols` が空 (どの変更シンボル由来か特定できない) 場合は変更ファイル単位で括る。
+fn group_impacts(result: &ReviewResult) -> BTreeMap<String, Vec<(&str, usize, &str)>> {
+    let mut groups: BTreeMap<String, Vec<(&str, usize, &str)>> = BTreeMap::new();
+    for change in &result.impact.changes {
+        for caller in &change.impacted_callers {
+            let entry = (caller.path.as_str(), caller.line, caller.name.as_str());
+            if caller.symbols.is_empty() {
+                groups
+                    .entry(format!("{} (any symbol)", change.path))
+                    .or_default()
+                    .push(entry);
+            } else {
+                for symbol in &caller.symbols {
+                    groups.entry(symbol.clone()).or_default().push(entry);
+                }
+            }
+        }
+    }
+    for callers in groups.values_mut() {
+        callers.sort();
+        callers.dedup();
+    }
+    groups
+}
+
+fn push_capped(out: &mut String, lines: impl Iterator<Item = String>, total: usize, indent: &str) {
+    for line in lines.take(MAX_ITEMS_PER_SECTION) {
+        out.push_str(&line);
+        out.push('\n');
+    }
+    if total > MAX_ITEMS_PER_SECTION {
+        let _ = writeln!(out, "{indent}- … {} more", total - MAX_ITEMS_PER_SECTION);
+    }
+}
+
+fn push_table(
+    out: &mut String,
+    title: &str,
+    header: &[&str],
+    rows: impl Iterator<Item = Vec<String>>,
+    total: usize,
+) {
+    let _ = writeln!(out, "**{title}**\n");
+    let _ = writeln!(out, "| {} |", header.join(" | "));
+    let _ = writeln!(out, "|{}", "---|".repeat(header.len()));
+    for row in rows.take(MAX_ITEMS_PER_SECTION) {
+        let cells: Vec<String> = row.iter().map(|c| escape_cell(c)).collect();
+        let _ = writeln!(out, "| {} |", cells.join(" | "));
+    }
+    if total > MAX_ITEMS_PER_SECTION {
+        let _ = writeln!(out, "\n_… {} more_", total - MAX_ITEMS_PER_SECTION);
+    }
+    out.push('\n');
+}
+
+fn push_details(
+    out: &mut String,
+    summary: &str,
+    lines: impl Iterator<Item = String>,
+    total: usize,
+) {
+    let _ = writeln!(out, "<details>\n<summary>{summary} ({total})</summary>\n");
+    push_capped(out, lines, total, "");
+    out.push_str("\n</details>\n\n");
+}
+
+/// 位置表記の組み立て。`base` があれば blob URL へのリンクにする。
+struct Links<'a> {
+    base: Option<&'a str>,
+}
+
+impl Links<'_> {
+    /// `line` は 0-indexed で受け、表示とアンカーは 1-indexed にする。
+    fn location(&self, file: &str, line: Option<usize>) -> String {
+        let label = match line {
+            Some(line) => format!("`{file}:{}`", line + 1),
+            None => format!("`{file}`"),
+        };
+        match (self.base, line) {
+            (Some(base), Some(line)) => format!("[{label}]({base}/{file}#L{})", line + 1),
+            (Some(base), None) => format!("[{label}]({base}/{file})"),
+            (None, _) => label,
+        }
+    }
+}
+
+/// `--link-base` 未指定時は GitHub Actions の環境変数からコミットの blob URL を組み立てる。
+pub(crate) fn resolve_link_base(explicit: Option<&str>) -> Option<String> {
+    if let Some(base) = explicit {
+        return Some(base.to_string());
+    }
+    let var = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+    Some(format!(
+        "{}/{}/blob/{}",
+        var("GITHUB_SERVER_URL")?.trim_end_matches('/'),
+        var("GITHUB_REPOSITORY")?,
+        var("GITHUB_SHA")?
+    ))
+}
+
+/// シグネチャ等をインラインコードにする。改行はテーブル行を壊すため空白に潰す。
+fn code(text: &str) -> String {
+    let flat = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if flat.contains('`') {
+        format!("`` {flat} ``")
+    } else {
+        format!("`{flat}`")
+    }
+}
+
+/// テーブルセル内の `|` は列区切りと解釈されるためエスケープする。
+fn escape_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::dupes::{CloneInstance, DuplicatedCode};
+    use crate::models::impact::{FileImpact, ImpactedCaller};
+    use crate::models::review::{
+        ApiSymbol, ApiSymbolChange, DeadSymbol, MissingCochange, MovedSymbol,
+    };
+
+    fn caller(path: &str, line: usize, symbols: &[&str]) -> ImpactedCaller {
+        ImpactedCaller {
+            path: path.to_string(),
+            name: "caller".to_string(),
+            line,
+            symbols: symbols.iter().map(|s| s.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn groups_callers_by_changed_symbol_with_one_indexed_locations() {
+        let mut result = ReviewResult::default();
+        result.impact.changes.push(FileImpact {
+            path: "src/lib.rs".to_string(),
+            impacted_callers: vec![
+                caller("src/a.rs", 9, &["parse"]),
+                caller("src/b.rs", 0, &["parse"]),
+            ],
+            ..Default::default()
+        });
+        let md = render_review_markdown(&result, None);
+        assert!(md.contains("- `parse`\n"));
+        assert!(md.contains("  - `src/a.rs:10` in `caller`"));
+        assert!(md.contains("  - `src/b.rs:1` in `caller`"));
+        assert!(md.contains("**2** impacted caller(s)"));
+    }
+
+    #[test]
+    fn renders_api_tables_and_escapes_pipes_in_signatures() {
+        let mut result = ReviewResult::default();
+        result.api_changes.modified.push(ApiSymbolChange {
+            name: "run".to_string(),
+            kind: "function".to_string(),
+            file: "src/lib.rs".to_string(),
+            old_signature: Some("fn run(a: u8)".to_string()),
+            new_signature: Some("fn run(f: impl Fn(u8) -> u8 | None)".to_string()),
+            no_resolved_internal_callers: false,
+        });
+        result.api_changes.removed.push(ApiSymbol {
+            name: "old".to_string(),
+            kind: "function".to_string(),
+            file: "src/lib.rs".to_string(),
+            refs_internal: 0,
+        });
+        let md = render_review_markdown(&result, None);
+        assert!(md.contains("| Symbol | File | Old signature | New signature |"));
+        assert!(md.contains("`fn run(a: u8)`"));
+        assert!(md.contains("u8 \\| None)`"));
+        assert!(md.contains("**Removed**"));
+        assert!(!md.contains("**Added**"));
+    }
+
+    #[test]
+    fn informational_buckets_are_collapsed_and_lists_are_capped() {
+        let mut result = ReviewResult::default();
+        result.api_changes.moved.push(MovedSymbol {
+            name: "helper".to_string(),
+            kind: "function".to_string(),
+            from: "a.rs".to_string(),
+            to: "b.rs".to_string(),
+        });
+        for i in 0..MAX_ITEMS_PER_SECTION + 5 {
+            result.missing_cochanges.push(MissingCochange {
+                file: format!("f{i}.rs"),
+                expected_with: "lib.rs".to_string(),
+                confidence: 0.8,
+                owners: Vec::new(),
+            });
+        }
+        let md = render_review_markdown(&result, None);
+        assert!(md.contains("<summary>Moved symbols (1)</summary>"));
+        assert!(md.contains("(80%)"));
+        assert!(md.contains("- … 5 more"));
+        assert!(!md.contains("f34.rs"));
+    }
+
+    #[test]
+    fn renders_duplicated_code_and_remaining_informational_buckets() {
+        let mut result = ReviewResult::default();
+        result.duplicated_code.push(DuplicatedCode {
+            file: "src/new.rs".to_string(),
+            start_line: 4,
+            end_line: 20,
+            tokens: 64,
+            similarity: 0.9,
+            duplicates: vec![CloneInstance {
+                file: "src/old.rs".to_string(),
+                start_line: 0,
+                end_line: 16,
+            }],
+        });
+        result.test_only_symbols.push(DeadSymbol {
+            name: "fixture".to_string(),
+            kind: "function".to_string(),
+            file: "src/lib.rs".to_string(),
+            line: Some(2),
+            visibility: None,
+            blame: None,
+        });
+        result
+            .api_changes
+            .const_value_changes
+            .push(ApiSymbolChange {
+                name: "LIMIT".to_string(),
+                kind: "const".to_string(),
+                file: "src/lib.rs".to_string(),
+                old_signature: Some("pub const LIMIT: u8 = 1".to_string()),
+                new_signature: Some("pub const LIMIT: u8 = 2".to_string()),
+                no_resolved_internal_callers: false,
+            });
+        let md = render_review_markdown(&result, None);
+        assert!(md.contains("<summary>Duplicated code (1)</summary>"));
+        assert!(md.contains("- `src/new.rs:5` (64 tokens, 90% similar) duplicates `src/old.rs:1`"));
+        assert!(md.contains("<summary>Symbols used only by tests (1)</summary>"));
+        assert!(md.contains("- `fixture` (function) at `src/lib.rs:3`"));
+        assert!(md.contains(
+            "- `LIMIT` in `src/lib.rs`: `pub const LIMIT: u8 = 1` → `pub const LIMIT: u8 = 2`"
+        ));
+        assert!(!md.contains("Properties turned into fields"));
+    }
+
+    #[test]
+    fn links_locations_when_base_is_given() {
+        let mut result = ReviewResult::default();
+        result.impact.changes.push(FileImpact {
+            path: "src/lib.rs".to_string(),
+            impacted_callers: vec![caller("src/a.rs", 9, &["parse"])],
+            ..Default::default()
+        });
+        let md = render_review_markdown(&result, Some("https://github.com/org/repo/blob/abc123/"));
+        assert!(md.contains(
+            "  - [`src/a.rs:10`](https://github.com/org/repo/blob/abc123/src/a.rs#L10) in `caller`"
+        ));
+    }
+
+    #[test]
+    fn impact_groups_are_capped() {
+        let mut result = ReviewResult::default();
+        let symbols: Vec<String> = (0..MAX_ITEMS_PER_SECTION + 3)
+            .map(|i| format!("sym{i:02}"))
+            .collect();
+        result.impact.changes.push(FileImpact {
+            path: "src/lib.rs".to_string(),
+            impacted_callers: symbols
+                .iter()
+                .map(|symbol| caller("src/a.rs", 1, &[symbol.as_str()]))
+                .collect(),
+            ..Default::default()
+        });
+        let md = render_review_markdown(&result, None);
+        assert!(md.contains("- `sym29`\n"));
+        assert!(!md.contains("- `sym30`"));
+        assert!(md.contains("- … 3 more changed symbol(s)"));
+    }
+}
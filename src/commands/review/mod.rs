@@ -2,25 +2,40 @@ use anyhow::Result;
 use std::collections::HashSet;
 use tracing::info;
 
-use crate::models::review::{ApiSymbol, DeadSymbol, ReviewResult};
+use crate::models::review::{
+    ApiSymbol, CargoFeatureIssue, DeadSymbol, ReviewResult, UnusedParameter,
+};
 use crate::models::skip::SkipInfo;
 use crate::service::AppService;
 
 use super::api_changes::detect_api_changes;
-use crate::output::{OutputOptions, serialize_cli_document};
+use crate::output::{OutputFormat, OutputOptions, serialize_cli_document};
 
 use super::common::{MAX_INPUT_SIZE, log_phase, read_to_string_limited, timed, timed_ok};
 use super::dead_code::{
     detect_dead_symbols_from_files, filter_dead_by_touched_symbols, filter_dead_by_wip_added,
-    filter_diff_files_for_dead_code, resolve_dead_code_excludes,
-    resolve_framework_globs_with_auto_detect,
+    filter_diff_files_for_dead_code, resolve_dead_code_conventions, resolve_dead_code_excludes,
+    resolve_framework_globs_with_auto_detect, retain_added_parameters,
 };
+use super::dead_code_features::{detect_cargo_feature_issues, touched_feature_manifests};
+use super::dead_code_params::detect_unused_parameters;
+use super::dupes::review_duplicated_code;
 use super::git_input::{DiffSourceResolution, resolve_diff_source};
+use codeclimate::review_codeclimate_issues;
 use hook::review_hook_output;
+use markdown::{render_review_markdown, resolve_link_base};
 use missing_cochange::detect_missing_cochanges;
+use owners::annotate_review_owners;
+use range::cmd_review_range;
+use risk::compute_review_risk;
 
+pub(crate) mod codeclimate;
 pub mod hook;
+mod markdown;
 pub(crate) mod missing_cochange;
+mod owners;
+mod range;
+mod risk;
 
 // ---------------------------------------------------------------------------
 // Review コマンド: impact / cochange / API surface diff / dead symbol 統合
@@ -41,14 +56,30 @@ pub struct CmdReviewOpts<'a> {
     pub framework: Option<&'a str>,
     pub extra_exclude_dirs: &'a [String],
     pub extra_exclude_globs: &'a [String],
+    /// dead 判定で entrypoint とみなす追加の annotation 名 (`--entry-annotation`)。
+    pub entry_annotations: &'a [String],
     pub dead_scope: crate::cli::DeadScope,
     pub strict_public_const_values: bool,
     pub include_wip_dead: bool,
+    /// `--hook` でリスクスコアがこの値以上なら block する。`--hook` 時はこの指定が
+    /// あるときだけスコアを算出する。
+    pub risk_threshold: Option<f64>,
+    /// 追加コードの重複 (`duplicated_code`) を検出するか (`--dupes` / `--no-dupes`)。
+    pub dupes: bool,
+    /// 公開ラッパー経由で `transitive_callers` を辿る段数 (0 は無効、blocking には影響しない)。
+    pub transitive_depth: usize,
+    /// `A..B` 形式のコミット範囲。指定時は作業ツリーではなく B をチェックアウトした
+    /// 一時 worktree 上で review する (`range.rs`)。
+    pub range: Option<&'a str>,
+    /// `range` の各コミットを親コミットとの差分で個別に review する。
+    pub per_commit: bool,
+    /// `--format markdown` の位置リンクの基点 URL (`--link-base`)。
+    pub link_base: Option<&'a str>,
 }
 
 pub fn cmd_review(service: &AppService, opts: &CmdReviewOpts<'_>) -> Result<()> {
     // 本体は従来の局所変数名のまま使うため、ここで一括分解する
-    // (全フィールド Copy。本体側の書き換えゼロ = 引数取り違えの余地ゼロ)。
+    // (全フィールド Copy。解析パイプライン側の引数は `review_diff` が同じ opts から分解する)。
     let &CmdReviewOpts {
         dir,
         diff,
@@ -56,21 +87,21 @@ pub fn cmd_review(service: &AppService, opts: &CmdReviewOpts<'_>) -> Result<()>
         git,
         base,
         staged,
-        min_confidence,
         output,
         hook,
         framework,
-        extra_exclude_dirs,
-        extra_exclude_globs,
-        dead_scope,
         strict_public_const_values,
-        include_wip_dead,
+        range,
+        ..
     } = opts;
     // `--hook` の出力は Claude Code の Stop hook が消費する compact JSON 契約。
     // 明示的な `--format toon` は満たせないのでここで弾く (config 由来なら JSON に倒す)。
     if hook {
         output.ensure_json_protocol("review --hook")?;
     }
+    if let Some(range) = range {
+        return cmd_review_range(service, opts, range);
+    }
     // framework 指定は早期に検証して未知名はここで弾く (dead_symbols 検出に到達する前に)。
     // 未指定時は package.json から next 依存を検出して nextjs プリセットを自動適用する。
     let framework_globs = resolve_framework_globs_with_auto_detect(framework, dir)?;
@@ -91,8 +122,68 @@ pub fn cmd_review(service: &AppService, opts: &CmdReviewOpts<'_>) -> Result<()>
             }
         };
 
-    if diff_input.trim().is_empty() {
+    let Some(result) = review_diff(
+        service,
+        opts,
+        &framework_globs,
+        &diff_input,
+        truncations.clone(),
+    )?
+    else {
         return emit_review_short_circuit(hook, output, None, truncations);
+    };
+
+    if hook {
+        return review_hook_output(
+            &result,
+            dir,
+            strict_public_const_values,
+            opts.risk_threshold,
+        );
+    }
+
+    let text = render_review(&result, output, opts.link_base)?;
+    info!(
+        command = "review",
+        dir = dir,
+        output_bytes = text.len(),
+        "command completed"
+    );
+    print!("{text}");
+    Ok(())
+}
+
+/// diff 取得後の review パイプライン (impact → cochange → API 差分 → dead → 重複 → リスク
+/// → CODEOWNERS)。
+///
+/// 空 diff / CI 言語のみの diff は解析せず `None` を返す (呼び出し側が短絡出力を選ぶ)。
+/// `opts` の `dir` / `base` だけを差し替えて呼べば、作業ツリー以外 (一時 worktree 上の
+/// 過去コミット) も同じ経路で review できる。
+fn review_diff(
+    service: &AppService,
+    opts: &CmdReviewOpts<'_>,
+    framework_globs: &[String],
+    diff_input: &str,
+    truncations: Vec<crate::models::truncation::TruncationInfo>,
+) -> Result<Option<ReviewResult>> {
+    let &CmdReviewOpts {
+        dir,
+        base,
+        min_confidence,
+        hook,
+        framework,
+        extra_exclude_dirs,
+        extra_exclude_globs,
+        entry_annotations,
+        dead_scope,
+        include_wip_dead,
+        risk_threshold,
+        dupes,
+        transitive_depth,
+        ..
+    } = opts;
+    if diff_input.trim().is_empty() {
+        return Ok(None);
     }
 
     // 2. impact 分析
@@ -107,10 +198,10 @@ pub fn cmd_review(service: &AppService, opts: &CmdReviewOpts<'_>) -> Result<()>
     // `ASTRO_SIGHT_FORCE_CI_LANG_IMPACT=1` で従来挙動に戻せる (デバッグ用)。
     // diff は CI 言語判定 / changed_file_set / api_changes / dead_code filter / touched-symbols
     // で繰り返し参照するため、ここで一度だけ parse して再利用する。
-    let diff_files = crate::engine::diff::parse_unified_diff(&diff_input);
+    let diff_files = crate::engine::diff::parse_unified_diff(diff_input);
     if crate::engine::impact::should_skip_ci_only_diff(&diff_files) {
         log_phase("review.skip_ci_only", "applied", 0);
-        return emit_review_short_circuit(hook, output, None, truncations);
+        return Ok(None);
     }
 
     let impact = timed_ok("context", || {
@@ -119,8 +210,9 @@ pub fn cmd_review(service: &AppService, opts: &CmdReviewOpts<'_>) -> Result<()>
         let context_options = crate::models::impact::ContextAnalysisOptions {
             exclude_dirs: extra_exclude_dirs.to_vec(),
             exclude_globs: extra_exclude_globs.to_vec(),
+            transitive_depth,
         };
-        service.analyze_context(&diff_input, dir, &context_options)
+        service.analyze_context(diff_input, dir, &context_options)
     })?;
 
     // 3. diff に含まれるファイルリストを収集
@@ -149,11 +241,13 @@ pub fn cmd_review(service: &AppService, opts: &CmdReviewOpts<'_>) -> Result<()>
     // 6. dead symbol 検出
     let dead_opts = ReviewDeadSymbolsOpts {
         dir,
-        diff_input: &diff_input,
+        diff_input,
         diff_files: &diff_files,
-        framework_globs: &framework_globs,
+        framework_globs,
+        framework,
         extra_exclude_dirs,
         extra_exclude_globs,
+        entry_annotations,
         dead_scope,
         hook,
         include_wip_dead,
@@ -162,30 +256,57 @@ pub fn cmd_review(service: &AppService, opts: &CmdReviewOpts<'_>) -> Result<()>
     let (dead_symbols, test_only_symbols) =
         timed_ok("dead_code", || review_dead_symbols(&dead_opts))?;
 
-    let result = ReviewResult {
+    // 7. diff で追加された未使用引数
+    let unused_parameters = timed_ok("unused_parameters", || review_unused_parameters(&dead_opts))?;
+
+    // 8. diff が Cargo.toml / feature gate に触れた crate の feature の食い違い
+    let cargo_features = timed("cargo_features", || review_cargo_features(&dead_opts));
+
+    // 9. 追加コードの重複 (既存コードのコピー&ペースト) 検出。同言語の全ファイルをハッシュ
+    // するため、hook の既定経路では `--dupes` 指定時だけ行う。
+    let duplicated_code = if dupes {
+        timed("dupes", || {
+            review_duplicated_code(
+                dir,
+                diff_input,
+                &diff_files,
+                extra_exclude_dirs,
+                extra_exclude_globs,
+            )
+        })
+    } else {
+        Vec::new()
+    };
+
+    let mut result = ReviewResult {
         impact,
         missing_cochanges: cochange_report.missing,
         cochange_diagnostics: cochange_report.diagnostics,
         api_changes,
         dead_symbols,
         test_only_symbols,
+        unused_parameters,
+        cargo_features,
+        duplicated_code,
+        risk: None,
+        owners_to_notify: Vec::new(),
         skipped: None,
         truncations,
     };
 
-    if hook {
-        return review_hook_output(&result, dir, strict_public_const_values);
+    // 10. リスクスコア (上記の結果から算出する)。`--diff` / `--diff-file` / stdin では
+    // git の基点が無いので、複雑度の base 側は読まない。
+    if !hook || risk_threshold.is_some() {
+        let git_base = (opts.git || opts.range.is_some()).then_some(base);
+        result.risk = Some(timed("risk", || {
+            compute_review_risk(service, dir, git_base, &result, &diff_files)
+        }));
     }
 
-    let text = serialize_cli_document(&result, output)?;
-    info!(
-        command = "review",
-        dir = dir,
-        output_bytes = text.len(),
-        "command completed"
-    );
-    print!("{text}");
-    Ok(())
+    // 11. CODEOWNERS による通知先の集約
+    annotate_review_owners(&mut result, dir);
+
+    Ok(Some(result))
 }
 
 /// 解析へ進まず空結果で打ち切る共通処理 (git 管理外 / 空 diff / CI 言語のみ の 3 経路)。
@@ -210,19 +331,42 @@ fn emit_review_short_circuit(
         truncations,
         ..Default::default()
     };
-    let text = serialize_cli_document(&result, output)?;
+    let text = render_review(&result, output, None)?;
     print!("{text}");
     Ok(())
 }
 
+/// 通常 (非 `--hook`) 出力の描画。`--format markdown` は PR コメント向けレポート、
+/// `--format codeclimate` は GitLab Code Quality 向けの issue 配列、
+/// それ以外は他コマンドと同じ汎用 serializer に任せる。
+fn render_review(
+    result: &ReviewResult,
+    output: OutputOptions,
+    link_base: Option<&str>,
+) -> Result<String> {
+    match output.format() {
+        OutputFormat::Markdown => Ok(render_review_markdown(
+            result,
+            resolve_link_base(link_base).as_deref(),
+        )),
+        OutputFormat::Codeclimate => {
+            crate::output::codeclimate::render(&review_codeclimate_issues(result), output)
+        }
+        _ => serialize_cli_document(result, output),
+    }
+}
+
 /// dead symbol 検出フェーズの入力。引数が多いため `CmdAstOpts` と同じく struct にまとめる。
 struct ReviewDeadSymbolsOpts<'a> {
     dir: &'a str,
     diff_input: &'a str,
     diff_files: &'a [crate::models::impact::DiffFile],
     framework_globs: &'a [String],
+    /// annotation プリセット (`spring`) の選択に使う `--framework` (未指定なら auto detect)。
+    framework: Option<&'a str>,
     extra_exclude_dirs: &'a [String],
     extra_exclude_globs: &'a [String],
+    entry_annotations: &'a [String],
     dead_scope: crate::cli::DeadScope,
     hook: bool,
     include_wip_dead: bool,
@@ -241,24 +385,11 @@ fn review_dead_symbols(
     let Ok(canonical_dir) = std::fs::canonicalize(opts.dir) else {
         return Ok((Vec::new(), Vec::new()));
     };
-
-    let default_excludes = resolve_dead_code_excludes(false, false, false);
-    let mut excludes: Vec<&str> = default_excludes.to_vec();
-    for name in opts.extra_exclude_dirs {
-        excludes.push(name.as_str());
-    }
-    let mut combined_globs: Vec<&str> = opts.framework_globs.iter().map(String::as_str).collect();
-    for pat in opts.extra_exclude_globs {
-        combined_globs.push(pat.as_str());
-    }
-    let files = filter_diff_files_for_dead_code(
-        &canonical_dir,
-        opts.diff_files,
-        &excludes,
-        &combined_globs,
-        None,
-    )?;
-    let (dead_symbols, test_only_symbols) = detect_dead_symbols_from_files(opts.dir, &files);
+    let files = review_dead_code_files(opts, &canonical_dir)?;
+    let conventions =
+        resolve_dead_code_conventions(opts.framework, opts.dir, opts.entry_annotations)?;
+    let (dead_symbols, test_only_symbols) =
+        detect_dead_symbols_from_files(opts.dir, &files, &conventions);
     // dead-scope=touched-symbols: 宣言行が diff の `+` 行と重ならない dead を除外。
     // `--hook` のデフォルトで「changed file 内の元から存在した dead」の
     // ノイズを抑える (Issue: zod-inferred-types-pre-existing-dead)。
@@ -283,6 +414,64 @@ fn review_dead_symbols(
     Ok((dead_symbols, test_only_symbols))
 }
 
+/// dead 判定の対象にする changed file (vendor / tests / build・framework プリセット・
+/// ユーザ指定 exclude を除いたもの)。
+fn review_dead_code_files(
+    opts: &ReviewDeadSymbolsOpts<'_>,
+    canonical_dir: &std::path::Path,
+) -> Result<Vec<std::path::PathBuf>> {
+    let default_excludes = resolve_dead_code_excludes(false, false, false);
+    let mut excludes: Vec<&str> = default_excludes.to_vec();
+    for name in opts.extra_exclude_dirs {
+        excludes.push(name.as_str());
+    }
+    let mut combined_globs: Vec<&str> = opts.framework_globs.iter().map(String::as_str).collect();
+    for pat in opts.extra_exclude_globs {
+        combined_globs.push(pat.as_str());
+    }
+    filter_diff_files_for_dead_code(
+        canonical_dir,
+        opts.diff_files,
+        &excludes,
+        &combined_globs,
+        None,
+    )
+}
+
+/// diff で追加された引数のうち、関数本体で一度も参照されないもの。
+///
+/// 対象ファイルは dead symbol 検出と同じ。既存の未使用引数は diff の責任ではないため、
+/// `--dead-scope` に関わらず引数名の行が `+` 行に含まれるものだけを返す。
+fn review_unused_parameters(opts: &ReviewDeadSymbolsOpts<'_>) -> Result<Vec<UnusedParameter>> {
+    let Ok(canonical_dir) = std::fs::canonicalize(opts.dir) else {
+        return Ok(Vec::new());
+    };
+    let files = review_dead_code_files(opts, &canonical_dir)?;
+    let mut params = detect_unused_parameters(opts.dir, &files);
+    retain_added_parameters(&mut params, opts.diff_input);
+    Ok(params)
+}
+
+/// diff が `Cargo.toml`、または `cfg` / `CARGO_FEATURE_` を含む `.rs` の行に触れた crate の、
+/// 使われない feature と未宣言 feature の参照。
+///
+/// `[features]` の編集や `cfg` の書き換えで宣言と参照がずれても、crate 内の別の場所で
+/// 初めて食い違いになるため、触れた crate 全体を報告する。
+fn review_cargo_features(opts: &ReviewDeadSymbolsOpts<'_>) -> Vec<CargoFeatureIssue> {
+    let Ok(canonical_dir) = std::fs::canonicalize(opts.dir) else {
+        return Vec::new();
+    };
+    let touched = touched_feature_manifests(&canonical_dir, opts.diff_input, opts.diff_files);
+    if touched.is_empty() {
+        return Vec::new();
+    }
+    let mut excludes: Vec<&str> = resolve_dead_code_excludes(false, false, false);
+    excludes.extend(opts.extra_exclude_dirs.iter().map(String::as_str));
+    let mut issues = detect_cargo_feature_issues(&canonical_dir, &excludes);
+    issues.retain(|issue| touched.contains(&issue.manifest));
+    issues
+}
+
 #[cfg(test)]
 mod review_command_tests {
     use super::*;
@@ -309,8 +498,10 @@ mod review_command_tests {
             diff_input: "",
             diff_files: &[],
             framework_globs: &[],
+            framework: None,
             extra_exclude_dirs: &[],
             extra_exclude_globs: &[],
+            entry_annotations: &[],
             dead_scope: crate::cli::DeadScope::All,
             hook: false,
             include_wip_dead: false,
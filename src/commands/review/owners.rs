@@ -0,0 +1,52 @@
+//! `review` の `owners_to_notify` 集約。
+//!
+//! caller の owner は `AppService::analyze_context_streaming` が `CODEOWNERS` から付与済み。
+//! ここでは missing_cochange に owner を付けたうえで、通知先ごとにまとめ直す。
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::commands::ChangedFileSet;
+use crate::engine::codeowners::CodeOwners;
+use crate::models::review::{OwnerToNotify, ReviewResult};
+
+/// missing_cochange に owner を付け、`owners_to_notify` を組み立てる。
+///
+/// 通知対象は diff 外に残る `impacted_callers` (diff 内で直した caller は対象外) と
+/// `missing_cochanges` のファイル。low confidence / informational な caller は含めない。
+/// `CODEOWNERS` が無ければ何もしない。
+pub(super) fn annotate_review_owners(result: &mut ReviewResult, dir: &str) {
+    let codeowners = CodeOwners::discover(std::path::Path::new(dir));
+    if codeowners.is_empty() {
+        return;
+    }
+    for missing in &mut result.missing_cochanges {
+        missing.owners = codeowners.owners_of(&missing.file).to_vec();
+    }
+
+    let changed = ChangedFileSet::build(dir, result.impact.changes.iter().map(|c| c.path.as_str()));
+    let mut by_owner: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+    let callers = result
+        .impact
+        .changes
+        .iter()
+        .flat_map(|change| &change.impacted_callers)
+        .filter(|caller| !changed.contains_caller(dir, &caller.path))
+        .map(|caller| (caller.path.as_str(), &caller.owners));
+    let missing = result
+        .missing_cochanges
+        .iter()
+        .map(|missing| (missing.file.as_str(), &missing.owners));
+    for (file, owners) in callers.chain(missing) {
+        for owner in owners {
+            by_owner.entry(owner).or_default().insert(file);
+        }
+    }
+    let owners_to_notify = by_owner
+        .into_iter()
+        .map(|(owner, files)| OwnerToNotify {
+            owner: owner.to_string(),
+            files: files.into_iter().map(str::to_string).collect(),
+        })
+        .collect();
+    result.owners_to_notify = owners_to_notify;
+}
@@ -0,0 +1,312 @@
+//! `review --range A..B [--per-commit]`: 作業ツリーではなく、コミット範囲を一時 worktree に
+//! チェックアウトして review する。
+//!
+//! review パイプラインは impact / dead-code / 重複検出のいずれも `dir` 配下の実ファイルを
+//! 読むため、過去コミットを review するにはそのコミットの tree がディスク上に必要になる。
+//! 利用者の作業ツリーには触れず、`git worktree add --detach` で作った一時 worktree を
+//! コミットごとに `checkout` し直して同じ `review_diff` を回す。
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use tracing::info;
+
+use crate::commands::dead_code::resolve_framework_globs_with_auto_detect;
+use crate::commands::git_input::{is_git_work_tree, run_git_diff, validate_git_revision};
+use crate::error::{AstroError, ErrorCode};
+use crate::models::review::{
+    CommitReview, RangeReviewSummary, RangeReviewSummaryRecord, ReviewResult, UnresolvedIssue,
+};
+use crate::models::skip::SkipInfo;
+use crate::service::AppService;
+
+use super::codeclimate::review_codeclimate_issues;
+use super::{CmdReviewOpts, emit_review_short_circuit, render_review, review_diff};
+
+/// 親を持たない (root) コミットの比較相手。git が組み込みで持つ空 tree の object id。
+const EMPTY_TREE: &str = "4b825dc642cb6eb9a060e54bf8d69288fbee4904";
+
+pub(super) fn cmd_review_range(
+    service: &AppService,
+    opts: &CmdReviewOpts<'_>,
+    range: &str,
+) -> Result<()> {
+    let (from, to) = parse_range(range)?;
+    if opts.per_commit {
+        // 1 行 1 レコードの NDJSON 契約なので、session と同じく JSON 以外は受けない。
+        opts.output.ensure_json_protocol("review --per-commit")?;
+    }
+    let framework_globs = resolve_framework_globs_with_auto_detect(opts.framework, opts.dir)?;
+
+    if !is_git_work_tree(opts.dir)? {
+        let skip = SkipInfo::not_git_repository();
+        if opts.per_commit {
+            let summary = RangeReviewSummary {
+                range: range.to_string(),
+                skipped: Some(skip),
+                ..Default::default()
+            };
+            return write_ndjson(&RangeReviewSummaryRecord { summary });
+        }
+        return emit_review_short_circuit(false, opts.output, Some(skip), Vec::new());
+    }
+
+    let from_sha = resolve_commit(opts.dir, from, "--range start")?;
+    let to_sha = resolve_commit(opts.dir, to, "--range end")?;
+    let prefix = git(Path::new(opts.dir), &["rev-parse", "--show-prefix"])?;
+    let worktree = TempWorktree::create(opts.dir, &to_sha)?;
+    let review_dir = worktree.root.join(prefix.trim());
+    let review_dir = review_dir.to_str().ok_or_else(|| {
+        AstroError::new(
+            ErrorCode::InvalidRequest,
+            format!(
+                "temporary worktree path is not UTF-8: {}",
+                review_dir.display()
+            ),
+        )
+    })?;
+    let revision = RevisionReviewer {
+        service,
+        opts,
+        framework_globs: &framework_globs,
+        dir: review_dir,
+    };
+
+    if !opts.per_commit {
+        let result = revision.review(&from_sha)?;
+        let text = render_review(&result, opts.output, opts.link_base)?;
+        info!(
+            command = "review",
+            range = range,
+            output_bytes = text.len(),
+            "command completed"
+        );
+        print!("{text}");
+        return Ok(());
+    }
+
+    let commits = list_commits(opts.dir, &from_sha, &to_sha)?;
+    // 指摘の同一性は `--format codeclimate` の fingerprint と同じ (種類, パス, 識別子) で見る。
+    // 行番号を含めないので、後続コミットで行がずれても同じ指摘として突き合わせられる。
+    let mut introduced_in: HashMap<(String, String, String), String> = HashMap::new();
+    for commit in &commits {
+        worktree.checkout(&commit.sha)?;
+        let review = revision.review(commit.parent.as_deref().unwrap_or(EMPTY_TREE))?;
+        for issue in review_codeclimate_issues(&review) {
+            introduced_in
+                .entry((issue.check_name, issue.path, issue.identity))
+                .or_insert_with(|| commit.sha.clone());
+        }
+        write_ndjson(&CommitReview {
+            sha: commit.sha.clone(),
+            subject: commit.subject.clone(),
+            review,
+        })?;
+    }
+
+    // 個々のコミットで直された指摘を除くため、B 時点の範囲全体 review に残るものだけを出す。
+    worktree.checkout(&to_sha)?;
+    let whole = revision.review(&from_sha)?;
+    let unresolved = review_codeclimate_issues(&whole)
+        .into_iter()
+        .map(|issue| {
+            let key = (issue.check_name, issue.path, issue.identity);
+            UnresolvedIssue {
+                introduced_in: introduced_in.get(&key).cloned(),
+                check: key.0,
+                description: issue.description,
+                path: key.1,
+                line: issue.line,
+            }
+        })
+        .collect::<Vec<_>>();
+    info!(
+        command = "review",
+        range = range,
+        commits = commits.len(),
+        unresolved = unresolved.len(),
+        "command completed"
+    );
+    write_ndjson(&RangeReviewSummaryRecord {
+        summary: RangeReviewSummary {
+            range: range.to_string(),
+            commits: commits.len(),
+            unresolved,
+            skipped: None,
+        },
+    })
+}
+
+/// `A..B` を分解して検証する。`A...B` (対称差) はコミット列の順序が定まらないため受けない。
+fn parse_range(range: &str) -> Result<(&str, &str)> {
+    let invalid = || {
+        AstroError::new(
+            ErrorCode::InvalidRequest,
+            format!("--range must be of the form A..B: {range}"),
+        )
+    };
+    let (from, to) = range.split_once("..").ok_or_else(invalid)?;
+    if to.starts_with('.') || from.is_empty() || to.is_empty() {
+        return Err(invalid().into());
+    }
+    validate_git_revision(from, "--range start")?;
+    validate_git_revision(to, "--range end")?;
+    Ok((from, to))
+}
+
+fn resolve_commit(dir: &str, rev: &str, arg_name: &str) -> Result<String> {
+    let spec = format!("{rev}^{{commit}}");
+    git(Path::new(dir), &["rev-parse", "--verify", "--quiet", &spec])
+        .map(|sha| sha.trim().to_string())
+        .map_err(|_| {
+            AstroError::new(
+                ErrorCode::InvalidRequest,
+                format!("{arg_name} is not a commit: {rev}"),
+            )
+            .into()
+        })
+}
+
+struct RangeCommit {
+    sha: String,
+    /// 第 1 親。merge コミットは第 1 親との差分 (= マージで入った変更全体) を review する。
+    parent: Option<String>,
+    subject: String,
+}
+
+/// `from..to` のコミットを古い順に返す。
+fn list_commits(dir: &str, from: &str, to: &str) -> Result<Vec<RangeCommit>> {
+    let spec = format!("{from}..{to}");
+    let log = git(
+        Path::new(dir),
+        &["log", "--reverse", "--format=%H%x09%P%x09%s", &spec, "--"],
+    )?;
+    Ok(log
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, '\t');
+            let sha = fields.next()?.to_string();
+            let parent = fields.next()?.split_whitespace().next().map(str::to_string);
+            let subject = fields.next().unwrap_or_default().to_string();
+            Some(RangeCommit {
+                sha,
+                parent,
+                subject,
+            })
+        })
+        .collect())
+}
+
+/// 一時 worktree 上の 1 リビジョンを review する。
+struct RevisionReviewer<'a> {
+    service: &'a AppService,
+    opts: &'a CmdReviewOpts<'a>,
+    framework_globs: &'a [String],
+    dir: &'a str,
+}
+
+impl RevisionReviewer<'_> {
+    /// worktree の現在のチェックアウトを `base` と比べて review する。worktree は clean なので
+    /// `git diff <base>` はそのまま `<base>..<checkout>` の差分になる。
+    fn review(&self, base: &str) -> Result<ReviewResult> {
+        let diff = run_git_diff(self.dir, base, false)?;
+        let opts = CmdReviewOpts {
+            dir: self.dir,
+            base,
+            ..*self.opts
+        };
+        Ok(
+            review_diff(self.service, &opts, self.framework_globs, &diff, Vec::new())?
+                .unwrap_or_default(),
+        )
+    }
+}
+
+/// `git worktree add --detach` で作る一時チェックアウト。Drop で worktree ごと片付ける。
+struct TempWorktree {
+    repo_dir: PathBuf,
+    root: PathBuf,
+}
+
+impl TempWorktree {
+    fn create(repo_dir: &str, rev: &str) -> Result<Self> {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.subsec_nanos())
+            .unwrap_or_default();
+        let root =
+            std::env::temp_dir().join(format!("astro-sight-review-{}-{nanos}", std::process::id()));
+        let root_arg = root.to_string_lossy().to_string();
+        git(
+            Path::new(repo_dir),
+            &["worktree", "add", "--detach", "--quiet", &root_arg, rev],
+        )?;
+        Ok(Self {
+            repo_dir: PathBuf::from(repo_dir),
+            root,
+        })
+    }
+
+    fn checkout(&self, rev: &str) -> Result<()> {
+        git(&self.root, &["checkout", "--quiet", "--detach", rev]).map(|_| ())
+    }
+}
+
+impl Drop for TempWorktree {
+    fn drop(&mut self) {
+        let root = self.root.to_string_lossy().to_string();
+        if git(&self.repo_dir, &["worktree", "remove", "--force", &root]).is_err() {
+            let _ = std::fs::remove_dir_all(&self.root);
+            let _ = git(&self.repo_dir, &["worktree", "prune"]);
+        }
+    }
+}
+
+/// git を実行して stdout を返す。利用者の hook (post-checkout 等) は一時 worktree の
+/// 操作で走らせる意味が無く副作用もあり得るため無効化する。
+fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = std::process::Command::new("git")
+        .args(["-c", "core.hooksPath=/dev/null", "-c", "core.quotepath=off"])
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .map_err(|e| {
+            AstroError::new(ErrorCode::InvalidRequest, format!("Failed to run git: {e}"))
+        })?;
+    if !output.status.success() {
+        return Err(AstroError::new(
+            ErrorCode::InvalidRequest,
+            format!(
+                "git {} failed: {}",
+                args.first().unwrap_or(&""),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+        )
+        .into());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn write_ndjson<T: serde::Serialize>(record: &T) -> Result<()> {
+    let mut stdout = std::io::stdout().lock();
+    writeln!(stdout, "{}", serde_json::to_string(record)?)?;
+    stdout.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_range;
+
+    #[test]
+    fn parse_range_accepts_two_dot_ranges_only() {
+        assert_eq!(parse_range("v1.0..HEAD").unwrap(), ("v1.0", "HEAD"));
+        assert!(parse_range("main...HEAD").is_err());
+        assert!(parse_range("..HEAD").is_err());
+        assert!(parse_range("main..").is_err());
+        assert!(parse_range("HEAD").is_err());
+        assert!(parse_range("main..--output=x").is_err());
+    }
+}
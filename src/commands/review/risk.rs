@@ -0,0 +1,446 @@
+//! `review` のリスクスコア算出。
+//!
+//! 変更ファイルごとに次の要因を計測し、係数と上限を掛けた寄与の和をスコアにする。
+//! 寄与はすべて出力に残すため、スコアの内訳をそのまま説明できる。
+//!
+//! - `blocking_callers`: diff 外に残る、modified / removed シンボルの caller 数
+//! - `caller_files`: それらの caller が散らばるファイル数
+//! - `complexity_delta`: 変更・追加した関数の循環的複雑度の増分 (base との比較)
+//! - `api_breaks`: 破壊的 API 変更 (removed / modified) の件数。同名・同シグネチャのまま
+//!   定義ファイルだけ移った `moved` は呼び出し側の変更が要らないため数えない
+//! - `missing_cochanges`: このファイルと一緒に変わるはずのファイルの confidence の和
+//! - `untested_symbols`: テストから 1 件も参照されない変更シンボル数
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use camino::Utf8Path;
+
+use crate::commands::ChangedFileSet;
+use crate::engine::functions::collect_functions;
+use crate::engine::impact::test_context::is_test_file_path;
+use crate::engine::parser;
+use crate::engine::symbols::extract_symbols;
+use crate::models::impact::{DiffFile, FileImpact};
+use crate::models::reference::RefKind;
+use crate::models::review::{ApiChanges, FileRisk, ReviewResult, ReviewRisk, RiskFactor};
+use crate::service::AppService;
+
+use super::super::affected_tests::FileFunctions;
+use super::super::git_input::git_show_blob;
+
+/// 要因 1 つ分の係数 (計測値 1 単位あたりの寄与) と寄与の上限。
+struct Weight {
+    factor: &'static str,
+    per_unit: f64,
+    cap: f64,
+}
+
+impl Weight {
+    /// 計測値を寄与に換算する。計測値 0 の要因は出力しない。
+    fn factor(&self, value: f64) -> Option<RiskFactor> {
+        (value > 0.0).then(|| RiskFactor {
+            factor: self.factor.to_string(),
+            value: round2(value),
+            contribution: round2((value * self.per_unit).min(self.cap)),
+        })
+    }
+}
+
+const BLOCKING_CALLERS: Weight = Weight {
+    factor: "blocking_callers",
+    per_unit: 2.0,
+    cap: 20.0,
+};
+const CALLER_FILES: Weight = Weight {
+    factor: "caller_files",
+    per_unit: 3.0,
+    cap: 15.0,
+};
+const COMPLEXITY_DELTA: Weight = Weight {
+    factor: "complexity_delta",
+    per_unit: 1.0,
+    cap: 20.0,
+};
+const API_BREAKS: Weight = Weight {
+    factor: "api_breaks",
+    per_unit: 10.0,
+    cap: 30.0,
+};
+const MISSING_COCHANGES: Weight = Weight {
+    factor: "missing_cochanges",
+    per_unit: 10.0,
+    cap: 20.0,
+};
+const UNTESTED_SYMBOLS: Weight = Weight {
+    factor: "untested_symbols",
+    per_unit: 5.0,
+    cap: 15.0,
+};
+
+/// 全体の `factors` を並べる順 (ファイル単位の `factors` も同じ順で出る)。
+const FACTOR_ORDER: [&Weight; 6] = [
+    &BLOCKING_CALLERS,
+    &CALLER_FILES,
+    &COMPLEXITY_DELTA,
+    &API_BREAKS,
+    &MISSING_COCHANGES,
+    &UNTESTED_SYMBOLS,
+];
+
+/// review 結果からリスクスコアを組み立てる。
+///
+/// 複雑度の base 側は `git show <base>:<old_path>` で読む。`base` が `None` (`--diff` /
+/// `--diff-file` / stdin 入力で git の基点が無い) のときと、読めないファイル (git 管理外、
+/// 未コミットの新規ファイル以外で blob が無い等) は複雑度の要因だけを計測しない
+/// (新規ファイルは base 側が空なので計測する)。
+pub(crate) fn compute_review_risk(
+    service: &AppService,
+    dir: &str,
+    base: Option<&str>,
+    result: &ReviewResult,
+    diff_files: &[DiffFile],
+) -> ReviewRisk {
+    let changed = ChangedFileSet::build(dir, result.impact.changes.iter().map(|c| c.path.as_str()));
+    let old_paths: HashMap<&str, &str> = diff_files
+        .iter()
+        .map(|f| (f.new_path.as_str(), f.old_path.as_str()))
+        .collect();
+
+    let api_breaks = api_break_counts(&result.api_changes);
+    let mut cochange_confidence: HashMap<&str, f64> = HashMap::new();
+    for missing in &result.missing_cochanges {
+        *cochange_confidence
+            .entry(missing.expected_with.as_str())
+            .or_default() += missing.confidence;
+    }
+    let untested = untested_symbols(service, dir, &result.impact.changes);
+
+    let mut files: Vec<FileRisk> = result
+        .impact
+        .changes
+        .iter()
+        .map(|change| {
+            let (callers, caller_files) = blocking_callers(change, &changed, dir);
+            let old_path = old_paths
+                .get(change.path.as_str())
+                .copied()
+                .unwrap_or(change.path.as_str());
+            let complexity = complexity_delta(dir, base, change, old_path).unwrap_or(0);
+            let untested_count = change
+                .affected_symbols
+                .iter()
+                .filter(|s| untested.contains(&(change.path.as_str(), s.name.as_str())))
+                .count();
+            let factors: Vec<RiskFactor> = [
+                BLOCKING_CALLERS.factor(callers as f64),
+                CALLER_FILES.factor(caller_files as f64),
+                COMPLEXITY_DELTA.factor(complexity as f64),
+                API_BREAKS
+                    .factor(api_breaks.get(change.path.as_str()).copied().unwrap_or(0) as f64),
+                MISSING_COCHANGES.factor(
+                    cochange_confidence
+                        .get(change.path.as_str())
+                        .copied()
+                        .unwrap_or(0.0),
+                ),
+                UNTESTED_SYMBOLS.factor(untested_count as f64),
+            ]
+            .into_iter()
+            .flatten()
+            .collect();
+            FileRisk {
+                path: change.path.clone(),
+                score: round2(factors.iter().map(|f| f.contribution).sum()),
+                factors,
+            }
+        })
+        .filter(|file| !file.factors.is_empty())
+        .collect();
+    files.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.path.cmp(&b.path))
+    });
+
+    let factors: Vec<RiskFactor> = FACTOR_ORDER
+        .iter()
+        .filter_map(|weight| {
+            let matching = files
+                .iter()
+                .flat_map(|file| &file.factors)
+                .filter(|f| f.factor == weight.factor);
+            let (value, contribution) =
+                matching.fold((0.0, 0.0), |(v, c), f| (v + f.value, c + f.contribution));
+            (contribution > 0.0).then(|| RiskFactor {
+                factor: weight.factor.to_string(),
+                value: round2(value),
+                contribution: round2(contribution),
+            })
+        })
+        .collect();
+
+    ReviewRisk {
+        score: round2(factors.iter().map(|f| f.contribution).sum()),
+        factors,
+        files,
+    }
+}
+
+/// diff 外に残る blocking caller の数と、それらが属するファイル数。
+///
+/// hook の `impacts` と同じく、diff 内で解決済みの caller と `added` シンボルだけを
+/// 参照する caller は数えない (新規シンボルの caller は破壊的影響ではない)。
+fn blocking_callers(change: &FileImpact, changed: &ChangedFileSet, dir: &str) -> (usize, usize) {
+    let breaking: HashSet<&str> = change
+        .affected_symbols
+        .iter()
+        .filter(|s| s.change_type != "added")
+        .map(|s| s.name.as_str())
+        .collect();
+    let callers: Vec<&str> = change
+        .impacted_callers
+        .iter()
+        .filter(|caller| {
+            !changed.contains_caller(dir, &caller.path)
+                && caller.symbols.iter().any(|s| breaking.contains(s.as_str()))
+        })
+        .map(|caller| caller.path.as_str())
+        .collect();
+    let files: BTreeSet<&str> = callers.iter().copied().collect();
+    (callers.len(), files.len())
+}
+
+/// 変更・追加した関数 / メソッドの複雑度の増分 (減少分は相殺しない)。
+/// base 側を読めない既存ファイルは `None`。
+fn complexity_delta(
+    dir: &str,
+    base: Option<&str>,
+    change: &FileImpact,
+    old_path: &str,
+) -> Option<usize> {
+    let names: HashSet<&str> = change
+        .affected_symbols
+        .iter()
+        .filter(|s| matches!(s.change_type.as_str(), "added" | "modified"))
+        .filter(|s| matches!(s.kind.as_str(), "function" | "method"))
+        .map(|s| s.name.as_str())
+        .collect();
+    if names.is_empty() {
+        return Some(0);
+    }
+
+    let new_path = std::path::Path::new(dir).join(&change.path);
+    let new_source = parser::read_file(Utf8Path::from_path(&new_path)?).ok()?;
+    let new = function_complexities(&change.path, new_source.as_bytes())?;
+    let old = if old_path == "/dev/null" {
+        HashMap::new()
+    } else {
+        let blob = git_show_blob(dir, base?, old_path)?;
+        function_complexities(old_path, &blob)?
+    };
+
+    Some(
+        names
+            .iter()
+            .filter_map(|name| {
+                let new = new.get(name)?;
+                Some(new.saturating_sub(old.get(name).copied().unwrap_or(0)))
+            })
+            .sum(),
+    )
+}
+
+/// 関数名 → 複雑度。同名の関数 (別 impl のメソッド等) は最大値を採る。
+fn function_complexities(path: &str, source: &[u8]) -> Option<HashMap<String, usize>> {
+    let (tree, lang) = parser::parse_file(Utf8Path::new(path), source).ok()?;
+    let symbols = extract_symbols(tree.root_node(), source, lang).ok()?;
+    let mut complexities: HashMap<String, usize> = HashMap::new();
+    collect_functions(&symbols, &mut |sym| {
+        if let Some(complexity) = sym.complexity {
+            let entry = complexities.entry(sym.name.clone()).or_default();
+            *entry = (*entry).max(complexity);
+        }
+    });
+    Some(complexities)
+}
+
+/// テストから 1 件も参照されない変更シンボル `(path, name)`。
+///
+/// テストファイル自体の変更と removed シンボルは対象外。参照元がテストかどうかは
+/// affected-tests と同じく、パス規約または囲む関数のテストコンテキストで判定する。
+/// 同名シンボルが複数ファイルで定義されている場合、別ファイルの同名シンボルを使うテストで
+/// 未テストの変更が隠れないよう、テスト参照は `refers_to_file` を満たすものだけを数える。
+/// 参照検索に失敗した場合は判定できないため空集合 (要因を計上しない)。
+fn untested_symbols<'a>(
+    service: &AppService,
+    dir: &str,
+    changes: &'a [FileImpact],
+) -> HashSet<(&'a str, &'a str)> {
+    let candidates: Vec<(&str, &str)> = changes
+        .iter()
+        .filter(|change| !is_test_file_path(&change.path))
+        .flat_map(|change| {
+            change
+                .affected_symbols
+                .iter()
+                .filter(|s| s.change_type != "removed")
+                .map(|s| (change.path.as_str(), s.name.as_str()))
+        })
+        .collect();
+    if candidates.is_empty() {
+        return HashSet::new();
+    }
+    let names: Vec<String> = candidates
+        .iter()
+        .map(|(_, name)| name.to_string())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let (Ok(results), Ok(canonical_dir)) = (
+        service.find_references_batch(&names, dir, None),
+        std::fs::canonicalize(dir),
+    ) else {
+        return HashSet::new();
+    };
+
+    let mut files: HashMap<String, Option<FileFunctions>> = HashMap::new();
+    // シンボル名 → (定義のあるファイル, テストからの参照元ファイル)
+    let mut usages: HashMap<&str, (BTreeSet<&str>, BTreeSet<&str>)> = HashMap::new();
+    for result in &results {
+        let (definitions, test_refs) = usages.entry(result.symbol.as_str()).or_default();
+        for reference in &result.references {
+            if reference.kind == Some(RefKind::Definition) {
+                definitions.insert(reference.path.as_str());
+                continue;
+            }
+            let functions = files
+                .entry(reference.path.clone())
+                .or_insert_with(|| FileFunctions::load(&canonical_dir, &reference.path));
+            let in_test = functions.as_ref().is_some_and(|functions| {
+                functions.test_file
+                    || functions
+                        .enclosing(reference.line)
+                        .is_some_and(|function| function.info.test_context)
+            });
+            if in_test {
+                test_refs.insert(reference.path.as_str());
+            }
+        }
+    }
+
+    let mut sources: HashMap<&str, Option<String>> = HashMap::new();
+    candidates
+        .into_iter()
+        .filter(|&(path, name)| {
+            let Some((definitions, test_refs)) = usages.get(name) else {
+                return true;
+            };
+            let ambiguous = definitions.iter().any(|definition| *definition != path);
+            !test_refs.iter().any(|&test_path| {
+                !ambiguous || refers_to_file(&canonical_dir, &mut sources, test_path, path)
+            })
+        })
+        .collect()
+}
+
+/// テスト参照 `test_path` が `path` の定義を使っているとみなせるか。同じファイル内のテスト
+/// (Rust の `#[cfg(test)]` mod 等) か、テストファイルが `path` のモジュール名に言及している
+/// (`use` / `import` / `require` 等) 場合に限る。
+fn refers_to_file<'a>(
+    canonical_dir: &std::path::Path,
+    sources: &mut HashMap<&'a str, Option<String>>,
+    test_path: &'a str,
+    path: &str,
+) -> bool {
+    if test_path == path {
+        return true;
+    }
+    let Some(module) = module_name(path) else {
+        return false;
+    };
+    sources
+        .entry(test_path)
+        .or_insert_with(|| std::fs::read_to_string(canonical_dir.join(test_path)).ok())
+        .as_deref()
+        .is_some_and(|source| source.contains(module))
+}
+
+/// import で使われるモジュール名。`mod.rs` / `index.ts` / `__init__.py` 等はディレクトリ名。
+fn module_name(path: &str) -> Option<&str> {
+    let path = Utf8Path::new(path);
+    match path.file_stem()? {
+        "mod" | "lib" | "main" | "index" | "__init__" => path.parent()?.file_name(),
+        stem => Some(stem),
+    }
+}
+
+/// ファイルごとの破壊的 API 変更 (removed / modified) の件数。
+fn api_break_counts(api: &ApiChanges) -> HashMap<&str, usize> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for file in api
+        .removed
+        .iter()
+        .map(|s| s.file.as_str())
+        .chain(api.modified.iter().map(|s| s.file.as_str()))
+    {
+        *counts.entry(file).or_default() += 1;
+    }
+    counts
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weight_caps_contribution_and_skips_zero() {
+        assert_eq!(BLOCKING_CALLERS.factor(0.0), None);
+        let factor = API_BREAKS.factor(5.0).expect("non-zero value");
+        assert_eq!(factor.value, 5.0);
+        assert_eq!(factor.contribution, API_BREAKS.cap);
+        let factor = MISSING_COCHANGES.factor(0.456).expect("non-zero value");
+        assert_eq!(factor.value, 0.46);
+        assert_eq!(factor.contribution, 4.56);
+    }
+
+    #[test]
+    fn api_breaks_ignore_moved_symbols() {
+        use crate::models::review::{ApiSymbol, MovedSymbol};
+        let mut api = ApiChanges::default();
+        api.removed.push(ApiSymbol {
+            name: "old".to_string(),
+            kind: "function".to_string(),
+            file: "src/lib.rs".to_string(),
+            refs_internal: 0,
+        });
+        api.moved.push(MovedSymbol {
+            name: "helper".to_string(),
+            kind: "function".to_string(),
+            from: "src/lib.rs".to_string(),
+            to: "src/util.rs".to_string(),
+        });
+        let counts = api_break_counts(&api);
+        assert_eq!(counts.get("src/lib.rs"), Some(&1));
+        assert!(!counts.contains_key("src/util.rs"));
+    }
+
+    #[test]
+    fn function_complexities_takes_max_per_name() {
+        let source = b"fn a() {}\nstruct S;\nimpl S {\n    fn a(&self, x: bool) {\n        if x {}\n    }\n}\n";
+        let complexities = function_complexities("lib.rs", source).expect("rust parses");
+        assert_eq!(complexities.get("a"), Some(&2));
+        assert!(!complexities.contains_key("S"));
+    }
+
+    #[test]
+    fn module_name_uses_directory_for_index_files() {
+        assert_eq!(module_name("src/parser.rs"), Some("parser"));
+        assert_eq!(module_name("src/engine/mod.rs"), Some("engine"));
+        assert_eq!(module_name("pkg/util/__init__.py"), Some("util"));
+        assert_eq!(module_name("web/index.ts"), Some("web"));
+    }
+}
@@ -0,0 +1,395 @@
+use anyhow::Result;
+use tracing::info;
+
+use crate::engine::diff::parse_unified_diff;
+use crate::error::{AstroError, ErrorCode};
+use crate::models::review::ApiChanges;
+use crate::models::semver::{BumpLevel, ManifestVersion, SemverReason, SemverResult};
+use crate::output::{OutputOptions, serialize_cli_document};
+
+use super::api_changes::detect_api_changes;
+use super::git_input::{GitDiffInput, git_show_blob, resolve_git_diff};
+
+/// 版を読む manifest。`--dir` 直下のものだけを見る (workspace 配下の member は対象外)。
+const VERSION_MANIFESTS: &[&str] = &["Cargo.toml", "package.json", "pyproject.toml"];
+
+/// `cmd_semver` の引数一式。
+pub struct CmdSemverOpts<'a> {
+    pub dir: &'a str,
+    pub base: &'a str,
+    pub output: OutputOptions,
+}
+
+/// `--base` (通常は直近のリリースタグ) から作業ツリーまでの API 変更を SemVer の
+/// major / minor / patch に分類し、manifest で実際に上げた版と突き合わせる。
+///
+/// 公開シンボルの削除・シグネチャ変更は major、追加は minor、移動を含むそれ以外の変更は
+/// patch。manifest の bump が必要な段階に届いていない (版が読めず確認できない場合を含む)
+/// なら結果を出力したうえで exit 1。
+pub fn cmd_semver(opts: &CmdSemverOpts<'_>) -> Result<()> {
+    let canonical_dir = std::fs::canonicalize(opts.dir)?;
+    if !canonical_dir.is_dir() {
+        return Err(AstroError::new(
+            ErrorCode::InvalidRequest,
+            format!("Not a directory: {}", opts.dir),
+        )
+        .into());
+    }
+
+    let mut result = SemverResult {
+        dir: canonical_dir.to_string_lossy().to_string(),
+        base: opts.base.to_string(),
+        ok: true,
+        ..Default::default()
+    };
+    match resolve_git_diff(opts.dir, opts.base, false)? {
+        GitDiffInput::Diff { diff, truncations } => {
+            result.truncations = truncations;
+            let diff_files = parse_unified_diff(&diff);
+            let api = detect_api_changes(opts.dir, opts.base, &diff_files);
+            result.reasons = classify_api_changes(&api);
+            result.required = result
+                .reasons
+                .iter()
+                .map(|reason| reason.level)
+                .max()
+                .unwrap_or(if diff_files.is_empty() {
+                    BumpLevel::None
+                } else {
+                    BumpLevel::Patch
+                });
+            result.manifests = VERSION_MANIFESTS
+                .iter()
+                .filter_map(|path| manifest_version(opts.dir, opts.base, path, result.required))
+                .collect();
+            result.ok = result.manifests.iter().all(|m| m.sufficient);
+        }
+        GitDiffInput::Skipped(skip) => result.skipped = Some(skip),
+    }
+
+    let text = serialize_cli_document(&result, opts.output)?;
+    info!(
+        command = "semver",
+        dir = opts.dir,
+        base = opts.base,
+        required = result.required.as_str(),
+        reasons = result.reasons.len(),
+        ok = result.ok,
+        "command completed"
+    );
+    print!("{text}");
+    if !result.ok {
+        let actual = result
+            .manifests
+            .iter()
+            .filter(|m| !m.sufficient)
+            .map(|m| {
+                format!(
+                    "{} ({})",
+                    m.path,
+                    m.actual.map_or("unknown", BumpLevel::as_str)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        return super::exit_with_status(
+            1,
+            &format!(
+                "{} version bump required since {}, but manifest bump is too small or unreadable: {actual}",
+                result.required.as_str(),
+                opts.base
+            ),
+        );
+    }
+    Ok(())
+}
+
+/// `ApiChanges` の各バケットを必要な bump 段階に割り当てる。
+///
+/// - major: `removed` / `removed_dead` / `modified` / `modified_closed_in_diff`。
+///   review では repo 内の参照有無や同一 diff 内の追随で informational に落とすものも、
+///   リポジトリ外の利用者は追随できないため破壊的変更として数える。
+/// - minor: `added`。
+/// - patch: `compatible_modified` / `property_to_field` / `const_value_changes` / `moved`
+///   (呼び出し側の互換性を保つ変更)。`moved` は同名・同シグネチャのまま公開 API に
+///   残っているため、定義ファイルが変わっただけでは利用者側の変更は要らない。
+fn classify_api_changes(api: &ApiChanges) -> Vec<SemverReason> {
+    let mut reasons = Vec::new();
+    let mut push = |level: BumpLevel, change: &str, name: &str, kind: &str, file: &str| {
+        reasons.push(SemverReason {
+            level,
+            change: change.to_string(),
+            name: name.to_string(),
+            kind: kind.to_string(),
+            file: file.to_string(),
+        });
+    };
+
+    for (change, symbols) in [
+        ("removed", &api.removed),
+        ("removed_dead", &api.removed_dead),
+    ] {
+        for s in symbols {
+            push(BumpLevel::Major, change, &s.name, &s.kind, &s.file);
+        }
+    }
+    for (change, symbols) in [
+        ("modified", &api.modified),
+        ("modified_closed_in_diff", &api.modified_closed_in_diff),
+    ] {
+        for s in symbols {
+            push(BumpLevel::Major, change, &s.name, &s.kind, &s.file);
+        }
+    }
+    for s in &api.added {
+        push(BumpLevel::Minor, "added", &s.name, &s.kind, &s.file);
+    }
+    for s in &api.moved {
+        push(BumpLevel::Patch, "moved", &s.name, &s.kind, &s.to);
+    }
+    for s in &api.compatible_modified {
+        push(
+            BumpLevel::Patch,
+            "compatible_modified",
+            &s.name,
+            &s.kind,
+            &s.file,
+        );
+    }
+    for s in &api.property_to_field {
+        push(
+            BumpLevel::Patch,
+            "property_to_field",
+            &s.name,
+            "property",
+            &s.file,
+        );
+    }
+    for s in &api.const_value_changes {
+        push(
+            BumpLevel::Patch,
+            "const_value_changes",
+            &s.name,
+            &s.kind,
+            &s.file,
+        );
+    }
+    reasons
+}
+
+/// `path` (dir 相対) の manifest について base 時点と作業ツリーの版を比較する。
+/// どちらにも存在しない manifest は `None` (結果に載せない)。
+fn manifest_version(
+    dir: &str,
+    base: &str,
+    path: &str,
+    required: BumpLevel,
+) -> Option<ManifestVersion> {
+    let new_text = std::fs::read_to_string(std::path::Path::new(dir).join(path)).ok();
+    let old_text = git_show_blob(dir, base, path).and_then(|bytes| String::from_utf8(bytes).ok());
+    if new_text.is_none() && old_text.is_none() {
+        return None;
+    }
+    // 今回の diff で追加 (または削除) された manifest は bump の比較元が無いので判定しない。
+    let compared = new_text.is_some() && old_text.is_some();
+    let old_version = old_text.and_then(|text| read_manifest_version(path, &text));
+    let new_version = new_text.and_then(|text| read_manifest_version(path, &text));
+    let actual = match (old_version.as_deref(), new_version.as_deref()) {
+        (Some(old), Some(new)) => version_bump(old, new),
+        _ => None,
+    };
+    Some(ManifestVersion {
+        path: path.to_string(),
+        old_version,
+        new_version,
+        actual,
+        sufficient: bump_sufficient(compared, actual, required),
+    })
+}
+
+/// `actual` の bump で `required` を満たすか。版が読めない (`version.workspace = true`、
+/// pyproject の `dynamic`、parse できない旧版等) と bump を確認できないため、bump が
+/// 要るなら不十分とみなす (確認を素通りさせない)。
+fn bump_sufficient(compared: bool, actual: Option<BumpLevel>, required: BumpLevel) -> bool {
+    match actual {
+        Some(actual) => actual >= required,
+        None => !compared || required == BumpLevel::None,
+    }
+}
+
+/// manifest 本文から版文字列を取り出す。版が文字列で書かれていない
+/// (`version.workspace = true`、pyproject の `dynamic` 等) 場合は `None`。
+fn read_manifest_version(path: &str, text: &str) -> Option<String> {
+    match path {
+        "package.json" => serde_json::from_str::<serde_json::Value>(text)
+            .ok()?
+            .get("version")?
+            .as_str()
+            .map(str::to_string),
+        "Cargo.toml" => {
+            let table = toml::from_str::<toml::Table>(text).ok()?;
+            let package = table.get("package").or_else(|| {
+                table
+                    .get("workspace")
+                    .and_then(|workspace| workspace.get("package"))
+            })?;
+            package.get("version")?.as_str().map(str::to_string)
+        }
+        "pyproject.toml" => {
+            let table = toml::from_str::<toml::Table>(text).ok()?;
+            let project = table
+                .get("project")
+                .or_else(|| table.get("tool").and_then(|tool| tool.get("poetry")))?;
+            project.get("version")?.as_str().map(str::to_string)
+        }
+        _ => None,
+    }
+}
+
+/// `old` → `new` の bump を段階に換算する。どちらかが版として読めなければ `None`。
+///
+/// 0.x 系は Cargo / npm の caret 互換規則に合わせ、`0.y.z` の y 上げを major、z 上げを
+/// minor とみなす (`0.0.z` は z 上げも major)。版が上がっていない (据え置き・巻き戻し)
+/// ときは `BumpLevel::None`。
+fn version_bump(old: &str, new: &str) -> Option<BumpLevel> {
+    let old = parse_version(old)?;
+    let new = parse_version(new)?;
+    if new <= old {
+        return Some(BumpLevel::None);
+    }
+    let level = if new.0 != old.0 {
+        BumpLevel::Major
+    } else if old.0 == 0 {
+        if new.1 != old.1 || old.1 == 0 {
+            BumpLevel::Major
+        } else {
+            BumpLevel::Minor
+        }
+    } else if new.1 != old.1 {
+        BumpLevel::Minor
+    } else {
+        BumpLevel::Patch
+    };
+    Some(level)
+}
+
+/// `MAJOR[.MINOR[.PATCH]]` の数値部を読む。先頭の `v`、pre-release (`-rc.1`) /
+/// build metadata (`+abc`)、PEP 440 の接尾辞 (`1.2.0rc1` の `rc1`) は比較から外す。
+fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let core = version.trim().trim_start_matches('v');
+    let core = core.split(['-', '+']).next()?;
+    let mut parts = core.split('.').map(|part| {
+        let digits: String = part.chars().take_while(char::is_ascii_digit).collect();
+        digits.parse::<u64>().ok()
+    });
+    let major = parts.next()??;
+    let minor = parts.next().unwrap_or(Some(0))?;
+    let patch = parts.next().unwrap_or(Some(0))?;
+    Some((major, minor, patch))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::review::{ApiSymbol, ApiSymbolChange, MovedSymbol};
+
+    #[test]
+    fn version_bump_follows_caret_rules_below_one() {
+        assert_eq!(version_bump("1.2.3", "2.0.0"), Some(BumpLevel::Major));
+        assert_eq!(version_bump("1.2.3", "1.3.0"), Some(BumpLevel::Minor));
+        assert_eq!(version_bump("1.2.3", "1.2.4"), Some(BumpLevel::Patch));
+        assert_eq!(version_bump("1.2.3", "1.2.3"), Some(BumpLevel::None));
+        assert_eq!(version_bump("1.2.3", "1.2.2"), Some(BumpLevel::None));
+        assert_eq!(version_bump("0.3.1", "0.4.0"), Some(BumpLevel::Major));
+        assert_eq!(version_bump("0.3.1", "0.3.2"), Some(BumpLevel::Minor));
+        assert_eq!(version_bump("0.0.1", "0.0.2"), Some(BumpLevel::Major));
+        assert_eq!(version_bump("1.0.0", "2.0.0-rc.1"), Some(BumpLevel::Major));
+        assert_eq!(version_bump("v1.2", "1.3.0rc1"), Some(BumpLevel::Minor));
+        assert_eq!(version_bump("1.0.0", "dynamic"), None);
+    }
+
+    #[test]
+    fn unreadable_versions_are_insufficient_when_a_bump_is_required() {
+        assert!(bump_sufficient(
+            true,
+            Some(BumpLevel::Major),
+            BumpLevel::Major
+        ));
+        assert!(!bump_sufficient(
+            true,
+            Some(BumpLevel::Minor),
+            BumpLevel::Major
+        ));
+        assert!(!bump_sufficient(true, None, BumpLevel::Patch));
+        assert!(bump_sufficient(true, None, BumpLevel::None));
+        // 今回の diff で追加された manifest は比較元が無いので判定しない。
+        assert!(bump_sufficient(false, None, BumpLevel::Major));
+    }
+
+    #[test]
+    fn reads_versions_from_each_manifest_kind() {
+        assert_eq!(
+            read_manifest_version(
+                "Cargo.toml",
+                "[package]\nname = \"a\"\nversion = \"1.2.3\"\n"
+            ),
+            Some("1.2.3".to_string())
+        );
+        assert_eq!(
+            read_manifest_version("Cargo.toml", "[package]\nversion.workspace = true\n"),
+            None
+        );
+        assert_eq!(
+            read_manifest_version("package.json", r#"{"name":"a","version":"0.4.0"}"#),
+            Some("0.4.0".to_string())
+        );
+        assert_eq!(
+            read_manifest_version("pyproject.toml", "[tool.poetry]\nversion = \"2.0.0\"\n"),
+            Some("2.0.0".to_string())
+        );
+    }
+
+    #[test]
+    fn breaking_buckets_drive_major() {
+        let mut api = ApiChanges::default();
+        api.added.push(ApiSymbol {
+            name: "fresh".to_string(),
+            kind: "function".to_string(),
+            file: "src/lib.rs".to_string(),
+            refs_internal: 0,
+        });
+        api.modified.push(ApiSymbolChange {
+            name: "run".to_string(),
+            kind: "function".to_string(),
+            file: "src/lib.rs".to_string(),
+            old_signature: Some("fn run(a: u8)".to_string()),
+            new_signature: Some("fn run(a: u16)".to_string()),
+            no_resolved_internal_callers: false,
+        });
+        let reasons = classify_api_changes(&api);
+        assert_eq!(reasons.len(), 2);
+        assert_eq!(reasons[0].level, BumpLevel::Major);
+        assert_eq!(reasons[0].change, "modified");
+        assert_eq!(reasons[1].level, BumpLevel::Minor);
+        assert_eq!(
+            reasons.iter().map(|r| r.level).max(),
+            Some(BumpLevel::Major)
+        );
+    }
+
+    #[test]
+    fn moved_symbols_only_require_patch() {
+        let mut api = ApiChanges::default();
+        api.moved.push(MovedSymbol {
+            name: "relocate".to_string(),
+            kind: "function".to_string(),
+            from: "src/lib.rs".to_string(),
+            to: "src/util.rs".to_string(),
+        });
+        let reasons = classify_api_changes(&api);
+        assert_eq!(reasons.len(), 1);
+        assert_eq!(reasons[0].level, BumpLevel::Patch);
+        assert_eq!(reasons[0].change, "moved");
+        assert_eq!(reasons[0].file, "src/util.rs");
+    }
+}
@@ -3,7 +3,7 @@ use anyhow::{Result, bail};
 use crate::doctor;
 use crate::error::{AstroError, ErrorCode};
 use crate::models::cochange::CoChangeOptions;
-use crate::service::{AppService, AstParams};
+use crate::service::{AppService, AstParams, SourceParams};
 
 pub fn handle_request(
     service: &AppService,
@@ -73,6 +73,7 @@ pub fn handle_request(
             let options = crate::models::impact::ContextAnalysisOptions {
                 exclude_dirs: req.exclude_dirs.clone(),
                 exclude_globs: req.exclude_globs.clone(),
+                transitive_depth: 0,
             };
             let result = service.analyze_context(diff_input, dir, &options)?;
             Ok(serde_json::to_value(result)?)
@@ -113,5 +114,21 @@ pub fn handle_request(
             let result = service.analyze_cochange(dir, &opts)?;
             Ok(serde_json::to_value(result)?)
         }
+        Command::Source => {
+            // path 未指定 (既定の空文字) かつ dir 指定ならワークスペース検索。
+            let path = Some(req.path.as_str()).filter(|p| !p.is_empty());
+            let params = SourceParams {
+                path,
+                dir: req.dir.as_deref().filter(|_| path.is_none()),
+                glob: req.glob.as_deref(),
+                name: req.name.as_deref().unwrap_or(""),
+                container: req.container.as_deref(),
+                max_bytes: req
+                    .max_bytes
+                    .unwrap_or(crate::engine::source::DEFAULT_MAX_SOURCE_BYTES),
+            };
+            let result = service.extract_source(&params)?;
+            Ok(serde_json::to_value(result)?)
+        }
     }
 }
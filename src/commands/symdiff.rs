@@ -0,0 +1,542 @@
+use anyhow::Result;
+use camino::Utf8Path;
+use rayon::prelude::*;
+use std::collections::{HashMap, VecDeque};
+use tracing::info;
+
+use crate::cache::store::CacheStore;
+use crate::engine::parser;
+use crate::engine::symbols::{extract_symbols, is_local_scope_symbol};
+use crate::error::{AstroError, ErrorCode};
+use crate::models::review::MovedSymbol;
+use crate::models::skip::SkipInfo;
+use crate::models::symbol::{Symbol, SymbolKind};
+use crate::models::symdiff::{RenamedSymbol, SymDiffResult, SymDiffSignatureChange, SymDiffSymbol};
+use crate::output::{OutputOptions, serialize_cli_document};
+
+use super::api_changes::{
+    ApiSymbolCandidate, extract_api_signature, normalize_signature_whitespace, reconcile_with_moves,
+};
+use super::dead_code::enclosing_container;
+use super::git_input::{
+    git_changed_paths_between, git_show_blob, is_git_work_tree, validate_git_revision,
+};
+
+/// `cmd_symdiff` の引数一式。
+pub struct CmdSymdiffOpts<'a> {
+    pub dir: &'a str,
+    pub from: &'a str,
+    pub to: &'a str,
+    pub output: OutputOptions,
+}
+
+/// 2 revision 間で変更されたファイルの全シンボル (private 含む) を比較する。
+pub fn cmd_symdiff(opts: &CmdSymdiffOpts<'_>) -> Result<()> {
+    let canonical_dir = std::fs::canonicalize(opts.dir)?;
+    if !canonical_dir.is_dir() {
+        return Err(AstroError::new(
+            ErrorCode::InvalidRequest,
+            format!("Not a directory: {}", opts.dir),
+        )
+        .into());
+    }
+    // revision 検証は git 管理外判定より前に行う (resolve_git_diff と同じく入力契約違反を優先)。
+    validate_git_revision(opts.from, "--from")?;
+    validate_git_revision(opts.to, "--to")?;
+
+    let mut result = SymDiffResult {
+        dir: canonical_dir.to_string_lossy().to_string(),
+        from: opts.from.to_string(),
+        to: opts.to.to_string(),
+        ..Default::default()
+    };
+    if !is_git_work_tree(opts.dir)? {
+        result.skipped = Some(SkipInfo::not_git_repository());
+    } else {
+        let paths = git_changed_paths_between(opts.dir, opts.from, opts.to)?;
+        let file_pairs: Vec<(Vec<RevisionSymbol>, Vec<RevisionSymbol>)> = paths
+            .par_iter()
+            .map(|(old_path, new_path)| {
+                (
+                    extract_revision_symbols(opts.dir, opts.from, old_path),
+                    extract_revision_symbols(opts.dir, opts.to, new_path),
+                )
+            })
+            .collect();
+        result.files_changed = paths.len();
+        let buckets = classify_symbol_changes(file_pairs);
+        result.added = buckets.added;
+        result.removed = buckets.removed;
+        result.renamed = buckets.renamed;
+        result.moved = buckets.moved;
+        result.signature_changed = buckets.signature_changed;
+        result.body_changed = buckets.body_changed;
+    }
+
+    let text = serialize_cli_document(&result, opts.output)?;
+    info!(
+        command = "symdiff",
+        dir = opts.dir,
+        files_changed = result.files_changed,
+        output_bytes = text.len(),
+        "command completed"
+    );
+    print!("{text}");
+    Ok(())
+}
+
+/// 1 revision 上の 1 シンボル。比較キーと分類に必要な指紋だけを持つ。
+#[derive(Debug, Clone)]
+struct RevisionSymbol {
+    /// container 付きの qualname (`Foo.bar`)。
+    name: String,
+    /// 定義本体の置換に使う bare name。
+    bare_name: String,
+    kind: String,
+    file: String,
+    line: usize,
+    signature: String,
+    /// 空白正規化した定義全体のハッシュ。
+    body_hash: String,
+    /// 定義中の自身の名前を伏せたハッシュ。rename 検出に使う。
+    shape_hash: String,
+    /// 本体変更を自身の変更として報告するか。メソッドを内包する class / trait 等は
+    /// メンバーの変更が本体ハッシュに波及するため、メンバー側だけで報告する。
+    reports_body_change: bool,
+}
+
+impl RevisionSymbol {
+    fn into_symbol(self) -> SymDiffSymbol {
+        SymDiffSymbol {
+            name: self.name,
+            kind: self.kind,
+            file: self.file,
+            line: self.line,
+        }
+    }
+
+    fn to_candidate(&self) -> ApiSymbolCandidate {
+        ApiSymbolCandidate {
+            name: self.name.clone(),
+            kind: self.kind.clone(),
+            file: self.file.clone(),
+            signature: self.signature.clone(),
+        }
+    }
+}
+
+/// `git show <rev>:<path>` の内容からシンボルを抽出する。`/dev/null` (追加 / 削除側) や
+/// 取得・parse 失敗、未対応言語は空。
+fn extract_revision_symbols(dir: &str, rev: &str, path: &str) -> Vec<RevisionSymbol> {
+    if path == "/dev/null" {
+        return Vec::new();
+    }
+    let Some(source) = git_show_blob(dir, rev, path) else {
+        return Vec::new();
+    };
+    symbols_from_source(path, &source)
+}
+
+fn symbols_from_source(path: &str, source: &[u8]) -> Vec<RevisionSymbol> {
+    let Ok(lang) = parser::detect_lang(Utf8Path::new(path), source) else {
+        return Vec::new();
+    };
+    if lang.is_lexer_only() {
+        return Vec::new();
+    }
+    let Ok(tree) = parser::parse_source(source, lang) else {
+        return Vec::new();
+    };
+    let root = tree.root_node();
+    let Ok(symbols) = extract_symbols(root, source, lang) else {
+        return Vec::new();
+    };
+    let lines: Vec<&str> = std::str::from_utf8(source).unwrap_or("").lines().collect();
+    let line_starts: Vec<usize> = std::iter::once(0)
+        .chain(memchr::memchr_iter(b'\n', source).map(|i| i + 1))
+        .collect();
+    let containers: Vec<&Symbol> = symbols
+        .iter()
+        .filter(|sym| {
+            matches!(
+                sym.kind,
+                SymbolKind::Class
+                    | SymbolKind::Struct
+                    | SymbolKind::Trait
+                    | SymbolKind::Interface
+                    | SymbolKind::Enum
+            )
+        })
+        .collect();
+
+    symbols
+        .iter()
+        .filter(|sym| !matches!(sym.kind, SymbolKind::Import | SymbolKind::Parameter))
+        .filter(|sym| !is_local_scope_symbol(root, source, lang, &sym.range))
+        .map(|sym| {
+            let definition =
+                normalize_signature_whitespace(symbol_bytes(source, &line_starts, sym));
+            RevisionSymbol {
+                name: qualname(sym, &containers),
+                bare_name: sym.name.clone(),
+                kind: format!("{:?}", sym.kind).to_lowercase(),
+                file: path.to_string(),
+                line: sym.range.start.line,
+                signature: extract_api_signature(sym, root, source, &lines, lang),
+                body_hash: CacheStore::hash(definition.as_bytes()),
+                shape_hash: CacheStore::hash(mask_identifier(&definition, &sym.name).as_bytes()),
+                reports_body_change: !matches!(
+                    sym.kind,
+                    SymbolKind::Class
+                        | SymbolKind::Trait
+                        | SymbolKind::Interface
+                        | SymbolKind::Module
+                ),
+            }
+        })
+        .collect()
+}
+
+/// api_changes の qualname 規則と揃える (メソッド / 関数は container 付き)。
+/// フィールドも struct 間の同名衝突を避けるため container 付きにする。
+fn qualname(sym: &Symbol, containers: &[&Symbol]) -> String {
+    if !matches!(
+        sym.kind,
+        SymbolKind::Method | SymbolKind::Function | SymbolKind::Field
+    ) {
+        return sym.name.clone();
+    }
+    if let Some(container) = sym.container.as_deref() {
+        return format!("{container}.{}", sym.name);
+    }
+    enclosing_container(sym, containers)
+        .map(|c| format!("{}.{}", c.name, sym.name))
+        .unwrap_or_else(|| sym.name.clone())
+}
+
+/// シンボル range のバイト列。tree-sitter の column はバイト単位なので行頭 offset に足す。
+fn symbol_bytes<'s>(source: &'s [u8], line_starts: &[usize], sym: &Symbol) -> &'s [u8] {
+    let offset = |line: usize, column: usize| {
+        line_starts
+            .get(line)
+            .map(|start| (start + column).min(source.len()))
+    };
+    match (
+        offset(sym.range.start.line, sym.range.start.column),
+        offset(sym.range.end.line, sym.range.end.column),
+    ) {
+        (Some(start), Some(end)) if start <= end => &source[start..end],
+        _ => &[],
+    }
+}
+
+/// `text` 中の識別子 `name` (単語境界で一致するもの) を固定の placeholder に置き換える。
+fn mask_identifier(text: &str, name: &str) -> String {
+    if name.is_empty() {
+        return text.to_string();
+    }
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_' || c == '$';
+    let mut out = String::with_capacity(text.len());
+    let mut copied = 0;
+    for (pos, _) in text.match_indices(name) {
+        let end = pos + name.len();
+        let before = text[..pos].chars().next_back();
+        let after = text[end..].chars().next();
+        if before.is_some_and(is_ident) || after.is_some_and(is_ident) {
+            continue;
+        }
+        out.push_str(&text[copied..pos]);
+        out.push('\u{0}');
+        copied = end;
+    }
+    out.push_str(&text[copied..]);
+    out
+}
+
+#[derive(Debug, Default)]
+struct SymDiffBuckets {
+    added: Vec<SymDiffSymbol>,
+    removed: Vec<SymDiffSymbol>,
+    renamed: Vec<RenamedSymbol>,
+    moved: Vec<MovedSymbol>,
+    signature_changed: Vec<SymDiffSignatureChange>,
+    body_changed: Vec<SymDiffSymbol>,
+}
+
+/// `(旧 revision のシンボル, 新 revision のシンボル)` のファイル組を分類する。
+///
+/// 1. 同一ファイル組内の同名・同種別ペア → シグネチャ差分なら `signature_changed`、
+///    本体ハッシュ差分のみなら `body_changed`、どちらも同じなら報告しない。ファイルごと
+///    rename された組 (旧パス ≠ 新パス) では、シグネチャが同じペアを旧パス → 新パスの
+///    `moved` にする (本体の差分は moved に含める)。
+/// 2. 残った add/rm を `reconcile_with_moves` (review の `api_changes.moved` と同じ規則:
+///    同名・同種別・同シグネチャ) で `moved` に相殺する。
+/// 3. さらに残った add/rm のうち、自身の名前を伏せた定義が一致するものを `renamed` にする
+///    (同一ファイル内を優先し、次にファイルをまたぐペアを探す)。
+/// 4. 最後まで残ったものが `added` / `removed`。
+fn classify_symbol_changes(
+    file_pairs: Vec<(Vec<RevisionSymbol>, Vec<RevisionSymbol>)>,
+) -> SymDiffBuckets {
+    let mut buckets = SymDiffBuckets::default();
+    let mut added_pool: Vec<RevisionSymbol> = Vec::new();
+    let mut removed_pool: Vec<RevisionSymbol> = Vec::new();
+
+    for (old_syms, new_syms) in file_pairs {
+        let mut old_by_key: HashMap<(String, String), VecDeque<usize>> = HashMap::new();
+        for (i, sym) in old_syms.iter().enumerate() {
+            old_by_key
+                .entry((sym.name.clone(), sym.kind.clone()))
+                .or_default()
+                .push_back(i);
+        }
+        let mut old_matched = vec![false; old_syms.len()];
+        for new in new_syms {
+            let Some(i) = old_by_key
+                .get_mut(&(new.name.clone(), new.kind.clone()))
+                .and_then(VecDeque::pop_front)
+            else {
+                added_pool.push(new);
+                continue;
+            };
+            old_matched[i] = true;
+            let old = &old_syms[i];
+            if old.signature != new.signature {
+                buckets.signature_changed.push(SymDiffSignatureChange {
+                    name: new.name,
+                    kind: new.kind,
+                    file: new.file,
+                    old_signature: old.signature.clone(),
+                    new_signature: new.signature,
+                });
+            } else if old.file != new.file {
+                buckets.moved.push(MovedSymbol {
+                    name: new.name,
+                    kind: new.kind,
+                    from: old.file.clone(),
+                    to: new.file,
+                });
+            } else if old.body_hash != new.body_hash && new.reports_body_change {
+                buckets.body_changed.push(new.into_symbol());
+            }
+        }
+        removed_pool.extend(
+            old_syms
+                .into_iter()
+                .zip(old_matched)
+                .filter_map(|(sym, matched)| (!matched).then_some(sym)),
+        );
+    }
+
+    let added_candidates: Vec<ApiSymbolCandidate> = added_pool
+        .iter()
+        .map(RevisionSymbol::to_candidate)
+        .collect();
+    let removed_candidates: Vec<ApiSymbolCandidate> = removed_pool
+        .iter()
+        .map(RevisionSymbol::to_candidate)
+        .collect();
+    let (kept_added, kept_removed, moved) = reconcile_with_moves(
+        added_candidates.clone(),
+        removed_candidates,
+        added_candidates,
+    );
+    buckets.moved.extend(moved);
+    let added_pool = retain_candidates(added_pool, &kept_added);
+    let removed_pool = retain_candidates(removed_pool, &kept_removed);
+
+    let mut added_taken = vec![false; added_pool.len()];
+    let mut removed_taken = vec![false; removed_pool.len()];
+    for same_file_only in [true, false] {
+        for (ri, removed) in removed_pool.iter().enumerate() {
+            if removed_taken[ri] {
+                continue;
+            }
+            let found = (0..added_pool.len()).find(|&ai| {
+                let added = &added_pool[ai];
+                !added_taken[ai]
+                    && added.kind == removed.kind
+                    && added.bare_name != removed.bare_name
+                    && added.shape_hash == removed.shape_hash
+                    && (!same_file_only || added.file == removed.file)
+            });
+            if let Some(ai) = found {
+                added_taken[ai] = true;
+                removed_taken[ri] = true;
+                buckets.renamed.push(RenamedSymbol {
+                    old_name: removed.name.clone(),
+                    new_name: added_pool[ai].name.clone(),
+                    kind: removed.kind.clone(),
+                    from: removed.file.clone(),
+                    to: added_pool[ai].file.clone(),
+                });
+            }
+        }
+    }
+
+    buckets.added = added_pool
+        .into_iter()
+        .zip(added_taken)
+        .filter_map(|(sym, taken)| (!taken).then(|| sym.into_symbol()))
+        .collect();
+    buckets.removed = removed_pool
+        .into_iter()
+        .zip(removed_taken)
+        .filter_map(|(sym, taken)| (!taken).then(|| sym.into_symbol()))
+        .collect();
+
+    let by_location = |a: &SymDiffSymbol, b: &SymDiffSymbol| {
+        a.file.cmp(&b.file).then_with(|| a.line.cmp(&b.line))
+    };
+    buckets.added.sort_by(by_location);
+    buckets.removed.sort_by(by_location);
+    buckets.body_changed.sort_by(by_location);
+    buckets
+        .signature_changed
+        .sort_by(|a, b| a.file.cmp(&b.file).then_with(|| a.name.cmp(&b.name)));
+    buckets
+        .renamed
+        .sort_by(|a, b| a.to.cmp(&b.to).then_with(|| a.new_name.cmp(&b.new_name)));
+    buckets
+        .moved
+        .sort_by(|a, b| a.to.cmp(&b.to).then_with(|| a.name.cmp(&b.name)));
+    buckets
+}
+
+/// `reconcile_with_moves` が残した候補 (多重集合) に対応するシンボルだけを `pool` から残す。
+fn retain_candidates(
+    pool: Vec<RevisionSymbol>,
+    kept: &[ApiSymbolCandidate],
+) -> Vec<RevisionSymbol> {
+    let mut remaining: HashMap<(String, String, String, String), usize> = HashMap::new();
+    for c in kept {
+        *remaining
+            .entry((
+                c.name.clone(),
+                c.kind.clone(),
+                c.file.clone(),
+                c.signature.clone(),
+            ))
+            .or_default() += 1;
+    }
+    pool.into_iter()
+        .filter(|sym| {
+            let key = (
+                sym.name.clone(),
+                sym.kind.clone(),
+                sym.file.clone(),
+                sym.signature.clone(),
+            );
+            match remaining.get_mut(&key) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    true
+                }
+                _ => false,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn classify(files: &[(&str, &str, &str, &str)]) -> SymDiffBuckets {
+        let pairs = files
+            .iter()
+            .map(|(old_path, old_src, new_path, new_src)| {
+                (
+                    symbols_from_source(old_path, old_src.as_bytes()),
+                    symbols_from_source(new_path, new_src.as_bytes()),
+                )
+            })
+            .collect();
+        classify_symbol_changes(pairs)
+    }
+
+    fn names(symbols: &[SymDiffSymbol]) -> Vec<&str> {
+        symbols.iter().map(|s| s.name.as_str()).collect()
+    }
+
+    #[test]
+    fn classifies_signature_and_body_changes() {
+        let old = "fn keep() -> i32 { 1 }\nfn sig(a: i32) -> i32 { a }\nfn body() -> i32 { 1 }\n";
+        let new = "fn keep() -> i32 { 1 }\nfn sig(a: i64) -> i64 { a }\nfn body() -> i32 { 2 }\n";
+
+        let buckets = classify(&[("lib.rs", old, "lib.rs", new)]);
+
+        assert_eq!(buckets.signature_changed.len(), 1, "{buckets:?}");
+        assert_eq!(buckets.signature_changed[0].name, "sig");
+        assert_eq!(names(&buckets.body_changed), ["body"]);
+        assert!(buckets.added.is_empty() && buckets.removed.is_empty());
+    }
+
+    #[test]
+    fn classifies_added_removed_and_private_symbols() {
+        let old = "fn private_old() {}\npub fn shared() {}\n";
+        let new = "pub fn shared() {}\nfn private_new(x: u8) -> u8 { x * 2 }\n";
+
+        let buckets = classify(&[("lib.rs", old, "lib.rs", new)]);
+
+        assert_eq!(names(&buckets.added), ["private_new"]);
+        assert_eq!(names(&buckets.removed), ["private_old"]);
+        assert_eq!(buckets.added[0].line, 1);
+    }
+
+    #[test]
+    fn renamed_symbol_is_matched_by_masked_definition() {
+        let old = "fn compute_total(items: &[u32]) -> u32 {\n    items.iter().sum()\n}\n";
+        let new = "fn sum_items(items: &[u32]) -> u32 {\n    items.iter().sum()\n}\n";
+
+        let buckets = classify(&[("lib.rs", old, "lib.rs", new)]);
+
+        assert_eq!(buckets.renamed.len(), 1, "{buckets:?}");
+        assert_eq!(buckets.renamed[0].old_name, "compute_total");
+        assert_eq!(buckets.renamed[0].new_name, "sum_items");
+        assert!(buckets.added.is_empty() && buckets.removed.is_empty());
+    }
+
+    #[test]
+    fn moved_symbol_reuses_api_move_reconciliation() {
+        let moved_fn = "pub fn helper(x: u32) -> u32 {\n    x + 1\n}\n";
+
+        let buckets = classify(&[
+            ("a.rs", moved_fn, "a.rs", ""),
+            ("b.rs", "", "b.rs", moved_fn),
+        ]);
+
+        assert_eq!(buckets.moved.len(), 1, "{buckets:?}");
+        assert_eq!(buckets.moved[0].from, "a.rs");
+        assert_eq!(buckets.moved[0].to, "b.rs");
+        assert!(buckets.renamed.is_empty());
+        assert!(buckets.added.is_empty() && buckets.removed.is_empty());
+    }
+
+    #[test]
+    fn renamed_file_reports_its_symbols_as_moved() {
+        let old =
+            "pub fn helper(x: u32) -> u32 {\n    x + 1\n}\n\nfn tweak(a: i32) -> i32 {\n    a\n}\n";
+        let new =
+            "pub fn helper(x: u32) -> u32 {\n    x + 1\n}\n\nfn tweak(a: i64) -> i64 {\n    a\n}\n";
+
+        let buckets = classify(&[("src/old.rs", old, "src/new.rs", new)]);
+
+        let moved: Vec<(&str, &str, &str)> = buckets
+            .moved
+            .iter()
+            .map(|m| (m.name.as_str(), m.from.as_str(), m.to.as_str()))
+            .collect();
+        assert_eq!(moved, [("helper", "src/old.rs", "src/new.rs")]);
+        assert_eq!(buckets.signature_changed.len(), 1, "{buckets:?}");
+        assert_eq!(buckets.signature_changed[0].file, "src/new.rs");
+        assert!(buckets.added.is_empty() && buckets.removed.is_empty());
+    }
+
+    #[test]
+    fn mask_identifier_respects_word_boundaries() {
+        assert_eq!(
+            mask_identifier("fn foo() { foo_bar(); foofoo(); foo() }", "foo"),
+            "fn \u{0}() { foo_bar(); foofoo(); \u{0}() }"
+        );
+    }
+}
@@ -1143,6 +1143,46 @@ fn auto_detect_framework_returns_none_when_no_next_dependency() {
     assert!(auto_detect_framework(dir.path().to_str().expect("utf-8")).is_none());
 }
 
+#[test]
+fn auto_detect_framework_returns_rails_for_gemfile() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    fs::write(
+        dir.path().join("Gemfile"),
+        "source 'https://rubygems.org'\n\ngem 'rails', '~> 7.1'\ngem 'pg'\n",
+    )
+    .expect("gemfile");
+    assert_eq!(
+        auto_detect_framework(dir.path().to_str().expect("utf-8")),
+        Some("rails")
+    );
+}
+
+/// `rails` を名前に含むだけの gem (`rails-html-sanitizer` 等) では Rails と判定しない。
+#[test]
+fn auto_detect_framework_ignores_rails_prefixed_gems() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    fs::write(
+        dir.path().join("Gemfile"),
+        "gem 'rails-html-sanitizer'\ngem 'rubocop-rails'\n",
+    )
+    .expect("gemfile");
+    assert!(auto_detect_framework(dir.path().to_str().expect("utf-8")).is_none());
+}
+
+#[test]
+fn auto_detect_framework_returns_spring_for_maven_dependency() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    fs::write(
+        dir.path().join("pom.xml"),
+        "<project><parent><groupId>org.springframework.boot</groupId></parent></project>\n",
+    )
+    .expect("pom");
+    assert_eq!(
+        auto_detect_framework(dir.path().to_str().expect("utf-8")),
+        Some("spring")
+    );
+}
+
 /// `resolve_framework_globs_with_auto_detect`: 明示指定があれば auto detect は無視する。
 #[test]
 fn resolve_framework_globs_with_auto_detect_prefers_explicit_framework() {
@@ -67,8 +67,11 @@ export class SampleComponent {
     fs::write(repo.join("sample.component.html"), component_html).expect("write html");
 
     let files = vec![repo.join("sample.component.ts")];
-    let (dead, _test_only) =
-        detect_dead_symbols_from_files(repo.to_str().expect("utf-8 path"), &files);
+    let (dead, _test_only) = detect_dead_symbols_from_files(
+        repo.to_str().expect("utf-8 path"),
+        &files,
+        &Default::default(),
+    );
     let names: Vec<&str> = dead.iter().map(|d| d.name.as_str()).collect();
 
     assert!(
@@ -108,8 +111,11 @@ fn detect_dead_php_duplicate_static_factory_methods_are_owner_aware() {
     .expect("write use");
 
     let files = vec![repo.join("A.php"), repo.join("B.php"), repo.join("use.php")];
-    let (dead, _test_only) =
-        detect_dead_symbols_from_files(repo.to_str().expect("utf-8 path"), &files);
+    let (dead, _test_only) = detect_dead_symbols_from_files(
+        repo.to_str().expect("utf-8 path"),
+        &files,
+        &Default::default(),
+    );
     let names: Vec<&str> = dead.iter().map(|d| d.name.as_str()).collect();
 
     assert!(
@@ -145,8 +151,11 @@ fn detect_dead_php_duplicate_static_factory_methods_on_single_line() {
     .expect("write use");
 
     let files = vec![repo.join("A.php"), repo.join("B.php"), repo.join("use.php")];
-    let (dead, _test_only) =
-        detect_dead_symbols_from_files(repo.to_str().expect("utf-8 path"), &files);
+    let (dead, _test_only) = detect_dead_symbols_from_files(
+        repo.to_str().expect("utf-8 path"),
+        &files,
+        &Default::default(),
+    );
     let names: Vec<&str> = dead.iter().map(|d| d.name.as_str()).collect();
 
     assert!(
@@ -178,8 +187,11 @@ fn detect_dead_php_duplicate_methods_with_dynamic_call_remain_ambiguous() {
     .expect("write use");
 
     let files = vec![repo.join("A.php"), repo.join("B.php"), repo.join("use.php")];
-    let (dead, _test_only) =
-        detect_dead_symbols_from_files(repo.to_str().expect("utf-8 path"), &files);
+    let (dead, _test_only) = detect_dead_symbols_from_files(
+        repo.to_str().expect("utf-8 path"),
+        &files,
+        &Default::default(),
+    );
     let names: Vec<&str> = dead.iter().map(|d| d.name.as_str()).collect();
 
     assert!(
@@ -205,8 +217,11 @@ public:\n\
     .expect("write header");
 
     let files = vec![repo.join("GenericClient.h")];
-    let (dead, _test_only) =
-        detect_dead_symbols_from_files(repo.to_str().expect("utf-8 path"), &files);
+    let (dead, _test_only) = detect_dead_symbols_from_files(
+        repo.to_str().expect("utf-8 path"),
+        &files,
+        &Default::default(),
+    );
     let names: Vec<&str> = dead.iter().map(|d| d.name.as_str()).collect();
 
     assert!(
@@ -229,8 +244,11 @@ fn detect_dead_cpp_forward_declaration_tag_excluded() {
     fs::write(repo.join("mysql_service.h"), header).expect("write header");
 
     let files = vec![repo.join("mysql_service.h")];
-    let (dead, _test_only) =
-        detect_dead_symbols_from_files(repo.to_str().expect("utf-8 path"), &files);
+    let (dead, _test_only) = detect_dead_symbols_from_files(
+        repo.to_str().expect("utf-8 path"),
+        &files,
+        &Default::default(),
+    );
     let names: Vec<&str> = dead.iter().map(|d| d.name.as_str()).collect();
 
     assert!(
@@ -282,8 +300,11 @@ int allocate_buffer(void* raw) {\n\
         repo.join("app_textserver.c"),
         repo.join("VoiceToTextConvertServer.cpp"),
     ];
-    let (dead, _test_only) =
-        detect_dead_symbols_from_files(repo.to_str().expect("utf-8 path"), &files);
+    let (dead, _test_only) = detect_dead_symbols_from_files(
+        repo.to_str().expect("utf-8 path"),
+        &files,
+        &Default::default(),
+    );
     let names: Vec<&str> = dead.iter().map(|d| d.name.as_str()).collect();
 
     for live in ["voice_options", "text_server_data", "buffer_data"] {
@@ -327,8 +348,11 @@ int useThem() {\n\
     );
 
     let files = vec![repo.join("svc.h"), repo.join("main.cpp")];
-    let (dead, _test_only) =
-        detect_dead_symbols_from_files(repo.to_str().expect("utf-8 path"), &files);
+    let (dead, _test_only) = detect_dead_symbols_from_files(
+        repo.to_str().expect("utf-8 path"),
+        &files,
+        &Default::default(),
+    );
     let names: Vec<&str> = dead.iter().map(|d| d.name.as_str()).collect();
 
     assert!(
@@ -375,8 +399,11 @@ int useMulti() {\n\
     );
 
     let files = vec![repo.join("svc.h"), repo.join("main.cpp")];
-    let (dead, _test_only) =
-        detect_dead_symbols_from_files(repo.to_str().expect("utf-8 path"), &files);
+    let (dead, _test_only) = detect_dead_symbols_from_files(
+        repo.to_str().expect("utf-8 path"),
+        &files,
+        &Default::default(),
+    );
     let names: Vec<&str> = dead.iter().map(|d| d.name.as_str()).collect();
 
     assert!(
@@ -423,8 +450,11 @@ export class InlineComponent {
     fs::write(repo.join("inline.component.ts"), component_ts).expect("write ts");
 
     let files = vec![repo.join("inline.component.ts")];
-    let (dead, _test_only) =
-        detect_dead_symbols_from_files(repo.to_str().expect("utf-8 path"), &files);
+    let (dead, _test_only) = detect_dead_symbols_from_files(
+        repo.to_str().expect("utf-8 path"),
+        &files,
+        &Default::default(),
+    );
     let names: Vec<&str> = dead.iter().map(|d| d.name.as_str()).collect();
 
     assert!(
@@ -465,8 +495,11 @@ export class MinimalComponent {
     fs::write(repo.join("minimal.component.ts"), component_ts).expect("write ts");
 
     let files = vec![repo.join("minimal.component.ts")];
-    let (dead, _test_only) =
-        detect_dead_symbols_from_files(repo.to_str().expect("utf-8 path"), &files);
+    let (dead, _test_only) = detect_dead_symbols_from_files(
+        repo.to_str().expect("utf-8 path"),
+        &files,
+        &Default::default(),
+    );
     let names: Vec<&str> = dead.iter().map(|d| d.name.as_str()).collect();
 
     for hook in ["ngOnInit", "ngAfterViewChecked", "ngOnDestroy"] {
@@ -502,8 +535,11 @@ export class FooDirective {
     fs::write(repo.join("foo.directive.ts"), directive_ts).expect("write ts");
 
     let files = vec![repo.join("foo.directive.ts")];
-    let (dead, _test_only) =
-        detect_dead_symbols_from_files(repo.to_str().expect("utf-8 path"), &files);
+    let (dead, _test_only) = detect_dead_symbols_from_files(
+        repo.to_str().expect("utf-8 path"),
+        &files,
+        &Default::default(),
+    );
     let names: Vec<&str> = dead.iter().map(|d| d.name.as_str()).collect();
 
     for hook in ["ngOnInit", "ngOnChanges"] {
@@ -534,8 +570,11 @@ export class PlainClass {
     fs::write(repo.join("plain.ts"), plain_ts).expect("write ts");
 
     let files = vec![repo.join("plain.ts")];
-    let (dead, _test_only) =
-        detect_dead_symbols_from_files(repo.to_str().expect("utf-8 path"), &files);
+    let (dead, _test_only) = detect_dead_symbols_from_files(
+        repo.to_str().expect("utf-8 path"),
+        &files,
+        &Default::default(),
+    );
     let names: Vec<&str> = dead.iter().map(|d| d.name.as_str()).collect();
 
     for hook in ["ngOnInit", "ngAfterViewChecked"] {
@@ -567,8 +606,11 @@ export function ghostHandler(): void {
     .expect("write html");
 
     let files = vec![repo.join("util.ts")];
-    let (dead, _test_only) =
-        detect_dead_symbols_from_files(repo.to_str().expect("utf-8 path"), &files);
+    let (dead, _test_only) = detect_dead_symbols_from_files(
+        repo.to_str().expect("utf-8 path"),
+        &files,
+        &Default::default(),
+    );
     let names: Vec<&str> = dead.iter().map(|d| d.name.as_str()).collect();
 
     assert!(
@@ -592,8 +634,11 @@ fn detect_dead_symbols_skips_auto_generated_marker_files() {
     fs::write(repo.join("hand.py"), "def unused_hand():\n    pass\n").expect("write");
 
     let files = vec![repo.join("gen.py"), repo.join("hand.py")];
-    let (dead, _test_only) =
-        detect_dead_symbols_from_files(repo.to_str().expect("utf-8 path"), &files);
+    let (dead, _test_only) = detect_dead_symbols_from_files(
+        repo.to_str().expect("utf-8 path"),
+        &files,
+        &Default::default(),
+    );
     let names: Vec<&str> = dead.iter().map(|d| d.name.as_str()).collect();
 
     assert!(
@@ -822,8 +867,11 @@ fn detect_dead_php_case_insensitive_method_call_is_not_dead() {
         .expect("write");
 
     let files = vec![repo.join("Vo.php"), repo.join("Caller.php")];
-    let (dead, _test_only) =
-        detect_dead_symbols_from_files(repo.to_str().expect("utf-8 path"), &files);
+    let (dead, _test_only) = detect_dead_symbols_from_files(
+        repo.to_str().expect("utf-8 path"),
+        &files,
+        &Default::default(),
+    );
     let dead_names: Vec<&str> = dead.iter().map(|d| d.name.as_str()).collect();
     assert!(
         !dead_names.iter().any(|n| n.ends_with("isLocalLInk")),
@@ -883,12 +931,16 @@ fn filter_dead_by_wip_added_drops_symbols_listed_in_added() {
             kind: "function".to_string(),
             file: "src/notes.ts".to_string(),
             line: None,
+            visibility: None,
+            blame: None,
         },
         DeadSymbol {
             name: "legacyUnused".to_string(),
             kind: "function".to_string(),
             file: "src/legacy.ts".to_string(),
             line: None,
+            visibility: None,
+            blame: None,
         },
     ];
     let added = vec![ApiSymbol {
@@ -916,6 +968,8 @@ fn filter_dead_by_wip_added_matches_on_file_and_name_pair() {
         kind: "function".to_string(),
         file: "src/a.ts".to_string(),
         line: None,
+        visibility: None,
+        blame: None,
     }];
     let added = vec![ApiSymbol {
         // 同じ name だが別 file の追加 — dead 側 (a.ts) は残るべき。
@@ -938,8 +992,288 @@ fn filter_dead_by_wip_added_passes_through_when_added_is_empty() {
         kind: "function".to_string(),
         file: "src/foo.rs".to_string(),
         line: None,
+        visibility: None,
+        blame: None,
     }];
     let filtered = filter_dead_by_wip_added(dead.clone(), &[]);
     assert_eq!(filtered.len(), 1);
     assert_eq!(filtered[0].name, "foo");
 }
+
+fn private_dead_names(repo: &std::path::Path) -> Vec<(String, String)> {
+    let repo = &fs::canonicalize(repo).expect("canonicalize");
+    let files = crate::engine::refs::collect_files(repo, None).expect("collect files");
+    detect_private_dead_symbols(repo.to_str().unwrap(), &files, &files, &Default::default())
+        .into_iter()
+        .inspect(|d| {
+            assert_eq!(
+                d.visibility,
+                Some(crate::models::review::DeadSymbolVisibility::Private)
+            )
+        })
+        .map(|d| (d.file, d.name))
+        .collect()
+}
+
+/// Rust の非公開関数はモジュール (宣言ファイル + 子モジュールのディレクトリ) 内の参照で判定し、
+/// `main` / `#[cfg(test)]` 内 / 公開関数は対象にしない。
+#[test]
+fn detect_private_dead_rust_uses_module_scope() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let repo = dir.path();
+    fs::create_dir_all(repo.join("src/net")).unwrap();
+    fs::write(
+        repo.join("src/main.rs"),
+        "mod net;\n\nfn main() {\n    used();\n}\n\nfn used() {}\n\nfn unused() {}\n\nconst LIMIT: usize = 3;\n\npub fn api() {}\n\n#[cfg(test)]\nmod tests {\n    fn fixture() {}\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        repo.join("src/net.rs"),
+        "mod client;\n\nfn from_child() -> u8 {\n    1\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        repo.join("src/net/client.rs"),
+        "pub fn connect() -> u8 {\n    super::from_child()\n}\n",
+    )
+    .unwrap();
+
+    let dead = private_dead_names(repo);
+    assert_eq!(
+        dead,
+        vec![
+            ("src/main.rs".to_string(), "unused".to_string()),
+            ("src/main.rs".to_string(), "LIMIT".to_string()),
+        ],
+    );
+}
+
+/// Go の非公開 (小文字) 関数は同一パッケージ (同じディレクトリ) の別ファイルからの参照で live。
+#[test]
+fn detect_private_dead_go_uses_package_scope() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let repo = dir.path();
+    fs::create_dir_all(repo.join("pkg/other")).unwrap();
+    fs::write(
+        repo.join("pkg/a.go"),
+        "package pkg\n\nfunc helper() int { return 1 }\n\nfunc stale() int { return 2 }\n\nfunc init() {}\n",
+    )
+    .unwrap();
+    fs::write(
+        repo.join("pkg/b.go"),
+        "package pkg\n\nfunc Run() int { return helper() }\n",
+    )
+    .unwrap();
+    // 別パッケージの同名呼び出しは stale を live にしない。
+    fs::write(
+        repo.join("pkg/other/c.go"),
+        "package other\n\nfunc stale() int { return 0 }\n\nfunc Use() int { return stale() }\n",
+    )
+    .unwrap();
+
+    let dead = private_dead_names(repo);
+    assert_eq!(dead, vec![("pkg/a.go".to_string(), "stale".to_string())]);
+}
+
+/// Python の `_` プレフィックス関数 / メソッドはファイル内の参照で判定し、dunder は対象外。
+#[test]
+fn detect_private_dead_python_skips_dunder_methods() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let repo = dir.path();
+    fs::write(
+        repo.join("service.py"),
+        "class Service:\n    def __init__(self):\n        self._load()\n\n    def _load(self):\n        pass\n\n    def _unused(self):\n        pass\n\n\ndef _orphan():\n    pass\n",
+    )
+    .unwrap();
+
+    let dead = private_dead_names(repo);
+    assert_eq!(
+        dead,
+        vec![
+            ("service.py".to_string(), "Service._unused".to_string()),
+            ("service.py".to_string(), "_orphan".to_string()),
+        ],
+    );
+}
+
+/// PHP の `protected` メソッドは別ファイルのサブクラスから呼べるため、ファイル内に
+/// 参照が無くても報告しない (`private` だけを対象にする)。
+#[test]
+fn detect_private_dead_php_skips_protected_methods() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let repo = dir.path();
+    fs::write(
+        repo.join("Base.php"),
+        "<?php\nclass Base {\n    protected function hook() {\n        return 1;\n    }\n\n    private function stale() {\n        return 2;\n    }\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        repo.join("Child.php"),
+        "<?php\nclass Child extends Base {\n    public function run() {\n        return $this->hook();\n    }\n}\n",
+    )
+    .unwrap();
+
+    let dead = private_dead_names(repo);
+    assert_eq!(
+        dead,
+        vec![("Base.php".to_string(), "Base.stale".to_string())]
+    );
+}
+
+fn unreachable_cluster_names(repo: &std::path::Path, entries: &[&str]) -> Vec<Vec<String>> {
+    let repo = &fs::canonicalize(repo).expect("canonicalize");
+    let files = crate::engine::refs::collect_files(repo, None).expect("collect files");
+    let entries: Vec<String> = entries.iter().map(|e| e.to_string()).collect();
+    detect_unreachable_clusters(
+        repo.to_str().unwrap(),
+        &files,
+        &files,
+        &HashSet::new(),
+        &entries,
+        &Default::default(),
+    )
+    .expect("reachability")
+    .into_iter()
+    .map(|cluster| cluster.symbols.into_iter().map(|d| d.name).collect())
+    .collect()
+}
+
+/// 相互再帰だけで繋がった関数は 1 つの cluster になり、テストからの参照・`use` だけの
+/// 参照はそれぞれ起点 / 非利用として扱う。
+#[test]
+fn reachability_groups_mutually_recursive_dead_code() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let repo = dir.path();
+    fs::create_dir_all(repo.join("src")).unwrap();
+    fs::write(
+        repo.join("src/main.rs"),
+        "mod util;\n\nuse crate::util::imported_only;\n\nfn main() {\n    live();\n}\n\nfn live() {}\n\nfn ping(n: u32) {\n    if n > 0 {\n        pong(n - 1);\n    }\n}\n\nfn pong(n: u32) {\n    ping(n);\n}\n\nfn lonely() {}\n\nfn tested() {}\n\n#[cfg(test)]\nmod tests {\n    #[test]\n    fn calls_tested() {\n        super::tested();\n    }\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        repo.join("src/util.rs"),
+        "pub(crate) fn imported_only() {}\n\npub(crate) struct Unused;\n\nimpl Unused {\n    pub(crate) fn new() -> Self {\n        Unused\n    }\n}\n",
+    )
+    .unwrap();
+
+    let clusters = unreachable_cluster_names(repo, &[]);
+    assert_eq!(
+        clusters,
+        vec![
+            vec!["ping".to_string(), "pong".to_string()],
+            vec!["lonely".to_string()],
+            vec!["imported_only".to_string()],
+            vec!["Unused".to_string(), "Unused.new".to_string()],
+        ],
+    );
+}
+
+/// `--entry` は bare name / `FILE_GLOB:NAME_GLOB` のどちらでも起点を追加できる。
+#[test]
+fn reachability_entry_patterns_add_roots() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let repo = dir.path();
+    fs::create_dir_all(repo.join("src/jobs")).unwrap();
+    fs::write(
+        repo.join("src/main.rs"),
+        "mod jobs;\n\nfn main() {}\n\nfn ping() {\n    pong();\n}\n\nfn pong() {\n    ping();\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        repo.join("src/jobs/nightly.rs"),
+        "fn run_cleanup() {\n    sweep();\n}\n\nfn sweep() {}\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+        unreachable_cluster_names(repo, &[]),
+        vec![
+            vec!["run_cleanup".to_string(), "sweep".to_string()],
+            vec!["ping".to_string(), "pong".to_string()],
+        ],
+    );
+    assert_eq!(
+        unreachable_cluster_names(repo, &["pong", "src/jobs/*.rs:run_*"]),
+        Vec::<Vec<String>>::new(),
+    );
+}
+
+fn unused_parameter_names(repo: &std::path::Path) -> Vec<(String, String)> {
+    let repo = &fs::canonicalize(repo).expect("canonicalize");
+    let files = crate::engine::refs::collect_files(repo, None).expect("collect files");
+    detect_unused_parameters(repo.to_str().unwrap(), &files)
+        .into_iter()
+        .map(|p| (p.function, p.name))
+        .collect()
+}
+
+/// trait 実装・`_` 始まり・stub 本体・補間での利用は報告せず、使用済み引数より前の
+/// 未使用引数は報告する。
+#[test]
+fn unused_parameters_skip_dictated_signatures_rust() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let repo = dir.path();
+    fs::write(
+        repo.join("lib.rs"),
+        "pub trait Handler {\n    fn handle(&self, input: u32) -> u32;\n}\n\npub struct Noop;\n\nimpl Handler for Noop {\n    fn handle(&self, input: u32) -> u32 {\n        0\n    }\n}\n\npub fn add(a: u32, b: u32, extra: u32) -> u32 {\n    a + b\n}\n\npub fn flagged(value: u32, _flag: bool) -> u32 {\n    value\n}\n\npub fn stub(input: u32) -> u32 {\n    todo!()\n}\n\npub fn second(first: u32, second: u32) -> u32 {\n    second\n}\n\npub fn greet(name: &str) -> String {\n    format!(\"hi {name}\")\n}\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+        unused_parameter_names(repo),
+        vec![
+            ("add".to_string(), "extra".to_string()),
+            ("second".to_string(), "first".to_string()),
+        ],
+    );
+}
+
+/// Go の関数も使用済み引数より前の未使用引数を報告する (callback 以外は位置を理由に残さない)。
+#[test]
+fn unused_parameters_report_leading_params_go() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let repo = dir.path();
+    fs::write(
+        repo.join("scale.go"),
+        "package scale\n\nfunc Scale(factor int, value int) int {\n\treturn value\n}\n\nfunc Pick(a, b int) int {\n\treturn b\n}\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+        unused_parameter_names(repo),
+        vec![
+            ("Scale".to_string(), "factor".to_string()),
+            ("Pick".to_string(), "a".to_string()),
+        ],
+    );
+}
+
+/// 親クラスを持つクラスのメソッド (override 修飾が任意の言語) と parameter property は
+/// 対象外。Python の docstring + raise だけの stub も同様。arrow function (callback) だけは
+/// 使用済み引数より前の引数を報告しない。
+#[test]
+fn unused_parameters_ts_and_python() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let repo = dir.path();
+    fs::write(
+        repo.join("web.ts"),
+        "export function register(app: App, verbose: boolean) {\n  app.start();\n}\n\nexport function mount(router: Router, app: App) {\n  app.start();\n}\n\nexport const handler = (req: Request, res: Response) => {\n  res.send(\"ok\");\n};\n\nexport class Service extends Base {\n  run(input: string) {\n    return 1;\n  }\n}\n\nexport class Plain {\n  constructor(private readonly repo: Repo) {}\n\n  load(id: string, cache: boolean) {\n    return this.repo.find(id);\n  }\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        repo.join("app.py"),
+        "class View(Base):\n    def get(self, request, pk):\n        return pk\n\n\ndef greet(name, loud):\n    return f\"hi {name}\"\n\n\ndef scale(factor, value):\n    return value\n\n\ndef later(value):\n    \"\"\"Not yet.\"\"\"\n    raise NotImplementedError\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+        unused_parameter_names(repo),
+        vec![
+            ("greet".to_string(), "loud".to_string()),
+            ("scale".to_string(), "factor".to_string()),
+            ("register".to_string(), "verbose".to_string()),
+            ("mount".to_string(), "router".to_string()),
+            ("Plain.load".to_string(), "cache".to_string()),
+        ],
+    );
+}
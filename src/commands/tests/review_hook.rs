@@ -24,22 +24,7 @@ use std::process::Command;
 fn build_review_hook_json_compatible_modified_is_informational() {
     let dir = tempfile::tempdir().expect("tempdir");
     let result = ReviewResult {
-        impact: crate::models::impact::ContextResult {
-            changes: Vec::new(),
-            skipped: None,
-            truncations: Vec::new(),
-        },
-        missing_cochanges: Vec::new(),
-        cochange_diagnostics: Default::default(),
         api_changes: ApiChanges {
-            added: Vec::new(),
-            removed: Vec::new(),
-            modified: Vec::new(),
-            moved: Vec::new(),
-            property_to_field: Vec::new(),
-            removed_dead: Vec::new(),
-            modified_closed_in_diff: Vec::new(),
-            const_value_changes: Vec::new(),
             compatible_modified: vec![CompatibleApiModification {
                 name: "ScheduleItem".to_string(),
                 kind: "constant".to_string(),
@@ -48,11 +33,9 @@ fn build_review_hook_json_compatible_modified_is_informational() {
                 new_signature: None,
                 reason: "react_component_wrapper".to_string(),
             }],
+            ..Default::default()
         },
-        dead_symbols: Vec::new(),
-        test_only_symbols: Vec::new(),
-        skipped: None,
-        truncations: Vec::new(),
+        ..Default::default()
     };
     let build = build_review_hook_json(&result, dir.path().to_str().expect("utf-8 path"), false);
     assert!(
@@ -83,37 +66,23 @@ fn build_review_hook_json_compatible_modified_impact_is_informational() {
         impact: crate::models::impact::ContextResult {
             changes: vec![crate::models::impact::FileImpact {
                 path: "TaskDetailHeader.tsx".to_string(),
-                hunks: Vec::new(),
                 affected_symbols: vec![crate::models::impact::AffectedSymbol {
                     name: "TaskDetailHeader".to_string(),
                     kind: "function".to_string(),
                     change_type: "modified".to_string(),
                 }],
-                signature_changes: Vec::new(),
                 impacted_callers: vec![crate::models::impact::ImpactedCaller {
                     path: "TaskDetailContent.tsx".to_string(),
                     name: "TaskDetailContent".to_string(),
                     line: 1,
                     symbols: vec!["TaskDetailHeader".to_string()],
-                    confidence: None,
+                    ..Default::default()
                 }],
-                low_confidence_callers: Vec::new(),
-                informational_callers: Vec::new(),
+                ..Default::default()
             }],
-            skipped: None,
-            truncations: Vec::new(),
+            ..Default::default()
         },
-        missing_cochanges: Vec::new(),
-        cochange_diagnostics: Default::default(),
         api_changes: ApiChanges {
-            added: Vec::new(),
-            removed: Vec::new(),
-            modified: Vec::new(),
-            moved: Vec::new(),
-            property_to_field: Vec::new(),
-            removed_dead: Vec::new(),
-            modified_closed_in_diff: Vec::new(),
-            const_value_changes: Vec::new(),
             compatible_modified: vec![CompatibleApiModification {
                 name: "TaskDetailHeader".to_string(),
                 kind: "function".to_string(),
@@ -124,11 +93,9 @@ fn build_review_hook_json_compatible_modified_impact_is_informational() {
                 ),
                 reason: "react_component_wrapper".to_string(),
             }],
+            ..Default::default()
         },
-        dead_symbols: Vec::new(),
-        test_only_symbols: Vec::new(),
-        skipped: None,
-        truncations: Vec::new(),
+        ..Default::default()
     };
 
     let build = build_review_hook_json(&result, dir.path().to_str().expect("utf-8 path"), false);
@@ -165,7 +132,6 @@ fn build_review_hook_json_mixed_compatible_and_breaking_impact_keeps_breaking_on
         impact: crate::models::impact::ContextResult {
             changes: vec![crate::models::impact::FileImpact {
                 path: "TaskDetailHeader.tsx".to_string(),
-                hunks: Vec::new(),
                 affected_symbols: vec![
                     crate::models::impact::AffectedSymbol {
                         name: "TaskDetailHeader".to_string(),
@@ -178,25 +144,18 @@ fn build_review_hook_json_mixed_compatible_and_breaking_impact_keeps_breaking_on
                         change_type: "modified".to_string(),
                     },
                 ],
-                signature_changes: Vec::new(),
                 impacted_callers: vec![crate::models::impact::ImpactedCaller {
                     path: "TaskDetailContent.tsx".to_string(),
                     name: "TaskDetailContent".to_string(),
                     line: 2,
                     symbols: vec!["TaskDetailHeader".to_string(), "loadTask".to_string()],
-                    confidence: None,
+                    ..Default::default()
                 }],
-                low_confidence_callers: Vec::new(),
-                informational_callers: Vec::new(),
+                ..Default::default()
             }],
-            skipped: None,
-            truncations: Vec::new(),
+            ..Default::default()
         },
-        missing_cochanges: Vec::new(),
-        cochange_diagnostics: Default::default(),
         api_changes: ApiChanges {
-            added: Vec::new(),
-            removed: Vec::new(),
             modified: vec![ApiSymbolChange {
                 name: "loadTask".to_string(),
                 kind: "function".to_string(),
@@ -207,11 +166,6 @@ fn build_review_hook_json_mixed_compatible_and_breaking_impact_keeps_breaking_on
                 ),
                 no_resolved_internal_callers: false,
             }],
-            moved: Vec::new(),
-            property_to_field: Vec::new(),
-            removed_dead: Vec::new(),
-            modified_closed_in_diff: Vec::new(),
-            const_value_changes: Vec::new(),
             compatible_modified: vec![CompatibleApiModification {
                 name: "TaskDetailHeader".to_string(),
                 kind: "function".to_string(),
@@ -222,11 +176,9 @@ fn build_review_hook_json_mixed_compatible_and_breaking_impact_keeps_breaking_on
                 ),
                 reason: "react_component_wrapper".to_string(),
             }],
+            ..Default::default()
         },
-        dead_symbols: Vec::new(),
-        test_only_symbols: Vec::new(),
-        skipped: None,
-        truncations: Vec::new(),
+        ..Default::default()
     };
 
     let build = build_review_hook_json(&result, dir.path().to_str().expect("utf-8 path"), false);
@@ -262,32 +214,13 @@ fn build_review_hook_json_cochange_only_is_informational() {
     let dir = tempfile::tempdir().expect("tempdir");
 
     let result = ReviewResult {
-        impact: crate::models::impact::ContextResult {
-            changes: Vec::new(),
-            skipped: None,
-            truncations: Vec::new(),
-        },
         missing_cochanges: vec![MissingCochange {
             file: "a.rs".to_string(),
             expected_with: "b.rs".to_string(),
             confidence: 0.9,
+            owners: Vec::new(),
         }],
-        cochange_diagnostics: Default::default(),
-        api_changes: ApiChanges {
-            added: Vec::new(),
-            removed: Vec::new(),
-            modified: Vec::new(),
-            moved: Vec::new(),
-            property_to_field: Vec::new(),
-            removed_dead: Vec::new(),
-            modified_closed_in_diff: Vec::new(),
-            const_value_changes: Vec::new(),
-            compatible_modified: Vec::new(),
-        },
-        dead_symbols: Vec::new(),
-        test_only_symbols: Vec::new(),
-        skipped: None,
-        truncations: Vec::new(),
+        ..Default::default()
     };
 
     let build = build_review_hook_json(&result, dir.path().to_str().expect("utf-8 path"), false);
@@ -322,43 +255,24 @@ fn build_review_hook_json_impact_info_only_is_informational() {
         impact: crate::models::impact::ContextResult {
             changes: vec![crate::models::impact::FileImpact {
                 path: "src/lib.ts".to_string(),
-                hunks: Vec::new(),
                 affected_symbols: vec![crate::models::impact::AffectedSymbol {
                     name: "compute".to_string(),
                     kind: "function".to_string(),
                     change_type: "modified".to_string(),
                 }],
-                signature_changes: Vec::new(),
-                impacted_callers: Vec::new(),
-                low_confidence_callers: Vec::new(),
                 informational_callers: vec![crate::models::impact::ImpactedCaller {
                     path: "src/consumer.ts".to_string(),
                     name: "compute".to_string(),
                     line: 1,
                     symbols: vec!["compute".to_string()],
                     confidence: Some("informational".to_string()),
+                    ..Default::default()
                 }],
+                ..Default::default()
             }],
-            skipped: None,
-            truncations: Vec::new(),
-        },
-        missing_cochanges: Vec::new(),
-        cochange_diagnostics: Default::default(),
-        api_changes: ApiChanges {
-            added: Vec::new(),
-            removed: Vec::new(),
-            modified: Vec::new(),
-            moved: Vec::new(),
-            property_to_field: Vec::new(),
-            removed_dead: Vec::new(),
-            modified_closed_in_diff: Vec::new(),
-            const_value_changes: Vec::new(),
-            compatible_modified: Vec::new(),
+            ..Default::default()
         },
-        dead_symbols: Vec::new(),
-        test_only_symbols: Vec::new(),
-        skipped: None,
-        truncations: Vec::new(),
+        ..Default::default()
     };
 
     let build = build_review_hook_json(&result, dir.path().to_str().expect("utf-8 path"), false);
@@ -383,13 +297,6 @@ fn build_review_hook_json_api_add_only_is_informational() {
     let dir = tempfile::tempdir().expect("tempdir");
 
     let result = ReviewResult {
-        impact: crate::models::impact::ContextResult {
-            changes: Vec::new(),
-            skipped: None,
-            truncations: Vec::new(),
-        },
-        missing_cochanges: Vec::new(),
-        cochange_diagnostics: Default::default(),
         api_changes: ApiChanges {
             added: vec![ApiSymbol {
                 name: "foo".to_string(),
@@ -397,19 +304,9 @@ fn build_review_hook_json_api_add_only_is_informational() {
                 file: "a.rs".to_string(),
                 refs_internal: 0,
             }],
-            removed: Vec::new(),
-            modified: Vec::new(),
-            moved: Vec::new(),
-            property_to_field: Vec::new(),
-            removed_dead: Vec::new(),
-            modified_closed_in_diff: Vec::new(),
-            const_value_changes: Vec::new(),
-            compatible_modified: Vec::new(),
+            ..Default::default()
         },
-        dead_symbols: Vec::new(),
-        test_only_symbols: Vec::new(),
-        skipped: None,
-        truncations: Vec::new(),
+        ..Default::default()
     };
 
     let build = build_review_hook_json(&result, dir.path().to_str().expect("utf-8 path"), false);
@@ -426,30 +323,9 @@ fn build_review_hook_json_api_add_only_is_informational() {
 #[test]
 fn build_review_hook_json_api_add_carries_extraction_scope() {
     let dir = tempfile::tempdir().expect("tempdir");
-    let empty_api = || ApiChanges {
-        added: Vec::new(),
-        removed: Vec::new(),
-        modified: Vec::new(),
-        moved: Vec::new(),
-        property_to_field: Vec::new(),
-        removed_dead: Vec::new(),
-        modified_closed_in_diff: Vec::new(),
-        const_value_changes: Vec::new(),
-        compatible_modified: Vec::new(),
-    };
     let review_with = |api_changes: ApiChanges| ReviewResult {
-        impact: crate::models::impact::ContextResult {
-            changes: Vec::new(),
-            skipped: None,
-            truncations: Vec::new(),
-        },
-        missing_cochanges: Vec::new(),
-        cochange_diagnostics: Default::default(),
         api_changes,
-        dead_symbols: Vec::new(),
-        test_only_symbols: Vec::new(),
-        skipped: None,
-        truncations: Vec::new(),
+        ..Default::default()
     };
 
     let with_add = review_with(ApiChanges {
@@ -459,7 +335,7 @@ fn build_review_hook_json_api_add_carries_extraction_scope() {
             file: "a.rs".to_string(),
             refs_internal: 0,
         }],
-        ..empty_api()
+        ..Default::default()
     });
     let build = build_review_hook_json(&with_add, dir.path().to_str().expect("utf-8 path"), false);
     let api = build.value.expect("hook JSON")["api"].clone();
@@ -476,7 +352,7 @@ fn build_review_hook_json_api_add_carries_extraction_scope() {
             file: "a.rs".to_string(),
             refs_internal: 0,
         }],
-        ..empty_api()
+        ..Default::default()
     });
     let build = build_review_hook_json(
         &without_add,
@@ -498,21 +374,11 @@ fn build_review_hook_json_api_add_carries_extraction_scope() {
 fn build_review_hook_json_api_add_carries_internal_ref_count() {
     let dir = tempfile::tempdir().expect("tempdir");
     let review_with_added = |added: Vec<ApiSymbol>| ReviewResult {
-        impact: crate::models::impact::ContextResult {
-            changes: Vec::new(),
-            skipped: None,
-            truncations: Vec::new(),
-        },
-        missing_cochanges: Vec::new(),
-        cochange_diagnostics: Default::default(),
         api_changes: ApiChanges {
             added,
             ..Default::default()
         },
-        dead_symbols: Vec::new(),
-        test_only_symbols: Vec::new(),
-        skipped: None,
-        truncations: Vec::new(),
+        ..Default::default()
     };
 
     // 同一ファイル内に実利用参照が 2 件 → `ri: 2` を出して refs 再実行を不要にする
@@ -594,39 +460,54 @@ fn build_review_hook_json_reports_truncations_without_blocking() {
     );
 }
 
+/// 重複コード (既存コードのコピー) は hook 出力の `dup` に載るが blocking にはしない。
+/// 共通化前の一時的なコピー等、意図的な複製もあり得るため。
+#[test]
+fn build_review_hook_json_reports_duplicated_code_without_blocking() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let result = ReviewResult {
+        unused_parameters: Vec::new(),
+        cargo_features: Vec::new(),
+        duplicated_code: vec![crate::models::dupes::DuplicatedCode {
+            file: "src/new.rs".to_string(),
+            start_line: 10,
+            end_line: 30,
+            tokens: 80,
+            similarity: 0.9,
+            duplicates: vec![crate::models::dupes::CloneInstance {
+                file: "src/old.rs".to_string(),
+                start_line: 4,
+                end_line: 24,
+            }],
+        }],
+        ..Default::default()
+    };
+
+    let build = build_review_hook_json(&result, dir.path().to_str().expect("utf-8 path"), false);
+    let value = build.value.expect("重複コードは hook 出力に載せるべき");
+    assert_eq!(value["dup"][0]["f"], "src/new.rs", "{value}");
+    assert_eq!(value["dup"][0]["l"], 10, "{value}");
+    assert_eq!(value["dup"][0]["w"], "src/old.rs", "{value}");
+    assert_eq!(value["dup"][0]["wl"], 4, "{value}");
+    assert!(!build.is_blocking, "重複コードは informational");
+}
+
 /// api.removed は破壊的変更の可能性があるため blocking になる
 #[test]
 fn build_review_hook_json_api_removed_is_blocking() {
     let dir = tempfile::tempdir().expect("tempdir");
 
     let result = ReviewResult {
-        impact: crate::models::impact::ContextResult {
-            changes: Vec::new(),
-            skipped: None,
-            truncations: Vec::new(),
-        },
-        missing_cochanges: Vec::new(),
-        cochange_diagnostics: Default::default(),
         api_changes: ApiChanges {
-            added: Vec::new(),
             removed: vec![ApiSymbol {
                 name: "foo".to_string(),
                 kind: "function".to_string(),
                 file: "a.rs".to_string(),
                 refs_internal: 0,
             }],
-            modified: Vec::new(),
-            moved: Vec::new(),
-            property_to_field: Vec::new(),
-            removed_dead: Vec::new(),
-            modified_closed_in_diff: Vec::new(),
-            const_value_changes: Vec::new(),
-            compatible_modified: Vec::new(),
+            ..Default::default()
         },
-        dead_symbols: Vec::new(),
-        test_only_symbols: Vec::new(),
-        skipped: None,
-        truncations: Vec::new(),
+        ..Default::default()
     };
 
     let build = build_review_hook_json(&result, dir.path().to_str().expect("utf-8 path"), false);
@@ -640,16 +521,7 @@ fn build_review_hook_json_api_modified_is_blocking() {
     let dir = tempfile::tempdir().expect("tempdir");
 
     let result = ReviewResult {
-        impact: crate::models::impact::ContextResult {
-            changes: Vec::new(),
-            skipped: None,
-            truncations: Vec::new(),
-        },
-        missing_cochanges: Vec::new(),
-        cochange_diagnostics: Default::default(),
         api_changes: ApiChanges {
-            added: Vec::new(),
-            removed: Vec::new(),
             modified: vec![ApiSymbolChange {
                 name: "foo".to_string(),
                 kind: "function".to_string(),
@@ -658,17 +530,9 @@ fn build_review_hook_json_api_modified_is_blocking() {
                 new_signature: Some("fn foo(x: u32)".to_string()),
                 no_resolved_internal_callers: false,
             }],
-            moved: Vec::new(),
-            property_to_field: Vec::new(),
-            removed_dead: Vec::new(),
-            modified_closed_in_diff: Vec::new(),
-            const_value_changes: Vec::new(),
-            compatible_modified: Vec::new(),
+            ..Default::default()
         },
-        dead_symbols: Vec::new(),
-        test_only_symbols: Vec::new(),
-        skipped: None,
-        truncations: Vec::new(),
+        ..Default::default()
     };
 
     let build = build_review_hook_json(&result, dir.path().to_str().expect("utf-8 path"), false);
@@ -688,16 +552,7 @@ fn build_review_hook_json_api_modified_without_callers_is_flagged_but_still_bloc
     let dir = tempfile::tempdir().expect("tempdir");
 
     let result = ReviewResult {
-        impact: crate::models::impact::ContextResult {
-            changes: Vec::new(),
-            skipped: None,
-            truncations: Vec::new(),
-        },
-        missing_cochanges: Vec::new(),
-        cochange_diagnostics: Default::default(),
         api_changes: ApiChanges {
-            added: Vec::new(),
-            removed: Vec::new(),
             modified: vec![ApiSymbolChange {
                 name: "foo".to_string(),
                 kind: "function".to_string(),
@@ -706,17 +561,9 @@ fn build_review_hook_json_api_modified_without_callers_is_flagged_but_still_bloc
                 new_signature: Some("fn foo(x: u32)".to_string()),
                 no_resolved_internal_callers: true,
             }],
-            moved: Vec::new(),
-            property_to_field: Vec::new(),
-            removed_dead: Vec::new(),
-            modified_closed_in_diff: Vec::new(),
-            const_value_changes: Vec::new(),
-            compatible_modified: Vec::new(),
+            ..Default::default()
         },
-        dead_symbols: Vec::new(),
-        test_only_symbols: Vec::new(),
-        skipped: None,
-        truncations: Vec::new(),
+        ..Default::default()
     };
 
     let build = build_review_hook_json(&result, dir.path().to_str().expect("utf-8 path"), false);
@@ -741,19 +588,7 @@ fn build_review_hook_json_api_modified_without_callers_is_flagged_but_still_bloc
 fn build_review_hook_json_removed_dead_only_is_not_blocking() {
     let dir = tempfile::tempdir().expect("tempdir");
     let result = ReviewResult {
-        impact: crate::models::impact::ContextResult {
-            changes: Vec::new(),
-            skipped: None,
-            truncations: Vec::new(),
-        },
-        missing_cochanges: Vec::new(),
-        cochange_diagnostics: Default::default(),
         api_changes: ApiChanges {
-            added: Vec::new(),
-            removed: Vec::new(),
-            modified: Vec::new(),
-            moved: Vec::new(),
-            property_to_field: Vec::new(),
             removed_dead: vec![
                 ApiSymbol {
                     name: "MapZoomUtils".to_string(),
@@ -768,14 +603,9 @@ fn build_review_hook_json_removed_dead_only_is_not_blocking() {
                     refs_internal: 0,
                 },
             ],
-            modified_closed_in_diff: Vec::new(),
-            const_value_changes: Vec::new(),
-            compatible_modified: Vec::new(),
+            ..Default::default()
         },
-        dead_symbols: Vec::new(),
-        test_only_symbols: Vec::new(),
-        skipped: None,
-        truncations: Vec::new(),
+        ..Default::default()
     };
 
     let build = build_review_hook_json(&result, dir.path().to_str().expect("utf-8 path"), false);
@@ -793,21 +623,7 @@ fn build_review_hook_json_removed_dead_only_is_not_blocking() {
 fn build_review_hook_json_const_value_only_is_informational() {
     let dir = tempfile::tempdir().expect("tempdir");
     let result = ReviewResult {
-        impact: crate::models::impact::ContextResult {
-            changes: Vec::new(),
-            skipped: None,
-            truncations: Vec::new(),
-        },
-        missing_cochanges: Vec::new(),
-        cochange_diagnostics: Default::default(),
         api_changes: ApiChanges {
-            added: Vec::new(),
-            removed: Vec::new(),
-            modified: Vec::new(),
-            moved: Vec::new(),
-            property_to_field: Vec::new(),
-            removed_dead: Vec::new(),
-            modified_closed_in_diff: Vec::new(),
             const_value_changes: vec![ApiSymbolChange {
                 name: "ENEMY_SPEED".to_string(),
                 kind: "constant".to_string(),
@@ -816,12 +632,9 @@ fn build_review_hook_json_const_value_only_is_informational() {
                 new_signature: Some("pub const ENEMY_SPEED: f32".to_string()),
                 no_resolved_internal_callers: false,
             }],
-            compatible_modified: Vec::new(),
+            ..Default::default()
         },
-        dead_symbols: Vec::new(),
-        test_only_symbols: Vec::new(),
-        skipped: None,
-        truncations: Vec::new(),
+        ..Default::default()
     };
     let build = build_review_hook_json(&result, dir.path().to_str().expect("utf-8 path"), false);
     assert!(
@@ -838,21 +651,7 @@ fn build_review_hook_json_const_value_only_is_informational() {
 fn build_review_hook_json_const_value_is_blocking_under_strict() {
     let dir = tempfile::tempdir().expect("tempdir");
     let result = ReviewResult {
-        impact: crate::models::impact::ContextResult {
-            changes: Vec::new(),
-            skipped: None,
-            truncations: Vec::new(),
-        },
-        missing_cochanges: Vec::new(),
-        cochange_diagnostics: Default::default(),
         api_changes: ApiChanges {
-            added: Vec::new(),
-            removed: Vec::new(),
-            modified: Vec::new(),
-            moved: Vec::new(),
-            property_to_field: Vec::new(),
-            removed_dead: Vec::new(),
-            modified_closed_in_diff: Vec::new(),
             const_value_changes: vec![ApiSymbolChange {
                 name: "ENEMY_SPEED".to_string(),
                 kind: "constant".to_string(),
@@ -861,12 +660,9 @@ fn build_review_hook_json_const_value_is_blocking_under_strict() {
                 new_signature: Some("pub const ENEMY_SPEED: f32".to_string()),
                 no_resolved_internal_callers: false,
             }],
-            compatible_modified: Vec::new(),
+            ..Default::default()
         },
-        dead_symbols: Vec::new(),
-        test_only_symbols: Vec::new(),
-        skipped: None,
-        truncations: Vec::new(),
+        ..Default::default()
     };
     let build = build_review_hook_json(&result, dir.path().to_str().expect("utf-8 path"), true);
     assert!(
@@ -887,13 +683,11 @@ fn build_review_hook_json_uses_changed_symbols_in_summary() {
         impact: crate::models::impact::ContextResult {
             changes: vec![crate::models::impact::FileImpact {
                 path: "src/lib.rs".to_string(),
-                hunks: Vec::new(),
                 affected_symbols: vec![crate::models::impact::AffectedSymbol {
                     name: "compute".to_string(),
                     kind: "function".to_string(),
                     change_type: "modified".to_string(),
                 }],
-                signature_changes: Vec::new(),
                 impacted_callers: vec![crate::models::impact::ImpactedCaller {
                     path: "src/main.rs".to_string(),
                     name: "main".to_string(),
@@ -902,31 +696,13 @@ fn build_review_hook_json_uses_changed_symbols_in_summary() {
                     // シンボル名」(pass3.rs::build_file_impact の構築意図)。
                     // 呼び出し元関数の名前は ImpactedCaller.name 側に入る。
                     symbols: vec!["compute".to_string()],
-                    confidence: None,
+                    ..Default::default()
                 }],
-                low_confidence_callers: Vec::new(),
-                informational_callers: Vec::new(),
+                ..Default::default()
             }],
-            skipped: None,
-            truncations: Vec::new(),
-        },
-        missing_cochanges: Vec::new(),
-        cochange_diagnostics: Default::default(),
-        api_changes: ApiChanges {
-            added: Vec::new(),
-            removed: Vec::new(),
-            modified: Vec::new(),
-            moved: Vec::new(),
-            property_to_field: Vec::new(),
-            removed_dead: Vec::new(),
-            modified_closed_in_diff: Vec::new(),
-            const_value_changes: Vec::new(),
-            compatible_modified: Vec::new(),
+            ..Default::default()
         },
-        dead_symbols: Vec::new(),
-        test_only_symbols: Vec::new(),
-        skipped: None,
-        truncations: Vec::new(),
+        ..Default::default()
     };
 
     let build = build_review_hook_json(&result, dir.path().to_str().expect("utf-8 path"), false);
@@ -959,7 +735,6 @@ fn build_review_hook_json_filters_non_causal_affected_symbols_from_syms() {
         impact: crate::models::impact::ContextResult {
             changes: vec![crate::models::impact::FileImpact {
                 path: "src/a.rs".to_string(),
-                hunks: Vec::new(),
                 affected_symbols: vec![
                     crate::models::impact::AffectedSymbol {
                         name: "foo".to_string(),
@@ -977,37 +752,18 @@ fn build_review_hook_json_filters_non_causal_affected_symbols_from_syms() {
                         change_type: "modified".to_string(),
                     },
                 ],
-                signature_changes: Vec::new(),
                 impacted_callers: vec![crate::models::impact::ImpactedCaller {
                     path: "src/b.rs".to_string(),
                     name: "caller".to_string(),
                     line: 1,
                     symbols: vec!["foo".to_string()],
-                    confidence: None,
+                    ..Default::default()
                 }],
-                low_confidence_callers: Vec::new(),
-                informational_callers: Vec::new(),
+                ..Default::default()
             }],
-            skipped: None,
-            truncations: Vec::new(),
-        },
-        missing_cochanges: Vec::new(),
-        cochange_diagnostics: Default::default(),
-        api_changes: ApiChanges {
-            added: Vec::new(),
-            removed: Vec::new(),
-            modified: Vec::new(),
-            moved: Vec::new(),
-            property_to_field: Vec::new(),
-            removed_dead: Vec::new(),
-            modified_closed_in_diff: Vec::new(),
-            const_value_changes: Vec::new(),
-            compatible_modified: Vec::new(),
+            ..Default::default()
         },
-        dead_symbols: Vec::new(),
-        test_only_symbols: Vec::new(),
-        skipped: None,
-        truncations: Vec::new(),
+        ..Default::default()
     };
 
     let build = build_review_hook_json(&result, dir.path().to_str().expect("utf-8 path"), false);
@@ -1046,43 +802,23 @@ fn build_review_hook_json_added_only_caller_is_not_blocking() {
         impact: crate::models::impact::ContextResult {
             changes: vec![crate::models::impact::FileImpact {
                 path: "src/constants.rs".to_string(),
-                hunks: Vec::new(),
                 affected_symbols: vec![crate::models::impact::AffectedSymbol {
                     name: "FOO".to_string(),
                     kind: "constant".to_string(),
                     change_type: "added".to_string(),
                 }],
-                signature_changes: Vec::new(),
                 impacted_callers: vec![crate::models::impact::ImpactedCaller {
                     path: "src/user.rs".to_string(),
                     name: "x".to_string(),
                     line: 1,
                     symbols: vec!["FOO".to_string()],
-                    confidence: None,
+                    ..Default::default()
                 }],
-                low_confidence_callers: Vec::new(),
-                informational_callers: Vec::new(),
+                ..Default::default()
             }],
-            skipped: None,
-            truncations: Vec::new(),
-        },
-        missing_cochanges: Vec::new(),
-        cochange_diagnostics: Default::default(),
-        api_changes: ApiChanges {
-            added: Vec::new(),
-            removed: Vec::new(),
-            modified: Vec::new(),
-            moved: Vec::new(),
-            property_to_field: Vec::new(),
-            removed_dead: Vec::new(),
-            modified_closed_in_diff: Vec::new(),
-            const_value_changes: Vec::new(),
-            compatible_modified: Vec::new(),
+            ..Default::default()
         },
-        dead_symbols: Vec::new(),
-        test_only_symbols: Vec::new(),
-        skipped: None,
-        truncations: Vec::new(),
+        ..Default::default()
     };
 
     let build = build_review_hook_json(&result, dir.path().to_str().unwrap(), false);
@@ -1125,7 +861,6 @@ fn build_review_hook_json_mixed_added_and_modified_keeps_only_modified() {
         impact: crate::models::impact::ContextResult {
             changes: vec![crate::models::impact::FileImpact {
                 path: "src/a.rs".to_string(),
-                hunks: Vec::new(),
                 affected_symbols: vec![
                     crate::models::impact::AffectedSymbol {
                         name: "modified_fn".to_string(),
@@ -1138,37 +873,18 @@ fn build_review_hook_json_mixed_added_and_modified_keeps_only_modified() {
                         change_type: "added".to_string(),
                     },
                 ],
-                signature_changes: Vec::new(),
                 impacted_callers: vec![crate::models::impact::ImpactedCaller {
                     path: "src/b.rs".to_string(),
                     name: "caller".to_string(),
                     line: 1,
                     symbols: vec!["modified_fn".to_string(), "NEW_CONST".to_string()],
-                    confidence: None,
+                    ..Default::default()
                 }],
-                low_confidence_callers: Vec::new(),
-                informational_callers: Vec::new(),
+                ..Default::default()
             }],
-            skipped: None,
-            truncations: Vec::new(),
-        },
-        missing_cochanges: Vec::new(),
-        cochange_diagnostics: Default::default(),
-        api_changes: ApiChanges {
-            added: Vec::new(),
-            removed: Vec::new(),
-            modified: Vec::new(),
-            moved: Vec::new(),
-            property_to_field: Vec::new(),
-            removed_dead: Vec::new(),
-            modified_closed_in_diff: Vec::new(),
-            const_value_changes: Vec::new(),
-            compatible_modified: Vec::new(),
+            ..Default::default()
         },
-        dead_symbols: Vec::new(),
-        test_only_symbols: Vec::new(),
-        skipped: None,
-        truncations: Vec::new(),
+        ..Default::default()
     };
 
     let build = build_review_hook_json(&result, dir.path().to_str().unwrap(), false);
@@ -1188,3 +904,79 @@ fn build_review_hook_json_mixed_added_and_modified_keeps_only_modified() {
         "refs[].s も modified_fn のみに絞られるべき"
     );
 }
+
+/// `--risk-threshold`: スコアが閾値以上なら `risk` を載せて blocking、未満なら hook 出力は
+/// 変えない。他の検出が無くても閾値到達だけで止める。
+#[test]
+fn apply_risk_threshold_blocks_only_at_or_above_threshold() {
+    use crate::models::review::{FileRisk, ReviewRisk, RiskFactor};
+
+    let dir = tempfile::tempdir().expect("tempdir");
+    let clean = ReviewResult::default();
+    let risk = ReviewRisk {
+        score: 40.0,
+        factors: Vec::new(),
+        files: vec![FileRisk {
+            path: "src/lib.rs".to_string(),
+            score: 40.0,
+            factors: vec![RiskFactor {
+                factor: "api_breaks".to_string(),
+                value: 4.0,
+                contribution: 40.0,
+            }],
+        }],
+    };
+    let build_for = |threshold: Option<f64>| {
+        apply_risk_threshold(
+            build_review_hook_json(&clean, dir.path().to_str().expect("utf-8 path"), false),
+            Some(&risk),
+            threshold,
+        )
+    };
+
+    let below = build_for(Some(40.5));
+    assert!(below.value.is_none() && !below.is_blocking);
+    let unset = build_for(None);
+    assert!(unset.value.is_none() && !unset.is_blocking);
+
+    let reached = build_for(Some(40.0));
+    assert!(reached.is_blocking);
+    let value = reached.value.expect("閾値到達時は hook 出力を出すべき");
+    assert_eq!(value["risk"]["score"], 40.0);
+    assert_eq!(value["risk"]["th"], 40.0);
+    assert_eq!(value["risk"]["top"][0]["f"], "src/lib.rs");
+    assert!(value.get("hint").is_some(), "{value}");
+}
+
+/// Cargo feature の食い違いは hook 出力の `cargo_features` に載るが blocking にはしない。
+/// feature は依存側の指定や CI の `--features` など crate の外から使われ得るため。
+#[test]
+fn build_review_hook_json_reports_cargo_features_without_blocking() {
+    use crate::models::review::{CargoFeatureIssue, CargoFeatureIssueKind};
+
+    let dir = tempfile::tempdir().expect("tempdir");
+    let result = ReviewResult {
+        cargo_features: vec![CargoFeatureIssue {
+            kind: CargoFeatureIssueKind::Undeclared,
+            feature: "sdt".to_string(),
+            file: "src/lib.rs".to_string(),
+            line: 3,
+            manifest: "Cargo.toml".to_string(),
+        }],
+        ..Default::default()
+    };
+
+    let build = build_review_hook_json(&result, dir.path().to_str().expect("utf-8 path"), false);
+    let value = build
+        .value
+        .expect("feature の食い違いだけでも hook 出力を出すべき");
+    assert_eq!(
+        value["cargo_features"],
+        serde_json::json!([{ "k": "undeclared", "n": "sdt", "f": "src/lib.rs", "l": 3 }]),
+        "{value}"
+    );
+    assert!(
+        !build.is_blocking,
+        "feature の食い違いは Stop hook を止めない"
+    );
+}
@@ -0,0 +1,150 @@
+use anyhow::Result;
+use camino::Utf8Path;
+use rayon::prelude::*;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+use crate::engine::parser;
+use crate::engine::paths::relative_path;
+use crate::engine::unused_imports::find_unused_imports;
+use crate::error::{AstroError, ErrorCode};
+use crate::models::import::{UnusedImport, UnusedImportsResult};
+use crate::output::{OutputOptions, serialize_cli_document};
+
+use super::common::timed;
+use super::dead_code::{filter_diff_files_for_dead_code, resolve_dead_code_excludes};
+use super::git_input::{DiffSourceResolution, resolve_diff_source};
+
+/// `cmd_unused_imports` の引数一式。
+pub struct CmdUnusedImportsOpts<'a> {
+    pub dir: &'a str,
+    /// 単一ファイルモード。指定時は dir 走査・diff を使わない。
+    pub path: Option<&'a str>,
+    pub glob: Option<&'a str>,
+    pub diff: Option<&'a str>,
+    pub diff_file: Option<&'a str>,
+    pub git: bool,
+    pub base: &'a str,
+    pub staged: bool,
+    pub extra_exclude_dirs: &'a [String],
+    pub extra_exclude_globs: &'a [String],
+    pub output: OutputOptions,
+}
+
+/// ファイル内で参照されていない import 名を報告する。
+///
+/// `--path` は 1 ファイル、diff 指定時は changed file、それ以外は dir 全体を対象にする。
+/// 判定はファイル単位で閉じるため、参照探索のワークスペース走査は行わない。
+pub fn cmd_unused_imports(opts: &CmdUnusedImportsOpts<'_>) -> Result<()> {
+    let canonical_dir = std::fs::canonicalize(opts.dir)?;
+    if !canonical_dir.is_dir() {
+        return Err(AstroError::new(
+            ErrorCode::InvalidRequest,
+            format!("Not a directory: {}", opts.dir),
+        )
+        .into());
+    }
+    let mut result = UnusedImportsResult {
+        dir: canonical_dir.to_string_lossy().to_string(),
+        ..Default::default()
+    };
+
+    let files: Vec<PathBuf> = if let Some(path) = opts.path {
+        let path = Path::new(path);
+        if !path.is_file() {
+            return Err(AstroError::file_not_found(&path.to_string_lossy()).into());
+        }
+        let path = std::fs::canonicalize(path)?;
+        // 出力の `file` は `--dir` 相対なので、その外のファイルは受け付けない。
+        if !path.starts_with(&canonical_dir) {
+            return Err(AstroError::new(
+                ErrorCode::InvalidRequest,
+                format!(
+                    "--path must be inside --dir: {} is not under {}",
+                    path.display(),
+                    canonical_dir.display()
+                ),
+            )
+            .into());
+        }
+        vec![path]
+    } else {
+        let mut excludes = resolve_dead_code_excludes(false, true, false);
+        excludes.extend(opts.extra_exclude_dirs.iter().map(String::as_str));
+        let globs: Vec<&str> = opts
+            .extra_exclude_globs
+            .iter()
+            .map(String::as_str)
+            .collect();
+        match resolve_diff_source(
+            opts.dir,
+            opts.diff,
+            opts.diff_file,
+            opts.git,
+            opts.base,
+            opts.staged,
+        )? {
+            DiffSourceResolution::Diff { diff, truncations } => {
+                result.truncations = truncations;
+                let diff_files = crate::engine::diff::parse_unified_diff(&diff);
+                filter_diff_files_for_dead_code(
+                    &canonical_dir,
+                    &diff_files,
+                    &excludes,
+                    &globs,
+                    opts.glob,
+                )?
+            }
+            DiffSourceResolution::Skipped(skip) => {
+                result.skipped = Some(skip);
+                print!("{}", serialize_cli_document(&result, opts.output)?);
+                return Ok(());
+            }
+            DiffSourceResolution::NotRequested => crate::engine::refs::collect_files_with_excludes(
+                &canonical_dir,
+                opts.glob,
+                &excludes,
+                &globs,
+            )?,
+        }
+    };
+
+    let mut unused: Vec<UnusedImport> = timed("unused_imports", || {
+        files
+            .par_iter()
+            .filter_map(|path| analyze_file(&canonical_dir, path))
+            .flatten()
+            .collect()
+    });
+    unused.sort_by(|a, b| {
+        a.file
+            .cmp(&b.file)
+            .then_with(|| a.span.start.line.cmp(&b.span.start.line))
+            .then_with(|| a.span.start.column.cmp(&b.span.start.column))
+    });
+    result.scanned_files = files.len();
+    result.unused_imports = unused;
+
+    let text = serialize_cli_document(&result, opts.output)?;
+    info!(
+        command = "unused-imports",
+        dir = opts.dir,
+        scanned_files = result.scanned_files,
+        unused_count = result.unused_imports.len(),
+        "command completed"
+    );
+    print!("{text}");
+    Ok(())
+}
+
+fn analyze_file(dir: &Path, path: &Path) -> Option<Vec<UnusedImport>> {
+    let utf8 = Utf8Path::from_path(path)?;
+    let source = parser::read_file(utf8).ok()?;
+    let lang = parser::detect_lang(utf8, &source).ok()?;
+    if lang.is_lexer_only() {
+        return None;
+    }
+    let tree = parser::parse_source(&source, lang).ok()?;
+    let file = relative_path(dir, path)?;
+    Some(find_unused_imports(tree.root_node(), &source, lang, &file))
+}
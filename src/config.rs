@@ -7,6 +7,17 @@ use std::path::{Path, PathBuf};
 
 use crate::output::OutputFormat;
 
+mod framework_preset;
+mod project;
+
+pub use framework_preset::{
+    FrameworkPreset, PRESET_DIR, discover_framework_presets, find_framework_preset,
+};
+pub use project::{
+    CochangeSettings, ConfigShow, HookSettings, LintSettings, PROJECT_CONFIG_FILE, ProjectConfig,
+    ProjectSettings, ReviewSettings,
+};
+
 /// 実行時に使う設定。
 #[derive(Debug, Clone, Deserialize)]
 #[serde(default)]
@@ -20,6 +31,10 @@ pub struct Config {
     /// 既定の出力フォーマット (`json` / `toon` / `auto`)。
     /// CLI の `--format` が指定されればそちらが優先される。
     pub format: OutputFormat,
+
+    /// コマンドオプションの既定値 (project 設定と同じキー)。project 設定に無いキーだけが使われる。
+    #[serde(flatten)]
+    pub settings: ProjectSettings,
 }
 
 impl Default for Config {
@@ -28,6 +43,7 @@ impl Default for Config {
             debug: false,
             log_path: default_log_path(),
             format: OutputFormat::Json,
+            settings: ProjectSettings::default(),
         }
     }
 }
@@ -37,6 +53,8 @@ struct RawConfig {
     debug: Option<bool>,
     log_path: Option<PathBuf>,
     format: Option<OutputFormat>,
+    #[serde(flatten)]
+    settings: ProjectSettings,
 }
 
 /// デフォルトのログ出力先: ~/.config/astro-sight/logs
@@ -107,10 +125,16 @@ impl ConfigService {
                 .unwrap_or_else(default_log_path),
         };
 
+        let mut settings = raw.settings;
+        if let Some(dir) = config_dir {
+            settings.resolve_paths(dir);
+        }
+
         Ok(Config {
             debug: raw.debug.unwrap_or(false),
             log_path,
             format: raw.format.unwrap_or_default(),
+            settings,
         })
     }
 
@@ -145,15 +169,43 @@ debug = false
 # ログディレクトリのパス (デフォルト: ~/.config/astro-sight/logs)
 # log_path = "~/.config/astro-sight/logs"
 
-# 既定の出力フォーマット: "json" | "toon" | "auto" (デフォルト: json)
+# 既定の出力フォーマット: "json" | "toon" | "auto" | "markdown" | "codeclimate" (デフォルト: json)
 # toon = Token-Oriented Object Notation v4.1 (https://toonformat.dev/)。
 #        同じ内容を少ないトークン数で表現でき、LLM へ渡す用途に向く。
 # auto = json と toon のうち、その出力で推定トークン数が小さい方を自動で選ぶ
 #        (文字数 + 行数罰則。BPE では改行とインデントが 1 行あたり 1 トークンほど掛かる)。
+# markdown = PR コメント向けの Markdown レポート (review のみ。他コマンドでは json になる)。
+# codeclimate = GitLab Code Quality 向けの issue 配列 (review / dead-code / lint のみ。他コマンドでは json)。
 # CLI の --format はこの設定より優先される。
 # session / review --hook / impact --hook / エラー出力は行指向 JSON の契約が
 # あるため、この設定に関わらず常に JSON。
 format = "json"
+
+# 以下はコマンドオプションの既定値。リポジトリルートの .astro-sight.toml にも同じキーを書け、
+# 優先順位は CLI > .astro-sight.toml > この設定 > 組み込み既定値。
+# `astro-sight config show --effective` で最終的な値と由来を確認できる。
+# exclude_dirs = ["generated"]
+# exclude_globs = ["app/Legacy/**"]
+# framework = "laravel"
+#
+# [lint]
+# rules_dir = "~/.config/astro-sight/rules"
+#
+# [cochange]
+# min_confidence = 0.3
+# min_samples = 2
+#
+# [review]
+# dead_scope = "all"
+# strict_public_const_values = false
+# dupes = true
+#
+# [hook]
+# dead_scope = "touched-symbols"
+# strict_public_const_values = false
+# include_wip_dead = false
+# risk_threshold = 40.0
+# dupes = false
 "#
         .to_string()
     }
@@ -0,0 +1,231 @@
+//! ユーザー定義のフレームワークプリセット (YAML)。
+//!
+//! 組み込みプリセット (`laravel` / `nextjs` / `rails` / `spring`) に無い社内フレームワーク用に、
+//! dead-code のエントリポイント規約をリポジトリの `.astro-sight/presets/<name>.yaml` または
+//! ユーザー設定ディレクトリの `presets/<name>.yaml` に書けるようにする。
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::ConfigService;
+
+/// リポジトリ内のプリセット置き場 (対象ディレクトリから親方向に探す)。
+pub const PRESET_DIR: &str = ".astro-sight/presets";
+
+/// 1 ファイル分のプリセット定義。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FrameworkPreset {
+    /// `--framework` で選ぶ名前。省略時はファイル名 (拡張子を除く)。
+    pub name: String,
+    /// 自動検出に使うファイル (対象ディレクトリ相対)。いずれかが存在すれば適用する。
+    pub markers: Vec<String>,
+    /// dead-code の走査から外すエントリファイルの glob。
+    pub entry_globs: Vec<String>,
+    /// 付いている宣言をエントリポイントとみなす annotation / decorator 名。
+    pub annotations: Vec<String>,
+    /// 継承したクラスとそのメソッドを実行時に呼ばれるとみなす基底クラス名。
+    pub base_classes: Vec<String>,
+    /// 実行時に呼ばれるとみなすシンボル名の正規表現 (部分一致)。
+    pub method_patterns: Vec<String>,
+    /// 文字列リテラルで名前を参照する呼び出しの型 (`route('{name}')`)。
+    pub string_refs: Vec<String>,
+}
+
+impl FrameworkPreset {
+    /// YAML ファイルを読む。未知のキーはエラーにする。
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read framework preset: {}", path.display()))?;
+        let mut preset: Self = serde_yaml::from_str(&content)
+            .with_context(|| format!("Failed to parse framework preset: {}", path.display()))?;
+        if preset.name.is_empty() {
+            preset.name = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default();
+        }
+        Ok(preset)
+    }
+
+    /// マーカーファイルのいずれかが `dir` に存在するか。
+    pub fn matches_markers(&self, dir: &Path) -> bool {
+        self.markers.iter().any(|marker| dir.join(marker).exists())
+    }
+}
+
+/// `start` から使えるプリセットを、優先順 (リポジトリ → ユーザー設定) に返す。
+///
+/// リポジトリ側は `.astro-sight.toml` と同じく親方向に `.astro-sight/presets/` を探し、
+/// `.git` を持つディレクトリで打ち切る。同名のプリセットはリポジトリ側が勝つ。
+/// 読めないファイルは自動検出の候補から外し、警告だけ出す (無関係な 1 ファイルの誤りで
+/// dead-code 全体を止めない)。
+pub fn discover_framework_presets(start: &Path) -> Vec<FrameworkPreset> {
+    let mut presets: Vec<FrameworkPreset> = Vec::new();
+    for (path, loaded) in preset_candidates(start) {
+        match loaded {
+            Ok(preset) => {
+                if !presets
+                    .iter()
+                    .any(|p| p.name.eq_ignore_ascii_case(&preset.name))
+                {
+                    presets.push(preset);
+                }
+            }
+            Err(error) => warn_skipped(&path, &error),
+        }
+    }
+    presets
+}
+
+/// `--framework` で明示された `name` のプリセットを探す。
+///
+/// 優先順で最初に `name` に当たるファイル (`name:` またはファイル名) が読めなければ
+/// エラーにする。それ以外の読めないファイルは `discover_framework_presets` と同じく
+/// 警告して飛ばす。
+pub fn find_framework_preset(start: &Path, name: &str) -> Result<Option<FrameworkPreset>> {
+    for (path, loaded) in preset_candidates(start) {
+        match loaded {
+            Ok(preset) if preset.name.eq_ignore_ascii_case(name) => return Ok(Some(preset)),
+            Ok(_) => {}
+            Err(error)
+                if path
+                    .file_stem()
+                    .is_some_and(|stem| stem.to_string_lossy().eq_ignore_ascii_case(name)) =>
+            {
+                return Err(error);
+            }
+            Err(error) => warn_skipped(&path, &error),
+        }
+    }
+    Ok(None)
+}
+
+/// プリセットファイルと読み込み結果を優先順に返す。
+fn preset_candidates(start: &Path) -> Vec<(PathBuf, Result<FrameworkPreset>)> {
+    let start = fs::canonicalize(start).unwrap_or_else(|_| start.to_path_buf());
+    let mut dirs = Vec::new();
+    for dir in start.ancestors() {
+        let presets = dir.join(PRESET_DIR);
+        if presets.is_dir() {
+            dirs.push(presets);
+            break;
+        }
+        if dir.join(".git").exists() {
+            break;
+        }
+    }
+    if let Some(config_dir) = ConfigService::default_path().parent() {
+        dirs.push(config_dir.join("presets"));
+    }
+    dirs.iter()
+        .flat_map(|dir| preset_files(dir))
+        .map(|path| {
+            let loaded = FrameworkPreset::load(&path);
+            (path, loaded)
+        })
+        .collect()
+}
+
+fn warn_skipped(path: &Path, error: &anyhow::Error) {
+    eprintln!(
+        "warning: skipping framework preset {}: {error:#}",
+        path.display()
+    );
+}
+
+/// `dir` 直下の `*.yaml` / `*.yml` (ファイル名順)。ディレクトリが無ければ空。
+fn preset_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .is_some_and(|ext| ext == "yaml" || ext == "yml")
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_defaults_name_to_file_stem_and_rejects_unknown_keys() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("acme.yaml");
+        fs::write(
+            &path,
+            "markers: [acme.json]\nbase_classes: [AcmeJob]\nmethod_patterns: ['^handle_']\n",
+        )
+        .unwrap();
+        let preset = FrameworkPreset::load(&path).unwrap();
+        assert_eq!(preset.name, "acme");
+        assert_eq!(preset.base_classes, vec!["AcmeJob".to_string()]);
+        assert!(!preset.matches_markers(tmp.path()));
+        fs::write(tmp.path().join("acme.json"), "{}").unwrap();
+        assert!(preset.matches_markers(tmp.path()));
+
+        fs::write(&path, "entry_glob: ['**/jobs/**']\n").unwrap();
+        assert!(FrameworkPreset::load(&path).is_err());
+    }
+
+    #[test]
+    fn discover_stops_at_repository_root() {
+        let tmp = tempfile::tempdir().unwrap();
+        let repo = tmp.path().join("repo");
+        fs::create_dir_all(repo.join(".git")).unwrap();
+        fs::create_dir_all(repo.join("src")).unwrap();
+        fs::create_dir_all(tmp.path().join(PRESET_DIR)).unwrap();
+        fs::write(
+            tmp.path().join(PRESET_DIR).join("outer.yaml"),
+            "entry_globs: []\n",
+        )
+        .unwrap();
+        let names = |presets: Vec<FrameworkPreset>| -> Vec<String> {
+            presets.into_iter().map(|p| p.name).collect()
+        };
+        assert!(
+            !names(discover_framework_presets(&repo.join("src"))).contains(&"outer".to_string())
+        );
+
+        fs::create_dir_all(repo.join(PRESET_DIR)).unwrap();
+        fs::write(
+            repo.join(PRESET_DIR).join("inner.yml"),
+            "name: acme\nentry_globs: ['**/jobs/**']\n",
+        )
+        .unwrap();
+        let found = discover_framework_presets(&repo.join("src"));
+        assert_eq!(found[0].name, "acme");
+        assert_eq!(found[0].entry_globs, vec!["**/jobs/**".to_string()]);
+    }
+
+    #[test]
+    fn broken_preset_fails_only_when_named() {
+        let tmp = tempfile::tempdir().unwrap();
+        let repo = tmp.path();
+        fs::create_dir_all(repo.join(".git")).unwrap();
+        fs::create_dir_all(repo.join(PRESET_DIR)).unwrap();
+        fs::write(repo.join(PRESET_DIR).join("acme.yaml"), "entry_globs: []\n").unwrap();
+        fs::write(repo.join(PRESET_DIR).join("broken.yaml"), "entry_glob: [\n").unwrap();
+
+        let found = discover_framework_presets(repo);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].name, "acme");
+        assert_eq!(
+            find_framework_preset(repo, "ACME").unwrap().map(|p| p.name),
+            Some("acme".to_string())
+        );
+        assert!(find_framework_preset(repo, "missing").unwrap().is_none());
+        assert!(find_framework_preset(repo, "broken").is_err());
+    }
+}
@@ -0,0 +1,739 @@
+//! リポジトリ単位の既定オプション (`.astro-sight.toml`)。
+//!
+//! hook や CI のコマンドごとに `--exclude-dir` / `--framework` / `--min-confidence` 等を
+//! 繰り返さなくて済むよう、リポジトリに置いた設定ファイルから既定値を読む。同じキーは
+//! ユーザー設定 (`config.toml`) にも書け、優先順位は CLI > project > user > 組み込み既定値。
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::cli::{ApiAction, Commands, DeadScope};
+use crate::error::{AstroError, ErrorCode};
+use crate::models::cochange::CoChangeOptions;
+
+use super::expand_tilde;
+
+/// リポジトリルートに置く project 設定のファイル名。
+pub const PROJECT_CONFIG_FILE: &str = ".astro-sight.toml";
+
+/// project 設定のトップレベルキー。ユーザー設定と違い typo を黙って無視しないよう検証する。
+const PROJECT_KEYS: &[&str] = &[
+    "exclude_dirs",
+    "exclude_globs",
+    "framework",
+    "entry_annotations",
+    "lint",
+    "cochange",
+    "review",
+    "hook",
+];
+
+/// project / user の両方に書ける既定オプション。未指定のキーは `None` で、下位の層に委ねる。
+///
+/// リスト (`exclude_dirs` / `exclude_globs`) も上位の層が丸ごと置き換え、CLI の
+/// `--exclude-dir` / `--exclude-glob` はその結果に追加される。
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ProjectSettings {
+    pub exclude_dirs: Option<Vec<String>>,
+    pub exclude_globs: Option<Vec<String>>,
+    /// `--framework` の既定値。
+    pub framework: Option<String>,
+    /// dead-code / review の `--entry-annotation` (CLI 指定はこの後ろに追加される)。
+    pub entry_annotations: Option<Vec<String>>,
+    pub lint: LintSettings,
+    pub cochange: CochangeSettings,
+    /// `review` (`--hook` 無し) の既定値。
+    pub review: ReviewSettings,
+    /// `review --hook` の既定値 (hook policy)。
+    pub hook: HookSettings,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LintSettings {
+    /// `--rules` / `--rules-dir` が無いときに使うルールディレクトリ。設定ファイルの
+    /// ディレクトリ相対で書け、読み込み時に絶対パスへ直す。
+    pub rules_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CochangeSettings {
+    /// `cochange --min-confidence` と `review --min-confidence` の既定値。
+    pub min_confidence: Option<f64>,
+    pub min_samples: Option<usize>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ReviewSettings {
+    pub dead_scope: Option<DeadScope>,
+    pub strict_public_const_values: Option<bool>,
+    /// `duplicated_code` (追加コードの重複検出) を行うか。
+    pub dupes: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HookSettings {
+    pub dead_scope: Option<DeadScope>,
+    pub strict_public_const_values: Option<bool>,
+    pub include_wip_dead: Option<bool>,
+    /// `review --hook --risk-threshold` の既定値。
+    pub risk_threshold: Option<f64>,
+    /// `review --hook` で `duplicated_code` を検出するか。同言語の全ファイルをハッシュするため
+    /// 既定では行わない。
+    pub dupes: Option<bool>,
+}
+
+impl ProjectSettings {
+    /// 組み込み既定値。CLI の既定値と同じ値で、`config show --effective` の最下層に使う。
+    pub fn builtin() -> Self {
+        let cochange = CoChangeOptions::default();
+        Self {
+            exclude_dirs: Some(Vec::new()),
+            exclude_globs: Some(Vec::new()),
+            framework: None,
+            entry_annotations: Some(Vec::new()),
+            lint: LintSettings { rules_dir: None },
+            cochange: CochangeSettings {
+                min_confidence: Some(cochange.min_confidence),
+                min_samples: Some(cochange.min_samples),
+            },
+            review: ReviewSettings {
+                dead_scope: Some(DeadScope::All),
+                strict_public_const_values: Some(false),
+                dupes: Some(true),
+            },
+            hook: HookSettings {
+                dead_scope: Some(DeadScope::TouchedSymbols),
+                strict_public_const_values: Some(false),
+                include_wip_dead: Some(false),
+                risk_threshold: None,
+                dupes: Some(false),
+            },
+        }
+    }
+
+    /// `self` を上位、`lower` を下位としてキー単位で重ねる。
+    pub fn or(self, lower: Self) -> Self {
+        Self {
+            exclude_dirs: self.exclude_dirs.or(lower.exclude_dirs),
+            exclude_globs: self.exclude_globs.or(lower.exclude_globs),
+            framework: self.framework.or(lower.framework),
+            entry_annotations: self.entry_annotations.or(lower.entry_annotations),
+            lint: LintSettings {
+                rules_dir: self.lint.rules_dir.or(lower.lint.rules_dir),
+            },
+            cochange: CochangeSettings {
+                min_confidence: self
+                    .cochange
+                    .min_confidence
+                    .or(lower.cochange.min_confidence),
+                min_samples: self.cochange.min_samples.or(lower.cochange.min_samples),
+            },
+            review: ReviewSettings {
+                dead_scope: self.review.dead_scope.or(lower.review.dead_scope),
+                strict_public_const_values: self
+                    .review
+                    .strict_public_const_values
+                    .or(lower.review.strict_public_const_values),
+                dupes: self.review.dupes.or(lower.review.dupes),
+            },
+            hook: HookSettings {
+                dead_scope: self.hook.dead_scope.or(lower.hook.dead_scope),
+                strict_public_const_values: self
+                    .hook
+                    .strict_public_const_values
+                    .or(lower.hook.strict_public_const_values),
+                include_wip_dead: self.hook.include_wip_dead.or(lower.hook.include_wip_dead),
+                risk_threshold: self.hook.risk_threshold.or(lower.hook.risk_threshold),
+                dupes: self.hook.dupes.or(lower.hook.dupes),
+            },
+        }
+    }
+
+    /// 各キーが指定されているか (`config show --effective` の由来表示用)。
+    fn presence(&self) -> [(&'static str, bool); 15] {
+        [
+            ("exclude_dirs", self.exclude_dirs.is_some()),
+            ("exclude_globs", self.exclude_globs.is_some()),
+            ("framework", self.framework.is_some()),
+            ("entry_annotations", self.entry_annotations.is_some()),
+            ("lint.rules_dir", self.lint.rules_dir.is_some()),
+            (
+                "cochange.min_confidence",
+                self.cochange.min_confidence.is_some(),
+            ),
+            ("cochange.min_samples", self.cochange.min_samples.is_some()),
+            ("review.dead_scope", self.review.dead_scope.is_some()),
+            (
+                "review.strict_public_const_values",
+                self.review.strict_public_const_values.is_some(),
+            ),
+            ("review.dupes", self.review.dupes.is_some()),
+            ("hook.dead_scope", self.hook.dead_scope.is_some()),
+            (
+                "hook.strict_public_const_values",
+                self.hook.strict_public_const_values.is_some(),
+            ),
+            (
+                "hook.include_wip_dead",
+                self.hook.include_wip_dead.is_some(),
+            ),
+            ("hook.risk_threshold", self.hook.risk_threshold.is_some()),
+            ("hook.dupes", self.hook.dupes.is_some()),
+        ]
+    }
+
+    /// 相対パスの設定値を、設定ファイルのあるディレクトリ基準の絶対パスに直す。
+    pub(crate) fn resolve_paths(&mut self, base: &Path) {
+        if let Some(dir) = self.lint.rules_dir.take() {
+            let dir = expand_tilde(&dir);
+            self.lint.rules_dir = Some(if dir.is_relative() {
+                base.join(dir)
+            } else {
+                dir
+            });
+        }
+    }
+
+    /// CLI で指定されなかったオプションを設定値で埋める。
+    ///
+    /// 除外リストは設定値の後ろに CLI 指定を足す。bool フラグは `--flag` / `--no-flag` の
+    /// どちらかを指定したときだけ CLI が勝ち、設定で true にしたものも `--no-flag` で戻せる。
+    pub fn apply(&self, command: &mut Commands) {
+        match command {
+            Commands::Context {
+                exclude_dirs,
+                exclude_globs,
+                ..
+            }
+            | Commands::Impact {
+                exclude_dirs,
+                exclude_globs,
+                ..
+            }
+            | Commands::Dupes {
+                exclude_dirs,
+                exclude_globs,
+                ..
+            }
+            | Commands::DocCoverage {
+                exclude_dirs,
+                exclude_globs,
+                ..
+            }
+            | Commands::UnusedImports {
+                exclude_dirs,
+                exclude_globs,
+                ..
+            }
+            | Commands::AffectedTests {
+                exclude_dirs,
+                exclude_globs,
+                ..
+            }
+            | Commands::ParseHealth {
+                exclude_dirs,
+                exclude_globs,
+                ..
+            }
+            | Commands::Api {
+                action:
+                    ApiAction::Dump {
+                        exclude_dirs,
+                        exclude_globs,
+                        ..
+                    }
+                    | ApiAction::Check {
+                        exclude_dirs,
+                        exclude_globs,
+                        ..
+                    },
+            } => self.apply_excludes(exclude_dirs, exclude_globs),
+            Commands::DeadCode {
+                exclude_dirs,
+                exclude_globs,
+                framework,
+                entry_annotations,
+                dead_scope,
+                ..
+            } => {
+                self.apply_excludes(exclude_dirs, exclude_globs);
+                fill(framework, &self.framework);
+                prepend(entry_annotations, &self.entry_annotations);
+                // dead-code の既定スコープは review (`--hook` 無し) と揃える。
+                fill(dead_scope, &self.review.dead_scope);
+            }
+            Commands::Review {
+                exclude_dirs,
+                exclude_globs,
+                framework,
+                entry_annotations,
+                min_confidence,
+                hook,
+                dead_scope,
+                strict_public_const_values,
+                no_strict_public_const_values,
+                include_wip_dead,
+                no_include_wip_dead,
+                risk_threshold,
+                dupes,
+                no_dupes,
+                ..
+            } => {
+                self.apply_excludes(exclude_dirs, exclude_globs);
+                fill(framework, &self.framework);
+                prepend(entry_annotations, &self.entry_annotations);
+                fill(min_confidence, &self.cochange.min_confidence);
+                if *hook {
+                    fill(dead_scope, &self.hook.dead_scope);
+                    fill(risk_threshold, &self.hook.risk_threshold);
+                    fill_flag(
+                        strict_public_const_values,
+                        *no_strict_public_const_values,
+                        &self.hook.strict_public_const_values,
+                    );
+                    fill_flag(
+                        include_wip_dead,
+                        *no_include_wip_dead,
+                        &self.hook.include_wip_dead,
+                    );
+                    fill_flag(dupes, *no_dupes, &self.hook.dupes);
+                } else {
+                    fill(dead_scope, &self.review.dead_scope);
+                    fill_flag(
+                        strict_public_const_values,
+                        *no_strict_public_const_values,
+                        &self.review.strict_public_const_values,
+                    );
+                    fill_flag(include_wip_dead, *no_include_wip_dead, &None);
+                    fill_flag(dupes, *no_dupes, &self.review.dupes);
+                }
+            }
+            Commands::Cochange {
+                exclude_globs,
+                min_confidence,
+                min_samples,
+                ..
+            } => {
+                prepend(exclude_globs, &self.exclude_globs);
+                fill(min_confidence, &self.cochange.min_confidence);
+                fill(min_samples, &self.cochange.min_samples);
+            }
+            Commands::Lint {
+                rules, rules_dir, ..
+            } => {
+                if rules.is_none() && rules_dir.is_none() {
+                    *rules_dir = self
+                        .lint
+                        .rules_dir
+                        .as_ref()
+                        .map(|dir| dir.to_string_lossy().into_owned());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn apply_excludes(&self, exclude_dirs: &mut Vec<String>, exclude_globs: &mut Vec<String>) {
+        prepend(exclude_dirs, &self.exclude_dirs);
+        prepend(exclude_globs, &self.exclude_globs);
+    }
+}
+
+fn fill<T: Clone>(cli: &mut Option<T>, configured: &Option<T>) {
+    if cli.is_none() {
+        cli.clone_from(configured);
+    }
+}
+
+/// `--flag` / `--no-flag` の組を解決する。`--no-flag` は `Some(false)` に畳み込み、
+/// どちらも無いときだけ設定値で埋める。
+fn fill_flag(cli: &mut Option<bool>, negated: bool, configured: &Option<bool>) {
+    if negated {
+        *cli = Some(false);
+    }
+    fill(cli, configured);
+}
+
+fn prepend(cli: &mut Vec<String>, configured: &Option<Vec<String>>) {
+    if let Some(configured) = configured {
+        let extra = std::mem::take(cli);
+        cli.extend(configured.iter().cloned());
+        cli.extend(extra);
+    }
+}
+
+/// 見つかった project 設定。
+#[derive(Debug, Clone)]
+pub struct ProjectConfig {
+    pub path: PathBuf,
+    pub settings: ProjectSettings,
+}
+
+impl ProjectConfig {
+    /// `start` から親方向に `.astro-sight.toml` を探して読む。
+    ///
+    /// リポジトリの外の設定を拾わないよう、`.git` を持つディレクトリ (リポジトリルート) で
+    /// 探索を打ち切る。見つからなければ `None`。
+    pub fn discover(start: &Path) -> Result<Option<Self>> {
+        let start = fs::canonicalize(start).unwrap_or_else(|_| start.to_path_buf());
+        for dir in start.ancestors() {
+            let path = dir.join(PROJECT_CONFIG_FILE);
+            if path.is_file() {
+                return Self::load(&path).map(Some);
+            }
+            if dir.join(".git").exists() {
+                break;
+            }
+        }
+        Ok(None)
+    }
+
+    /// 指定パスの project 設定を読む。未知のキーはエラーにする。
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read project config: {}", path.display()))?;
+        let table: toml::Table = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse project config: {}", path.display()))?;
+        if let Some(key) = table
+            .keys()
+            .find(|key| !PROJECT_KEYS.contains(&key.as_str()))
+        {
+            return Err(AstroError::new(
+                ErrorCode::InvalidRequest,
+                format!("Unknown key `{key}` in project config: {}", path.display()),
+            )
+            .into());
+        }
+        let mut settings: ProjectSettings = toml::Value::Table(table)
+            .try_into()
+            .with_context(|| format!("Failed to parse project config: {}", path.display()))?;
+        if let Some(dir) = path.parent() {
+            settings.resolve_paths(dir);
+        }
+        Ok(Self {
+            path: path.to_path_buf(),
+            settings,
+        })
+    }
+}
+
+/// `config show` の出力。
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigShow {
+    /// 読み込んだユーザー設定のパス (ファイルが無ければ `null`)。
+    pub user_config: Option<PathBuf>,
+    /// 見つかった project 設定のパス (無ければ `null`)。
+    pub project_config: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<ProjectSettings>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project: Option<ProjectSettings>,
+    /// `--effective`: 全層を重ねた結果。
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effective: Option<ProjectSettings>,
+    /// `--effective`: キーごとの由来 (`"project"` / `"user"` / `"default"`)。
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub sources: BTreeMap<&'static str, &'static str>,
+}
+
+impl ConfigShow {
+    /// 各層をそのまま並べる (`config show`)。
+    pub fn layers(
+        user_config: Option<PathBuf>,
+        user: ProjectSettings,
+        project: Option<ProjectConfig>,
+    ) -> Self {
+        Self {
+            user_config,
+            project_config: project.as_ref().map(|p| p.path.clone()),
+            user: Some(user),
+            project: project.map(|p| p.settings),
+            effective: None,
+            sources: BTreeMap::new(),
+        }
+    }
+
+    /// 全層を重ねた値と、キーごとにどの層の値が採用されたかを出す (`config show --effective`)。
+    pub fn effective(
+        user_config: Option<PathBuf>,
+        user: ProjectSettings,
+        project: Option<ProjectConfig>,
+    ) -> Self {
+        let project_path = project.as_ref().map(|p| p.path.clone());
+        let project = project.map(|p| p.settings).unwrap_or_default();
+        let sources = project
+            .presence()
+            .into_iter()
+            .zip(user.presence())
+            .map(|((key, in_project), (_, in_user))| {
+                let source = if in_project {
+                    "project"
+                } else if in_user {
+                    "user"
+                } else {
+                    "default"
+                };
+                (key, source)
+            })
+            .collect();
+        Self {
+            user_config,
+            project_config: project_path,
+            user: None,
+            project: None,
+            effective: Some(project.or(user).or(ProjectSettings::builtin())),
+            sources,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn review(hook: bool) -> Commands {
+        Commands::Review {
+            dir: ".".to_string(),
+            diff: None,
+            diff_file: None,
+            git: true,
+            base: "HEAD".to_string(),
+            staged: false,
+            min_confidence: None,
+            hook,
+            framework: None,
+            exclude_dirs: vec!["cli".to_string()],
+            exclude_globs: Vec::new(),
+            entry_annotations: Vec::new(),
+            dead_scope: None,
+            strict_public_const_values: None,
+            no_strict_public_const_values: false,
+            include_wip_dead: None,
+            no_include_wip_dead: false,
+            risk_threshold: None,
+            dupes: None,
+            no_dupes: false,
+            transitive_depth: 0,
+            range: None,
+            per_commit: false,
+            link_base: None,
+        }
+    }
+
+    #[test]
+    fn discover_stops_at_repository_root() {
+        let tmp = tempfile::tempdir().unwrap();
+        let repo = tmp.path().join("repo");
+        fs::create_dir_all(repo.join(".git")).unwrap();
+        fs::create_dir_all(repo.join("src/nested")).unwrap();
+        fs::write(
+            tmp.path().join(PROJECT_CONFIG_FILE),
+            "framework = \"outer\"\n",
+        )
+        .unwrap();
+
+        assert!(
+            ProjectConfig::discover(&repo.join("src/nested"))
+                .unwrap()
+                .is_none()
+        );
+
+        fs::write(
+            repo.join(PROJECT_CONFIG_FILE),
+            "framework = \"laravel\"\n[lint]\nrules_dir = \"rules\"\n",
+        )
+        .unwrap();
+        let found = ProjectConfig::discover(&repo.join("src/nested"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.settings.framework.as_deref(), Some("laravel"));
+        let rules_dir = found.settings.lint.rules_dir.unwrap();
+        assert!(rules_dir.is_absolute() && rules_dir.ends_with("repo/rules"));
+    }
+
+    #[test]
+    fn load_rejects_unknown_keys() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join(PROJECT_CONFIG_FILE);
+        fs::write(&path, "exclude_dir = [\"gen\"]\n").unwrap();
+        assert!(ProjectConfig::load(&path).is_err());
+        fs::write(&path, "[hook]\ndead_scop = \"all\"\n").unwrap();
+        assert!(ProjectConfig::load(&path).is_err());
+    }
+
+    #[test]
+    fn cli_beats_project_beats_user() {
+        let user = ProjectSettings {
+            framework: Some("user".to_string()),
+            cochange: CochangeSettings {
+                min_confidence: Some(0.1),
+                min_samples: None,
+            },
+            ..Default::default()
+        };
+        let project = ProjectSettings {
+            framework: Some("project".to_string()),
+            exclude_dirs: Some(vec!["generated".to_string()]),
+            hook: HookSettings {
+                dead_scope: Some(DeadScope::All),
+                include_wip_dead: Some(true),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let merged = project.or(user);
+
+        let mut command = review(true);
+        merged.apply(&mut command);
+        let Commands::Review {
+            framework,
+            min_confidence,
+            exclude_dirs,
+            dead_scope,
+            include_wip_dead,
+            ..
+        } = &command
+        else {
+            unreachable!()
+        };
+        assert_eq!(framework.as_deref(), Some("project"));
+        assert_eq!(*min_confidence, Some(0.1));
+        assert_eq!(exclude_dirs, &["generated", "cli"]);
+        assert_eq!(*dead_scope, Some(DeadScope::All));
+        assert_eq!(*include_wip_dead, Some(true));
+
+        // hook policy は `--hook` 無しの review には効かない。
+        let mut command = review(false);
+        merged.apply(&mut command);
+        let Commands::Review {
+            dead_scope,
+            include_wip_dead,
+            ..
+        } = &command
+        else {
+            unreachable!()
+        };
+        assert_eq!(*dead_scope, None);
+        assert_eq!(*include_wip_dead, None);
+    }
+
+    #[test]
+    fn dupes_setting_follows_hook_and_review_sections() {
+        let settings = ProjectSettings {
+            review: ReviewSettings {
+                dupes: Some(false),
+                ..Default::default()
+            },
+            hook: HookSettings {
+                dupes: Some(true),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        for (hook, expected) in [(true, Some(true)), (false, Some(false))] {
+            let mut command = review(hook);
+            settings.apply(&mut command);
+            let Commands::Review { dupes, .. } = &command else {
+                unreachable!()
+            };
+            assert_eq!(*dupes, expected, "hook: {hook}");
+        }
+
+        let mut command = <crate::cli::Cli as clap::Parser>::try_parse_from([
+            "astro-sight",
+            "review",
+            "--hook",
+            "--no-dupes",
+        ])
+        .expect("review args should parse")
+        .command;
+        settings.apply(&mut command);
+        let Commands::Review { dupes, .. } = &command else {
+            unreachable!()
+        };
+        assert_eq!(*dupes, Some(false));
+    }
+
+    #[test]
+    fn negated_cli_flags_override_enabled_settings() {
+        let settings = ProjectSettings {
+            review: ReviewSettings {
+                strict_public_const_values: Some(true),
+                ..Default::default()
+            },
+            hook: HookSettings {
+                strict_public_const_values: Some(true),
+                include_wip_dead: Some(true),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let mut command = <crate::cli::Cli as clap::Parser>::try_parse_from([
+            "astro-sight",
+            "review",
+            "--hook",
+            "--no-strict-public-const-values",
+            "--no-include-wip-dead",
+        ])
+        .expect("review args should parse")
+        .command;
+        settings.apply(&mut command);
+        let Commands::Review {
+            strict_public_const_values,
+            include_wip_dead,
+            ..
+        } = &command
+        else {
+            unreachable!()
+        };
+        assert_eq!(*strict_public_const_values, Some(false));
+        assert_eq!(*include_wip_dead, Some(false));
+
+        // どちらも指定しなければ設定値が効く。
+        let mut command = review(false);
+        settings.apply(&mut command);
+        let Commands::Review {
+            strict_public_const_values,
+            ..
+        } = &command
+        else {
+            unreachable!()
+        };
+        assert_eq!(*strict_public_const_values, Some(true));
+    }
+
+    #[test]
+    fn effective_reports_the_source_of_each_key() {
+        let user = ProjectSettings {
+            framework: Some("user".to_string()),
+            ..Default::default()
+        };
+        let project = ProjectConfig {
+            path: PathBuf::from(PROJECT_CONFIG_FILE),
+            settings: ProjectSettings {
+                cochange: CochangeSettings {
+                    min_confidence: Some(0.5),
+                    min_samples: None,
+                },
+                ..Default::default()
+            },
+        };
+        let show = ConfigShow::effective(None, user, Some(project));
+        let effective = show.effective.unwrap();
+        assert_eq!(effective.framework.as_deref(), Some("user"));
+        assert_eq!(effective.cochange.min_confidence, Some(0.5));
+        assert_eq!(effective.cochange.min_samples, Some(2));
+        assert_eq!(show.sources["framework"], "user");
+        assert_eq!(show.sources["cochange.min_confidence"], "project");
+        assert_eq!(show.sources["cochange.min_samples"], "default");
+    }
+}
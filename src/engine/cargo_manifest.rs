@@ -0,0 +1,51 @@
+//! `Cargo.toml` の探索と読み込み。
+
+use std::path::{Path, PathBuf};
+
+pub(crate) const MANIFEST_FILE: &str = "Cargo.toml";
+
+/// `path` を含む crate の `Cargo.toml` (最も近い祖先ディレクトリのもの)。`root` より上は
+/// 辿らない。`Cargo.toml` 自身はその crate に属する。
+pub(crate) fn nearest_manifest(root: &Path, path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .skip(1)
+        .take_while(|dir| dir.starts_with(root))
+        .map(|dir| dir.join(MANIFEST_FILE))
+        .find(|manifest| manifest.is_file())
+}
+
+/// `Cargo.toml` を読み、元のテキストと TOML テーブルを返す。読めない・parse できない
+/// ものは `None`。
+pub(crate) fn read_manifest_table(path: &Path) -> Option<(String, toml::Table)> {
+    let text = std::fs::read_to_string(path).ok()?;
+    let table = toml::from_str(&text).ok()?;
+    Some((text, table))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nearest_manifest_stops_at_root_and_owns_itself() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        std::fs::create_dir_all(root.join("crates/core/src")).unwrap();
+        std::fs::write(root.join(MANIFEST_FILE), "[workspace]\n").unwrap();
+        std::fs::write(root.join("crates/core").join(MANIFEST_FILE), "[package]\n").unwrap();
+
+        let core = root.join("crates/core");
+        assert_eq!(
+            nearest_manifest(root, &core.join("src/lib.rs")),
+            Some(core.join(MANIFEST_FILE))
+        );
+        assert_eq!(
+            nearest_manifest(root, &core.join(MANIFEST_FILE)),
+            Some(core.join(MANIFEST_FILE))
+        );
+        assert_eq!(
+            nearest_manifest(&core.join("src"), &core.join("src/lib.rs")),
+            None
+        );
+    }
+}
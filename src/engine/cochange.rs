@@ -878,8 +878,9 @@ fn collect_blame_commits_for_file(
 
 /// blame `--line-porcelain` 出力から SHA → BlameInfo を抽出する。
 /// 各 entry の先頭行 `<sha40> <orig> <final> [count]` をエントリ境界とし、
-/// それ以降の `author-mail <addr>` / `author-time <unix>` を SHA に紐付ける。
-fn parse_blame_porcelain(blame_text: &str) -> Result<HashMap<String, BlameInfo>> {
+/// それ以降の `author <name>` / `author-mail <addr>` / `author-time <unix>` を SHA に紐付ける。
+/// dead-code `--blame` も宣言範囲の最終変更コミットを引くのに使う。
+pub(crate) fn parse_blame_porcelain(blame_text: &str) -> Result<HashMap<String, BlameInfo>> {
     let mut out: HashMap<String, BlameInfo> = HashMap::new();
     let mut current_sha: Option<String> = None;
     for line in blame_text.lines() {
@@ -901,7 +902,12 @@ fn parse_blame_porcelain(blame_text: &str) -> Result<HashMap<String, BlameInfo>>
         let Some(sha) = current_sha.as_ref() else {
             continue;
         };
-        if let Some(rest) = line.strip_prefix("author-mail ") {
+        if let Some(rest) = line.strip_prefix("author ") {
+            let name = rest.trim();
+            if !name.is_empty() {
+                out.entry(sha.clone()).or_default().author_name = Some(name.to_string());
+            }
+        } else if let Some(rest) = line.strip_prefix("author-mail ") {
             // git porcelain の author-mail は通常 `<addr>` で囲まれている
             let trimmed = rest.trim().trim_start_matches('<').trim_end_matches('>');
             if !trimmed.is_empty() {
@@ -919,9 +925,10 @@ fn parse_blame_porcelain(blame_text: &str) -> Result<HashMap<String, BlameInfo>>
 /// blame で抽出した SHA ごとのコミットメタデータ。
 /// `author_unit_window_days > 0` のとき、(author_mail, time_bucket) を unit キーとして使う。
 #[derive(Debug, Clone, Default)]
-struct BlameInfo {
-    author_mail: Option<String>,
-    author_time: Option<i64>,
+pub(crate) struct BlameInfo {
+    pub(crate) author_name: Option<String>,
+    pub(crate) author_mail: Option<String>,
+    pub(crate) author_time: Option<i64>,
 }
 
 /// 履歴 fallback: `git log <base> -n <limit> -- <file>` でファイル自身のコミット集合を取る。
@@ -979,6 +986,7 @@ fn collect_history_commits_for_file(
         out.insert(
             sha.to_string(),
             BlameInfo {
+                author_name: None,
                 author_mail: (!mail.is_empty()).then(|| mail.to_string()),
                 author_time: time.parse::<i64>().ok(),
             },
@@ -2885,6 +2893,7 @@ filename a.rs
         let info = parsed
             .get("abcd1234567890abcd1234567890abcd12345678")
             .unwrap();
+        assert_eq!(info.author_name.as_deref(), Some("Test User"));
         assert_eq!(info.author_mail.as_deref(), Some("test@example.com"));
         assert_eq!(info.author_time, Some(1_700_000_000));
         let info2 = parsed
@@ -0,0 +1,197 @@
+//! `CODEOWNERS` の最小パーサ。impact / review の caller や missing_cochange に
+//! 通知先のチーム (owner) を添えるために利用する。
+
+use std::path::Path;
+
+use super::gitattributes::Pattern;
+
+/// GitHub と同じ探索順 (`.github/` → ルート → `docs/`)。最初に見つかった 1 ファイルだけを使う。
+pub const CODEOWNERS_LOCATIONS: [&str; 3] = [".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
+
+/// `CODEOWNERS` のルール集。ルールは登場順に保持し、後勝ちで評価する。
+#[derive(Debug, Default, Clone)]
+pub struct CodeOwners {
+    rules: Vec<Rule>,
+    /// 解析対象 dir のリポジトリルートからの相対位置 (`sub/dir/`、ルートなら空)。
+    /// astro-sight のパスは dir 相対なので、照合前にこれを前置してルート相対に直す。
+    prefix: String,
+}
+
+#[derive(Debug, Clone)]
+struct Rule {
+    pattern: Pattern,
+    /// 末尾 `/` のパターン (`apps/`)。ディレクトリにだけマッチする。
+    dir_only: bool,
+    /// 空なら「担当なし」を明示するルール (それ以前のマッチを打ち消す)。
+    owners: Vec<String>,
+}
+
+impl CodeOwners {
+    /// `dir` から親方向に `.git` を持つディレクトリ (リポジトリルート) を探し、
+    /// その `CODEOWNERS` を読み込む。ルートが見つからなければ `dir` をルートとみなす。
+    /// ファイルが無ければ空 (どのパスにも owner を付けない)。
+    pub fn discover(dir: &Path) -> Self {
+        let dir = std::fs::canonicalize(dir).unwrap_or_else(|_| dir.to_path_buf());
+        let root = dir
+            .ancestors()
+            .find(|ancestor| ancestor.join(".git").exists())
+            .unwrap_or(dir.as_path());
+        let Some(content) = CODEOWNERS_LOCATIONS
+            .iter()
+            .find_map(|location| std::fs::read_to_string(root.join(location)).ok())
+        else {
+            return Self::default();
+        };
+        let prefix = dir
+            .strip_prefix(root)
+            .map(|rel| rel.to_string_lossy().replace('\\', "/"))
+            .unwrap_or_default();
+        Self {
+            prefix: if prefix.is_empty() {
+                prefix
+            } else {
+                format!("{prefix}/")
+            },
+            ..Self::parse(&content)
+        }
+    }
+
+    /// 文字列から直接パース (パスはリポジトリルート相対として評価する)。
+    ///
+    /// 行末コメント (`# ...`) と GitLab のセクション見出し (`[Section]` / `^[Section]`) は
+    /// 読み飛ばす。owner の書式 (`@org/team` / `@user` / メールアドレス) は検証しない。
+    pub fn parse(content: &str) -> Self {
+        let rules = content
+            .lines()
+            .filter_map(|raw| {
+                let line = raw.trim();
+                if line.is_empty()
+                    || line.starts_with('#')
+                    || line.starts_with('[')
+                    || line.starts_with("^[")
+                {
+                    return None;
+                }
+                let mut parts = line.split_whitespace();
+                let pattern = parts.next()?;
+                let owners = parts
+                    .take_while(|part| !part.starts_with('#'))
+                    .map(str::to_string)
+                    .collect();
+                Some(Rule {
+                    pattern: Pattern::parse(pattern),
+                    dir_only: pattern.ends_with('/'),
+                    owners,
+                })
+            })
+            .collect();
+        Self {
+            rules,
+            prefix: String::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// dir 相対パスの owner。最後にマッチしたルールが勝つ。
+    pub fn owners_of(&self, rel_path: &str) -> &[String] {
+        if self.rules.is_empty() {
+            return &[];
+        }
+        let normalized = rel_path.replace('\\', "/");
+        let path = format!(
+            "{}{}",
+            self.prefix,
+            normalized.trim_start_matches("./").trim_start_matches('/')
+        );
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| rule.matches(&path))
+            .map(|rule| rule.owners.as_slice())
+            .unwrap_or_default()
+    }
+}
+
+impl Rule {
+    /// パス自身、またはその祖先ディレクトリにマッチするか。
+    ///
+    /// ディレクトリへのマッチは配下全体に及ぶ (`docs` / `/build/logs` は配下すべて)。
+    /// ただし末尾が wildcard の `docs/*` は直下のエントリにだけ効き、
+    /// `dir_only` (`apps/`) はファイル自身にはマッチしない。
+    fn matches(&self, path: &str) -> bool {
+        if !self.dir_only && self.pattern.matches(path) {
+            return true;
+        }
+        if self.pattern.ends_with_wildcard() && !self.dir_only {
+            return false;
+        }
+        let parts: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        (1..parts.len()).any(|end| self.pattern.matches(&parts[..end].join("/")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CODEOWNERS: &str = "\
+# default owners
+*       @org/core
+*.js    @org/frontend # inline comment
+/docs/  @org/docs
+apps/   @org/apps
+/build/logs @org/infra
+/src/generated/*  @org/codegen
+/src/generated/keep.rs
+";
+
+    #[test]
+    fn last_match_wins() {
+        let owners = CodeOwners::parse(CODEOWNERS);
+        assert_eq!(owners.owners_of("src/main.rs"), ["@org/core"]);
+        assert_eq!(owners.owners_of("web/app.js"), ["@org/frontend"]);
+        assert_eq!(owners.owners_of("docs/app.js"), ["@org/docs"]);
+    }
+
+    #[test]
+    fn directory_patterns_cover_descendants() {
+        let owners = CodeOwners::parse(CODEOWNERS);
+        assert_eq!(owners.owners_of("docs/guide/intro.md"), ["@org/docs"]);
+        assert_eq!(owners.owners_of("services/apps/api/main.go"), ["@org/apps"]);
+        assert_eq!(owners.owners_of("build/logs/2024/out.log"), ["@org/infra"]);
+        // ルート固定 (`/docs/`) は深い階層の同名ディレクトリには効かない
+        assert_eq!(owners.owners_of("pkg/docs/readme.md"), ["@org/core"]);
+    }
+
+    #[test]
+    fn trailing_wildcard_matches_direct_children_only() {
+        let owners = CodeOwners::parse(CODEOWNERS);
+        assert_eq!(owners.owners_of("src/generated/api.rs"), ["@org/codegen"]);
+        assert_eq!(owners.owners_of("src/generated/v1/api.rs"), ["@org/core"]);
+    }
+
+    #[test]
+    fn rule_without_owners_clears_ownership() {
+        let owners = CodeOwners::parse(CODEOWNERS);
+        assert!(owners.owners_of("src/generated/keep.rs").is_empty());
+    }
+
+    #[test]
+    fn discover_prefixes_subdirectory_and_prefers_github_dir() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        std::fs::create_dir_all(root.join(".git")).unwrap();
+        std::fs::create_dir_all(root.join(".github")).unwrap();
+        std::fs::create_dir_all(root.join("backend/src")).unwrap();
+        std::fs::write(root.join(".github/CODEOWNERS"), "/backend/ @org/backend\n").unwrap();
+        std::fs::write(root.join("CODEOWNERS"), "* @org/ignored\n").unwrap();
+
+        let owners = CodeOwners::discover(&root.join("backend"));
+        assert_eq!(owners.owners_of("src/lib.rs"), ["@org/backend"]);
+        let owners = CodeOwners::discover(root);
+        assert!(owners.owners_of("frontend/app.ts").is_empty());
+    }
+}
@@ -181,6 +181,44 @@ pub(crate) fn has_deletion_in_new_range(
     false
 }
 
+/// 単一ファイルの unified diff から、追加行 (`+`) と削除行 (`-`) の本文を出現順に集める。
+///
+/// 削除ファイルは `+++ /dev/null` になるため、`file_path` は `--- a/<path>` /
+/// `+++ b/<path>` のどちらかと完全一致すれば対象とする。
+pub(crate) fn extract_changed_line_texts<'a>(input: &'a str, file_path: &str) -> Vec<&'a str> {
+    let mut result = Vec::new();
+    let mut in_target_file = false;
+    let mut active_hunk: Option<HunkProgress> = None;
+
+    for line in input.lines() {
+        // hunk 本体を消費中はヘッダに見える行も本体行として扱う (extract_changed_new_lines と同じ規約)。
+        if let Some(progress) = active_hunk.as_mut() {
+            match progress.consume(line) {
+                HunkBodyLine::Added(text) | HunkBodyLine::Removed(text) if in_target_file => {
+                    result.push(text);
+                }
+                _ => {}
+            }
+            if progress.is_complete() {
+                active_hunk = None;
+            }
+            continue;
+        }
+        if let Some(path) = line.strip_prefix("--- a/") {
+            in_target_file = path == file_path;
+        } else if line.starts_with("--- ") {
+            in_target_file = false;
+        } else if let Some(path) = line.strip_prefix("+++ b/") {
+            in_target_file |= path == file_path;
+        } else if line.starts_with("@@ ")
+            && let Some(hunk) = parse_hunk_header(line)
+        {
+            active_hunk = Some(HunkProgress::new(&hunk));
+        }
+    }
+    result
+}
+
 /// unified diff 文字列を `DiffFile` の配列に変換する。
 ///
 /// 削除ファイル (`+++ /dev/null`) の hunk 内 `-` 行は旧ソース復元用に蓄積し、
@@ -499,6 +537,21 @@ index 1234567..0000000
         assert_eq!(sorted, vec![0, 1, 2]);
     }
 
+    /// 追加行と削除行の本文を対象ファイル分だけ集め、削除ファイルも `--- a/` で拾う。
+    #[test]
+    fn extract_changed_line_texts_collects_both_sides() {
+        let diff = "--- a/foo.rs\n+++ b/foo.rs\n@@ -1,2 +1,2 @@\n keep\n-#[cfg(feature = \"old\")]\n+#[cfg(feature = \"new\")]\n--- a/gone.rs\n+++ /dev/null\n@@ -1 +0,0 @@\n-fn gone() {}\n";
+        assert_eq!(
+            extract_changed_line_texts(diff, "foo.rs"),
+            vec!["#[cfg(feature = \"old\")]", "#[cfg(feature = \"new\")]"]
+        );
+        assert_eq!(
+            extract_changed_line_texts(diff, "gone.rs"),
+            vec!["fn gone() {}"]
+        );
+        assert!(extract_changed_line_texts(diff, "other.rs").is_empty());
+    }
+
     /// 追加のみ (削除行なし) の hunk では has_deletion_in_new_range は false。
     #[test]
     fn has_deletion_in_new_range_pure_add_returns_false() {
@@ -0,0 +1,509 @@
+//! 正規化 AST の部分木ハッシュによるコピー&ペースト (type-2 clone) 検出。
+//! 識別子とリテラルを種別だけに伏せた部分木を blake3 でハッシュし
+//! (キャッシュキーの `CacheStore::hash` と同じ関数族)、同一ハッシュの部分木を
+//! clone group としてまとめる。rename / 定数差し替えを伴うコピーも同一視できる。
+
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+use camino::Utf8Path;
+use rayon::prelude::*;
+use tree_sitter::Node;
+
+use crate::engine::parser;
+use crate::engine::paths::relative_path;
+use crate::models::dupes::{CloneGroup, CloneInstance, DuplicatedCode};
+
+/// `--min-tokens` の既定値。review の重複検出もこの閾値を使う。
+/// 小さすぎると getter / 定型 import 群などの「似て当然」な断片が大量に出るため、
+/// 関数 1 つ分程度の規模を下限にする。
+pub const DEFAULT_MIN_TOKENS: usize = 50;
+
+/// 出力する fingerprint の桁数 (blake3 hex の先頭)。group の同一性判定に十分な長さ。
+const FINGERPRINT_HEX_LEN: usize = 16;
+
+/// 1 ファイル分の解析結果。`leaves` は正規化前の葉トークン (テキストのハッシュ) を
+/// DFS 順に並べたもので、部分木の葉は連続区間になるため similarity 算出に使う。
+struct FileFragments {
+    file: String,
+    leaves: Vec<u64>,
+    fragments: Vec<Fragment>,
+}
+
+/// `min_tokens` 以上の named 部分木。
+struct Fragment {
+    hash: [u8; 32],
+    tokens: usize,
+    first_leaf: usize,
+    start_byte: usize,
+    end_byte: usize,
+    start_line: usize,
+    end_line: usize,
+}
+
+/// 同一ハッシュの部分木集合。members は `(file index, fragment index)`。
+struct RawGroup {
+    hash: [u8; 32],
+    tokens: usize,
+    members: Vec<(usize, usize)>,
+}
+
+/// 反復 DFS のスタックフレーム。深いネストの生成コードでもスタックを溢れさせないよう
+/// 再帰ではなく明示スタックで post-order に畳み込む。
+struct Frame<'t> {
+    node: Node<'t>,
+    hasher: blake3::Hasher,
+    tokens: usize,
+    first_leaf: usize,
+    next_child: usize,
+}
+
+impl<'t> Frame<'t> {
+    fn new(node: Node<'t>, first_leaf: usize) -> Self {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(node.kind().as_bytes());
+        hasher.update(b"(");
+        Self {
+            node,
+            hasher,
+            tokens: 0,
+            first_leaf,
+            next_child: 0,
+        }
+    }
+}
+
+/// 識別子として伏せるノード種別。grammar ごとに名前が揺れるため接尾辞で広く拾う
+/// (`type_identifier` / `field_identifier` / `property_identifier` / `simple_identifier` 等)。
+/// PHP の `name` / `variable_name`、Ruby の `constant`、Bash の `word` も識別子扱い。
+fn is_identifier_kind(kind: &str) -> bool {
+    kind == "identifier"
+        || kind.ends_with("_identifier")
+        || matches!(kind, "name" | "variable_name" | "constant" | "word")
+}
+
+/// リテラルとして伏せるノード種別。文字列は引用符・エスケープ等の子を持つが、
+/// 中身の差は type-2 clone では無視したいため子に降りず 1 トークンとして扱う。
+fn is_literal_kind(kind: &str) -> bool {
+    kind.ends_with("_literal")
+        || kind.contains("string")
+        || matches!(
+            kind,
+            "number" | "integer" | "float" | "char" | "character" | "true" | "false" | "boolean"
+        )
+}
+
+/// コメントは clone 判定のトークンに含めない (説明文の差で clone を見逃さないため)。
+fn is_comment_kind(kind: &str) -> bool {
+    kind.contains("comment")
+}
+
+fn text_hash(text: &[u8]) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    text.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// 終端が次行の 0 桁目 (末尾改行込みのノード) の場合は直前行を終端行とする。
+fn node_end_line(node: Node<'_>) -> usize {
+    let start = node.start_position();
+    let end = node.end_position();
+    if end.column == 0 && end.row > start.row {
+        end.row - 1
+    } else {
+        end.row
+    }
+}
+
+/// 1 ファイルを parse し、`min_tokens` 以上の named 部分木を正規化ハッシュ付きで列挙する。
+/// 未対応言語 / lexer-only 言語 / 読み込み・parse 失敗は `None` (dupes の対象外)。
+fn analyze_file(dir: &Path, path: &Path, min_tokens: usize) -> Option<FileFragments> {
+    let utf8 = Utf8Path::from_path(path)?;
+    let source = parser::read_file(utf8).ok()?;
+    let lang = parser::detect_lang(utf8, &source).ok()?;
+    if lang.is_lexer_only() {
+        return None;
+    }
+    let tree = parser::parse_source(&source, lang).ok()?;
+
+    let mut leaves: Vec<u64> = Vec::new();
+    let mut fragments: Vec<Fragment> = Vec::new();
+    let mut stack = vec![Frame::new(tree.root_node(), 0)];
+
+    while let Some(frame) = stack.last_mut() {
+        let node = frame.node;
+        let kind = node.kind();
+        let is_leaf = node.child_count() == 0 || is_identifier_kind(kind) || is_literal_kind(kind);
+
+        if !is_leaf && frame.next_child < node.child_count() {
+            let child = node.child(frame.next_child as u32);
+            frame.next_child += 1;
+            if let Some(child) = child
+                && !is_comment_kind(child.kind())
+            {
+                let first_leaf = leaves.len();
+                stack.push(Frame::new(child, first_leaf));
+            }
+            continue;
+        }
+
+        let frame = stack.pop().expect("frame stack is non-empty");
+        let (hash, tokens) = if is_leaf {
+            let label: &[u8] = if is_identifier_kind(kind) {
+                b"$id"
+            } else if is_literal_kind(kind) {
+                b"$lit"
+            } else {
+                kind.as_bytes()
+            };
+            leaves.push(text_hash(&source[node.byte_range()]));
+            (*blake3::hash(label).as_bytes(), 1)
+        } else {
+            let mut hasher = frame.hasher;
+            hasher.update(b")");
+            let hash = *hasher.finalize().as_bytes();
+            if node.is_named() && frame.tokens >= min_tokens {
+                fragments.push(Fragment {
+                    hash,
+                    tokens: frame.tokens,
+                    first_leaf: frame.first_leaf,
+                    start_byte: node.start_byte(),
+                    end_byte: node.end_byte(),
+                    start_line: node.start_position().row,
+                    end_line: node_end_line(node),
+                });
+            }
+            (hash, frame.tokens)
+        };
+
+        if let Some(parent) = stack.last_mut() {
+            parent.hasher.update(&hash);
+            parent.tokens += tokens;
+        }
+    }
+
+    Some(FileFragments {
+        file: relative_path(dir, path)?,
+        leaves,
+        fragments,
+    })
+}
+
+/// 同一ハッシュの部分木を group 化し、大きい clone から採用する。
+///
+/// 関数全体が clone なら内部の block / statement も当然 clone になるが、それらを個別に
+/// 報告するとノイズにしかならない。採用済み instance に **全 member が包含される** group は
+/// 落とし、一部でも包含されない (= 別の場所にもコピーがある) group だけを残す。
+fn group_fragments(files: &[FileFragments]) -> Vec<RawGroup> {
+    let mut by_hash: HashMap<[u8; 32], Vec<(usize, usize)>> = HashMap::new();
+    for (fi, file) in files.iter().enumerate() {
+        for (gi, fragment) in file.fragments.iter().enumerate() {
+            by_hash.entry(fragment.hash).or_default().push((fi, gi));
+        }
+    }
+
+    let fragment = move |(fi, gi): (usize, usize)| &files[fi].fragments[gi];
+    let mut groups: Vec<RawGroup> = by_hash
+        .into_iter()
+        .filter(|(_, members)| members.len() >= 2)
+        .map(|(hash, mut members)| {
+            members.sort_by(|&a, &b| {
+                files[a.0]
+                    .file
+                    .cmp(&files[b.0].file)
+                    .then_with(|| fragment(a).start_byte.cmp(&fragment(b).start_byte))
+            });
+            let tokens = fragment(members[0]).tokens;
+            RawGroup {
+                hash,
+                tokens,
+                members,
+            }
+        })
+        .collect();
+
+    let span = |g: &RawGroup| {
+        let f = fragment(g.members[0]);
+        f.end_byte - f.start_byte
+    };
+    groups.sort_by(|a, b| {
+        b.tokens
+            .cmp(&a.tokens)
+            .then_with(|| span(b).cmp(&span(a)))
+            .then_with(|| a.hash.cmp(&b.hash))
+    });
+
+    let mut kept_ranges: HashMap<usize, Vec<(usize, usize)>> = HashMap::new();
+    groups.retain(|group| {
+        let all_covered = group.members.iter().all(|&member| {
+            let f = fragment(member);
+            kept_ranges.get(&member.0).is_some_and(|ranges| {
+                ranges
+                    .iter()
+                    .any(|&(start, end)| start <= f.start_byte && f.end_byte <= end)
+            })
+        });
+        if all_covered {
+            return false;
+        }
+        for &member in &group.members {
+            let f = fragment(member);
+            kept_ranges
+                .entry(member.0)
+                .or_default()
+                .push((f.start_byte, f.end_byte));
+        }
+        true
+    });
+    groups
+}
+
+/// 先頭 member を基準に、正規化前トークンが位置ごとに一致する割合の平均を返す。
+fn group_similarity(files: &[FileFragments], members: &[(usize, usize)]) -> f64 {
+    let leaves_of = move |(fi, gi): (usize, usize)| {
+        let f = &files[fi].fragments[gi];
+        &files[fi].leaves[f.first_leaf..f.first_leaf + f.tokens]
+    };
+    let reference = leaves_of(members[0]);
+    if members.len() < 2 || reference.is_empty() {
+        return 1.0;
+    }
+    let total: f64 = members[1..]
+        .iter()
+        .map(|&member| {
+            let same = reference
+                .iter()
+                .zip(leaves_of(member))
+                .filter(|(a, b)| a == b)
+                .count();
+            same as f64 / reference.len() as f64
+        })
+        .sum();
+    let mean = total / (members.len() - 1) as f64;
+    (mean * 1000.0).round() / 1000.0
+}
+
+/// `files` (絶対パス、`dir` 配下) から clone group を検出する。
+/// group はトークン数の降順、同数ならハッシュ順で決定的に並ぶ。
+pub fn find_clone_groups(dir: &Path, files: &[PathBuf], min_tokens: usize) -> Vec<CloneGroup> {
+    let mut analyzed: Vec<FileFragments> = files
+        .par_iter()
+        .filter_map(|path| analyze_file(dir, path, min_tokens))
+        .collect();
+    analyzed.sort_by(|a, b| a.file.cmp(&b.file));
+
+    group_fragments(&analyzed)
+        .into_iter()
+        .map(|group| CloneGroup {
+            fingerprint: group
+                .hash
+                .iter()
+                .take(FINGERPRINT_HEX_LEN / 2)
+                .map(|b| format!("{b:02x}"))
+                .collect(),
+            tokens: group.tokens,
+            similarity: group_similarity(&analyzed, &group.members),
+            instances: group
+                .members
+                .iter()
+                .map(|&(fi, gi)| {
+                    let f = &analyzed[fi].fragments[gi];
+                    CloneInstance {
+                        file: analyzed[fi].file.clone(),
+                        start_line: f.start_line,
+                        end_line: f.end_line,
+                    }
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+/// clone group のうち「diff で追加された instance」と「diff 外に既存の instance」を
+/// 両方含むものを、追加側 1 箇所ごとの `DuplicatedCode` に変換する。
+///
+/// `added_lines` は workspace 相対パス → 追加行 (0-indexed) の set。instance の全行が
+/// 追加行なら追加側とみなす (既存関数に 1 行足しただけの instance を「新規コピー」と
+/// 誤認しないため、部分一致は既存側に倒す)。追加側同士だけの group (同一 diff 内で
+/// 2 回書いた) は既存コードの複製ではないため対象外。
+pub fn find_added_duplicates(
+    groups: &[CloneGroup],
+    added_lines: &HashMap<String, HashSet<usize>>,
+) -> Vec<DuplicatedCode> {
+    let is_added = |instance: &CloneInstance| {
+        added_lines.get(&instance.file).is_some_and(|lines| {
+            (instance.start_line..=instance.end_line).all(|line| lines.contains(&line))
+        })
+    };
+
+    let mut result: Vec<DuplicatedCode> = Vec::new();
+    for group in groups {
+        let (added, existing): (Vec<&CloneInstance>, Vec<&CloneInstance>) = group
+            .instances
+            .iter()
+            .partition(|instance| is_added(instance));
+        if added.is_empty() || existing.is_empty() {
+            continue;
+        }
+        for instance in added {
+            result.push(DuplicatedCode {
+                file: instance.file.clone(),
+                start_line: instance.start_line,
+                end_line: instance.end_line,
+                tokens: group.tokens,
+                similarity: group.similarity,
+                duplicates: existing.iter().copied().cloned().collect(),
+            });
+        }
+    }
+    result.sort_by(|a, b| {
+        a.file
+            .cmp(&b.file)
+            .then_with(|| a.start_line.cmp(&b.start_line))
+    });
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOTAL_PRICE: &str = "\
+def total_price(items):
+    result = 0
+    for item in items:
+        if item.active:
+            result += item.price * item.qty
+    return result
+";
+
+    const SUM_WEIGHTS: &str = "\
+def sum_weights(rows):
+    acc = 0
+    for row in rows:
+        if row.enabled:
+            acc += row.weight * row.count
+    return acc
+";
+
+    fn write_files(files: &[(&str, &str)]) -> (tempfile::TempDir, Vec<PathBuf>) {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut paths = Vec::new();
+        for (name, content) in files {
+            let path = dir.path().join(name);
+            std::fs::write(&path, content).expect("write fixture");
+            paths.push(path);
+        }
+        (dir, paths)
+    }
+
+    #[test]
+    fn renamed_copy_is_reported_as_type2_clone() {
+        let other =
+            format!("import os\n\n{SUM_WEIGHTS}\n\ndef unrelated():\n    return os.getcwd()\n");
+        let (dir, paths) = write_files(&[("a.py", TOTAL_PRICE), ("b.py", &other)]);
+
+        let groups = find_clone_groups(dir.path(), &paths, 20);
+
+        assert_eq!(groups.len(), 1, "{groups:?}");
+        let group = &groups[0];
+        assert_eq!(group.instances.len(), 2);
+        assert_eq!(group.instances[0].file, "a.py");
+        assert_eq!(group.instances[1].file, "b.py");
+        assert_eq!(group.instances[1].start_line, 2);
+        assert_eq!(group.instances[1].end_line, 7);
+        assert!(
+            group.similarity > 0.0 && group.similarity < 1.0,
+            "rename を伴うコピーは完全一致ではない: {}",
+            group.similarity
+        );
+        assert_eq!(group.fingerprint.len(), FINGERPRINT_HEX_LEN);
+    }
+
+    #[test]
+    fn exact_copy_has_full_similarity() {
+        let (dir, paths) = write_files(&[("a.py", TOTAL_PRICE), ("b.py", TOTAL_PRICE)]);
+
+        let groups = find_clone_groups(dir.path(), &paths, 20);
+
+        assert_eq!(
+            groups.len(),
+            1,
+            "内側の block clone は関数 clone に吸収される: {groups:?}"
+        );
+        assert_eq!(groups[0].similarity, 1.0);
+    }
+
+    #[test]
+    fn fragments_below_min_tokens_are_ignored() {
+        let (dir, paths) = write_files(&[("a.py", TOTAL_PRICE), ("b.py", SUM_WEIGHTS)]);
+
+        assert!(find_clone_groups(dir.path(), &paths, 500).is_empty());
+    }
+
+    #[test]
+    fn comments_do_not_break_clone_detection() {
+        let commented = TOTAL_PRICE.replace(
+            "    result = 0\n",
+            "    # accumulate the total\n    result = 0\n",
+        );
+        let (dir, paths) = write_files(&[("a.py", TOTAL_PRICE), ("b.py", &commented)]);
+
+        let groups = find_clone_groups(dir.path(), &paths, 20);
+
+        assert_eq!(groups.len(), 1, "{groups:?}");
+        assert_eq!(groups[0].similarity, 1.0);
+    }
+
+    fn instance(file: &str, start_line: usize, end_line: usize) -> CloneInstance {
+        CloneInstance {
+            file: file.to_string(),
+            start_line,
+            end_line,
+        }
+    }
+
+    #[test]
+    fn added_duplicates_require_an_existing_counterpart() {
+        let groups = vec![
+            CloneGroup {
+                fingerprint: "0".repeat(FINGERPRINT_HEX_LEN),
+                tokens: 60,
+                similarity: 0.8,
+                instances: vec![instance("new.rs", 10, 20), instance("old.rs", 0, 10)],
+            },
+            // 同一 diff 内で 2 回書いただけの group は既存コードの複製ではない
+            CloneGroup {
+                fingerprint: "1".repeat(FINGERPRINT_HEX_LEN),
+                tokens: 55,
+                similarity: 1.0,
+                instances: vec![instance("new.rs", 30, 35), instance("new.rs", 40, 45)],
+            },
+        ];
+        let added: HashMap<String, HashSet<usize>> =
+            HashMap::from([("new.rs".to_string(), (10..=45).collect())]);
+
+        let dupes = find_added_duplicates(&groups, &added);
+
+        assert_eq!(dupes.len(), 1, "{dupes:?}");
+        assert_eq!(dupes[0].file, "new.rs");
+        assert_eq!(dupes[0].start_line, 10);
+        assert_eq!(dupes[0].duplicates, vec![instance("old.rs", 0, 10)]);
+    }
+
+    #[test]
+    fn partially_added_instance_is_treated_as_existing() {
+        let groups = vec![CloneGroup {
+            fingerprint: "0".repeat(FINGERPRINT_HEX_LEN),
+            tokens: 60,
+            similarity: 1.0,
+            instances: vec![instance("a.rs", 0, 10), instance("b.rs", 0, 10)],
+        }];
+        // a.rs は 1 行だけ追加 (既存関数の編集)、b.rs は既存のまま
+        let added: HashMap<String, HashSet<usize>> =
+            HashMap::from([("a.rs".to_string(), HashSet::from([5]))]);
+
+        assert!(find_added_duplicates(&groups, &added).is_empty());
+    }
+}
@@ -0,0 +1,64 @@
+//! ファイル内の関数 / メソッドの行範囲。
+//!
+//! 参照行を囲む関数を引く処理 (affected-tests / review のリスクスコア / `--transitive-depth`)
+//! で共有する。関数ごとの付加情報 (テストかどうか、公開かどうか等) は呼び出し側が決める。
+
+use std::path::Path;
+
+use camino::Utf8Path;
+use tree_sitter::Node;
+
+use crate::engine::parser;
+use crate::engine::symbols::extract_symbols;
+use crate::language::LangId;
+use crate::models::symbol::{Symbol, SymbolKind};
+
+/// 関数 / メソッド 1 つ分の行範囲 (0-indexed) と、呼び出し側が付けた情報 `info`。
+pub(crate) struct FunctionSpan<T> {
+    pub(crate) name: String,
+    pub(crate) start_line: usize,
+    pub(crate) end_line: usize,
+    pub(crate) info: T,
+}
+
+/// `path` の関数 / メソッドを列挙する。`annotate` は `(root, source, lang, symbol)` から
+/// 各関数の `info` を作る。読めない / 非対応言語のファイルは `None`。
+pub(crate) fn load_functions<T>(
+    path: &Path,
+    mut annotate: impl FnMut(Node<'_>, &[u8], LangId, &Symbol) -> T,
+) -> Option<(LangId, Vec<FunctionSpan<T>>)> {
+    let utf8 = Utf8Path::from_path(path)?;
+    let source = parser::read_file(utf8).ok()?;
+    let (tree, lang) = parser::parse_file(utf8, &source).ok()?;
+    let root = tree.root_node();
+    let symbols = extract_symbols(root, &source, lang).ok()?;
+
+    let mut functions = Vec::new();
+    collect_functions(&symbols, &mut |sym| {
+        functions.push(FunctionSpan {
+            name: sym.name.clone(),
+            start_line: sym.range.start.line,
+            end_line: sym.range.end.line,
+            info: annotate(root, &source, lang, sym),
+        });
+    });
+    Some((lang, functions))
+}
+
+/// `line` を含む最も内側の関数。
+pub(crate) fn enclosing<T>(functions: &[FunctionSpan<T>], line: usize) -> Option<&FunctionSpan<T>> {
+    functions
+        .iter()
+        .filter(|f| f.start_line <= line && line <= f.end_line)
+        .min_by_key(|f| f.end_line - f.start_line)
+}
+
+/// シンボル木を深さ優先で辿り、関数 / メソッドだけを `f` に渡す。
+pub(crate) fn collect_functions(symbols: &[Symbol], f: &mut impl FnMut(&Symbol)) {
+    for sym in symbols {
+        if matches!(sym.kind, SymbolKind::Function | SymbolKind::Method) {
+            f(sym);
+        }
+        collect_functions(&sym.children, f);
+    }
+}
@@ -17,8 +17,9 @@ struct Rule {
     set: bool,
 }
 
+/// gitignore 系のパスパターン。CODEOWNERS (`codeowners.rs`) も同じ規則で評価する。
 #[derive(Debug, Clone)]
-struct Pattern {
+pub(crate) struct Pattern {
     /// `/` 区切りで分解したセグメント。
     segments: Vec<Segment>,
     /// パターンが `/` で始まる、または途中に `/` を含む場合 true。
@@ -110,7 +111,7 @@ fn match_linguist_generated(attr: &str) -> Option<bool> {
 }
 
 impl Pattern {
-    fn parse(raw: &str) -> Self {
+    pub(crate) fn parse(raw: &str) -> Self {
         let trimmed = raw.trim_start_matches('/');
         let anchored = raw.starts_with('/') || raw.trim_end_matches('/').contains('/');
         let segments = trimmed
@@ -127,7 +128,7 @@ impl Pattern {
         Self { segments, anchored }
     }
 
-    fn matches(&self, path: &str) -> bool {
+    pub(crate) fn matches(&self, path: &str) -> bool {
         let parts: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
         if self.anchored {
             match_segments(&self.segments, &parts)
@@ -137,6 +138,14 @@ impl Pattern {
             (0..=parts.len()).any(|start| match_segments(&self.segments, &parts[start..]))
         }
     }
+
+    /// 末尾セグメントが `*` / `?` を含む glob か (`docs/*` 等)。
+    pub(crate) fn ends_with_wildcard(&self) -> bool {
+        matches!(
+            self.segments.last(),
+            Some(Segment::Glob(pat)) if pat.contains(['*', '?'])
+        )
+    }
 }
 
 fn match_segments(segments: &[Segment], parts: &[&str]) -> bool {
@@ -6,6 +6,7 @@ mod pass3;
 mod reexport_move;
 mod signature;
 pub(crate) mod test_context;
+mod transitive;
 mod types;
 
 use std::collections::{HashMap, HashSet};
@@ -26,6 +27,8 @@ use reexport_move::ReexportMoveIndex;
 use signature::detect_signature_changes;
 use test_context::is_in_test_context;
 
+pub(crate) use transitive::TransitiveCallers;
+
 struct FileContext {
     new_path: String,
     lang_id: LangId,
@@ -257,6 +260,7 @@ fn assemble_without_cross_file(
             impacted_callers: Vec::new(),
             low_confidence_callers: Vec::new(),
             informational_callers: Vec::new(),
+            transitive_callers: Vec::new(),
         })
         .collect()
 }
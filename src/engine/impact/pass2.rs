@@ -163,14 +163,7 @@ fn prepare_scan(
     // ユーザー指定の `options.exclude_dirs` / `options.exclude_globs` は
     // デフォルト除外に **追加** で適用される (`INCLUDE_VENDOR=1` 時はデフォルトだけ
     // 解除され、ユーザー指定は引き続き効く)。
-    let mut excluded_dirs: Vec<&str> = if impact_include_vendor() {
-        Vec::new()
-    } else {
-        IMPACT_DEFAULT_EXCLUDED_DIRS.to_vec()
-    };
-    excluded_dirs.extend(options.exclude_dirs.iter().map(String::as_str));
-    excluded_dirs.sort_unstable();
-    excluded_dirs.dedup();
+    let excluded_dirs = impact_excluded_dirs(options);
     let excluded_globs: Vec<&str> = options.exclude_globs.iter().map(String::as_str).collect();
     let files =
         refs::collect_files_with_excludes(dir, None, &excluded_dirs, &excluded_globs).ok()?;
@@ -186,6 +179,22 @@ fn prepare_scan(
     Some(Pass2Scan { ac, files, pool })
 }
 
+/// impact の cross-file 検索で除外するディレクトリ名 (デフォルト除外 + ユーザー指定)。
+/// transitive 伝播 (`transitive.rs`) も同じ集合で参照を絞る。
+pub(super) fn impact_excluded_dirs(
+    options: &crate::models::impact::ContextAnalysisOptions,
+) -> Vec<&str> {
+    let mut excluded_dirs: Vec<&str> = if impact_include_vendor() {
+        Vec::new()
+    } else {
+        IMPACT_DEFAULT_EXCLUDED_DIRS.to_vec()
+    };
+    excluded_dirs.extend(options.exclude_dirs.iter().map(String::as_str));
+    excluded_dirs.sort_unstable();
+    excluded_dirs.dedup();
+    excluded_dirs
+}
+
 /// `TARGET_FILE_CACHE_SIZE` 上限の per-worker LRU キャッシュを作る。
 fn new_target_file_cache<V>() -> LruCache<String, V> {
     LruCache::new(NonZeroUsize::new(TARGET_FILE_CACHE_SIZE).expect("cache size is non-zero"))
@@ -113,6 +113,7 @@ pub(super) fn build_file_impact(
                 line,
                 symbols,
                 confidence: None,
+                owners: Vec::new(),
             }
         })
         .collect();
@@ -155,6 +156,7 @@ pub(super) fn build_file_impact(
                 line,
                 symbols,
                 confidence: Some("low".to_string()),
+                owners: Vec::new(),
             })
         })
         .collect();
@@ -187,6 +189,7 @@ pub(super) fn build_file_impact(
                 line,
                 symbols,
                 confidence: Some("informational".to_string()),
+                owners: Vec::new(),
             })
         })
         .collect();
@@ -200,6 +203,7 @@ pub(super) fn build_file_impact(
         impacted_callers,
         low_confidence_callers,
         informational_callers,
+        transitive_callers: Vec::new(),
     }
 }
 
@@ -0,0 +1,311 @@
+//! `--transitive-depth`: 公開ラッパー経由の間接的な影響伝播。
+//!
+//! 直接の caller (`impacted_callers`) を含む関数が公開 (`is_symbol_exported`) なら、
+//! その関数自体の振る舞いも変わったとみなし、さらにその呼び出し元を辿る。
+//! 結果は `transitive_callers` に入り、blocking 判定 (`impacted_callers`) には影響しない。
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::engine::functions::{FunctionSpan, enclosing, load_functions};
+use crate::engine::paths::relative_path;
+use crate::engine::{refs, symbols};
+use crate::models::impact::{ContextAnalysisOptions, FileImpact, TransitiveCaller};
+use crate::models::reference::RefKind;
+
+use super::pass2::impact_excluded_dirs;
+
+/// diff 全体で共有する間接 caller の探索状態。
+///
+/// 参照元として許すファイル集合 (ワークスペース走査) と、ファイルごとの関数一覧
+/// (`info` は公開有無) を変更ファイル間で使い回す。
+pub(crate) struct TransitiveCallers<'a> {
+    dir: &'a Path,
+    options: &'a ContextAnalysisOptions,
+    allowed: HashSet<String>,
+    functions: HashMap<String, Vec<FunctionSpan<bool>>>,
+}
+
+impl<'a> TransitiveCallers<'a> {
+    /// impact と同じ除外 (vendor / build artifact / ユーザー指定) でワークスペースを 1 度だけ走査する。
+    pub(crate) fn new(dir: &'a Path, options: &'a ContextAnalysisOptions) -> Result<Self> {
+        let excluded_dirs = impact_excluded_dirs(options);
+        let excluded_globs: Vec<&str> = options.exclude_globs.iter().map(String::as_str).collect();
+        let allowed =
+            refs::collect_files_with_excludes(dir, None, &excluded_dirs, &excluded_globs)?
+                .into_iter()
+                .filter_map(|path| path.to_str().map(str::to_string))
+                .collect();
+        Ok(Self {
+            dir,
+            options,
+            allowed,
+            functions: HashMap::new(),
+        })
+    }
+
+    /// `path` (dir 相対) の `line` を含む最も内側の関数。読めない / 非対応言語は `None`。
+    fn enclosing(&mut self, path: &str, line: usize) -> Option<&FunctionSpan<bool>> {
+        let dir = self.dir;
+        let functions = self.functions.entry(path.to_string()).or_insert_with(|| {
+            load_functions(&dir.join(path), |root, source, lang, sym| {
+                symbols::is_symbol_exported(root, source, lang, &sym.range)
+            })
+            .map(|(_, functions)| functions)
+            .unwrap_or_default()
+        });
+        enclosing(functions, line)
+    }
+
+    /// `impact` (パスは `dir` 相対化済み) の直接 caller から公開関数を辿り、
+    /// `options.transitive_depth` 段までの間接 caller を返す。
+    ///
+    /// 各段で「公開関数名 → そこに至る経路」を集め、参照検索は 1 段につき 1 回の
+    /// `find_references_batch` で済ませる。同じ関数名は最初 (最短経路) に到達したものだけを
+    /// 辿り、直接 caller と同じ位置・自己再帰・Definition は除く。
+    pub(crate) fn collect(&mut self, impact: &FileImpact) -> Result<Vec<TransitiveCaller>> {
+        let max_depth = self.options.transitive_depth;
+        if max_depth == 0 || impact.impacted_callers.is_empty() {
+            return Ok(Vec::new());
+        }
+        let dir = self.dir;
+
+        let mut seen_locations: HashSet<(String, usize)> = impact
+            .impacted_callers
+            .iter()
+            .map(|c| (c.path.clone(), c.line))
+            .collect();
+        let mut visited: HashSet<String> = impact
+            .affected_symbols
+            .iter()
+            .map(|s| s.name.clone())
+            .collect();
+
+        // 公開関数名 → 変更シンボルからその関数までの経路。
+        let mut frontier: Vec<(String, Vec<String>)> = Vec::new();
+        // 経路の起点は caller が参照する変更シンボル。不明なら変更ファイルの唯一の
+        // 変更シンボルを使い、それも決まらない caller は起点なしの経路を作らず飛ばす。
+        let sole_affected = match impact.affected_symbols.as_slice() {
+            [only] => Some(only.name.clone()),
+            _ => None,
+        };
+        for caller in &impact.impacted_callers {
+            let Some(origin) = caller.symbols.first().cloned().or(sole_affected.clone()) else {
+                continue;
+            };
+            let Some(function) = self.enclosing(&caller.path, caller.line) else {
+                continue;
+            };
+            if !function.info || !visited.insert(function.name.clone()) {
+                continue;
+            }
+            frontier.push((function.name.clone(), vec![origin, function.name.clone()]));
+        }
+
+        let mut result = Vec::new();
+        for depth in 1..=max_depth {
+            if frontier.is_empty() {
+                break;
+            }
+            let names: Vec<String> = frontier.iter().map(|(name, _)| name.clone()).collect();
+            let chains: HashMap<String, Vec<String>> = frontier.drain(..).collect();
+            let found = refs::find_references_batch(&names, dir, None)?;
+
+            let mut found: Vec<(String, Vec<_>)> = found.into_iter().collect();
+            found.sort_by(|a, b| a.0.cmp(&b.0));
+            for (target, references) in found {
+                let Some(chain) = chains.get(&target) else {
+                    continue;
+                };
+                for reference in references {
+                    if matches!(reference.kind, Some(RefKind::Definition))
+                        || !self.allowed.contains(&reference.path)
+                    {
+                        continue;
+                    }
+                    let Some(rel) = relative_path(dir, Path::new(&reference.path)) else {
+                        continue;
+                    };
+                    if !seen_locations.insert((rel.clone(), reference.line)) {
+                        continue;
+                    }
+                    let enclosing = self
+                        .enclosing(&rel, reference.line)
+                        .map(|f| (f.name.clone(), f.info));
+                    // 公開関数自身の再帰呼び出しは新しい影響ではない。
+                    if enclosing.as_ref().is_some_and(|(name, _)| *name == target) {
+                        continue;
+                    }
+                    let name = enclosing
+                        .as_ref()
+                        .map(|(name, _)| name.clone())
+                        .unwrap_or_default();
+                    if let Some((next, true)) = &enclosing
+                        && depth < max_depth
+                        && visited.insert(next.clone())
+                    {
+                        let mut next_chain = chain.clone();
+                        next_chain.push(next.clone());
+                        frontier.push((next.clone(), next_chain));
+                    }
+                    result.push(TransitiveCaller {
+                        path: rel,
+                        name,
+                        line: reference.line,
+                        chain: chain.clone(),
+                        depth,
+                        owners: Vec::new(),
+                    });
+                }
+            }
+        }
+
+        result.sort_by(|a, b| {
+            a.depth
+                .cmp(&b.depth)
+                .then_with(|| a.path.cmp(&b.path))
+                .then_with(|| a.line.cmp(&b.line))
+        });
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::impact::{AffectedSymbol, ImpactedCaller};
+
+    fn impact_with_caller(path: &str, name: &str, line: usize) -> FileImpact {
+        FileImpact {
+            path: "src/util.rs".to_string(),
+            hunks: Vec::new(),
+            affected_symbols: Vec::new(),
+            signature_changes: Vec::new(),
+            impacted_callers: vec![ImpactedCaller {
+                path: path.to_string(),
+                name: name.to_string(),
+                line,
+                symbols: vec!["helper".to_string()],
+                confidence: None,
+                owners: Vec::new(),
+            }],
+            low_confidence_callers: Vec::new(),
+            informational_callers: Vec::new(),
+            transitive_callers: Vec::new(),
+        }
+    }
+
+    fn write(root: &Path, rel: &str, content: &str) {
+        let path = root.join(rel);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn propagates_through_exported_wrapper_only() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = std::fs::canonicalize(tmp.path()).unwrap();
+        write(
+            &root,
+            "src/lib.rs",
+            "pub fn run() {\n    helper();\n}\n\nfn internal() {\n    helper();\n}\n",
+        );
+        write(
+            &root,
+            "src/app.rs",
+            "pub fn start() {\n    run();\n}\n\nfn main() {\n    start();\n}\n",
+        );
+        let line = index_line(&root, "src/lib.rs", "run");
+        let impact = impact_with_caller("src/lib.rs", "run", line);
+
+        let collect = |transitive_depth| {
+            let options = ContextAnalysisOptions {
+                transitive_depth,
+                ..Default::default()
+            };
+            TransitiveCallers::new(&root, &options)
+                .unwrap()
+                .collect(&impact)
+                .unwrap()
+        };
+        let depth1 = collect(1);
+        assert_eq!(depth1.len(), 1, "{depth1:?}");
+        assert_eq!(depth1[0].path, "src/app.rs");
+        assert_eq!(depth1[0].name, "start");
+        assert_eq!(depth1[0].chain, ["helper", "run"]);
+        assert_eq!(depth1[0].depth, 1);
+
+        let depth2 = collect(2);
+        let second: Vec<_> = depth2.iter().filter(|c| c.depth == 2).collect();
+        assert_eq!(second.len(), 1, "{depth2:?}");
+        assert_eq!(second[0].name, "main");
+        assert_eq!(second[0].chain, ["helper", "run", "start"]);
+    }
+
+    #[test]
+    fn private_enclosing_function_stops_propagation() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = std::fs::canonicalize(tmp.path()).unwrap();
+        write(&root, "src/lib.rs", "fn internal() {\n    helper();\n}\n");
+        write(&root, "src/app.rs", "fn main() {\n    internal();\n}\n");
+        let line = index_line(&root, "src/lib.rs", "internal");
+        let impact = impact_with_caller("src/lib.rs", "internal", line);
+
+        let options = ContextAnalysisOptions {
+            transitive_depth: 3,
+            ..Default::default()
+        };
+        let result = TransitiveCallers::new(&root, &options)
+            .unwrap()
+            .collect(&impact)
+            .unwrap();
+        assert!(result.is_empty(), "{result:?}");
+    }
+
+    #[test]
+    fn caller_without_symbols_uses_sole_affected_symbol_or_is_skipped() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = std::fs::canonicalize(tmp.path()).unwrap();
+        write(&root, "src/lib.rs", "pub fn run() {\n    helper();\n}\n");
+        write(&root, "src/app.rs", "fn main() {\n    run();\n}\n");
+        let line = index_line(&root, "src/lib.rs", "run");
+        let mut impact = impact_with_caller("src/lib.rs", "run", line);
+        impact.impacted_callers[0].symbols.clear();
+
+        let options = ContextAnalysisOptions {
+            transitive_depth: 1,
+            ..Default::default()
+        };
+        let collect = |impact: &FileImpact| {
+            TransitiveCallers::new(&root, &options)
+                .unwrap()
+                .collect(impact)
+                .unwrap()
+        };
+        let result = collect(&impact);
+        assert!(result.is_empty(), "{result:?}");
+
+        impact.affected_symbols.push(AffectedSymbol {
+            name: "helper".to_string(),
+            kind: "function".to_string(),
+            change_type: "modified".to_string(),
+        });
+        let result = collect(&impact);
+        assert_eq!(result.len(), 1, "{result:?}");
+        assert_eq!(result[0].chain, ["helper", "run"]);
+    }
+
+    /// `function` 内の `helper()` 呼び出し行 (参照検索と同じ行番号系)。
+    fn index_line(root: &Path, rel: &str, function: &str) -> usize {
+        let found = refs::find_references_batch(&["helper".to_string()], root, None).unwrap();
+        let (_, functions) = load_functions(&root.join(rel), |_, _, _, _| ()).unwrap();
+        found["helper"]
+            .iter()
+            .filter(|r| r.path.ends_with(rel))
+            .map(|r| r.line)
+            .find(|&line| enclosing(&functions, line).is_some_and(|f| f.name == function))
+            .expect("helper call inside function")
+    }
+}
@@ -2,20 +2,31 @@ pub mod angular_template_refs;
 pub mod bash_trap_refs;
 pub(crate) mod bounded_read;
 pub mod calls;
+pub(crate) mod cargo_manifest;
 pub mod cochange;
+pub mod codeowners;
 pub mod diff;
+pub mod dupes;
 pub mod extractor;
+pub(crate) mod functions;
 pub mod generated;
 pub mod gitattributes;
 pub mod impact;
 pub mod imports;
 pub mod lexer;
 pub mod lint;
+pub mod parse_health;
 pub mod parser;
+pub(crate) mod paths;
 pub mod phpunit_refs;
+pub mod preset_refs;
 pub mod query_cache;
+pub mod rails_refs;
 pub mod refs;
 pub mod sequence;
 pub mod snippet;
+pub mod source;
+pub mod spring_refs;
 pub mod symbols;
+pub mod unused_imports;
 pub mod xml_refs;
@@ -0,0 +1,155 @@
+//! ファイル単位の parse 健全性 (ERROR / MISSING ノード) の収集。
+//!
+//! grammar が新しい構文に追従していないと、tree-sitter は該当区間を ERROR ノードに
+//! 落とし、symbols / refs / dead-code はその区間を静かに取りこぼす。ここでは
+//! その箇所を数えて位置と一緒に返し、利用者が結果の信頼度を判断できるようにする。
+
+use std::path::{Path, PathBuf};
+
+use camino::Utf8Path;
+use rayon::prelude::*;
+use tree_sitter::Node;
+
+use crate::models::parse_health::{FileParseHealth, ParseErrorKind, ParseErrorLocation};
+
+use super::parser;
+use super::paths::relative_path;
+
+/// `root` 配下の ERROR / MISSING ノードを出現順に集める。
+///
+/// ERROR ノードの内側は 1 つの失敗区間とみなし、入れ子の ERROR / MISSING は数えない
+/// (1 箇所の構文エラーで件数が膨れ上がるのを避ける)。
+pub fn collect_parse_errors(root: Node<'_>) -> Vec<ParseErrorLocation> {
+    let mut locations = Vec::new();
+    if root.has_error() {
+        collect_into(root, &mut locations);
+    }
+    locations
+}
+
+fn collect_into(node: Node<'_>, locations: &mut Vec<ParseErrorLocation>) {
+    if node.is_error() || node.is_missing() {
+        let pos = node.start_position();
+        let missing = node.is_missing();
+        locations.push(ParseErrorLocation {
+            kind: if missing {
+                ParseErrorKind::Missing
+            } else {
+                ParseErrorKind::Error
+            },
+            line: pos.row,
+            column: pos.column,
+            expected: missing.then(|| node.kind().to_string()),
+        });
+        return;
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if child.has_error() || child.is_missing() {
+            collect_into(child, locations);
+        }
+    }
+}
+
+/// `files` (絶対パス、`dir` 配下) の parse 状態を並列に調べる。
+///
+/// 対応言語でないファイル・lexer-only 言語・読めないファイルは結果に含めない。
+/// 健全なファイルも `errors == 0` で含める (言語ごとの走査数の集計に使うため)。
+/// 戻り値はパス順。
+pub fn scan_parse_health(dir: &Path, files: &[PathBuf]) -> Vec<FileParseHealth> {
+    let mut results: Vec<FileParseHealth> = files
+        .par_iter()
+        .filter_map(|path| file_parse_health(dir, path))
+        .collect();
+    results.sort_by(|a, b| a.path.cmp(&b.path));
+    results
+}
+
+fn file_parse_health(dir: &Path, path: &Path) -> Option<FileParseHealth> {
+    let utf8 = Utf8Path::from_path(path)?;
+    let source = parser::read_file(utf8).ok()?;
+    let lang = parser::detect_lang(utf8, &source).ok()?;
+    if lang.is_lexer_only() {
+        return None;
+    }
+    let mut health = FileParseHealth {
+        path: relative_path(dir, path)?,
+        lang,
+        errors: 0,
+        missing: 0,
+        locations: Vec::new(),
+        failed: false,
+    };
+    let Ok(tree) = parser::parse_source(&source, lang) else {
+        // タイムアウト等で木が得られなかった。ファイル全体が解析結果から欠ける。
+        health.failed = true;
+        return Some(health);
+    };
+    health.locations = collect_parse_errors(tree.root_node());
+    health.missing = health
+        .locations
+        .iter()
+        .filter(|loc| loc.kind == ParseErrorKind::Missing)
+        .count();
+    health.errors = health.locations.len() - health.missing;
+    Some(health)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::LangId;
+
+    fn locations(src: &str, lang: LangId) -> Vec<ParseErrorLocation> {
+        let tree = parser::parse_source(src.as_bytes(), lang).unwrap();
+        collect_parse_errors(tree.root_node())
+    }
+
+    #[test]
+    fn clean_source_has_no_locations() {
+        assert!(locations("fn main() {}\n", LangId::Rust).is_empty());
+    }
+
+    #[test]
+    fn reports_error_region_with_position() {
+        let found = locations(
+            "def ok():\n    pass\n\ndef broken(:\n    pass\n",
+            LangId::Python,
+        );
+        assert!(!found.is_empty());
+        assert!(found.iter().all(|loc| loc.line >= 3), "{found:?}");
+        assert!(found.iter().any(|loc| loc.kind == ParseErrorKind::Error));
+    }
+
+    #[test]
+    fn reports_missing_token_kind() {
+        let found = locations("int main() { return 0 }\n", LangId::C);
+        let missing: Vec<_> = found
+            .iter()
+            .filter(|loc| loc.kind == ParseErrorKind::Missing)
+            .collect();
+        assert_eq!(missing.len(), 1, "{found:?}");
+        assert_eq!(missing[0].expected.as_deref(), Some(";"));
+        assert_eq!(missing[0].line, 0);
+    }
+
+    #[test]
+    fn scan_skips_unsupported_files_and_relativizes_paths() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("ok.rs"), "fn ok() {}\n").unwrap();
+        std::fs::write(dir.path().join("bad.rs"), "fn bad( {}\n").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "plain text\n").unwrap();
+        let files: Vec<PathBuf> = ["ok.rs", "bad.rs", "notes.txt"]
+            .iter()
+            .map(|name| dir.path().join(name))
+            .collect();
+
+        let health = scan_parse_health(dir.path(), &files);
+        assert_eq!(health.len(), 2);
+        assert_eq!(health[0].path, "bad.rs");
+        assert!(health[0].is_unhealthy());
+        assert_eq!(health[1].path, "ok.rs");
+        assert!(!health[1].is_unhealthy());
+    }
+}
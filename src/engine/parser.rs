@@ -169,6 +169,15 @@ pub fn parse_source(source: &[u8], lang_id: LangId) -> Result<Tree> {
     })
 }
 
+/// `node` の直下で最初に現れる `kind` の named child。
+pub(crate) fn child_of_kind<'tree>(
+    node: tree_sitter::Node<'tree>,
+    kind: &str,
+) -> Option<tree_sitter::Node<'tree>> {
+    let mut cursor = node.walk();
+    node.named_children(&mut cursor).find(|c| c.kind() == kind)
+}
+
 /// ファイルサイズ上限: 100 MB。
 const MAX_FILE_SIZE: u64 = 100 * 1024 * 1024;
 
@@ -0,0 +1,15 @@
+use std::path::Path;
+
+/// `dir` 配下の `path` を workspace 相対・`/` 区切りのパスにする (diff のパス表記と突き合わせるため)。
+/// `dir` の外にあるパスは `None`。
+///
+/// Unix ではバックスラッシュがファイル名の正当な文字なので、`MAIN_SEPARATOR` が
+/// `/` のプラットフォームでは何も置換しない。
+pub(crate) fn relative_path(dir: &Path, path: &Path) -> Option<String> {
+    let rel = path.strip_prefix(dir).ok()?.to_string_lossy();
+    Some(if std::path::MAIN_SEPARATOR == '/' {
+        rel.into_owned()
+    } else {
+        rel.replace(std::path::MAIN_SEPARATOR, "/")
+    })
+}
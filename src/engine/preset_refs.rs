@@ -0,0 +1,250 @@
+//! ユーザー定義フレームワークプリセットの規約で、実行時に呼ばれるシンボルを収集する。
+//!
+//! プリセット (`.astro-sight/presets/<name>.yaml`) のうち、ファイル単位の glob と
+//! annotation 以外の規約をここで扱う。
+//!
+//! - `base_classes`: これらを継承するクラス (宣言と同じ行に継承句があるもの) と、その
+//!   メンバーを生存扱いにする
+//! - `method_patterns`: 名前が正規表現に部分一致するシンボルを生存扱いにする
+//! - `string_refs`: `route('{name}')` のような呼び出しの文字列リテラルから名前を拾う
+//!
+//! Rails / Spring の収集と同じく、言語パーサを使わないテキスト走査で多めに集める。
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use anyhow::Result;
+use regex::Regex;
+
+use crate::engine::bounded_read::read_utf8_file_limited;
+use crate::error::{AstroError, ErrorCode};
+
+/// 走査するファイル 1 件あたりの最大サイズ (1MB)。
+const MAX_PRESET_SCAN_FILE_SIZE: u64 = 1_048_576;
+
+/// `string_refs` の `{name}` が捕まえる文字列 (`users.show` / `UserController@index` 等)。
+const NAME_CAPTURE: &str = r"([A-Za-z_$][A-Za-z0-9_$.:@/\\-]*)";
+
+/// プリセットの規約をコンパイルしたもの。
+#[derive(Debug, Clone, Default)]
+pub struct PresetMatcher {
+    base_classes: Vec<String>,
+    method_patterns: Vec<Regex>,
+    string_refs: Vec<Regex>,
+}
+
+impl PresetMatcher {
+    /// `method_patterns` は正規表現、`string_refs` は `{name}` を 1 つ含む呼び出しの型として
+    /// コンパイルする。不正な値は `InvalidRequest`。
+    pub fn new(
+        preset: &str,
+        base_classes: &[String],
+        method_patterns: &[String],
+        string_refs: &[String],
+    ) -> Result<Self> {
+        let method_patterns = method_patterns
+            .iter()
+            .map(|pattern| {
+                Regex::new(pattern).map_err(|err| {
+                    invalid_preset(
+                        preset,
+                        format!("invalid method_patterns entry `{pattern}`: {err}"),
+                    )
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let string_refs =
+            string_refs
+                .iter()
+                .map(|template| {
+                    string_ref_regex(template).ok_or_else(|| {
+                invalid_preset(
+                    preset,
+                    format!("string_refs entry `{template}` must contain `{{name}}` exactly once"),
+                )
+            })
+                })
+                .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            base_classes: base_classes.to_vec(),
+            method_patterns,
+            string_refs,
+        })
+    }
+}
+
+/// プリセット規約で生存扱いになる名前。
+#[derive(Debug, Clone, Default)]
+pub struct PresetRefs {
+    /// `string_refs` で拾った名前。
+    names: HashSet<String>,
+    /// `base_classes` を継承するクラス名。
+    runtime_classes: HashSet<String>,
+    method_patterns: Vec<Regex>,
+}
+
+impl PresetRefs {
+    /// bare name / qualname (`Container.method`) のいずれかがプリセット規約で参照されているか。
+    pub fn contains_symbol(&self, name: &str) -> bool {
+        let (container, bare) = match name.rsplit_once('.') {
+            Some((container, bare)) => (
+                Some(container.rsplit('.').next().unwrap_or(container)),
+                bare,
+            ),
+            None => (None, name),
+        };
+        self.names.contains(name)
+            || self.names.contains(bare)
+            || self.runtime_classes.contains(bare)
+            || container.is_some_and(|c| self.runtime_classes.contains(c))
+            || self.method_patterns.iter().any(|re| re.is_match(bare))
+    }
+}
+
+/// `dir` 配下を走査してプリセット規約の参照を集める。
+pub fn collect_preset_references(dir: &Path, matcher: &PresetMatcher) -> PresetRefs {
+    let mut refs = PresetRefs {
+        method_patterns: matcher.method_patterns.clone(),
+        ..PresetRefs::default()
+    };
+    if matcher.base_classes.is_empty() && matcher.string_refs.is_empty() {
+        return refs;
+    }
+    let walker = ignore::WalkBuilder::new(dir).build();
+    for entry in walker.flatten() {
+        if !entry.file_type().is_some_and(|t| t.is_file()) {
+            continue;
+        }
+        // バイナリ / 非 UTF-8 ファイルは読めずに None になる。
+        if let Some(content) = read_utf8_file_limited(entry.path(), MAX_PRESET_SCAN_FILE_SIZE) {
+            extract_preset_refs(&content, matcher, &mut refs);
+        }
+    }
+    refs
+}
+
+fn extract_preset_refs(content: &str, matcher: &PresetMatcher, refs: &mut PresetRefs) {
+    for re in &matcher.string_refs {
+        for caps in re.captures_iter(content) {
+            let Some(name) = caps.get(1) else { continue };
+            let name = name.as_str();
+            refs.names.insert(name.to_string());
+            // `UserController@index` / `users.show` の各部分も名前として扱う。
+            refs.names.extend(
+                name.split(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '$'))
+                    .filter(|part| !part.is_empty())
+                    .map(str::to_string),
+            );
+        }
+    }
+    if matcher.base_classes.is_empty() {
+        return;
+    }
+    for line in content.lines() {
+        let mut words = identifiers(line)
+            .skip_while(|word| *word != "class")
+            .skip(1);
+        let Some(class_name) = words.next() else {
+            continue;
+        };
+        if words.any(|word| matcher.base_classes.iter().any(|base| base == word)) {
+            refs.runtime_classes.insert(class_name.to_string());
+        }
+    }
+}
+
+/// `route('{name}')` → `route\s*\(\s*['"](...)['"]\s*\)`。引用符はどちらでも一致させ、
+/// 空白は任意長にする。`{name}` がちょうど 1 つでなければ `None`。
+fn string_ref_regex(template: &str) -> Option<Regex> {
+    let (before, after) = template.split_once("{name}")?;
+    if after.contains("{name}") {
+        return None;
+    }
+    let mut pattern = literal_pattern(before);
+    pattern.push_str(NAME_CAPTURE);
+    pattern.push_str(&literal_pattern(after));
+    Regex::new(&pattern).ok()
+}
+
+fn literal_pattern(text: &str) -> String {
+    let mut pattern = String::new();
+    for c in text.chars() {
+        match c {
+            '\'' | '"' => pattern.push_str(r#"['"]"#),
+            c if c.is_whitespace() => pattern.push_str(r"\s*"),
+            // 開き括弧の後ろ / 閉じ括弧の前の空白も許す。
+            '(' => pattern.push_str(r"\(\s*"),
+            ')' => pattern.push_str(r"\s*\)"),
+            c => pattern.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    pattern
+}
+
+fn identifiers(line: &str) -> impl Iterator<Item = &str> {
+    line.split(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '$'))
+        .filter(|word| !word.is_empty())
+}
+
+fn invalid_preset(preset: &str, message: String) -> anyhow::Error {
+    AstroError::new(
+        ErrorCode::InvalidRequest,
+        format!("Framework preset `{preset}`: {message}"),
+    )
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matcher(base: &[&str], methods: &[&str], strings: &[&str]) -> PresetMatcher {
+        let owned = |items: &[&str]| items.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        PresetMatcher::new("test", &owned(base), &owned(methods), &owned(strings)).unwrap()
+    }
+
+    #[test]
+    fn string_refs_capture_names_and_their_parts() {
+        let m = matcher(&[], &[], &["route('{name}')", "dispatch(\"{name}\")"]);
+        let mut refs = PresetRefs::default();
+        extract_preset_refs(
+            "url = route( \"reports.export\" )\ndispatch('SyncOrders@run')\nroute(other)\n",
+            &m,
+            &mut refs,
+        );
+        for name in ["reports.export", "export", "SyncOrders", "run"] {
+            assert!(
+                refs.names.contains(name),
+                "{name} missing from {:?}",
+                refs.names
+            );
+        }
+        assert!(!refs.names.contains("other"));
+    }
+
+    #[test]
+    fn base_classes_mark_subclasses_and_members() {
+        let m = matcher(&["AcmeJob"], &["^on[A-Z]"], &[]);
+        let mut refs = PresetRefs {
+            method_patterns: m.method_patterns.clone(),
+            ..PresetRefs::default()
+        };
+        extract_preset_refs(
+            "class NightlySync(jobs.AcmeJob):\n    pass\nexport class Plain extends Base {}\n",
+            &m,
+            &mut refs,
+        );
+        assert!(refs.contains_symbol("NightlySync"));
+        assert!(refs.contains_symbol("NightlySync.run"));
+        assert!(refs.contains_symbol("Plain.onStart"));
+        assert!(!refs.contains_symbol("Plain.start"));
+        assert!(!refs.contains_symbol("Plain"));
+    }
+
+    #[test]
+    fn invalid_patterns_are_rejected() {
+        let owned = |s: &str| vec![s.to_string()];
+        assert!(PresetMatcher::new("x", &[], &owned("(unclosed"), &[]).is_err());
+        assert!(PresetMatcher::new("x", &[], &[], &owned("route('name')")).is_err());
+    }
+}
@@ -0,0 +1,461 @@
+//! Ruby on Rails プロジェクトで規約経由に呼ばれるシンボル参照を収集する。
+//!
+//! Rails のコントローラアクションは `config/routes.rb` の `resources :users` /
+//! `get 'users#export'` から、コールバックは `before_action :authenticate` のような
+//! シンボル引数から、ヘルパーはビューテンプレートから呼ばれる。いずれも Ruby AST の
+//! cross-file refs では caller を追跡できないため、ここで集めた名前を dead-code 判定時に
+//! 「仮想的な参照」として扱う。
+//!
+//! routes のコントローラ指定 (`admin/user_profiles`) は Zeitwerk の命名規則
+//! (`Admin::UserProfilesController`) でクラス名に変換し、
+//! `config/initializers/inflections.rb` の `acronym` 定義も反映する。
+//! 判定は false-positive を減らすのが目的なので、Ruby パーサを使わない行単位の
+//! 走査で多めに集める (標準アクション名 `show` 等は同名メソッドすべてを live にする)。
+//! Rails プロジェクト (`config/routes.rb` または `Gemfile` の rails gem) でなければ
+//! 空集合を返す。
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::engine::bounded_read::read_utf8_file_limited;
+use crate::engine::paths::relative_path;
+
+/// Ruby / ビューファイル 1 件あたりの最大サイズ (1MB)。
+const MAX_RAILS_FILE_SIZE: u64 = 1_048_576;
+
+/// シンボル引数でメソッド名を受け取るクラスマクロ (コントローラ / モデル / ジョブ /
+/// メーラーのコールバック、バリデーション、`helper_method`、`rescue_from ... with:` 等)。
+const RAILS_CALLBACK_METHODS: &[&str] = &[
+    "before_action",
+    "after_action",
+    "around_action",
+    "prepend_before_action",
+    "prepend_after_action",
+    "prepend_around_action",
+    "append_before_action",
+    "append_after_action",
+    "append_around_action",
+    "skip_before_action",
+    "skip_after_action",
+    "skip_around_action",
+    "before_validation",
+    "after_validation",
+    "before_save",
+    "around_save",
+    "after_save",
+    "before_create",
+    "around_create",
+    "after_create",
+    "before_update",
+    "around_update",
+    "after_update",
+    "before_destroy",
+    "around_destroy",
+    "after_destroy",
+    "after_commit",
+    "after_create_commit",
+    "after_update_commit",
+    "after_destroy_commit",
+    "after_save_commit",
+    "after_rollback",
+    "after_initialize",
+    "after_find",
+    "after_touch",
+    "before_enqueue",
+    "around_enqueue",
+    "after_enqueue",
+    "before_perform",
+    "around_perform",
+    "after_perform",
+    "before_deliver",
+    "around_deliver",
+    "after_deliver",
+    "validate",
+    "validates",
+    "validates_each",
+    "helper_method",
+    "rescue_from",
+    "layout",
+    "delegate",
+];
+
+const HTTP_VERBS: &[&str] = &["get", "post", "put", "patch", "delete", "match"];
+
+/// `resources` / `resource` が生成する標準アクション。
+const RESOURCES_ACTIONS: &[&str] = &[
+    "index", "show", "new", "create", "edit", "update", "destroy",
+];
+const RESOURCE_ACTIONS: &[&str] = &["show", "new", "create", "edit", "update", "destroy"];
+
+/// `dir` が Rails プロジェクトと判定される場合、routes / コールバック / ビューから
+/// 規約経由で呼ばれる名前を収集して返す。Rails プロジェクトでなければ空集合。
+pub fn collect_rails_references(dir: &Path) -> HashSet<String> {
+    if !dir.join("config/routes.rb").is_file() && !gemfile_has_rails(dir) {
+        return HashSet::new();
+    }
+    let acronyms = collect_acronyms(dir);
+    let mut refs = HashSet::new();
+    let walker = ignore::WalkBuilder::new(dir).build();
+    for entry in walker.flatten() {
+        let path = entry.path();
+        let Some(ext) = path.extension().and_then(|e| e.to_str()) else {
+            continue;
+        };
+        if !matches!(ext, "rb" | "erb" | "haml" | "slim") {
+            continue;
+        }
+        let Some(content) = read_utf8_file_limited(path, MAX_RAILS_FILE_SIZE) else {
+            continue;
+        };
+        if ext != "rb" {
+            extract_view_refs(&content, ext == "erb", &mut refs);
+            continue;
+        }
+        if relative_path(dir, path).is_some_and(|rel| is_routes_file(&rel)) {
+            extract_route_refs(&content, &acronyms, &mut refs);
+        }
+        extract_callback_refs(&content, &mut refs);
+    }
+    refs
+}
+
+/// `dir/Gemfile` が `rails` (または `railties`) gem を宣言しているか。
+pub fn gemfile_has_rails(dir: &Path) -> bool {
+    let Some(text) = read_utf8_file_limited(&dir.join("Gemfile"), MAX_RAILS_FILE_SIZE) else {
+        return false;
+    };
+    text.lines().any(|line| {
+        line.trim_start()
+            .strip_prefix("gem")
+            .filter(|rest| rest.starts_with([' ', '(']))
+            .and_then(|rest| quoted_strings(rest).next())
+            .is_some_and(|name| matches!(name, "rails" | "railties"))
+    })
+}
+
+fn is_routes_file(rel: &str) -> bool {
+    rel == "config/routes.rb"
+        || rel.ends_with("/config/routes.rb")
+        || rel.contains("config/routes/")
+}
+
+/// `inflect.acronym 'API'` で登録された頭字語。Zeitwerk はこれを使って
+/// `api_controller` を `APIController` に変換する。
+fn collect_acronyms(dir: &Path) -> Vec<String> {
+    let path = dir.join("config/initializers/inflections.rb");
+    let Some(text) = read_utf8_file_limited(&path, MAX_RAILS_FILE_SIZE) else {
+        return Vec::new();
+    };
+    text.lines()
+        .filter(|line| !line.trim_start().starts_with('#') && line.contains(".acronym"))
+        .flat_map(|line| quoted_strings(line).map(str::to_string).collect::<Vec<_>>())
+        .collect()
+}
+
+/// routes 定義からコントローラクラス名とアクション名を集める。
+fn extract_route_refs(content: &str, acronyms: &[String], refs: &mut HashSet<String>) {
+    for line in content.lines() {
+        let line = line.trim();
+        if line.starts_with('#') {
+            continue;
+        }
+        // `get 'users#export'` / `to: "admin/users#index"` / `root 'pages#home'`
+        for target in quoted_strings(line) {
+            if let Some((controller, action)) = target.split_once('#')
+                && is_route_path(controller)
+                && is_ruby_identifier(action)
+            {
+                add_controller(controller, acronyms, refs);
+                refs.insert(action.to_string());
+            }
+        }
+        for (key, is_controller) in [("controller:", true), ("action:", false)] {
+            if let Some(name) = line
+                .split_once(key)
+                .and_then(|(_, rest)| option_value(rest))
+            {
+                if is_controller {
+                    add_controller(name, acronyms, refs);
+                } else {
+                    refs.insert(name.to_string());
+                }
+            }
+        }
+
+        let (keyword, args) = split_call(line);
+        let leading: Vec<&str> = leading_symbols(args).collect();
+        match keyword {
+            "resources" => {
+                for name in leading {
+                    add_controller(name, acronyms, refs);
+                    refs.extend(RESOURCES_ACTIONS.iter().map(|a| a.to_string()));
+                }
+            }
+            // 単数リソースも複数形のコントローラに解決される (`resource :profile` →
+            // `ProfilesController`)。
+            "resource" => {
+                for name in leading {
+                    add_controller(&pluralize(name), acronyms, refs);
+                    refs.extend(RESOURCE_ACTIONS.iter().map(|a| a.to_string()));
+                }
+            }
+            "controller" => {
+                for name in leading {
+                    add_controller(name, acronyms, refs);
+                }
+            }
+            "namespace" => {
+                for name in leading {
+                    refs.insert(camelize(name, acronyms));
+                }
+            }
+            // `member do get :preview end` のようにアクション名だけを渡す形。
+            verb if HTTP_VERBS.contains(&verb) => {
+                refs.extend(leading.into_iter().map(str::to_string));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// コールバック系クラスマクロのシンボル引数 (`before_action :authenticate, if: :admin?`)
+/// を参照として集める。
+fn extract_callback_refs(content: &str, refs: &mut HashSet<String>) {
+    for line in content.lines() {
+        let (keyword, args) = split_call(line.trim());
+        if RAILS_CALLBACK_METHODS.contains(&keyword) {
+            refs.extend(symbol_literals(args).map(str::to_string));
+        }
+    }
+}
+
+/// ビューから参照される識別子。ERB は `<% %>` タグ内だけ、Haml / Slim は
+/// Ruby 部分と本文を区別せずファイル全体から集める。
+fn extract_view_refs(content: &str, erb: bool, refs: &mut HashSet<String>) {
+    if !erb {
+        refs.extend(ruby_identifiers(content).map(str::to_string));
+        return;
+    }
+    let mut rest = content;
+    while let Some(start) = rest.find("<%") {
+        let tag = &rest[start + 2..];
+        let end = tag.find("%>").unwrap_or(tag.len());
+        refs.extend(ruby_identifiers(&tag[..end]).map(str::to_string));
+        rest = &tag[end..];
+    }
+}
+
+/// routes のコントローラ指定 (`admin/users`) を Zeitwerk の規則でクラス名にし、
+/// bare name (`UsersController`)・完全名 (`Admin::UsersController`)・名前空間 module を登録する。
+fn add_controller(path: &str, acronyms: &[String], refs: &mut HashSet<String>) {
+    let segments: Vec<String> = path
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .map(|s| camelize(s, acronyms))
+        .collect();
+    let Some((last, namespaces)) = segments.split_last() else {
+        return;
+    };
+    let class = format!("{last}Controller");
+    if !namespaces.is_empty() {
+        refs.insert(format!("{}::{class}", namespaces.join("::")));
+    }
+    refs.extend(namespaces.iter().cloned());
+    refs.insert(class);
+}
+
+/// Zeitwerk (`String#camelize`) と同じく `_` 区切りの各語を先頭大文字にする。
+/// 頭字語として登録された語はその表記を使う。
+fn camelize(segment: &str, acronyms: &[String]) -> String {
+    segment
+        .split('_')
+        .map(|word| {
+            if let Some(acronym) = acronyms.iter().find(|a| a.eq_ignore_ascii_case(word)) {
+                return acronym.clone();
+            }
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/// 英語の規則的な複数形。不規則変化 (`person` → `people`) は扱わない。
+fn pluralize(word: &str) -> String {
+    if word.ends_with(['s', 'x', 'z']) || word.ends_with("ch") || word.ends_with("sh") {
+        format!("{word}es")
+    } else if let Some(stem) = word.strip_suffix('y')
+        && !stem.ends_with(['a', 'e', 'i', 'o', 'u'])
+    {
+        format!("{stem}ies")
+    } else {
+        format!("{word}s")
+    }
+}
+
+/// 行頭のメソッド名と引数部分に分ける (`before_action(:a)` / `before_action :a`)。
+fn split_call(line: &str) -> (&str, &str) {
+    let end = line
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or(line.len());
+    let (keyword, rest) = line.split_at(end);
+    if rest.starts_with([' ', '(']) {
+        (keyword, rest)
+    } else {
+        (keyword, "")
+    }
+}
+
+/// オプション (`key:`) より前に並ぶシンボル引数 (`resources :users, :posts, only: ...`)。
+fn leading_symbols(args: &str) -> impl Iterator<Item = &str> {
+    args.trim_start_matches([' ', '('])
+        .split(',')
+        .map(str::trim)
+        .map_while(|arg| arg.strip_prefix(':'))
+        .map(|name| name.trim_end_matches(|c: char| c == ')' || c.is_whitespace()))
+        .map(|name| name.split_whitespace().next().unwrap_or(""))
+        .filter(|name| is_ruby_identifier(name))
+}
+
+/// `key: :name` / `key: 'name'` の値。
+fn option_value(rest: &str) -> Option<&str> {
+    let rest = rest.trim_start();
+    let value = if let Some(symbol) = rest.strip_prefix(':') {
+        let end = symbol
+            .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '_' | '/')))
+            .unwrap_or(symbol.len());
+        &symbol[..end]
+    } else {
+        quoted_strings(rest).next()?
+    };
+    (!value.is_empty() && !value.contains('#')).then_some(value)
+}
+
+/// シンボルリテラル (`:name` / `:admin?`)。`Foo::Bar` の `::` と `key:` は除く。
+fn symbol_literals(text: &str) -> impl Iterator<Item = &str> {
+    let bytes = text.as_bytes();
+    text.match_indices(':').filter_map(move |(ix, _)| {
+        if ix > 0 && (bytes[ix - 1] == b':' || bytes[ix - 1].is_ascii_alphanumeric()) {
+            return None;
+        }
+        let name = ruby_identifiers(&text[ix + 1..]).next()?;
+        text[ix + 1..].starts_with(name).then_some(name)
+    })
+}
+
+/// Ruby のメソッド名になり得る語 (末尾の `?` / `!` を含む)。
+fn ruby_identifiers(text: &str) -> impl Iterator<Item = &str> {
+    let bytes = text.as_bytes();
+    let mut pos = 0;
+    std::iter::from_fn(move || {
+        while pos < bytes.len() {
+            let start = pos;
+            if !(bytes[start].is_ascii_alphabetic() || bytes[start] == b'_') {
+                pos += 1;
+                continue;
+            }
+            let mut end = start;
+            while end < bytes.len() && (bytes[end].is_ascii_alphanumeric() || bytes[end] == b'_') {
+                end += 1;
+            }
+            if end < bytes.len() && matches!(bytes[end], b'?' | b'!') {
+                end += 1;
+            }
+            pos = end;
+            return Some(&text[start..end]);
+        }
+        None
+    })
+}
+
+/// 行中の `'...'` / `"..."` の中身。
+fn quoted_strings(line: &str) -> impl Iterator<Item = &str> {
+    let mut rest = line;
+    std::iter::from_fn(move || {
+        let start = rest.find(['\'', '"'])?;
+        let quote = rest[start..].chars().next()?;
+        let body = &rest[start + 1..];
+        let end = body.find(quote)?;
+        rest = &body[end + 1..];
+        Some(&body[..end])
+    })
+}
+
+fn is_route_path(path: &str) -> bool {
+    !path.is_empty()
+        && path
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '_' | '/'))
+}
+
+fn is_ruby_identifier(name: &str) -> bool {
+    ruby_identifiers(name).next() == Some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn routes_resolve_controllers_and_actions() {
+        let routes = "Rails.application.routes.draw do\n  root 'pages#home'\n  resources :articles, only: [:index, :show] do\n    member do\n      get :preview\n    end\n  end\n  resource :profile\n  namespace :admin do\n    get 'user_reports/export', to: 'user_reports#export'\n  end\n  get 'api_keys', controller: 'api/keys', action: :rotate\nend\n";
+        let mut refs = HashSet::new();
+        extract_route_refs(routes, &["API".to_string()], &mut refs);
+        for name in [
+            "PagesController",
+            "home",
+            "ArticlesController",
+            "index",
+            "preview",
+            "ProfilesController",
+            "edit",
+            "Admin",
+            "UserReportsController",
+            "export",
+            "API::KeysController",
+            "KeysController",
+            "rotate",
+        ] {
+            assert!(refs.contains(name), "{name} missing from {refs:?}");
+        }
+    }
+
+    #[test]
+    fn callback_symbols_are_references() {
+        let model = "class Order < ApplicationRecord\n  before_save :normalize_total, if: :draft?\n  after_commit(:notify_warehouse)\n  validate :stock_available\n  has_many :items\n  scope :recent, -> { where(kind: :recent_only) }\nend\n";
+        let mut refs = HashSet::new();
+        extract_callback_refs(model, &mut refs);
+        assert!(refs.contains("normalize_total"));
+        assert!(refs.contains("draft?"));
+        assert!(refs.contains("notify_warehouse"));
+        assert!(refs.contains("stock_available"));
+        assert!(!refs.contains("items"));
+        assert!(!refs.contains("recent_only"));
+    }
+
+    #[test]
+    fn erb_refs_come_only_from_ruby_tags() {
+        let view = "<h1>Title</h1>\n<p><%= format_price(@order.total) %></p>\n<% if admin? %>hidden<% end %>\n";
+        let mut refs = HashSet::new();
+        extract_view_refs(view, true, &mut refs);
+        assert!(refs.contains("format_price"));
+        assert!(refs.contains("admin?"));
+        assert!(!refs.contains("Title"));
+        assert!(!refs.contains("hidden"));
+    }
+
+    #[test]
+    fn gemfile_detection_requires_rails_gem() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::write(dir.path().join("Gemfile"), "gem 'rake'\ngem 'railsish'\n").unwrap();
+        assert!(!gemfile_has_rails(dir.path()));
+        std::fs::write(
+            dir.path().join("Gemfile"),
+            "source 'https://rubygems.org'\ngem \"rails\", \"~> 7.1\"\n",
+        )
+        .unwrap();
+        assert!(gemfile_has_rails(dir.path()));
+    }
+}
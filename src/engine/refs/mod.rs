@@ -452,6 +452,78 @@ where
     Ok(out)
 }
 
+/// 指定ファイルだけを対象に非 Definition 参照件数を数える。
+/// workspace walk は行わない (dead-code `--scope private` の可視範囲内判定用)。
+pub fn count_non_definition_refs_in_files(
+    symbol_names: &[String],
+    files: &[std::path::PathBuf],
+) -> Result<std::collections::HashMap<String, usize>> {
+    use std::collections::HashMap;
+
+    if symbol_names.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let acs = build_batch_acs(symbol_names)?;
+    let totals = files
+        .par_iter()
+        .filter_map(|path| {
+            let utf8_path = camino::Utf8Path::new(path.to_str()?);
+            count_refs_in_file(symbol_names, &acs, utf8_path).ok()
+        })
+        .reduce(
+            || vec![0; symbol_names.len()],
+            |mut acc, per_file| {
+                for (total, cnt) in acc.iter_mut().zip(per_file) {
+                    *total += cnt;
+                }
+                acc
+            },
+        );
+    Ok(symbol_names.iter().cloned().zip(totals).collect())
+}
+
+/// 指定ファイルだけを対象に参照 (Definition を含む) を集める。
+/// workspace walk は行わない (dead-code `--reachability` の参照グラフ構築用)。
+pub fn find_references_in_files(
+    symbol_names: &[String],
+    files: &[std::path::PathBuf],
+) -> Result<std::collections::HashMap<String, Vec<SymbolReference>>> {
+    use std::collections::HashMap;
+
+    if symbol_names.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let acs = build_batch_acs(symbol_names)?;
+    let buckets = files
+        .par_iter()
+        .fold(
+            || vec![Vec::new(); symbol_names.len()],
+            |mut local, path| {
+                let Some(path_str) = path.to_str() else {
+                    return local;
+                };
+                let utf8_path = camino::Utf8Path::new(path_str);
+                if let Ok(per_file) = find_refs_batch_in_file_indexed(symbol_names, &acs, utf8_path)
+                {
+                    for (ix, mut refs) in per_file.into_iter().enumerate() {
+                        local[ix].append(&mut refs);
+                    }
+                }
+                local
+            },
+        )
+        .reduce(
+            || vec![Vec::new(); symbol_names.len()],
+            |mut acc, mut local| {
+                for (acc_refs, local_refs) in acc.iter_mut().zip(local.iter_mut()) {
+                    acc_refs.append(local_refs);
+                }
+                acc
+            },
+        );
+    Ok(symbol_names.iter().cloned().zip(buckets).collect())
+}
+
 /// visitor callback 版の per-file ref 走査。
 ///
 /// `SymbolReference` を 1 件も生成せず、identifier にヒットした瞬間に `visitor.on_ref`
@@ -0,0 +1,190 @@
+//! 名前で指定したシンボル定義のソーステキスト抽出 (`source` コマンド)。
+
+use tree_sitter::Node;
+
+use crate::language::LangId;
+use crate::models::location::{Point, Range};
+use crate::models::source::SymbolSource;
+use crate::models::symbol::{Symbol, SymbolKind};
+
+use super::symbols::{definition_range, is_local_scope_symbol};
+
+/// 1 定義あたりの既定の返却上限 (バイト)。
+pub const DEFAULT_MAX_SOURCE_BYTES: usize = 64 * 1024;
+
+/// 引く定義の条件。
+pub struct SourceQuery<'a> {
+    pub name: &'a str,
+    /// 指定時は `Symbol.container` が一致する定義だけを返す (同名メソッドの曖昧さ解消)。
+    pub container: Option<&'a str>,
+    pub max_bytes: usize,
+}
+
+/// `symbols` の中から `query` に一致する定義を探し、ソーステキストを返す。
+///
+/// import / 引数 / 関数内ローカルは定義として扱わない。`max_bytes` を超える定義は
+/// 行境界 (無ければ UTF-8 文字境界) で切り詰め、`truncated` を立てる。
+/// 戻り値の 2 要素目は切り詰め前のバイト数 (切り詰めなしなら `None`)。
+/// 定義ノードを引けないシンボルはシンボル自身の範囲を返し、それも `source` 外なら除く。
+pub fn find_symbol_sources(
+    root: Node,
+    source: &[u8],
+    lang_id: LangId,
+    symbols: &[Symbol],
+    path: &str,
+    query: &SourceQuery<'_>,
+) -> Vec<(SymbolSource, Option<usize>)> {
+    symbols
+        .iter()
+        .filter(|sym| sym.name == query.name)
+        .filter(|sym| {
+            query
+                .container
+                .is_none_or(|c| sym.container.as_deref() == Some(c))
+        })
+        .filter(|sym| !matches!(sym.kind, SymbolKind::Import | SymbolKind::Parameter))
+        .filter(|sym| !is_local_scope_symbol(root, source, lang_id, &sym.range))
+        .filter_map(|sym| {
+            let (start, end, range) = match definition_range(root, sym) {
+                Some(r) => (r.start_byte, r.end_byte, Range::from(r)),
+                None => (
+                    byte_offset(source, sym.range.start)?,
+                    byte_offset(source, sym.range.end)?,
+                    sym.range,
+                ),
+            };
+            let bytes = source.get(start..end)?;
+            let (text, truncated) = truncate_at_boundary(bytes, query.max_bytes);
+            let symbol_source = SymbolSource {
+                path: path.to_string(),
+                kind: sym.kind,
+                container: sym.container.clone(),
+                range,
+                source: String::from_utf8_lossy(text).into_owned(),
+                truncated,
+            };
+            Some((symbol_source, truncated.then_some(bytes.len())))
+        })
+        .collect()
+}
+
+/// 0 始まりの行とバイト列の位置を `source` 先頭からのバイトオフセットにする。範囲外は `None`。
+fn byte_offset(source: &[u8], point: Point) -> Option<usize> {
+    let line_start = match point.line {
+        0 => 0,
+        line => memchr::memchr_iter(b'\n', source).nth(line - 1)? + 1,
+    };
+    let offset = line_start + point.column;
+    (offset <= source.len()).then_some(offset)
+}
+
+/// `bytes` を `max_bytes` 以内に収める。行の途中で切らないよう最後の改行までを優先し、
+/// 改行が無ければ UTF-8 文字境界で切る。
+fn truncate_at_boundary(bytes: &[u8], max_bytes: usize) -> (&[u8], bool) {
+    if bytes.len() <= max_bytes {
+        return (bytes, false);
+    }
+    let head = &bytes[..max_bytes];
+    if let Some(newline) = memchr::memrchr(b'\n', head) {
+        return (&bytes[..=newline], true);
+    }
+    let mut end = max_bytes;
+    // UTF-8 の継続バイト (0b10xx_xxxx) の途中なら文字の先頭まで戻す。
+    while end > 0 && (bytes[end] & 0xC0) == 0x80 {
+        end -= 1;
+    }
+    (&bytes[..end], true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::parser;
+    use crate::engine::symbols::extract_symbols;
+
+    fn sources(
+        src: &str,
+        lang_id: LangId,
+        name: &str,
+        container: Option<&str>,
+        max_bytes: usize,
+    ) -> Vec<(SymbolSource, Option<usize>)> {
+        let tree = parser::parse_source(src.as_bytes(), lang_id).unwrap();
+        let root = tree.root_node();
+        let symbols = extract_symbols(root, src.as_bytes(), lang_id).unwrap();
+        let query = SourceQuery {
+            name,
+            container,
+            max_bytes,
+        };
+        find_symbol_sources(root, src.as_bytes(), lang_id, &symbols, "f", &query)
+    }
+
+    #[test]
+    fn includes_doc_comment_and_attributes() {
+        let src = "// license header\n\n/// Adds.\n#[inline]\npub fn add(a: i32) -> i32 {\n    a + 1\n}\n";
+        let found = sources(src, LangId::Rust, "add", None, DEFAULT_MAX_SOURCE_BYTES);
+        assert_eq!(found.len(), 1);
+        assert_eq!(
+            found[0].0.source,
+            "/// Adds.\n#[inline]\npub fn add(a: i32) -> i32 {\n    a + 1\n}"
+        );
+        assert_eq!(found[0].0.range.start.line, 2);
+        assert!(!found[0].0.truncated);
+    }
+
+    #[test]
+    fn includes_python_decorators() {
+        let src = "import functools\n\n@functools.cache\ndef load():\n    return 1\n";
+        let found = sources(src, LangId::Python, "load", None, DEFAULT_MAX_SOURCE_BYTES);
+        assert_eq!(
+            found[0].0.source,
+            "@functools.cache\ndef load():\n    return 1"
+        );
+    }
+
+    #[test]
+    fn container_disambiguates_same_named_methods() {
+        let src = "class A:\n    def run(self):\n        return 'a'\n\nclass B:\n    def run(self):\n        return 'b'\n";
+        assert_eq!(
+            sources(src, LangId::Python, "run", None, DEFAULT_MAX_SOURCE_BYTES).len(),
+            2
+        );
+        let found = sources(
+            src,
+            LangId::Python,
+            "run",
+            Some("B"),
+            DEFAULT_MAX_SOURCE_BYTES,
+        );
+        assert_eq!(found.len(), 1);
+        assert!(found[0].0.source.contains("'b'"));
+    }
+
+    #[test]
+    fn truncates_at_line_boundary_and_reports_original_size() {
+        let src = "fn long() {\n    let a = 1;\n    let b = 2;\n}\n";
+        let found = sources(src, LangId::Rust, "long", None, 20);
+        let (symbol_source, original) = &found[0];
+        assert!(symbol_source.truncated);
+        assert_eq!(symbol_source.source, "fn long() {\n");
+        assert_eq!(*original, Some(src.trim_end().len()));
+    }
+
+    #[test]
+    fn truncate_without_newline_respects_char_boundary() {
+        let (text, truncated) = truncate_at_boundary("ああ".as_bytes(), 4);
+        assert!(truncated);
+        assert_eq!(text, "あ".as_bytes());
+    }
+
+    #[test]
+    fn byte_offset_maps_points_and_rejects_out_of_range() {
+        let src = b"ab\ncde\n";
+        assert_eq!(byte_offset(src, Point { line: 0, column: 1 }), Some(1));
+        assert_eq!(byte_offset(src, Point { line: 1, column: 2 }), Some(5));
+        assert_eq!(byte_offset(src, Point { line: 2, column: 0 }), Some(7));
+        assert_eq!(byte_offset(src, Point { line: 3, column: 0 }), None);
+        assert_eq!(byte_offset(src, Point { line: 1, column: 9 }), None);
+    }
+}
@@ -0,0 +1,481 @@
+//! annotation 駆動のフレームワーク (Spring / Jakarta EE) がリフレクションで呼ぶ宣言を収集する。
+//!
+//! `@RestController` のハンドラ、`@Bean` ファクトリ、`@Scheduled` / `@EventListener` の
+//! コールバック、`@Configuration` クラスはコンテナが annotation を見て呼び出すため、
+//! Java / Kotlin AST の cross-file refs では caller を追跡できない。ここでは allowlist に
+//! 含まれる annotation が付いた宣言名と、`@Autowired` / `@Inject` で注入される型名を集め、
+//! dead-code 判定時に「仮想的な参照」として扱う。
+//!
+//! 判定は false-positive を減らすのが目的なので、言語パーサを使わずテキスト上の
+//! `@Name` を走査する。`@Name` 形式の decorator を持つ TypeScript / Python にも
+//! 同じ規則で適用できる。
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::engine::bounded_read::read_utf8_file_limited;
+
+/// ソース / ビルドファイル 1 件あたりの最大サイズ (1MB)。
+const MAX_SOURCE_FILE_SIZE: u64 = 1_048_576;
+
+/// annotation を走査する拡張子。
+const ANNOTATED_SOURCE_EXTENSIONS: &[&str] = &[
+    "java", "kt", "kts", "groovy", "scala", "ts", "tsx", "js", "jsx", "mjs", "py",
+];
+
+/// Spring / Jakarta のビルド定義ファイル。
+const BUILD_FILES: &[&str] = &["pom.xml", "build.gradle", "build.gradle.kts"];
+
+/// `spring` プリセットで、付いている宣言をフレームワーク entrypoint とみなす annotation。
+const SPRING_ENTRY_ANNOTATIONS: &[&str] = &[
+    // Spring Boot / stereotype / 設定
+    "SpringBootApplication",
+    "Configuration",
+    "AutoConfiguration",
+    "ConfigurationProperties",
+    "Bean",
+    "Component",
+    "Service",
+    "Repository",
+    "Controller",
+    "RestController",
+    "ControllerAdvice",
+    "RestControllerAdvice",
+    // Web / messaging ハンドラ
+    "RequestMapping",
+    "GetMapping",
+    "PostMapping",
+    "PutMapping",
+    "DeleteMapping",
+    "PatchMapping",
+    "ExceptionHandler",
+    "ModelAttribute",
+    "InitBinder",
+    "MessageMapping",
+    "SubscribeMapping",
+    "KafkaListener",
+    "RabbitListener",
+    "JmsListener",
+    // スケジューラ / イベント / ライフサイクル
+    "Scheduled",
+    "Schedules",
+    "EventListener",
+    "TransactionalEventListener",
+    "PostConstruct",
+    "PreDestroy",
+    // Jakarta REST / CDI / Servlet / EJB
+    "Path",
+    "GET",
+    "POST",
+    "PUT",
+    "DELETE",
+    "PATCH",
+    "HEAD",
+    "OPTIONS",
+    "Provider",
+    "ApplicationScoped",
+    "RequestScoped",
+    "SessionScoped",
+    "Produces",
+    "Observes",
+    "WebServlet",
+    "WebFilter",
+    "WebListener",
+    "Startup",
+    "Schedule",
+];
+
+/// 付いている宣言 (フィールド / コンストラクタ / setter) の型を注入対象として使用済みにする
+/// annotation。宣言自身もコンテナから呼ばれるため entrypoint として扱う。
+const SPRING_INJECT_ANNOTATIONS: &[&str] = &["Autowired", "Inject", "Resource"];
+
+/// annotation 名 (先頭の `@` とパッケージ修飾を除いた単純名) による生存判定ルール。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AnnotationRules {
+    /// 付いている宣言をフレームワーク entrypoint とみなす annotation。
+    pub entry: Vec<String>,
+    /// 付いている宣言に現れる型を注入対象として使用済みにする annotation。
+    pub inject: Vec<String>,
+}
+
+impl AnnotationRules {
+    /// `spring` プリセットの組み込み allowlist。
+    pub fn spring() -> Self {
+        Self {
+            entry: SPRING_ENTRY_ANNOTATIONS
+                .iter()
+                .map(|s| (*s).to_string())
+                .collect(),
+            inject: SPRING_INJECT_ANNOTATIONS
+                .iter()
+                .map(|s| (*s).to_string())
+                .collect(),
+        }
+    }
+
+    /// entry annotation を追加する。`@` 接頭辞や完全修飾名
+    /// (`org.springframework.stereotype.Service`) は単純名に正規化する。
+    pub fn add_entry(&mut self, name: &str) {
+        let name = annotation_simple_name(name);
+        if !name.is_empty() && !self.entry.iter().any(|e| e == name) {
+            self.entry.push(name.to_string());
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entry.is_empty() && self.inject.is_empty()
+    }
+
+    fn is_entry(&self, name: &str) -> bool {
+        self.entry.iter().any(|e| e == name)
+    }
+
+    fn is_inject(&self, name: &str) -> bool {
+        self.inject.iter().any(|e| e == name)
+    }
+}
+
+/// `dir` 直下の `pom.xml` / `build.gradle(.kts)` が Spring または Jakarta EE に依存しているか。
+pub fn build_files_have_spring(dir: &Path) -> bool {
+    BUILD_FILES.iter().any(|name| {
+        read_utf8_file_limited(&dir.join(name), MAX_SOURCE_FILE_SIZE)
+            .is_some_and(|text| text.contains("org.springframework") || text.contains("jakarta."))
+    })
+}
+
+/// `rules` の annotation が付いた宣言名と注入される型名を `dir` 配下から収集する。
+/// ルールが空なら走査せず空集合を返す。
+pub fn collect_annotation_references(dir: &Path, rules: &AnnotationRules) -> HashSet<String> {
+    let mut refs = HashSet::new();
+    if rules.is_empty() {
+        return refs;
+    }
+    let walker = ignore::WalkBuilder::new(dir).build();
+    for entry in walker.flatten() {
+        let path = entry.path();
+        let is_source = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|ext| ANNOTATED_SOURCE_EXTENSIONS.contains(&ext));
+        if !is_source {
+            continue;
+        }
+        if let Some(content) = read_utf8_file_limited(path, MAX_SOURCE_FILE_SIZE) {
+            extract_annotated_refs(&content, rules, &mut refs);
+        }
+    }
+    refs
+}
+
+/// 1 ファイル分の annotation 参照を `refs` に追加する。
+pub(crate) fn extract_annotated_refs(
+    content: &str,
+    rules: &AnnotationRules,
+    refs: &mut HashSet<String>,
+) {
+    let text = blank_comments_and_strings(content);
+    let bytes = text.as_bytes();
+    let mut pos = 0;
+    while let Some(offset) = text[pos..].find('@') {
+        let at = pos + offset;
+        pos = at + 1;
+        // `this@Outer` / `return@forEach` (Kotlin のラベル) は annotation ではない。
+        if at > 0 && is_ident_byte(bytes[at - 1]) {
+            continue;
+        }
+        let Some((name, after)) = parse_annotation(&text, at) else {
+            continue;
+        };
+        let entry = rules.is_entry(name);
+        let inject = rules.is_inject(name);
+        if !entry && !inject {
+            continue;
+        }
+        let decl = declaration_text(&text, skip_annotations(&text, after));
+        if let Some(declared) = declared_name(decl) {
+            refs.insert(declared.to_string());
+        }
+        if inject {
+            refs.extend(
+                identifiers(decl)
+                    .filter(|(_, ident)| ident.starts_with(|c: char| c.is_ascii_uppercase()))
+                    .map(|(_, ident)| ident.to_string()),
+            );
+        }
+    }
+}
+
+/// 先頭の `@` を除き、パッケージ修飾の末尾セグメントを返す。
+fn annotation_simple_name(name: &str) -> &str {
+    let name = name.trim().trim_start_matches('@');
+    name.rsplit('.').next().unwrap_or(name)
+}
+
+/// `at` の `@Name(...)` を読み、単純名と annotation 直後の位置を返す。
+fn parse_annotation(text: &str, at: usize) -> Option<(&str, usize)> {
+    let bytes = text.as_bytes();
+    let mut end = at + 1;
+    while end < bytes.len() && (is_ident_byte(bytes[end]) || bytes[end] == b'.') {
+        end += 1;
+    }
+    let name = annotation_simple_name(&text[at + 1..end]);
+    if name.is_empty() || name == "interface" {
+        return None;
+    }
+    let mut after = end;
+    let args = skip_whitespace(text, end);
+    if bytes.get(args) == Some(&b'(') {
+        after = skip_balanced(text, args);
+    }
+    Some((name, after))
+}
+
+/// 連続する annotation を読み飛ばし、宣言本体の開始位置を返す。
+fn skip_annotations(text: &str, mut pos: usize) -> usize {
+    loop {
+        let next = skip_whitespace(text, pos);
+        if text.as_bytes().get(next) != Some(&b'@') {
+            return next;
+        }
+        match parse_annotation(text, next) {
+            Some((_, after)) => pos = after,
+            None => return next,
+        }
+    }
+}
+
+/// 宣言本体 (ブロック / 初期化子 / 行末の手前まで)。括弧内の改行や `=` では切らない。
+fn declaration_text(text: &str, start: usize) -> &str {
+    let mut depth = 0usize;
+    for (offset, c) in text[start..].char_indices() {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth = depth.saturating_sub(1),
+            '{' | ';' | '=' | '\n' if depth == 0 => return &text[start..start + offset],
+            _ => {}
+        }
+    }
+    &text[start..]
+}
+
+/// 宣言される名前。型宣言はキーワード直後、関数 / メソッドは最初の `(` 直前、
+/// フィールド / プロパティは型注釈 (`:`) の手前または末尾の識別子。
+fn declared_name(decl: &str) -> Option<&str> {
+    let idents: Vec<(usize, &str)> = identifiers(decl).collect();
+    let paren = decl.find('(');
+    for (i, (offset, ident)) in idents.iter().enumerate() {
+        if paren.is_some_and(|p| *offset > p) {
+            break;
+        }
+        if matches!(
+            *ident,
+            "class" | "interface" | "enum" | "object" | "record" | "trait" | "val" | "var"
+        ) {
+            return idents.get(i + 1).map(|(_, name)| *name);
+        }
+    }
+    if let Some(paren) = paren {
+        let head = decl[..paren].trim_end();
+        // `foo<T>(` の型引数を読み飛ばす。
+        let head = match head.strip_suffix('>') {
+            Some(rest) => rest.rfind('<').map_or(rest, |lt| &rest[..lt]),
+            None => head,
+        };
+        return identifiers(head).last().map(|(_, name)| name);
+    }
+    let head = decl.split(':').next().unwrap_or(decl);
+    identifiers(head).last().map(|(_, name)| name)
+}
+
+/// 識別子とその位置。
+fn identifiers(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    let bytes = text.as_bytes();
+    let mut pos = 0;
+    std::iter::from_fn(move || {
+        while pos < bytes.len() {
+            if !(bytes[pos].is_ascii_alphabetic() || bytes[pos] == b'_' || bytes[pos] == b'$') {
+                pos += 1;
+                continue;
+            }
+            let start = pos;
+            while pos < bytes.len() && is_ident_byte(bytes[pos]) {
+                pos += 1;
+            }
+            return Some((start, &text[start..pos]));
+        }
+        None
+    })
+}
+
+/// コメントを除き、文字列リテラルの中身を空白にする (改行と引用符は残す)。
+/// 文字列中の `@` やコメント中の `@param` を annotation と誤認しないため。
+fn blank_comments_and_strings(content: &str) -> String {
+    let mut out = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push('\n');
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push('\n');
+                    }
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+                out.push(' ');
+            }
+            '"' | '\'' | '`' => {
+                out.push(c);
+                while let Some(s) = chars.next() {
+                    if s == c || s == '\n' {
+                        out.push(s);
+                        break;
+                    }
+                    if s == '\\' {
+                        chars.next();
+                    }
+                    out.push(' ');
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn skip_whitespace(text: &str, pos: usize) -> usize {
+    text[pos..]
+        .find(|c: char| !c.is_whitespace())
+        .map_or(text.len(), |offset| pos + offset)
+}
+
+/// `open` の `(` に対応する `)` の直後。閉じていなければ末尾。
+fn skip_balanced(text: &str, open: usize) -> usize {
+    let mut depth = 0usize;
+    for (offset, c) in text[open..].char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return open + offset + 1;
+                }
+            }
+            _ => {}
+        }
+    }
+    text.len()
+}
+
+fn is_ident_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_' || b == b'$'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn refs_of(content: &str) -> HashSet<String> {
+        let mut refs = HashSet::new();
+        extract_annotated_refs(content, &AnnotationRules::spring(), &mut refs);
+        refs
+    }
+
+    #[test]
+    fn java_handlers_beans_and_listeners() {
+        let src = r#"
+@RestController
+@RequestMapping("/orders")
+public class OrderController {
+    /** Returns orders. @see Order */
+    @GetMapping(value = "/{id}", produces = "application/json")
+    public ResponseEntity<Order> show(@PathVariable long id) { return null; }
+
+    @Scheduled(cron = "0 0 * * * *") public void purge() {}
+
+    @EventListener
+    public <E extends Event> void onEvent(
+        E event) {}
+
+    public void helper() { String mail = "ops@Service.example"; }
+}
+"#;
+        let refs = refs_of(src);
+        for name in ["OrderController", "show", "purge", "onEvent"] {
+            assert!(refs.contains(name), "{name} missing from {refs:?}");
+        }
+        assert!(!refs.contains("helper"));
+        assert!(!refs.contains("mail"));
+    }
+
+    #[test]
+    fn injected_types_are_used() {
+        let src = "
+@Service
+class OrderService @Autowired constructor(
+    private val repo: OrderRepository,
+    private val clock: Clock,
+) {
+    @Autowired
+    lateinit var notifier: Notifier
+
+    @Bean
+    fun restTemplate(builder: RestTemplateBuilder): RestTemplate = builder.build()
+
+    fun unrelated(x: Unrelated) = this@OrderService
+}
+";
+        let refs = refs_of(src);
+        for name in [
+            "OrderService",
+            "OrderRepository",
+            "Clock",
+            "notifier",
+            "Notifier",
+            "restTemplate",
+        ] {
+            assert!(refs.contains(name), "{name} missing from {refs:?}");
+        }
+        assert!(!refs.contains("unrelated"));
+        assert!(!refs.contains("Unrelated"));
+        // `@Bean` は注入 annotation ではないので引数型は使用済みにしない。
+        assert!(!refs.contains("RestTemplateBuilder"));
+    }
+
+    #[test]
+    fn custom_entry_annotations_use_simple_names() {
+        let mut rules = AnnotationRules::default();
+        rules.add_entry("@com.example.Job");
+        let mut refs = HashSet::new();
+        extract_annotated_refs(
+            "class Jobs {\n  @com.example.Job(\"nightly\")\n  void nightly() {}\n  @Override void other() {}\n}\n",
+            &rules,
+            &mut refs,
+        );
+        assert_eq!(refs, HashSet::from(["nightly".to_string()]));
+    }
+
+    #[test]
+    fn build_file_detection() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        assert!(!build_files_have_spring(dir.path()));
+        std::fs::write(
+            dir.path().join("build.gradle.kts"),
+            "dependencies {\n  implementation(\"org.springframework.boot:spring-boot-starter-web\")\n}\n",
+        )
+        .unwrap();
+        assert!(build_files_have_spring(dir.path()));
+    }
+}
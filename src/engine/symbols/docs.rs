@@ -0,0 +1,157 @@
+use tree_sitter::Node;
+
+use crate::language::LangId;
+use crate::models::symbol::Symbol;
+
+use super::node_for_symbol_range;
+
+/// シンボルに doc comment / docstring が付いているかを判定する。
+///
+/// 定義の直前の行で終わるコメントだけを doc とみなす。間に attribute / decorator が
+/// 挟まってもよく、`export` / decorator の wrapper ノードの外側に付いたコメント
+/// (`/** doc */ export function f`) も辿る。空行で離れたライセンスヘッダや前の文の
+/// 行末コメントは数えない。doc 専用の記法を持つ言語はその記法 (`///` / `/** */` 等) に限り、
+/// Python は本体先頭の docstring だけを数える。
+pub fn has_doc_comment(root: Node, source: &[u8], lang_id: LangId, sym: &Symbol) -> bool {
+    let Some(node) = node_for_symbol_range(root, &sym.range) else {
+        return false;
+    };
+    if lang_id == LangId::Python {
+        return has_python_docstring(node);
+    }
+
+    let mut cur = node;
+    loop {
+        // attribute を挟む場合は、その先頭行の直前にコメントが要る。
+        let mut top_row = cur.start_position().row;
+        let mut prev = cur.prev_named_sibling();
+        while let Some(p) = prev
+            && is_attribute_kind(p.kind())
+        {
+            top_row = p.start_position().row;
+            prev = p.prev_named_sibling();
+        }
+        if let Some(p) = prev {
+            return p.kind().contains("comment")
+                && last_row(p) + 1 >= top_row
+                && !is_trailing_comment(p)
+                && p.utf8_text(source)
+                    .is_ok_and(|text| is_doc_comment_syntax(lang_id, text));
+        }
+        match cur.parent() {
+            Some(parent) if is_declaration_wrapper_kind(parent.kind()) => cur = parent,
+            _ => return false,
+        }
+    }
+}
+
+/// `text` が `lang_id` の doc comment 記法か。
+///
+/// Go / Ruby / Bash / Xojo は専用記法を持たず、定義直前のコメントを doc とする慣習なので
+/// どのコメントも受け入れる。Rust の `//!` は外側の item の doc なので含めない。
+fn is_doc_comment_syntax(lang_id: LangId, text: &str) -> bool {
+    let block_doc = text.starts_with("/**") && !text.starts_with("/**/");
+    let line_doc = text.starts_with("///") && !text.starts_with("////");
+    match lang_id {
+        LangId::Rust | LangId::Swift | LangId::CSharp => line_doc || block_doc,
+        LangId::Zig => line_doc,
+        LangId::C | LangId::Cpp => {
+            line_doc || block_doc || text.starts_with("//!") || text.starts_with("/*!")
+        }
+        LangId::Javascript
+        | LangId::Typescript
+        | LangId::Tsx
+        | LangId::Java
+        | LangId::Kotlin
+        | LangId::Php => block_doc,
+        LangId::Python => false,
+        LangId::Go | LangId::Ruby | LangId::Bash | LangId::Xojo => true,
+    }
+}
+
+/// 前の文と同じ行に始まる行末コメント (`let x = 1; // note`) か。
+fn is_trailing_comment(comment: Node) -> bool {
+    comment.prev_sibling().is_some_and(|before| {
+        !before.kind().contains("comment")
+            && !is_attribute_kind(before.kind())
+            && last_row(before) == comment.start_position().row
+    })
+}
+
+/// 定義に付随する doc comment / attribute / decorator を含めた定義全体の範囲を返す。
+///
+/// `export` / decorator の wrapper ノードまで遡り、その直前に隣接する (空行を挟まない)
+/// コメントと attribute を取り込む。ファイル先頭のライセンスコメントのように空行で離れた
+/// コメントや、前の文の行末コメントは含めない。ノードを引けなければ `None`。
+pub fn definition_range(root: Node, sym: &Symbol) -> Option<tree_sitter::Range> {
+    let mut node = node_for_symbol_range(root, &sym.range)?;
+    while let Some(parent) = node.parent()
+        && is_declaration_wrapper_kind(parent.kind())
+    {
+        node = parent;
+    }
+
+    let mut range = node.range();
+    let mut prev = node.prev_named_sibling();
+    while let Some(p) = prev {
+        let kind = p.kind();
+        if !(kind.contains("comment") || is_attribute_kind(kind)) {
+            break;
+        }
+        if last_row(p) + 1 < range.start_point.row {
+            break;
+        }
+        if is_trailing_comment(p) {
+            break;
+        }
+        range.start_byte = p.start_byte();
+        range.start_point = p.start_position();
+        prev = p.prev_named_sibling();
+    }
+    Some(range)
+}
+
+/// ノードの最終行。改行を含む行コメント (終端が次行の 0 桁目) は前の行とみなす。
+fn last_row(node: Node) -> usize {
+    let end = node.end_position();
+    if end.column == 0 && end.row > node.start_position().row {
+        end.row - 1
+    } else {
+        end.row
+    }
+}
+
+/// 定義の直前に置かれ、doc comment との間に挟まり得る attribute 系ノード。
+fn is_attribute_kind(kind: &str) -> bool {
+    matches!(
+        kind,
+        "attribute_item" | "decorator" | "attribute" | "attribute_list" | "annotation"
+    )
+}
+
+/// 定義ノードを包み、doc comment がその外側に付く wrapper ノード。
+fn is_declaration_wrapper_kind(kind: &str) -> bool {
+    matches!(
+        kind,
+        "export_statement"
+            | "decorated_definition"
+            | "template_declaration"
+            | "ambient_declaration"
+    )
+}
+
+/// Python の `def` / `class` 本体の先頭文が文字列リテラル (docstring) かを判定する。
+fn has_python_docstring(node: Node) -> bool {
+    let Some(body) = node.child_by_field_name("body") else {
+        return false;
+    };
+    let Some(first) = body.named_child(0) else {
+        return false;
+    };
+    if first.kind() != "expression_statement" {
+        return false;
+    }
+    first
+        .named_child(0)
+        .is_some_and(|expr| matches!(expr.kind(), "string" | "concatenated_string"))
+}
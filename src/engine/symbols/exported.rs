@@ -491,6 +491,24 @@ fn is_exported_rust(node: Node) -> bool {
     false
 }
 
+/// Rust: `pub(crate)` / `pub(super)` / `pub(in path)` のように可視性がクレート内に
+/// 制限されているかを判定する。`is_symbol_exported` はこれらも `true` とするため、
+/// クレート外に出る公開 API だけを見たい呼び出し側が併用する。
+pub(crate) fn is_restricted_visibility_rust(
+    root: Node,
+    source: &[u8],
+    symbol_range: &Range,
+) -> bool {
+    let Some(node) = node_for_symbol_range(root, symbol_range) else {
+        return false;
+    };
+    let mut cursor = node.walk();
+    node.children(&mut cursor)
+        .find(|child| child.kind() == "visibility_modifier")
+        .and_then(|vis| vis.utf8_text(source).ok())
+        .is_some_and(|text| text.trim() != "pub")
+}
+
 /// Go: 大文字で始まる識別子はエクスポート。
 fn is_exported_go(node: Node, source: &[u8]) -> bool {
     let name = node
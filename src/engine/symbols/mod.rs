@@ -8,12 +8,14 @@ use crate::models::symbol::{Symbol, SymbolKind};
 
 mod complexity;
 mod cpp;
+mod docs;
 mod exported;
 mod framework;
 mod overrides;
 mod scope;
 
 pub use complexity::calculate_complexity;
+pub use docs::{definition_range, has_doc_comment};
 pub use exported::is_symbol_exported;
 pub use framework::{
     has_framework_entrypoint_decorator_python, is_java_flyway_migration_class,
@@ -31,6 +33,7 @@ pub(crate) use cpp::{
 };
 pub(crate) use exported::{
     collect_js_ts_named_export_surface_names, collect_rust_reexported_names,
+    is_restricted_visibility_rust,
 };
 
 use cpp::cpp_enclosing_function_definition;
@@ -3175,3 +3175,61 @@ fn go_generic_receiver_container_strips_type_params() {
     let push = syms.iter().find(|s| s.name == "Push").expect("Push");
     assert_eq!(push.container.as_deref(), Some("Stack"));
 }
+
+fn check_documented(source: &str, lang_id: LangId, symbol_name: &str) -> bool {
+    let language = lang_id.ts_language();
+    let mut parser = tree_sitter::Parser::new();
+    parser.set_language(&language).unwrap();
+    let tree = parser.parse(source, None).unwrap();
+    let root = tree.root_node();
+
+    let syms = extract_symbols(root, source.as_bytes(), lang_id).unwrap();
+    let sym = syms
+        .iter()
+        .find(|s| s.name == symbol_name)
+        .unwrap_or_else(|| panic!("symbol '{symbol_name}' not found"));
+    has_doc_comment(root, source.as_bytes(), lang_id, sym)
+}
+
+#[test]
+fn rust_doc_comment_before_attribute_counts_as_documented() {
+    let src =
+        "/// A point.\n#[derive(Debug)]\npub struct Point;\n\n#[derive(Debug)]\npub struct Bare;\n";
+    assert!(check_documented(src, LangId::Rust, "Point"));
+    assert!(!check_documented(src, LangId::Rust, "Bare"));
+}
+
+#[test]
+fn ts_doc_comment_outside_export_counts_as_documented() {
+    let src = "/** Adds. */\nexport function add(a: number, b: number) { return a + b; }\n\nexport function sub(a: number, b: number) { return a - b; }\n";
+    assert!(check_documented(src, LangId::Typescript, "add"));
+    assert!(!check_documented(src, LangId::Typescript, "sub"));
+}
+
+#[test]
+fn plain_or_detached_comments_do_not_count_as_documented() {
+    let rust = "// Copyright header\n\npub fn licensed() {}\n\n// just a note\npub fn noted() {}\n\n/// Doc.\n\npub fn detached() {}\n";
+    assert!(!check_documented(rust, LangId::Rust, "licensed"));
+    assert!(!check_documented(rust, LangId::Rust, "noted"));
+    assert!(!check_documented(rust, LangId::Rust, "detached"));
+
+    let ts = "// TODO: split\nexport function todo() {}\n";
+    assert!(!check_documented(ts, LangId::Typescript, "todo"));
+
+    let py = "# helper\ndef commented():\n    return 1\n";
+    assert!(!check_documented(py, LangId::Python, "commented"));
+}
+
+#[test]
+fn go_plain_comment_directly_above_counts_as_documented() {
+    let src = "package p\n\n// Add adds.\nfunc Add() {}\n\n// Header.\n\nfunc Sub() {}\n";
+    assert!(check_documented(src, LangId::Go, "Add"));
+    assert!(!check_documented(src, LangId::Go, "Sub"));
+}
+
+#[test]
+fn python_docstring_counts_as_documented() {
+    let src = "@cache\ndef documented():\n    \"\"\"Return one.\"\"\"\n    return 1\n\ndef bare():\n    return 2\n";
+    assert!(check_documented(src, LangId::Python, "documented"));
+    assert!(!check_documented(src, LangId::Python, "bare"));
+}
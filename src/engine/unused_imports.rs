@@ -0,0 +1,758 @@
+//! ファイル内で一度も参照されない import 名の検出。
+//!
+//! import 文が束縛する名前 (alias があれば alias) を取り出し、import 文以外に現れる
+//! 識別子とコメント中の単語 (doc link / PHPDoc の型指定) に含まれるかで判定する。
+//! スコープや shadowing は見ないため、同名の識別子があれば使用扱いに倒れる。
+//! 再 export (`pub use` / `export { } from`)、副作用 import、wildcard は対象外。
+//!
+//! `find_orphaned_imports` は dead-code `--fix-diff` 用に、宣言の削除で不要になる
+//! import と削除するシンボルの再 export を返す。
+
+use std::collections::HashSet;
+use std::ops::Range as ByteRange;
+
+use tree_sitter::Node;
+
+use crate::engine::parser::child_of_kind;
+use crate::language::LangId;
+use crate::models::import::UnusedImport;
+use crate::models::location::Range;
+
+/// Kotlin で演算子・委譲規約として暗黙に呼ばれる関数名 (`by lazy` の `getValue` 等)。
+const KOTLIN_CONVENTION_NAMES: &[&str] = &[
+    "compareTo",
+    "contains",
+    "dec",
+    "div",
+    "get",
+    "getValue",
+    "inc",
+    "invoke",
+    "iterator",
+    "minus",
+    "next",
+    "not",
+    "plus",
+    "provideDelegate",
+    "rangeTo",
+    "rem",
+    "set",
+    "setValue",
+    "times",
+    "unaryMinus",
+    "unaryPlus",
+];
+
+struct ImportedName<'tree> {
+    name: String,
+    node: Node<'tree>,
+}
+
+/// import 文と、そこから束縛される名前。
+struct ImportStatement<'tree> {
+    node: Node<'tree>,
+    names: Vec<ImportedName<'tree>>,
+    /// wildcard や trait import 等、使用有無を判定できない名前を含むか。
+    /// 含む場合は文ごとの削除を提案しない。
+    has_opaque: bool,
+}
+
+impl<'tree> ImportStatement<'tree> {
+    fn push(&mut self, name: &str, node: Node<'tree>) {
+        self.names.push(ImportedName {
+            name: name.to_string(),
+            node,
+        });
+    }
+}
+
+/// `file` (表示用のパス) 内の未使用 import 名を出現順に返す。
+pub fn find_unused_imports(
+    root: Node<'_>,
+    source: &[u8],
+    lang_id: LangId,
+    file: &str,
+) -> Vec<UnusedImport> {
+    // パッケージの `__init__.py` の import は再 export を兼ねるため対象外。
+    if lang_id == LangId::Python && (file == "__init__.py" || file.ends_with("/__init__.py")) {
+        return Vec::new();
+    }
+    let mut statements = Vec::new();
+    collect_statements(root, source, lang_id, &mut statements);
+    if statements.iter().all(|s| s.names.is_empty()) {
+        return Vec::new();
+    }
+
+    let used = collect_used_words(root, source, lang_id, &[]);
+    // Rust の trait import は名前が本文に現れず、メソッド / 関連関数 (`use clap::Parser;` +
+    // `Cli::parse()`) として使われる。型か trait かはファイル単体では分からないため、
+    // trait 経由で解決され得る呼び出しがあれば型名らしい import は判定しない。
+    let maybe_trait = lang_id == LangId::Rust && has_trait_resolvable_call(root, source);
+    let mut result = Vec::new();
+    for statement in &statements {
+        let (skipped, unused): (Vec<&ImportedName>, Vec<&ImportedName>) = statement
+            .names
+            .iter()
+            .filter(|n| !used.contains(n.name.as_str()))
+            .partition(|n| maybe_trait && is_type_like(&n.name));
+        if unused.is_empty() {
+            continue;
+        }
+        let whole_statement =
+            !statement.has_opaque && skipped.is_empty() && unused.len() == statement.names.len();
+        let text = statement.node.utf8_text(source).unwrap_or("");
+        for name in unused {
+            result.push(UnusedImport {
+                file: file.to_string(),
+                name: name.name.clone(),
+                span: Range::from(name.node.range()),
+                statement_span: Range::from(statement.node.range()),
+                statement: text.to_string(),
+                whole_statement,
+            });
+        }
+    }
+    result
+}
+
+/// `removed` (削除予定の宣言の byte 範囲) を消したときに不要になる import 名を返す。
+///
+/// 削除範囲の中でしか使われていなかった名前と、`removed_names` (削除するシンボルの名前) を
+/// 束縛する import が対象で、削除範囲の外でまだ使われている名前は残す。再 export
+/// (`pub use` / `export { } from` / `export { }`) は `removed_names` を指すものだけを返す。
+/// 削除範囲の中にある import 文自体は宣言と一緒に消えるため返さない。
+pub fn find_orphaned_imports(
+    root: Node<'_>,
+    source: &[u8],
+    lang_id: LangId,
+    file: &str,
+    removed: &[ByteRange<usize>],
+    removed_names: &HashSet<&str>,
+) -> Vec<UnusedImport> {
+    let inside_removed = |node: Node<'_>| {
+        removed
+            .iter()
+            .any(|r| r.start <= node.start_byte() && node.end_byte() <= r.end)
+    };
+    let mut result = Vec::new();
+
+    let mut reexports = Vec::new();
+    collect_reexports(root, source, lang_id, &mut reexports);
+    // 再 export から外す名前も削除範囲として扱い、それを経由してだけ使われていた
+    // import (`import { a } from "./a"; export { a };`) も不要にする。
+    let mut hidden = removed.to_vec();
+    for statement in reexports.iter().filter(|s| !inside_removed(s.node)) {
+        let is_removed = |name: &str| removed_names.contains(name);
+        push_removed_names(statement, source, file, is_removed, &mut result);
+        hidden.extend(
+            statement
+                .names
+                .iter()
+                .filter(|n| is_removed(&n.name))
+                .map(|n| n.node.byte_range()),
+        );
+    }
+
+    let mut statements = Vec::new();
+    collect_statements(root, source, lang_id, &mut statements);
+    if statements.iter().all(|s| s.names.is_empty()) {
+        return result;
+    }
+    // パッケージの `__init__.py` の import は再 export を兼ねるため、削除するシンボル
+    // 自体の import だけを外す。
+    let package_init =
+        lang_id == LangId::Python && (file == "__init__.py" || file.ends_with("/__init__.py"));
+    let used_before = collect_used_words(root, source, lang_id, &[]);
+    let used_after = collect_used_words(root, source, lang_id, &hidden);
+    for statement in statements.iter().filter(|s| !inside_removed(s.node)) {
+        push_removed_names(
+            statement,
+            source,
+            file,
+            |name| {
+                !used_after.contains(name)
+                    && (removed_names.contains(name)
+                        || (!package_init && used_before.contains(name)))
+            },
+            &mut result,
+        );
+    }
+    result
+}
+
+/// `statement` のうち `remove` が真になる名前を `UnusedImport` として積む。
+fn push_removed_names(
+    statement: &ImportStatement<'_>,
+    source: &[u8],
+    file: &str,
+    remove: impl Fn(&str) -> bool,
+    out: &mut Vec<UnusedImport>,
+) {
+    let names: Vec<&ImportedName> = statement
+        .names
+        .iter()
+        .filter(|n| remove(n.name.as_str()))
+        .collect();
+    if names.is_empty() {
+        return;
+    }
+    let whole_statement = !statement.has_opaque && names.len() == statement.names.len();
+    let text = statement.node.utf8_text(source).unwrap_or("");
+    for name in names {
+        out.push(UnusedImport {
+            file: file.to_string(),
+            name: name.name.clone(),
+            span: Range::from(name.node.range()),
+            statement_span: Range::from(statement.node.range()),
+            statement: text.to_string(),
+            whole_statement,
+        });
+    }
+}
+
+/// 再 export 文 (Rust の `pub use`、JS/TS の宣言を伴わない `export { }`) と、
+/// そこで公開される元の名前を集める。
+fn collect_reexports<'tree>(
+    node: Node<'tree>,
+    source: &[u8],
+    lang_id: LangId,
+    out: &mut Vec<ImportStatement<'tree>>,
+) {
+    let mut statement = ImportStatement {
+        node,
+        names: Vec::new(),
+        has_opaque: false,
+    };
+    match (lang_id, node.kind()) {
+        (LangId::Rust, "use_declaration") => {
+            if child_of_kind(node, "visibility_modifier").is_some()
+                && let Some(argument) = node.child_by_field_name("argument")
+            {
+                collect_rust_reexport_tree(argument, source, &mut statement);
+                out.push(statement);
+            }
+            return;
+        }
+        (LangId::Javascript | LangId::Typescript | LangId::Tsx, "export_statement") => {
+            if node.child_by_field_name("declaration").is_none()
+                && let Some(clause) = child_of_kind(node, "export_clause")
+            {
+                let mut cursor = clause.walk();
+                for spec in clause.named_children(&mut cursor) {
+                    if spec.kind() != "export_specifier" {
+                        continue;
+                    }
+                    if let Some(name) = spec
+                        .child_by_field_name("name")
+                        .and_then(|n| n.utf8_text(source).ok())
+                    {
+                        statement.push(name, spec);
+                    }
+                }
+                out.push(statement);
+            }
+            return;
+        }
+        _ => {}
+    }
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        collect_reexports(child, source, lang_id, out);
+    }
+}
+
+/// `pub use` の use tree から元の名前 (alias ではなく公開元のシンボル名) を取り出す。
+fn collect_rust_reexport_tree<'tree>(
+    node: Node<'tree>,
+    source: &[u8],
+    statement: &mut ImportStatement<'tree>,
+) {
+    let name = match node.kind() {
+        "identifier" => Some(node),
+        "scoped_identifier" => node.child_by_field_name("name"),
+        "use_as_clause" => node.child_by_field_name("path").and_then(|path| {
+            if path.kind() == "identifier" {
+                Some(path)
+            } else {
+                path.child_by_field_name("name")
+            }
+        }),
+        "scoped_use_list" => {
+            if let Some(list) = node.child_by_field_name("list") {
+                collect_rust_reexport_tree(list, source, statement);
+            }
+            return;
+        }
+        "use_list" => {
+            let mut cursor = node.walk();
+            for child in node.named_children(&mut cursor) {
+                collect_rust_reexport_tree(child, source, statement);
+            }
+            return;
+        }
+        _ => None,
+    };
+    match name.and_then(|n| n.utf8_text(source).ok()) {
+        Some(name) if name != "self" => statement.push(name, node),
+        _ => statement.has_opaque = true,
+    }
+}
+
+/// import 文として扱う (使用箇所の収集から外す) ノード種別か。
+fn is_import_kind(lang_id: LangId, kind: &str) -> bool {
+    match lang_id {
+        LangId::Rust => matches!(kind, "use_declaration" | "extern_crate_declaration"),
+        LangId::Javascript | LangId::Typescript | LangId::Tsx => kind == "import_statement",
+        LangId::Python => matches!(
+            kind,
+            "import_statement" | "import_from_statement" | "future_import_statement"
+        ),
+        LangId::Java => kind == "import_declaration",
+        LangId::Kotlin => kind == "import_header",
+        // PHP の `use_declaration` はクラス内の trait 利用で、import ではない。
+        LangId::Php => kind == "namespace_use_declaration",
+        LangId::CSharp => kind == "using_directive",
+        _ => false,
+    }
+}
+
+fn collect_statements<'tree>(
+    node: Node<'tree>,
+    source: &[u8],
+    lang_id: LangId,
+    out: &mut Vec<ImportStatement<'tree>>,
+) {
+    if is_import_kind(lang_id, node.kind()) {
+        if let Some(statement) = import_statement(node, source, lang_id) {
+            out.push(statement);
+        }
+        return;
+    }
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        collect_statements(child, source, lang_id, out);
+    }
+}
+
+/// import 文から束縛名を取り出す。再 export・副作用 import・wildcard のみの文は `None`。
+fn import_statement<'tree>(
+    node: Node<'tree>,
+    source: &[u8],
+    lang_id: LangId,
+) -> Option<ImportStatement<'tree>> {
+    let mut statement = ImportStatement {
+        node,
+        names: Vec::new(),
+        has_opaque: false,
+    };
+    match (lang_id, node.kind()) {
+        (LangId::Rust, "use_declaration") => {
+            // `pub use` / `pub(crate) use` は再 export。
+            if child_of_kind(node, "visibility_modifier").is_some() {
+                return None;
+            }
+            collect_rust_use_tree(
+                node.child_by_field_name("argument")?,
+                source,
+                &mut statement,
+            );
+        }
+        (LangId::Javascript | LangId::Typescript | LangId::Tsx, "import_statement") => {
+            // 副作用 import (`import "./polyfill"`) は import_clause を持たない。
+            let clause = child_of_kind(node, "import_clause")?;
+            collect_js_import_clause(clause, source, &mut statement);
+        }
+        (LangId::Python, "import_statement" | "import_from_statement") => {
+            if child_of_kind(node, "wildcard_import").is_some() {
+                return None;
+            }
+            let mut cursor = node.walk();
+            for name in node.children_by_field_name("name", &mut cursor) {
+                push_python_name(name, source, &mut statement);
+            }
+        }
+        (LangId::Java, "import_declaration") => {
+            if child_of_kind(node, "asterisk").is_some() {
+                return None;
+            }
+            let path = child_of_kind(node, "scoped_identifier")
+                .or_else(|| child_of_kind(node, "identifier"))?;
+            let name = match path.kind() {
+                "scoped_identifier" => path.child_by_field_name("name")?,
+                _ => path,
+            };
+            statement.push(name.utf8_text(source).ok()?, path);
+        }
+        (LangId::Kotlin, "import_header") => {
+            let text = node.utf8_text(source).ok()?;
+            if text.trim_end().trim_end_matches(';').ends_with('*') {
+                return None;
+            }
+            if let Some(alias) = child_of_kind(node, "import_alias") {
+                let name = alias.named_child(alias.named_child_count().checked_sub(1)?)?;
+                statement.push(name.utf8_text(source).ok()?, alias);
+            } else {
+                let path = child_of_kind(node, "identifier")?;
+                let last = path.named_child(path.named_child_count().checked_sub(1)?)?;
+                let name = last.utf8_text(source).ok()?;
+                if KOTLIN_CONVENTION_NAMES.contains(&name) || name.starts_with("component") {
+                    statement.has_opaque = true;
+                } else {
+                    statement.push(name, path);
+                }
+            }
+        }
+        (LangId::Php, "namespace_use_declaration") => {
+            collect_php_use_clauses(node, source, &mut statement);
+        }
+        (LangId::CSharp, "using_directive") => {
+            // 名前空間の using は個々の型との対応が取れないため、alias
+            // (`using Json = System.Text.Json;`) だけを対象にする。
+            if node
+                .utf8_text(source)
+                .ok()?
+                .trim_start()
+                .starts_with("global")
+            {
+                return None;
+            }
+            let alias = node.child_by_field_name("name").or_else(|| {
+                child_of_kind(node, "name_equals").and_then(|n| child_of_kind(n, "identifier"))
+            })?;
+            statement.push(alias.utf8_text(source).ok()?, node);
+        }
+        _ => return None,
+    }
+    Some(statement)
+}
+
+fn collect_rust_use_tree<'tree>(
+    node: Node<'tree>,
+    source: &[u8],
+    statement: &mut ImportStatement<'tree>,
+) {
+    let name = match node.kind() {
+        "identifier" => Some(node),
+        "scoped_identifier" => node.child_by_field_name("name"),
+        // `use foo::Trait as _;` はメソッド解決のためだけの import。
+        "use_as_clause" => node
+            .child_by_field_name("alias")
+            .filter(|alias| alias.utf8_text(source) != Ok("_")),
+        "scoped_use_list" => {
+            if let Some(list) = node.child_by_field_name("list") {
+                collect_rust_use_tree(list, source, statement);
+            }
+            return;
+        }
+        "use_list" => {
+            let mut cursor = node.walk();
+            for child in node.named_children(&mut cursor) {
+                collect_rust_use_tree(child, source, statement);
+            }
+            return;
+        }
+        // use_wildcard / `self` / `super` 等。
+        _ => None,
+    };
+    let Some(name) = name.and_then(|n| n.utf8_text(source).ok()) else {
+        statement.has_opaque = true;
+        return;
+    };
+    if name == "self" {
+        statement.has_opaque = true;
+        return;
+    }
+    statement.push(name, node);
+}
+
+fn collect_js_import_clause<'tree>(
+    clause: Node<'tree>,
+    source: &[u8],
+    statement: &mut ImportStatement<'tree>,
+) {
+    let mut cursor = clause.walk();
+    for child in clause.named_children(&mut cursor) {
+        match child.kind() {
+            // default import
+            "identifier" => {
+                if let Ok(name) = child.utf8_text(source) {
+                    statement.push(name, child);
+                }
+            }
+            "namespace_import" => {
+                if let Some(name) =
+                    child_of_kind(child, "identifier").and_then(|n| n.utf8_text(source).ok())
+                {
+                    statement.push(name, child);
+                }
+            }
+            "named_imports" => {
+                let mut specs = child.walk();
+                for spec in child.named_children(&mut specs) {
+                    if spec.kind() != "import_specifier" {
+                        continue;
+                    }
+                    let bound = spec
+                        .child_by_field_name("alias")
+                        .or_else(|| spec.child_by_field_name("name"));
+                    if let Some(name) = bound
+                        .filter(|n| n.kind() == "identifier")
+                        .and_then(|n| n.utf8_text(source).ok())
+                    {
+                        statement.push(name, spec);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn push_python_name<'tree>(
+    node: Node<'tree>,
+    source: &[u8],
+    statement: &mut ImportStatement<'tree>,
+) {
+    let bound = match node.kind() {
+        "aliased_import" => node
+            .child_by_field_name("alias")
+            .and_then(|alias| alias.utf8_text(source).ok()),
+        // `import a.b` は先頭の `a` を束縛する。
+        "dotted_name" => node
+            .utf8_text(source)
+            .ok()
+            .and_then(|text| text.split('.').next()),
+        _ => None,
+    };
+    if let Some(name) = bound {
+        statement.push(name, node);
+    }
+}
+
+fn collect_php_use_clauses<'tree>(
+    node: Node<'tree>,
+    source: &[u8],
+    statement: &mut ImportStatement<'tree>,
+) {
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        match child.kind() {
+            "namespace_use_clause" | "namespace_use_group_clause" => {
+                if let Some(name) = php_bound_name(child).and_then(|n| n.utf8_text(source).ok()) {
+                    statement.push(name, child);
+                }
+            }
+            "namespace_use_group" => collect_php_use_clauses(child, source, statement),
+            _ => {}
+        }
+    }
+}
+
+/// `use A\B\C as D;` の `D`、alias が無ければ末尾の `C`。
+fn php_bound_name(clause: Node<'_>) -> Option<Node<'_>> {
+    if let Some(alias) = clause.child_by_field_name("alias").or_else(|| {
+        child_of_kind(clause, "namespace_aliasing_clause").and_then(|c| child_of_kind(c, "name"))
+    }) {
+        return Some(alias);
+    }
+    let path = child_of_kind(clause, "qualified_name").or_else(|| child_of_kind(clause, "name"))?;
+    if path.kind() == "name" {
+        return Some(path);
+    }
+    let mut cursor = path.walk();
+    path.named_children(&mut cursor)
+        .filter(|n| n.kind() == "name")
+        .last()
+}
+
+/// trait のメソッド / 関連関数として解決され得る呼び出し (`x.m()` / `Type::m()`) を含むか。
+fn has_trait_resolvable_call(root: Node<'_>, source: &[u8]) -> bool {
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        if node.kind() == "call_expression"
+            && let Some(function) = node.child_by_field_name("function")
+        {
+            match function.kind() {
+                "field_expression" => return true,
+                "scoped_identifier" => {
+                    let path = function
+                        .child_by_field_name("path")
+                        .map(|path| match path.kind() {
+                            "generic_type" => path.child_by_field_name("type").unwrap_or(path),
+                            "scoped_identifier" | "scoped_type_identifier" => {
+                                path.child_by_field_name("name").unwrap_or(path)
+                            }
+                            _ => path,
+                        });
+                    if path
+                        .and_then(|p| p.utf8_text(source).ok())
+                        .is_some_and(is_type_like)
+                    {
+                        return true;
+                    }
+                }
+                _ => {}
+            }
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.named_children(&mut cursor));
+    }
+    false
+}
+
+/// 型 / trait らしい名前 (大文字始まりで、定数のような全大文字ではない)。
+fn is_type_like(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_uppercase()) && name.chars().any(|c| c.is_lowercase())
+}
+
+/// import 文以外に現れる識別子と、コメント中の単語を集める。
+///
+/// コメントは Rust の intra-doc link や PHPDoc / JSDoc の型指定で import を使うため含める。
+/// Python は `__all__` と文字列の前方参照アノテーションのため文字列リテラルの単語も含める。
+/// `removed` に収まるノードは数えない。
+fn collect_used_words<'a>(
+    root: Node<'_>,
+    source: &'a [u8],
+    lang_id: LangId,
+    removed: &[ByteRange<usize>],
+) -> HashSet<&'a str> {
+    let is_js = matches!(
+        lang_id,
+        LangId::Javascript | LangId::Typescript | LangId::Tsx
+    );
+    let mut used = HashSet::new();
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        let kind = node.kind();
+        if is_import_kind(lang_id, kind)
+            || removed
+                .iter()
+                .any(|r| r.start <= node.start_byte() && node.end_byte() <= r.end)
+        {
+            continue;
+        }
+        if kind.contains("comment") || (lang_id == LangId::Python && kind == "string") {
+            let text = std::str::from_utf8(&source[node.byte_range()]).unwrap_or("");
+            used.extend(
+                text.split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+                    .filter(|word| !word.is_empty()),
+            );
+            continue;
+        }
+        // classic JSX runtime は `React.createElement` に変換されるため `React` を使用扱いにする。
+        if is_js && kind.starts_with("jsx_") {
+            used.insert("React");
+        }
+        if node.child_count() == 0 {
+            if node.is_named()
+                && !kind.contains("string")
+                && let Ok(text) = std::str::from_utf8(&source[node.byte_range()])
+            {
+                used.insert(text);
+            }
+            continue;
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.children(&mut cursor));
+    }
+    used
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::parser;
+
+    fn unused(source: &str, lang_id: LangId, file: &str) -> Vec<(String, bool)> {
+        let tree = parser::parse_source(source.as_bytes(), lang_id).unwrap();
+        find_unused_imports(tree.root_node(), source.as_bytes(), lang_id, file)
+            .into_iter()
+            .map(|u| (u.name, u.whole_statement))
+            .collect()
+    }
+
+    #[test]
+    fn rust_use_leaves_skip_reexports() {
+        let source = "use std::collections::{HashMap, HashSet};\nuse crate::util::helper as h;\npub use crate::api::Client;\n\nfn main(_m: HashMap<u8, u8>) {}\n";
+        assert_eq!(
+            unused(source, LangId::Rust, "src/main.rs"),
+            vec![("HashSet".to_string(), false), ("h".to_string(), true)],
+        );
+    }
+
+    /// trait はメソッド / 関連関数としてだけ使われ、名前が本文に現れない。
+    #[test]
+    fn rust_trait_imports_used_through_calls_are_not_reported() {
+        let source = "use clap::Parser;\nuse std::io::Write;\nuse crate::cli::{Cli, helper};\n\nfn main() {\n    let cli = Cli::parse();\n    std::io::stdout().flush().ok();\n    drop(cli);\n}\n";
+        assert_eq!(
+            unused(source, LangId::Rust, "src/main.rs"),
+            vec![("helper".to_string(), false)],
+        );
+
+        // 関連関数呼び出しだけでも trait の可能性がある (`Cli::parse()`)。
+        let source = "use clap::Parser;\n\nfn main() {\n    let _cli = Cli::parse();\n}\n";
+        assert!(unused(source, LangId::Rust, "src/main.rs").is_empty());
+
+        // trait 経由の呼び出しが無ければ型名の import も報告する。
+        let source = "use clap::Parser;\n\nfn main() {}\n";
+        assert_eq!(
+            unused(source, LangId::Rust, "src/main.rs"),
+            vec![("Parser".to_string(), true)],
+        );
+    }
+
+    #[test]
+    fn typescript_named_default_and_side_effect_imports() {
+        let source = "import \"./polyfill\";\nimport React, { useState, useEffect as effect } from \"react\";\nimport * as path from \"path\";\nexport { join } from \"path\";\n\nexport function App() {\n  const [v] = useState(0);\n  return <div>{v}</div>;\n}\n";
+        assert_eq!(
+            unused(source, LangId::Tsx, "src/App.tsx"),
+            vec![("effect".to_string(), false), ("path".to_string(), true)],
+        );
+    }
+
+    #[test]
+    fn python_from_import_counts_all_and_string_annotations() {
+        let source = "import os.path\nfrom typing import List, Optional\nfrom .models import User, Group as G\nfrom helpers import *\n\n__all__ = [\"User\"]\n\ndef f(x: \"Optional[int]\") -> List[int]:\n    return []\n";
+        assert_eq!(
+            unused(source, LangId::Python, "pkg/mod.py"),
+            vec![("os".to_string(), true), ("G".to_string(), false)],
+        );
+        assert!(unused(source, LangId::Python, "pkg/__init__.py").is_empty());
+    }
+
+    #[test]
+    fn orphaned_imports_follow_removed_declarations_and_reexports() {
+        let source = "use std::collections::{HashMap, HashSet};\nuse crate::util::Unused;\npub use crate::inner::{dead_fn, live_fn};\n\nfn keep() -> HashSet<u8> {\n    HashSet::new()\n}\n\nfn dead() {\n    let _m: HashMap<u8, u8> = HashMap::new();\n}\n";
+        let tree = parser::parse_source(source.as_bytes(), LangId::Rust).unwrap();
+        let start = source.find("fn dead").unwrap();
+        let names: HashSet<&str> = ["dead", "dead_fn"].into_iter().collect();
+        let orphaned: Vec<(String, bool)> = find_orphaned_imports(
+            tree.root_node(),
+            source.as_bytes(),
+            LangId::Rust,
+            "src/lib.rs",
+            &[start..source.len()],
+            &names,
+        )
+        .into_iter()
+        .map(|u| (u.name, u.whole_statement))
+        .collect();
+        // 元から未使用の `Unused` は unused-imports の担当なので触らない。
+        assert_eq!(
+            orphaned,
+            vec![
+                ("dead_fn".to_string(), false),
+                ("HashMap".to_string(), false)
+            ],
+        );
+    }
+
+    #[test]
+    fn php_use_counts_docblock_and_trait_use() {
+        let source = "<?php\nnamespace App;\n\nuse App\\Models\\User;\nuse App\\Support\\{Cache, Clock as C};\nuse App\\Concerns\\HasName;\n\nclass Service {\n    use HasName;\n\n    /** @var User */\n    private $user;\n}\n";
+        assert_eq!(
+            unused(source, LangId::Php, "src/Service.php"),
+            vec![("Cache".to_string(), true), ("C".to_string(), true)],
+        );
+    }
+}
@@ -46,38 +46,59 @@ fn tree_sitter_kotlin() -> *const std::ffi::c_void {
     unsafe { ffi_kotlin::tree_sitter_kotlin() }
 }
 
+/// 拡張子 → 言語の対応表 (`from_path` / `referencing_pathspecs` で共有する)。
+const EXTENSIONS: &[(LangId, &[&str])] = &[
+    (LangId::Rust, &["rs"]),
+    (LangId::C, &["c", "h"]),
+    (LangId::Cpp, &["cpp", "cc", "cxx", "hpp", "hh", "hxx"]),
+    (LangId::Python, &["py", "pyi"]),
+    (LangId::Javascript, &["js", "mjs", "cjs", "jsx"]),
+    (LangId::Typescript, &["ts", "mts", "cts"]),
+    (LangId::Tsx, &["tsx"]),
+    (LangId::Go, &["go"]),
+    (LangId::Php, &["php", "phtml"]),
+    (LangId::Java, &["java"]),
+    (LangId::Kotlin, &["kt", "kts"]),
+    (LangId::Swift, &["swift"]),
+    (LangId::CSharp, &["cs"]),
+    (LangId::Bash, &["sh", "bash", "zsh"]),
+    (LangId::Ruby, &["rb", "rake", "gemspec"]),
+    (LangId::Zig, &["zig", "zon"]),
+    (
+        LangId::Xojo,
+        &[
+            "xojo_code",
+            "xojo_window",
+            "xojo_menu",
+            "xojo_toolbar",
+            "xojo_report",
+            "rbbas",
+        ],
+    ),
+];
+
 impl LangId {
     /// Detect language from file extension.
     pub fn from_path(path: &Utf8Path) -> Result<Self, AstroError> {
         let ext = path.extension().unwrap_or("").to_lowercase();
-
-        match ext.as_str() {
-            "rs" => Ok(Self::Rust),
-            "c" | "h" => Ok(Self::C),
-            "cpp" | "cc" | "cxx" | "hpp" | "hh" | "hxx" => Ok(Self::Cpp),
-            "py" | "pyi" => Ok(Self::Python),
-            "js" | "mjs" | "cjs" | "jsx" => Ok(Self::Javascript),
-            "ts" | "mts" | "cts" => Ok(Self::Typescript),
-            "tsx" => Ok(Self::Tsx),
-            "go" => Ok(Self::Go),
-            "php" | "phtml" => Ok(Self::Php),
-            "java" => Ok(Self::Java),
-            "kt" | "kts" => Ok(Self::Kotlin),
-            "swift" => Ok(Self::Swift),
-            "cs" => Ok(Self::CSharp),
-            "sh" | "bash" | "zsh" => Ok(Self::Bash),
-            "rb" | "rake" | "gemspec" => Ok(Self::Ruby),
-            "zig" | "zon" => Ok(Self::Zig),
-            "xojo_code" | "xojo_window" | "xojo_menu" | "xojo_toolbar" | "xojo_report"
-            | "rbbas" => Ok(Self::Xojo),
-            other => {
-                if other.is_empty() {
-                    Err(AstroError::unsupported_language("<no extension>"))
-                } else {
-                    Err(AstroError::unsupported_language(other))
-                }
-            }
+        if ext.is_empty() {
+            return Err(AstroError::unsupported_language("<no extension>"));
         }
+        EXTENSIONS
+            .iter()
+            .find(|(_, exts)| exts.contains(&ext.as_str()))
+            .map(|&(lang, _)| lang)
+            .ok_or_else(|| AstroError::unsupported_language(&ext))
+    }
+
+    /// `def_lang` の定義を参照し得る言語 (`can_reference_definition_in`) のファイルに
+    /// 一致する git pathspec (`*.rs` 等)。
+    pub fn referencing_pathspecs(def_lang: LangId) -> Vec<String> {
+        EXTENSIONS
+            .iter()
+            .filter(|&&(lang, _)| lang.can_reference_definition_in(def_lang))
+            .flat_map(|&(_, exts)| exts.iter().map(|ext| format!("*.{ext}")))
+            .collect()
     }
 
     /// 拡張子からの言語検出に失敗した場合、source の先頭行 shebang を見て再検出する。
@@ -621,6 +642,18 @@ mod tests {
         assert!(LangId::from_path(Utf8Path::new("file.xyz")).is_err());
     }
 
+    #[test]
+    fn referencing_pathspecs_follow_reference_compatibility() {
+        let ts = LangId::referencing_pathspecs(LangId::Typescript);
+        assert!(ts.contains(&"*.ts".to_string()));
+        assert!(ts.contains(&"*.jsx".to_string()));
+        assert!(!ts.contains(&"*.py".to_string()));
+        let c = LangId::referencing_pathspecs(LangId::C);
+        assert!(c.contains(&"*.h".to_string()));
+        assert!(c.contains(&"*.rs".to_string()));
+        assert!(!c.contains(&"*.java".to_string()));
+    }
+
     #[test]
     fn shebang_python() {
         assert_eq!(
@@ -4,17 +4,21 @@ use std::any::Any;
 use std::io::{self, Write};
 use tracing::info;
 
-use astro_sight::cli::{Cli, Commands};
+use astro_sight::cli::{ApiAction, Cli, Commands, ConfigAction};
 use astro_sight::commands::{
-    self, CmdAstOpts, CmdContextOpts, CmdImpactOpts, CmdReviewOpts, batch_ast, batch_calls,
-    batch_imports, batch_lint, batch_sequence, batch_symbols, cmd_ast, cmd_calls, cmd_cochange,
-    cmd_context, cmd_dead_code, cmd_doctor, cmd_impact, cmd_imports, cmd_lint, cmd_mcp, cmd_refs,
-    cmd_refs_batch, cmd_review, cmd_sequence, cmd_session, cmd_symbols, cmd_symbols_dir,
+    self, CmdAffectedTestsOpts, CmdApiCheckOpts, CmdApiDumpOpts, CmdAstOpts, CmdContextOpts,
+    CmdDeadCodeOpts, CmdDocCoverageOpts, CmdDupesOpts, CmdImpactOpts, CmdParseHealthOpts,
+    CmdReviewOpts, CmdSemverOpts, CmdSymdiffOpts, CmdUnusedImportsOpts, batch_ast, batch_calls,
+    batch_imports, batch_lint, batch_sequence, batch_symbols, cmd_affected_tests, cmd_api_check,
+    cmd_api_dump, cmd_ast, cmd_calls, cmd_cochange, cmd_context, cmd_dead_code, cmd_doc_coverage,
+    cmd_doctor, cmd_dupes, cmd_impact, cmd_imports, cmd_lint, cmd_mcp, cmd_parse_health, cmd_refs,
+    cmd_refs_batch, cmd_review, cmd_semver, cmd_sequence, cmd_session, cmd_source, cmd_symbols,
+    cmd_symbols_dir, cmd_symdiff, cmd_unused_imports,
 };
-use astro_sight::config::ConfigService;
+use astro_sight::config::{ConfigService, ConfigShow, ProjectConfig};
 use astro_sight::error::{AstroError, ErrorCode};
-use astro_sight::output::OutputOptions;
-use astro_sight::service::AppService;
+use astro_sight::output::{OutputFormat, OutputOptions};
+use astro_sight::service::{AppService, SourceParams};
 
 // dhat-heap feature 有効時のみヒーププロファイラを差し込む。
 // 実行後に `dhat-heap.json` が書き出されるので dh_view.html で可視化する。
@@ -249,11 +253,41 @@ fn run(cli: Cli) -> Result<()> {
     initialize_logging(&cli, &config)?;
     // 優先順位: CLI `--format` > config.toml の `format` > json。
     let output = OutputOptions::resolve(cli.format, config.format, cli.pretty);
+    // レポート形式 (markdown / codeclimate) は対応コマンドだけが描画できる。ここで弾くか JSON に倒して、
+    // 以降の汎用出力経路がレポート形式を受け取らないようにする。
+    let output = match &cli.command {
+        Commands::Review { .. } => output.ensure_report_format(
+            "review",
+            &[OutputFormat::Markdown, OutputFormat::Codeclimate],
+        )?,
+        Commands::DeadCode { .. } => {
+            output.ensure_report_format("dead-code", &[OutputFormat::Codeclimate])?
+        }
+        Commands::Lint { .. } => {
+            output.ensure_report_format("lint", &[OutputFormat::Codeclimate])?
+        }
+        _ => output.ensure_report_format("this command", &[])?,
+    };
+
+    // オプション既定値の優先順位: CLI > .astro-sight.toml > config.toml > 組み込み既定値。
+    let project = ProjectConfig::discover(std::path::Path::new(command_dir(&cli.command)))?;
+    if let Commands::Config {
+        action: ConfigAction::Show { effective, .. },
+    } = &cli.command
+    {
+        return show_config(&cli, config.settings, project, *effective, output);
+    }
+    let mut command = cli.command;
+    project
+        .map(|project| project.settings)
+        .unwrap_or_default()
+        .or(config.settings)
+        .apply(&mut command);
 
     // カレントディレクトリと入力パラメータを含めてコマンド実行を記録する
     let cwd = std::env::current_dir().unwrap_or_default();
     info!(
-        command = ?cli.command,
+        command = ?command,
         cwd = %cwd.display(),
         "🚀 command invoked"
     );
@@ -261,7 +295,7 @@ fn run(cli: Cli) -> Result<()> {
     let service = AppService::new();
     let start = std::time::Instant::now();
 
-    let result = dispatch_command(&service, cli.command, output);
+    let result = dispatch_command(&service, command, output);
 
     let elapsed = start.elapsed();
     info!(
@@ -272,6 +306,59 @@ fn run(cli: Cli) -> Result<()> {
     result
 }
 
+/// project 設定を探し始めるディレクトリ。`--dir` を持たないコマンドはカレントディレクトリ。
+fn command_dir(command: &Commands) -> &str {
+    match command {
+        Commands::Refs { dir, .. }
+        | Commands::Context { dir, .. }
+        | Commands::Impact { dir, .. }
+        | Commands::Cochange { dir, .. }
+        | Commands::Review { dir, .. }
+        | Commands::DeadCode { dir, .. }
+        | Commands::Dupes { dir, .. }
+        | Commands::Symdiff { dir, .. }
+        | Commands::DocCoverage { dir, .. }
+        | Commands::UnusedImports { dir, .. }
+        | Commands::AffectedTests { dir, .. }
+        | Commands::Semver { dir, .. }
+        | Commands::ParseHealth { dir, .. }
+        | Commands::Api {
+            action: ApiAction::Dump { dir, .. } | ApiAction::Check { dir, .. },
+        }
+        | Commands::Config {
+            action: ConfigAction::Show { dir, .. },
+        } => dir,
+        Commands::Symbols { dir: Some(dir), .. } | Commands::Source { dir: Some(dir), .. } => dir,
+        _ => ".",
+    }
+}
+
+/// `config show [--effective]`。設定の読み込み結果をそのまま出すため、他コマンドと違い
+/// `dispatch_command` を通さずに読み込み済みの各層から組み立てる。
+fn show_config(
+    cli: &Cli,
+    user: astro_sight::config::ProjectSettings,
+    project: Option<ProjectConfig>,
+    effective: bool,
+    output: OutputOptions,
+) -> Result<()> {
+    let user_config = cli
+        .config
+        .clone()
+        .unwrap_or_else(ConfigService::default_path);
+    let user_config = user_config.is_file().then_some(user_config);
+    let show = if effective {
+        ConfigShow::effective(user_config, user, project)
+    } else {
+        ConfigShow::layers(user_config, user, project)
+    };
+    print!(
+        "{}",
+        astro_sight::output::serialize_cli_document(&show, output)?
+    );
+    Ok(())
+}
+
 fn dispatch_command(service: &AppService, command: Commands, output: OutputOptions) -> Result<()> {
     match command {
         Commands::Ast {
@@ -406,10 +493,34 @@ fn dispatch_command(service: &AppService, command: Commands, output: OutputOptio
             names,
             dir,
             glob,
+            parse_warnings,
         } => match resolve_names(name.as_deref(), names.as_deref())? {
-            NameInput::Single(n) => cmd_refs(service, &n, &dir, glob.as_deref(), output),
-            NameInput::Batch(ns) => cmd_refs_batch(service, &ns, &dir, glob.as_deref(), output),
+            NameInput::Single(n) => {
+                cmd_refs(service, &n, &dir, glob.as_deref(), parse_warnings, output)
+            }
+            NameInput::Batch(ns) => {
+                cmd_refs_batch(service, &ns, &dir, glob.as_deref(), parse_warnings, output)
+            }
         },
+        Commands::Source {
+            path,
+            dir,
+            glob,
+            name,
+            container,
+            max_bytes,
+        } => cmd_source(
+            service,
+            &SourceParams {
+                path: path.as_deref(),
+                dir: dir.as_deref(),
+                glob: glob.as_deref(),
+                name: &name,
+                container: container.as_deref(),
+                max_bytes,
+            },
+            output,
+        ),
         Commands::Review {
             dir,
             diff,
@@ -422,9 +533,19 @@ fn dispatch_command(service: &AppService, command: Commands, output: OutputOptio
             framework,
             exclude_dirs,
             exclude_globs,
+            entry_annotations,
             dead_scope,
             strict_public_const_values,
+            no_strict_public_const_values: _,
             include_wip_dead,
+            no_include_wip_dead: _,
+            risk_threshold,
+            dupes,
+            no_dupes: _,
+            transitive_depth,
+            range,
+            per_commit,
+            link_base,
         } => {
             // --hook 指定時、未指定なら touched-symbols に降格して
             // 「changed file 内の元から存在した dead」のノイズを抑える。
@@ -440,15 +561,26 @@ fn dispatch_command(service: &AppService, command: Commands, output: OutputOptio
                 git,
                 base: &base,
                 staged,
-                min_confidence,
+                min_confidence: min_confidence.unwrap_or(
+                    astro_sight::models::cochange::CoChangeOptions::default().min_confidence,
+                ),
                 output,
                 hook,
                 framework: framework.as_deref(),
                 extra_exclude_dirs: &exclude_dirs,
                 extra_exclude_globs: &exclude_globs,
+                entry_annotations: &entry_annotations,
                 dead_scope: resolved_dead_scope,
-                strict_public_const_values,
-                include_wip_dead,
+                // `--no-*` は設定適用時に `Some(false)` へ畳み込み済み。
+                strict_public_const_values: strict_public_const_values.unwrap_or(false),
+                include_wip_dead: include_wip_dead.unwrap_or(false),
+                risk_threshold,
+                // 既定では通常 review だけで検出し、hook の既定経路には載せない。
+                dupes: dupes.unwrap_or(!hook),
+                transitive_depth,
+                range: range.as_deref(),
+                per_commit,
+                link_base: link_base.as_deref(),
             };
             cmd_review(service, &opts)
         }
@@ -462,6 +594,7 @@ fn dispatch_command(service: &AppService, command: Commands, output: OutputOptio
             staged,
             exclude_dirs,
             exclude_globs,
+            transitive_depth,
         } => cmd_context(
             service,
             &CmdContextOpts {
@@ -474,6 +607,7 @@ fn dispatch_command(service: &AppService, command: Commands, output: OutputOptio
                 output,
                 exclude_dirs: &exclude_dirs,
                 exclude_globs: &exclude_globs,
+                transitive_depth,
             },
         ),
         Commands::Impact {
@@ -511,25 +645,185 @@ fn dispatch_command(service: &AppService, command: Commands, output: OutputOptio
             framework,
             exclude_dirs,
             exclude_globs,
+            entry_annotations,
             dead_scope,
-        } => cmd_dead_code(
-            &dir,
-            glob.as_deref(),
-            diff.as_deref(),
-            diff_file.as_deref(),
+            scope,
+            reachability,
+            entries,
+            params,
+            cargo_features,
+            parse_warnings,
+            fix_diff,
+            blame,
+            sort,
+        } => cmd_dead_code(&CmdDeadCodeOpts {
+            dir: &dir,
+            glob: glob.as_deref(),
+            diff: diff.as_deref(),
+            diff_file: diff_file.as_deref(),
             git,
-            &base,
+            base: &base,
             staged,
             include_vendor,
             include_tests,
             include_build,
-            framework.as_deref(),
-            &exclude_dirs,
-            &exclude_globs,
+            framework: framework.as_deref(),
+            extra_exclude_dirs: &exclude_dirs,
+            extra_exclude_globs: &exclude_globs,
+            entry_annotations: &entry_annotations,
             output,
-            dead_scope,
+            dead_scope: dead_scope.unwrap_or(astro_sight::cli::DeadScope::All),
+            scope,
+            reachability,
+            entries: &entries,
+            params,
+            cargo_features,
+            parse_warnings,
+            fix_diff,
+            blame,
+            sort,
+        }),
+        Commands::Dupes {
+            dir,
+            glob,
+            min_tokens,
+            include_tests,
+            exclude_dirs,
+            exclude_globs,
+        } => cmd_dupes(&CmdDupesOpts {
+            dir: &dir,
+            glob: glob.as_deref(),
+            min_tokens,
+            include_tests,
+            extra_exclude_dirs: &exclude_dirs,
+            extra_exclude_globs: &exclude_globs,
+            output,
+        }),
+        Commands::Symdiff { dir, from, to } => cmd_symdiff(&CmdSymdiffOpts {
+            dir: &dir,
+            from: &from,
+            to: &to,
+            output,
+        }),
+        Commands::DocCoverage {
+            dir,
+            glob,
+            diff,
+            diff_file,
+            git,
+            base,
+            staged,
+            exclude_dirs,
+            exclude_globs,
+        } => cmd_doc_coverage(&CmdDocCoverageOpts {
+            dir: &dir,
+            glob: glob.as_deref(),
+            diff: diff.as_deref(),
+            diff_file: diff_file.as_deref(),
+            git,
+            base: &base,
+            staged,
+            extra_exclude_dirs: &exclude_dirs,
+            extra_exclude_globs: &exclude_globs,
+            output,
+        }),
+        Commands::UnusedImports {
+            dir,
+            path,
+            glob,
+            diff,
+            diff_file,
+            git,
+            base,
+            staged,
+            exclude_dirs,
+            exclude_globs,
+        } => cmd_unused_imports(&CmdUnusedImportsOpts {
+            dir: &dir,
+            path: path.as_deref(),
+            glob: glob.as_deref(),
+            diff: diff.as_deref(),
+            diff_file: diff_file.as_deref(),
+            git,
+            base: &base,
+            staged,
+            extra_exclude_dirs: &exclude_dirs,
+            extra_exclude_globs: &exclude_globs,
+            output,
+        }),
+        Commands::AffectedTests {
+            dir,
+            diff,
+            diff_file,
+            git,
+            base,
+            staged,
+            max_depth,
+            runner,
+            exclude_dirs,
+            exclude_globs,
+        } => cmd_affected_tests(
+            service,
+            &CmdAffectedTestsOpts {
+                dir: &dir,
+                diff: diff.as_deref(),
+                diff_file: diff_file.as_deref(),
+                git,
+                base: &base,
+                staged,
+                max_depth,
+                runner,
+                exclude_dirs: &exclude_dirs,
+                exclude_globs: &exclude_globs,
+                output,
+            },
         ),
+        Commands::Semver { dir, base } => cmd_semver(&CmdSemverOpts {
+            dir: &dir,
+            base: &base,
+            output,
+        }),
+        Commands::Api { action } => match action {
+            ApiAction::Dump {
+                dir,
+                glob,
+                exclude_dirs,
+                exclude_globs,
+            } => cmd_api_dump(&CmdApiDumpOpts {
+                dir: &dir,
+                glob: glob.as_deref(),
+                extra_exclude_dirs: &exclude_dirs,
+                extra_exclude_globs: &exclude_globs,
+            }),
+            ApiAction::Check {
+                dir,
+                snapshot,
+                glob,
+                exclude_dirs,
+                exclude_globs,
+            } => cmd_api_check(&CmdApiCheckOpts {
+                dir: &dir,
+                snapshot: &snapshot,
+                glob: glob.as_deref(),
+                extra_exclude_dirs: &exclude_dirs,
+                extra_exclude_globs: &exclude_globs,
+                output,
+            }),
+        },
+        Commands::ParseHealth {
+            dir,
+            glob,
+            exclude_dirs,
+            exclude_globs,
+        } => cmd_parse_health(&CmdParseHealthOpts {
+            dir: &dir,
+            glob: glob.as_deref(),
+            extra_exclude_dirs: &exclude_dirs,
+            extra_exclude_globs: &exclude_globs,
+            output,
+        }),
         Commands::Doctor => cmd_doctor(output),
+        Commands::Config { .. } => unreachable!("handled in run"),
         Commands::Session => cmd_session(output),
         Commands::Mcp => cmd_mcp(output),
         Commands::Init { .. } | Commands::SkillInstall { .. } => unreachable!("handled above"),
@@ -588,12 +882,13 @@ fn dispatch_cochange(service: &AppService, command: Commands, output: OutputOpti
     // 既定 true。`--include-merges` で旧挙動 (false) に戻す。`--ignore-merges` は既定値と同値の
     // no-op かつ `--include-merges` と排他なので、resolved 値は `!include_merges` で表現できる。
     let resolved_ignore_merges = !include_merges;
+    let defaults = astro_sight::models::cochange::CoChangeOptions::default();
     let opts = astro_sight::models::cochange::CoChangeOptions {
         source_files,
         base,
-        min_confidence,
+        min_confidence: min_confidence.unwrap_or(defaults.min_confidence),
         min_score,
-        min_samples,
+        min_samples: min_samples.unwrap_or(defaults.min_samples),
         max_files_per_commit,
         commit_size_pivot,
         exclude_globs,
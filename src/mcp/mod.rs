@@ -9,7 +9,7 @@ use serde::Deserialize;
 
 use crate::doctor;
 use crate::output::{OutputOptions, serialize_document};
-use crate::service::{AppService, AstParams};
+use crate::service::{AppService, AstParams, SourceParams};
 
 // ---------------------------------------------------------------------------
 // Tool parameter structs
@@ -53,6 +53,31 @@ pub struct SymbolsExtractParams {
     pub path: String,
 }
 
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SourceExtractParams {
+    /// Symbol name whose definition to return
+    pub name: String,
+    /// Path to the source file (mutually exclusive with dir)
+    #[serde(default)]
+    pub path: Option<String>,
+    /// Directory to search the workspace in (mutually exclusive with path)
+    #[serde(default)]
+    pub dir: Option<String>,
+    /// Container (class / struct / trait) name to disambiguate same-named symbols
+    #[serde(default)]
+    pub container: Option<String>,
+    /// Glob pattern to filter files when searching a directory (e.g. "**/*.rs")
+    #[serde(default)]
+    pub glob: Option<String>,
+    /// Maximum bytes of source returned per definition (default: 65536)
+    #[serde(default = "default_max_source_bytes")]
+    pub max_bytes: usize,
+}
+
+fn default_max_source_bytes() -> usize {
+    crate::engine::source::DEFAULT_MAX_SOURCE_BYTES
+}
+
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct CallsExtractParams {
     /// Path to the source file
@@ -241,6 +266,26 @@ impl AstroSightServer {
         self.to_tool_result(self.service.extract_symbols(&params.0.path))
     }
 
+    #[tool(
+        name = "source_extract",
+        description = "Return a symbol definition's full source text (with leading docs and attributes/decorators) by name"
+    )]
+    async fn source_extract(
+        &self,
+        params: Parameters<SourceExtractParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let p = params.0;
+        let source_params = SourceParams {
+            path: p.path.as_deref(),
+            dir: p.dir.as_deref(),
+            glob: p.glob.as_deref(),
+            name: &p.name,
+            container: p.container.as_deref(),
+            max_bytes: p.max_bytes,
+        };
+        self.to_tool_result(self.service.extract_source(&source_params))
+    }
+
     #[tool(
         name = "calls_extract",
         description = "Extract call graph from a source file"
@@ -316,6 +361,7 @@ impl AstroSightServer {
         let options = crate::models::impact::ContextAnalysisOptions {
             exclude_dirs: p.exclude_dirs,
             exclude_globs: p.exclude_globs,
+            transitive_depth: 0,
         };
         self.to_tool_result(self.service.analyze_context(&p.diff, &p.dir, &options))
     }
@@ -0,0 +1,34 @@
+use serde::Serialize;
+
+use super::skip::SkipInfo;
+use super::truncation::TruncationInfo;
+use crate::language::LangId;
+
+/// diff の影響を受けるテストファイル 1 件。
+///
+/// `tests` は個別に特定できたテスト関数名 (Rust の `#[test]`、Python の `test_*`、
+/// Go の `Test*`)。JS/TS など関数単位で絞れない言語やファイル全体が対象のときは空。
+#[derive(Debug, Clone, Serialize)]
+pub struct AffectedTestFile {
+    pub path: String,
+    pub lang: LangId,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub tests: Vec<String>,
+    /// 変更シンボルからの参照段数。0 は diff がこのテストファイル自体を変更したもの、
+    /// 1 は変更シンボルを直接参照しているもの。
+    pub depth: usize,
+}
+
+/// affected-tests コマンドのレスポンス。
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AffectedTestsResult {
+    /// 追跡の起点にした変更シンボル (テストファイル外)。
+    pub changed_symbols: Vec<String>,
+    pub test_files: Vec<AffectedTestFile>,
+    /// git 管理外 dir で `--git` が要求され diff を取得できず skip した場合の理由。
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub skipped: Option<SkipInfo>,
+    /// 段数上限で追跡を打ち切った等。空なら出力に含まれない。
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub truncations: Vec<TruncationInfo>,
+}
@@ -0,0 +1,20 @@
+use serde::Serialize;
+
+use super::review::{ApiSymbol, ApiSymbolChange};
+
+/// `api check` のレスポンス。
+///
+/// `added` / `removed` / `modified` は review の `api_changes` と同じ型で、snapshot
+/// (`api dump` の出力) を旧側、現在の作業ツリーを新側として比較した結果。
+/// いずれかが空でなければ `ok == false` になり、結果を出力したうえで exit 1。
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ApiCheckResult {
+    pub dir: String,
+    pub snapshot: String,
+    /// 現在の作業ツリーから抽出した公開シンボル数。
+    pub symbols: usize,
+    pub added: Vec<ApiSymbol>,
+    pub removed: Vec<ApiSymbol>,
+    pub modified: Vec<ApiSymbolChange>,
+    pub ok: bool,
+}
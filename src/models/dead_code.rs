@@ -1,6 +1,6 @@
 use serde::Serialize;
 
-use super::review::DeadSymbol;
+use super::review::{CargoFeatureIssue, DeadSymbol, UnusedParameter};
 use super::skip::SkipInfo;
 
 /// dead-code コマンドのレスポンス。
@@ -22,4 +22,25 @@ pub struct DeadCodeResult {
     /// 解析対象から意図的に外したもの (未追跡の巨大ファイル等)。空なら出力に含まれない。
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub truncations: Vec<crate::models::truncation::TruncationInfo>,
+    /// `--parse-warnings` 指定時のみ: parse error を持つファイル。ここに載ったファイルの
+    /// 定義・参照は取りこぼされている可能性がある。
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub parse_warnings: Vec<crate::models::parse_health::ParseWarning>,
+    /// `--reachability` 指定時のみ: 到達不能シンボルを参照で繋がった塊ごとにまとめたもの。
+    /// 同じシンボルは `dead_symbols` にも平坦に載る (codeclimate 等の既存出力向け)。
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub dead_clusters: Vec<DeadCluster>,
+    /// `--params` 指定時のみ: 関数本体で一度も参照されない引数。
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub unused_parameters: Vec<UnusedParameter>,
+    /// `--cargo-features` 指定時のみ: 使われない Cargo feature と未宣言 feature の参照。
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub cargo_features: Vec<CargoFeatureIssue>,
+}
+
+/// 互いに参照し合うが起点からは辿れない到達不能シンボルの連結成分。
+/// まとめて削除できる単位としてレビューしやすくするために分ける。
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DeadCluster {
+    pub symbols: Vec<DeadSymbol>,
 }
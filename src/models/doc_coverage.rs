@@ -0,0 +1,59 @@
+use serde::Serialize;
+
+use super::skip::SkipInfo;
+use super::truncation::TruncationInfo;
+
+/// doc comment の無い公開シンボル。`line` は宣言行 (0-indexed)。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UndocumentedSymbol {
+    pub name: String,
+    pub kind: String,
+    pub file: String,
+    pub line: usize,
+}
+
+/// 1 ファイル分の doc coverage。`coverage` は百分率 (小数 1 桁)。
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FileDocCoverage {
+    pub file: String,
+    pub total: usize,
+    pub documented: usize,
+    pub coverage: f64,
+}
+
+/// パッケージ単位の doc coverage。
+///
+/// パッケージはファイルから祖先方向に遡って最初に見つかった manifest
+/// (`Cargo.toml` / `package.json` / `pyproject.toml` / `go.mod` 等) のディレクトリ
+/// (dir 相対)。manifest が見つからなければ `"."`。
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PackageDocCoverage {
+    pub package: String,
+    pub total: usize,
+    pub documented: usize,
+    pub coverage: f64,
+}
+
+/// doc-coverage コマンドのレスポンス。
+///
+/// `--git` / `--diff` / `--diff-file` 指定時は changed file だけを集計し、diff で追加・変更された
+/// 行に掛かる undocumented 公開シンボルを `violations` に出す (空でなければ exit 1)。
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DocCoverageResult {
+    pub dir: String,
+    pub scanned_files: usize,
+    pub total: usize,
+    pub documented: usize,
+    pub coverage: f64,
+    pub packages: Vec<PackageDocCoverage>,
+    pub files: Vec<FileDocCoverage>,
+    pub undocumented: Vec<UndocumentedSymbol>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub violations: Vec<UndocumentedSymbol>,
+    /// git 管理外 dir で `--git` が要求され diff を取得できず skip した場合の理由。
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub skipped: Option<SkipInfo>,
+    /// 解析対象から意図的に外したもの (未追跡の巨大ファイル等)。空なら出力に含まれない。
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub truncations: Vec<TruncationInfo>,
+}
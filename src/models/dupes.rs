@@ -0,0 +1,47 @@
+use serde::Serialize;
+
+/// clone group を構成する 1 箇所。行番号は 0-indexed (start/end とも含む)。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CloneInstance {
+    pub file: String,
+    pub start_line: usize,
+    pub end_line: usize,
+}
+
+/// 正規化 AST (識別子・リテラルを伏せた部分木) が一致した箇所の集合 (type-2 clone)。
+///
+/// `similarity` は正規化前のトークン列の一致率 (先頭 instance との位置ごとの一致数 /
+/// トークン数 の平均)。1.0 は識別子・リテラルまで同一の完全コピー (type-1)、
+/// 値が低いほど rename / 定数差し替えを伴うコピーであることを示す。
+#[derive(Debug, Clone, Serialize)]
+pub struct CloneGroup {
+    /// 正規化部分木の blake3 ハッシュ (先頭 16 桁)。同一内容なら実行間で安定する。
+    pub fingerprint: String,
+    /// 1 instance あたりのトークン数 (コメントを除く葉ノード数)。
+    pub tokens: usize,
+    pub similarity: f64,
+    pub instances: Vec<CloneInstance>,
+}
+
+/// dupes コマンドのレスポンス。
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DupesResult {
+    pub dir: String,
+    pub scanned_files: usize,
+    pub min_tokens: usize,
+    pub groups: Vec<CloneGroup>,
+}
+
+/// review で報告する「今回の diff で追加されたコードが既存コードの複製になっている」箇所。
+///
+/// `file` / `start_line` / `end_line` が追加側、`duplicates` が diff 外に既に存在する
+/// 複製元。リファクタ途中のコピーなど意図的なケースもあるため informational 扱い。
+#[derive(Debug, Clone, Serialize)]
+pub struct DuplicatedCode {
+    pub file: String,
+    pub start_line: usize,
+    pub end_line: usize,
+    pub tokens: usize,
+    pub similarity: f64,
+    pub duplicates: Vec<CloneInstance>,
+}
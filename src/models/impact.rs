@@ -29,7 +29,7 @@ pub struct SignatureChange {
 }
 
 /// 変更の影響を受ける呼び出し元。
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ImpactedCaller {
     pub path: String,
     pub name: String,
@@ -40,6 +40,9 @@ pub struct ImpactedCaller {
     /// receiver-aware 確信度。`exact` / `inferred` / `bare` のいずれか。互換のため省略可。
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub confidence: Option<String>,
+    /// `CODEOWNERS` 上の `path` の owner。CODEOWNERS が無い / マッチしない場合は省略。
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub owners: Vec<String>,
 }
 
 /// 変更内容と hunk 情報を含む解析済み diff ファイル。
@@ -56,7 +59,7 @@ pub struct DiffFile {
 }
 
 /// 単一の変更ファイルに対する影響分析。
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct FileImpact {
     pub path: String,
     pub hunks: Vec<HunkInfo>,
@@ -75,6 +78,27 @@ pub struct FileImpact {
     /// 空の場合は出力に含めない (互換維持)。
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub informational_callers: Vec<ImpactedCaller>,
+    /// `--transitive-depth` 指定時のみ: 公開ラッパー経由で間接的に影響を受ける呼び出し元。
+    /// blocking 判定 (hook の `impacts`) には使わない別バケット。空の場合は出力に含めない。
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub transitive_callers: Vec<TransitiveCaller>,
+}
+
+/// 公開ラッパー経由で辿った間接的な呼び出し元 (`--transitive-depth`)。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransitiveCaller {
+    pub path: String,
+    /// 参照を含む関数名 (関数外のトップレベル参照は空)。
+    pub name: String,
+    pub line: usize,
+    /// 変更シンボルからこの caller が参照するシンボルまでの経路。`["helper", "run"]` は
+    /// 変更された `helper` を公開関数 `run` が呼び、この caller が `run` を呼ぶことを表す。
+    pub chain: Vec<String>,
+    /// 直接の caller (`impacted_callers`) を 0 とした段数 (1 以上)。
+    pub depth: usize,
+    /// `CODEOWNERS` 上の `path` の owner。CODEOWNERS が無い / マッチしない場合は省略。
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub owners: Vec<String>,
 }
 
 /// context（影響分析）のレスポンスエンベロープ。
@@ -110,4 +134,8 @@ pub struct ContextAnalysisOptions {
     /// workspace-relative の glob パターン。`refs::collect_files_with_excludes`
     /// で negative override として扱われる (先頭の `!` は不要)。
     pub exclude_globs: Vec<String>,
+
+    /// 0 より大きければ、公開シンボル内の caller から `transitive_callers` を
+    /// この段数まで辿る (0 は無効)。
+    pub transitive_depth: usize,
 }
@@ -33,3 +33,34 @@ pub struct ImportsResult {
     pub language: String,
     pub imports: Vec<ImportEdge>,
 }
+
+/// ファイル内で一度も参照されていない import 名。
+///
+/// 範囲 (行・列は 0 始まり) は修正 diff を作れるよう、import 名の指定子 (`span`) と
+/// import 文全体 (`statement_span`) の両方を持つ。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UnusedImport {
+    pub file: String,
+    /// 束縛される名前 (alias があれば alias)。
+    pub name: String,
+    pub span: crate::models::location::Range,
+    pub statement_span: crate::models::location::Range,
+    /// import 文の元テキスト。
+    pub statement: String,
+    /// 文中の import 名がすべて未使用で、文ごと削除できる場合 true。
+    pub whole_statement: bool,
+}
+
+/// unused-imports コマンドのレスポンス。
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct UnusedImportsResult {
+    pub dir: String,
+    pub scanned_files: usize,
+    pub unused_imports: Vec<UnusedImport>,
+    /// git 管理外 dir で `--git` が要求され diff を取得できず skip した場合の理由。
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub skipped: Option<super::skip::SkipInfo>,
+    /// 解析対象から意図的に外したもの (未追跡の巨大ファイル等)。空なら出力に含まれない。
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub truncations: Vec<super::truncation::TruncationInfo>,
+}
@@ -1,17 +1,25 @@
+pub mod affected_tests;
+pub mod api_snapshot;
 pub mod ast_node;
 pub mod call;
 pub mod cochange;
 pub mod dead_code;
 pub mod diagnostic;
+pub mod doc_coverage;
+pub mod dupes;
 pub mod impact;
 pub mod import;
 pub mod lint;
 pub mod location;
+pub mod parse_health;
 pub mod reference;
 pub mod request;
 pub mod response;
 pub mod review;
+pub mod semver;
 pub mod sequence;
 pub mod skip;
+pub mod source;
 pub mod symbol;
+pub mod symdiff;
 pub mod truncation;
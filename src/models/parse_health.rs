@@ -0,0 +1,94 @@
+use serde::{Deserialize, Serialize};
+
+use super::review::is_false;
+use crate::language::LangId;
+
+/// parse 失敗箇所の種別。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ParseErrorKind {
+    /// grammar が解釈できなかった区間 (tree-sitter の `ERROR` ノード)。
+    Error,
+    /// error recovery で補われた欠落トークン (tree-sitter の `MISSING` ノード)。
+    Missing,
+}
+
+/// parse 失敗 1 箇所。行・列は 0-indexed。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ParseErrorLocation {
+    pub kind: ParseErrorKind,
+    pub line: usize,
+    pub column: usize,
+    /// `missing` のとき補われたノード種別 (例: `";"`, `"identifier"`)。
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<String>,
+}
+
+/// 1 ファイルの parse 状態。
+#[derive(Debug, Clone, Serialize)]
+pub struct FileParseHealth {
+    pub path: String,
+    pub lang: LangId,
+    pub errors: usize,
+    pub missing: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub locations: Vec<ParseErrorLocation>,
+    /// parse 自体が完了しなかった (タイムアウト等)。このファイルの解析結果は全く得られない。
+    #[serde(skip_serializing_if = "is_false", default)]
+    pub failed: bool,
+}
+
+impl FileParseHealth {
+    /// ERROR / MISSING ノードを含むか、parse 自体に失敗したか。
+    pub fn is_unhealthy(&self) -> bool {
+        self.failed || self.errors > 0 || self.missing > 0
+    }
+}
+
+/// 言語ごとの集計。`files` には問題のあるファイルだけが入る。
+#[derive(Debug, Clone, Serialize)]
+pub struct LanguageParseHealth {
+    pub lang: LangId,
+    pub scanned_files: usize,
+    pub files_with_errors: usize,
+    pub errors: usize,
+    pub missing: usize,
+    pub files: Vec<FileParseHealth>,
+}
+
+/// parse-health コマンドのレスポンス。
+///
+/// `languages` は問題のあるファイルを持つ言語だけを、件数の多い順に並べる。
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ParseHealthResult {
+    pub dir: String,
+    pub scanned_files: usize,
+    pub files_with_errors: usize,
+    pub languages: Vec<LanguageParseHealth>,
+}
+
+/// 解析コマンド (`refs` / `dead-code` 等) の `--parse-warnings` で付ける警告。
+///
+/// 対象ファイルに parse error があると、そのファイル内の定義・参照は取りこぼされ得る。
+/// 結果が静かに誤るのを避けるため、該当ファイルと最初の失敗行だけを軽量に報告する。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParseWarning {
+    pub path: String,
+    pub lang: LangId,
+    /// ERROR + MISSING ノードの合計。
+    pub count: usize,
+    /// 最初の失敗箇所の行 (0-indexed)。parse 自体に失敗した場合は `None`。
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+}
+
+impl From<&FileParseHealth> for ParseWarning {
+    fn from(health: &FileParseHealth) -> Self {
+        Self {
+            path: health.path.clone(),
+            lang: health.lang,
+            count: health.errors + health.missing,
+            line: health.locations.first().map(|loc| loc.line),
+        }
+    }
+}
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use super::parse_health::ParseWarning;
+
 /// 参照の種類（定義または利用）。
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum RefKind {
@@ -51,4 +53,7 @@ pub struct RefsResult {
     pub symbol: String,
     #[serde(rename = "refs")]
     pub references: Vec<SymbolReference>,
+    /// `--parse-warnings` 指定時のみ: 検索対象のうち parse error を持つファイル。
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub parse_warnings: Vec<ParseWarning>,
 }
@@ -26,6 +26,12 @@ pub struct AstgenRequest {
     /// 検索するシンボル名（refs コマンドの単一検索用）
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
+    /// 同名シンボルの絞り込みに使う container 名（source コマンド用）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub container: Option<String>,
+    /// 定義ソースの返却上限バイト数（source コマンド用）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_bytes: Option<usize>,
     /// refs バッチ検索用のシンボル名
     #[serde(skip_serializing_if = "Option::is_none")]
     pub names: Option<Vec<String>>,
@@ -77,4 +83,5 @@ pub enum Command {
     Lint,
     Sequence,
     Cochange,
+    Source,
 }
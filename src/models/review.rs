@@ -1,6 +1,7 @@
 use serde::Serialize;
 
 use super::cochange::CoChangeDiagnostics;
+use super::dupes::DuplicatedCode;
 use super::impact::ContextResult;
 use super::skip::SkipInfo;
 
@@ -23,6 +24,25 @@ pub struct ReviewResult {
     pub dead_symbols: Vec<DeadSymbol>,
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub test_only_symbols: Vec<DeadSymbol>,
+    /// diff で追加された引数のうち、関数本体で一度も参照されないもの。
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub unused_parameters: Vec<UnusedParameter>,
+    /// diff が `Cargo.toml` か `cfg` を含む行に触れた crate の、`[features]` と
+    /// `cfg(feature = "...")` の食い違い。
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub cargo_features: Vec<CargoFeatureIssue>,
+    /// diff で追加されたコードのうち、diff 外の既存コードと正規化 AST が一致するもの
+    /// (コピー&ペースト)。意図的な複製もあり得るため informational 扱い。
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub duplicated_code: Vec<DuplicatedCode>,
+    /// 変更ファイル単位と diff 全体のリスクスコア。`--hook` では `--risk-threshold`
+    /// 指定時だけ算出する (参照検索のコストを hook の既定経路に載せないため)。
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub risk: Option<ReviewRisk>,
+    /// diff 外に残る呼び出し側 (`impacted_callers`) と `missing_cochanges` のファイルを
+    /// `CODEOWNERS` の owner ごとにまとめたもの。レビューで声をかけるべきチームの一覧。
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub owners_to_notify: Vec<OwnerToNotify>,
     /// git 管理外 dir で `--git` が要求され diff を取得できず skip した場合の理由。
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub skipped: Option<SkipInfo>,
@@ -32,12 +52,96 @@ pub struct ReviewResult {
     pub truncations: Vec<crate::models::truncation::TruncationInfo>,
 }
 
+/// `owners_to_notify` の 1 件。`files` は owner が担当する、影響を受けるファイル (ソート済み)。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OwnerToNotify {
+    pub owner: String,
+    pub files: Vec<String>,
+}
+
+/// review のリスクスコア。
+///
+/// スコアは各要因の寄与 (`contribution`) の単純和で、要因ごとに上限を持つ。`factors` は
+/// 全ファイルの寄与を要因ごとに合算したもので、`files` は寄与のあるファイルだけをスコアの
+/// 高い順に並べる。係数は優先順位付けの目安であり、絶対的な尺度ではない。
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ReviewRisk {
+    pub score: f64,
+    pub factors: Vec<RiskFactor>,
+    pub files: Vec<FileRisk>,
+}
+
+/// 変更ファイル 1 件のリスクスコア。
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FileRisk {
+    pub path: String,
+    pub score: f64,
+    pub factors: Vec<RiskFactor>,
+}
+
+/// リスク要因 1 件。`value` は要因の生の計測値 (件数、複雑度の増分、confidence の和) で、
+/// `contribution` はそれに係数と上限を適用したスコアへの寄与。
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RiskFactor {
+    pub factor: String,
+    pub value: f64,
+    pub contribution: f64,
+}
+
+/// `review --range A..B --per-commit` の 1 コミット分のレコード (NDJSON の 1 行)。
+///
+/// 中身は通常の `ReviewResult` そのままで、どのコミットの結果かを `sha` / `subject` で添える。
+#[derive(Debug, Clone, Serialize)]
+pub struct CommitReview {
+    pub sha: String,
+    pub subject: String,
+    #[serde(flatten)]
+    pub review: ReviewResult,
+}
+
+/// `review --range A..B --per-commit` の最終行。`{"summary": {...}}` の形で出し、
+/// コミット単位のレコードと行の形だけで区別できるようにする。
+#[derive(Debug, Clone, Serialize)]
+pub struct RangeReviewSummaryRecord {
+    pub summary: RangeReviewSummary,
+}
+
+/// 範囲全体 (A..B を 1 つの diff として B 上で review した結果) に残っている指摘の一覧。
+/// 途中のコミットで入って後のコミットで直った指摘はここに現れない。
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RangeReviewSummary {
+    pub range: String,
+    pub commits: usize,
+    pub unresolved: Vec<UnresolvedIssue>,
+    /// git 管理外 dir で範囲を解決できず skip した場合の理由。
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub skipped: Option<SkipInfo>,
+}
+
+/// B 時点で未解決の指摘 1 件。
+#[derive(Debug, Clone, Serialize)]
+pub struct UnresolvedIssue {
+    /// 指摘の種類 (`--format codeclimate` の `check_name` と同じ値)。
+    pub check: String,
+    pub description: String,
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub line: Option<usize>,
+    /// 同じ指摘が最初に現れたコミット。個々のコミットには現れず、複数コミットの
+    /// 組み合わせで初めて生じた指摘は省略される。
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub introduced_in: Option<String>,
+}
+
 /// cochange で検出された「一緒に変更されるはずだが diff に含まれないファイル」。
 #[derive(Debug, Clone, Serialize)]
 pub struct MissingCochange {
     pub file: String,
     pub expected_with: String,
     pub confidence: f64,
+    /// `CODEOWNERS` 上の `file` の owner。CODEOWNERS が無い / マッチしない場合は省略。
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub owners: Vec<String>,
 }
 
 /// 公開シンボルの変更サマリ。
@@ -171,6 +275,78 @@ pub struct DeadSymbol {
     /// 宣言行を解決できなかった場合のみ省略される。
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub line: Option<usize>,
+    /// `dead-code --scope private|all` で検出した非公開シンボルは `"private"`。
+    /// 公開シンボル (従来の検出対象) では省略される。
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub visibility: Option<DeadSymbolVisibility>,
+    /// `dead-code --blame` で付ける、宣言範囲を最後に変更したコミット。
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub blame: Option<DeadSymbolBlame>,
+}
+
+/// dead シンボルの可視性。公開シンボルは `DeadSymbol::visibility` 自体を省略する。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeadSymbolVisibility {
+    /// 宣言の可視範囲 (ファイル / モジュール / パッケージ) の外から参照できない。
+    Private,
+}
+
+/// dead シンボルの宣言と参照の履歴 (`git blame` / `git log -S`)。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DeadSymbolBlame {
+    /// 宣言範囲の行のうち最も新しいコミット。
+    #[serde(flatten)]
+    pub commit: BlameCommit,
+    /// 最後の参照を消したと推定されるコミット (名前の出現数が減った最新のコミット)。
+    /// 一度も参照されたことが無い・履歴から見つからない場合は省略される。
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference_removed_by: Option<BlameCommit>,
+}
+
+/// コミットの識別子と author。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BlameCommit {
+    pub sha: String,
+    pub author: String,
+    /// author date (RFC 3339, UTC)。
+    pub date: String,
+    /// author date の unix 秒。`--sort age` の並べ替えキー。
+    pub timestamp: i64,
+}
+
+/// 本体で一度も参照されない関数 / メソッドの引数。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UnusedParameter {
+    pub name: String,
+    /// 引数を持つ関数の qualname (`Container.method` / `function`)。
+    pub function: String,
+    pub file: String,
+    /// 引数名の位置 (0-indexed)。
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Cargo feature の宣言と `cfg(feature = "...")` 参照の食い違い。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CargoFeatureIssue {
+    pub kind: CargoFeatureIssueKind,
+    pub feature: String,
+    /// `unused` は宣言のある `Cargo.toml`、`undeclared` は参照している `.rs`。
+    pub file: String,
+    /// 宣言 / 参照の位置 (0-indexed)。
+    pub line: usize,
+    /// feature を持つ crate の `Cargo.toml`。
+    pub manifest: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CargoFeatureIssueKind {
+    /// `[features]` にあるが、どの `cfg` からも参照されず、依存や他の feature も有効にしない。
+    Unused,
+    /// `cfg(feature = "...")` が宣言されていない feature を参照している (typo)。
+    Undeclared,
 }
 
 /// 別ファイルへ移動された公開シンボル。
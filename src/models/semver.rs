@@ -0,0 +1,80 @@
+use serde::Serialize;
+
+use super::skip::SkipInfo;
+use super::truncation::TruncationInfo;
+
+/// SemVer の bump 段階。`Ord` は `None < Patch < Minor < Major` の順で、
+/// 「必要な bump」と「実際の bump」の大小比較にそのまま使う。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BumpLevel {
+    #[default]
+    None,
+    Patch,
+    Minor,
+    Major,
+}
+
+impl BumpLevel {
+    /// JSON 出力と同じ小文字表記。
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Patch => "patch",
+            Self::Minor => "minor",
+            Self::Major => "major",
+        }
+    }
+}
+
+/// 必要な bump を決めた根拠となる公開シンボル 1 件。
+///
+/// `change` は根拠になった `ApiChanges` のバケット名 (`"removed"` / `"modified"` /
+/// `"added"` 等) で、review の `api_changes` 出力と突き合わせられるようにそのまま使う。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SemverReason {
+    pub level: BumpLevel,
+    pub change: String,
+    pub name: String,
+    pub kind: String,
+    pub file: String,
+}
+
+/// manifest (`Cargo.toml` / `package.json` / `pyproject.toml`) に書かれた版の比較結果。
+///
+/// `actual` は `old_version` → `new_version` の bump を、0.x 系の慣習 (0.y.z の y 上げは
+/// 破壊的変更、z 上げは互換な機能追加) を織り込んだ段階に換算したもの。どちらかの版が
+/// 読めない (新規 manifest、`version.workspace = true` 等) 場合は `None`。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ManifestVersion {
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actual: Option<BumpLevel>,
+    /// `actual` が `required` 以上か。bump が必要なのに版が読めず `actual` が `None` の
+    /// ときは false。今回の diff で追加・削除された manifest は比較元が無いため true。
+    pub sufficient: bool,
+}
+
+/// semver コマンドのレスポンス。
+///
+/// `required` は `--base` からの API 変更から導いた最低限必要な bump。`manifests` のうち
+/// 1 つでも `sufficient == false` があれば `ok == false` になり、結果を出力したうえで exit 1。
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SemverResult {
+    pub dir: String,
+    pub base: String,
+    pub required: BumpLevel,
+    pub reasons: Vec<SemverReason>,
+    pub manifests: Vec<ManifestVersion>,
+    pub ok: bool,
+    /// git 管理外 dir で diff を取得できず skip した場合の理由。
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub skipped: Option<SkipInfo>,
+    /// 解析対象から意図的に外したもの (未追跡の巨大ファイル等)。空なら出力に含まれない。
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub truncations: Vec<TruncationInfo>,
+}
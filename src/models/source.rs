@@ -0,0 +1,37 @@
+use serde::Serialize;
+
+use super::location::Range;
+use super::review::is_false;
+use super::symbol::SymbolKind;
+use super::truncation::TruncationInfo;
+
+/// 名前で引いた 1 定義のソーステキスト。
+///
+/// `range` / `source` は直前の doc comment と attribute / decorator を含む定義全体。
+/// `truncated` のときは `source` が上限バイト数で切り詰められている。
+#[derive(Debug, Clone, Serialize)]
+pub struct SymbolSource {
+    pub path: String,
+    pub kind: SymbolKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub container: Option<String>,
+    pub range: Range,
+    pub source: String,
+    #[serde(skip_serializing_if = "is_false", default)]
+    pub truncated: bool,
+}
+
+/// source コマンドのレスポンス。
+///
+/// 同名の定義が複数ある場合 (オーバーロード、別 container、`--dir` 検索での複数ファイル) は
+/// すべて `matches` に入る。`container` 指定で絞り込める。
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SourceResult {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub container: Option<String>,
+    pub matches: Vec<SymbolSource>,
+    /// 上限を超えて切り詰めた定義。空なら出力に含まれない。
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub truncations: Vec<TruncationInfo>,
+}
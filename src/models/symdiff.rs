@@ -0,0 +1,60 @@
+use serde::Serialize;
+
+use super::review::MovedSymbol;
+use super::skip::SkipInfo;
+
+/// symdiff で追加 / 削除 / 本体のみ変更と判定されたシンボル。
+///
+/// `line` は宣言行 (0-indexed)。removed は `--from` 側、それ以外は `--to` 側の行。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SymDiffSymbol {
+    pub name: String,
+    pub kind: String,
+    pub file: String,
+    pub line: usize,
+}
+
+/// 名前だけが変わったシンボル。名前を伏せた定義本体が一致した add/rm ペア。
+/// ファイルをまたぐ場合は `from` と `to` が異なる。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RenamedSymbol {
+    pub old_name: String,
+    pub new_name: String,
+    pub kind: String,
+    pub from: String,
+    pub to: String,
+}
+
+/// シグネチャが変わったシンボル。シグネチャは review の `api_changes.modified` と同じ抽出規則。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SymDiffSignatureChange {
+    pub name: String,
+    pub kind: String,
+    pub file: String,
+    pub old_signature: String,
+    pub new_signature: String,
+}
+
+/// symdiff コマンドのレスポンス。
+///
+/// review の `api_changes` が「今回の diff の公開面」だけを見るのに対し、private を含む
+/// 全シンボルを 2 revision 間で比較する。分類は排他的で、1 シンボルは高々 1 バケットに入る
+/// (判定順: 同名 → moved → renamed → added / removed)。
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SymDiffResult {
+    pub dir: String,
+    pub from: String,
+    pub to: String,
+    pub files_changed: usize,
+    pub added: Vec<SymDiffSymbol>,
+    pub removed: Vec<SymDiffSymbol>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub renamed: Vec<RenamedSymbol>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub moved: Vec<MovedSymbol>,
+    pub signature_changed: Vec<SymDiffSignatureChange>,
+    pub body_changed: Vec<SymDiffSymbol>,
+    /// git 管理外 dir のため解析を行わなかった場合の理由。
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub skipped: Option<SkipInfo>,
+}
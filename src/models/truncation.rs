@@ -29,6 +29,10 @@ pub struct TruncationInfo {
 pub enum TruncationReason {
     /// 未追跡ファイルが `--git` 合成 diff の取り込み上限を超えたため対象外にした。
     UntrackedFileTooLarge,
+    /// `source` の定義テキストが返却サイズ上限を超えたため末尾を切り詰めた。
+    SourceTooLarge,
+    /// 参照の推移的な追跡を段数上限で打ち切った。
+    DepthLimitReached,
 }
 
 impl TruncationInfo {
@@ -50,4 +54,24 @@ impl TruncationInfo {
             ),
         }
     }
+
+    /// `source` の定義テキストを `limit` バイトで切り詰めた打ち切り (`actual` は元のサイズ)。
+    pub fn source_too_large(path: &str, actual: usize, limit: usize) -> Self {
+        Self {
+            path: Some(path.to_string()),
+            reason: TruncationReason::SourceTooLarge,
+            message: format!("definition source truncated: {actual} bytes exceeds limit {limit}"),
+        }
+    }
+
+    /// 参照の推移的な追跡を `depth` 段で打ち切り、未追跡のシンボルが `remaining` 件残った。
+    pub fn depth_limit_reached(depth: usize, remaining: usize) -> Self {
+        Self {
+            path: None,
+            reason: TruncationReason::DepthLimitReached,
+            message: format!(
+                "transitive reference walk stopped at depth {depth}: {remaining} symbols not followed"
+            ),
+        }
+    }
 }
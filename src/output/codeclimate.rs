@@ -0,0 +1,179 @@
+//! Code Climate / GitLab Code Quality 形式の issue 配列。
+//!
+//! 仕様: <https://github.com/codeclimate/platform/blob/master/spec/analyzers/SPEC.md#data-types>
+//! (GitLab は `description` / `check_name` / `fingerprint` / `severity` /
+//! `location.path` / `location.lines.begin` を必須として読む)。
+//!
+//! `fingerprint` は MR 間で「同じ指摘」を突き合わせるキーなので、**行番号を含めない**。
+//! 無関係な行の追加で行がずれても同じ指摘は同じ fingerprint のままになり、
+//! GitLab の widget 上で「新規」「解消」に化けない。
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use serde::Serialize;
+
+use super::{JsonStyle, OutputOptions};
+
+/// Code Climate の issue category。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Category {
+    #[serde(rename = "Bug Risk")]
+    BugRisk,
+    Clarity,
+    Compatibility,
+    Duplication,
+    Style,
+}
+
+/// Code Climate の severity。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Minor,
+    Major,
+    Critical,
+}
+
+/// 1 件の指摘。`line` は他の出力と同じく 0 始まりで持ち、描画時に 1 始まりへ直す。
+#[derive(Debug, Clone)]
+pub struct Issue {
+    pub check_name: String,
+    pub description: String,
+    pub category: Category,
+    pub severity: Severity,
+    pub path: String,
+    pub line: Option<usize>,
+    /// fingerprint の元になる「指摘の同一性」。シンボル名など、行がずれても変わらない値を入れる。
+    pub identity: String,
+}
+
+#[derive(Serialize)]
+struct IssueDocument<'a> {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    check_name: &'a str,
+    description: &'a str,
+    categories: [Category; 1],
+    severity: Severity,
+    location: Location<'a>,
+    fingerprint: String,
+}
+
+#[derive(Serialize)]
+struct Location<'a> {
+    path: &'a str,
+    lines: Lines,
+}
+
+#[derive(Serialize)]
+struct Lines {
+    begin: usize,
+}
+
+/// issue 配列を 1 つの JSON ドキュメントとして描画する (末尾改行付き)。
+///
+/// 同じ `(check_name, path, identity)` が複数ある場合 (同一ファイル内の同じ lint 一致等) は
+/// 出現順の序数を混ぜて fingerprint を分ける。GitLab は fingerprint の重複を 1 件に畳むため。
+pub fn render(issues: &[Issue], output: OutputOptions) -> Result<String> {
+    let mut seen: HashMap<(&str, &str, &str), usize> = HashMap::new();
+    let documents: Vec<IssueDocument<'_>> = issues
+        .iter()
+        .map(|issue| {
+            let ordinal = seen
+                .entry((
+                    issue.check_name.as_str(),
+                    issue.path.as_str(),
+                    issue.identity.as_str(),
+                ))
+                .or_default();
+            let fingerprint = fingerprint(issue, *ordinal);
+            *ordinal += 1;
+            IssueDocument {
+                kind: "issue",
+                check_name: &issue.check_name,
+                description: &issue.description,
+                categories: [issue.category],
+                severity: issue.severity,
+                location: Location {
+                    path: &issue.path,
+                    lines: Lines {
+                        begin: issue.line.map_or(1, |line| line + 1),
+                    },
+                },
+                fingerprint,
+            }
+        })
+        .collect();
+    let mut text = match output.json_style {
+        JsonStyle::Compact => serde_json::to_string(&documents)?,
+        JsonStyle::Pretty => serde_json::to_string_pretty(&documents)?,
+    };
+    text.push('\n');
+    Ok(text)
+}
+
+fn fingerprint(issue: &Issue, ordinal: usize) -> String {
+    let mut hasher = blake3::Hasher::new();
+    for part in [&issue.check_name, &issue.path, &issue.identity] {
+        hasher.update(part.as_bytes());
+        hasher.update(b"\0");
+    }
+    hasher.update(&ordinal.to_le_bytes());
+    // GitLab の例 (MD5) と同じ 32 桁に揃える。衝突耐性は 128 bit で十分。
+    hasher.finalize().to_hex()[..32].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::OutputFormat;
+
+    fn issue(line: usize, identity: &str) -> Issue {
+        Issue {
+            check_name: "astro-sight/dead-symbol".to_string(),
+            description: "unused".to_string(),
+            category: Category::Clarity,
+            severity: Severity::Minor,
+            path: "src/lib.rs".to_string(),
+            line: Some(line),
+            identity: identity.to_string(),
+        }
+    }
+
+    fn render_values(issues: &[Issue]) -> Vec<serde_json::Value> {
+        let output = OutputOptions::new(OutputFormat::Codeclimate, JsonStyle::Compact);
+        serde_json::from_str(&render(issues, output).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn renders_required_gitlab_fields_with_one_indexed_lines() {
+        let values = render_values(&[issue(4, "helper")]);
+        let value = &values[0];
+        assert_eq!(value["type"], "issue");
+        assert_eq!(value["check_name"], "astro-sight/dead-symbol");
+        assert_eq!(value["categories"][0], "Clarity");
+        assert_eq!(value["severity"], "minor");
+        assert_eq!(value["location"]["path"], "src/lib.rs");
+        assert_eq!(value["location"]["lines"]["begin"], 5);
+        assert_eq!(value["fingerprint"].as_str().unwrap().len(), 32);
+    }
+
+    #[test]
+    fn fingerprint_ignores_line_shifts_but_separates_duplicates() {
+        let moved = render_values(&[issue(4, "helper")]);
+        let shifted = render_values(&[issue(40, "helper")]);
+        assert_eq!(moved[0]["fingerprint"], shifted[0]["fingerprint"]);
+
+        let twice = render_values(&[issue(4, "helper"), issue(9, "helper")]);
+        assert_eq!(twice[0]["fingerprint"], moved[0]["fingerprint"]);
+        assert_ne!(twice[0]["fingerprint"], twice[1]["fingerprint"]);
+    }
+
+    #[test]
+    fn bug_risk_category_uses_spec_spelling() {
+        let value = serde_json::to_value(Category::BugRisk).unwrap();
+        assert_eq!(value, "Bug Risk");
+    }
+}
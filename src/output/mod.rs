@@ -14,7 +14,12 @@
 //! 意味するに過ぎないため、プロトコル面では黙って JSON に倒す。
 //! そうしないと `format = "toon"` を設定しただけで `session` や Stop hook が
 //! 全滅する (設定として使い物にならない)。
+//!
+//! `markdown` / `codeclimate` は特定コマンドの結果だけを描ける **レポート形式**。
+//! 汎用 serializer は扱わず、`OutputOptions::ensure_report_format` で対応コマンド以外を
+//! 入口で弾く (config 由来なら JSON に倒す)。
 
+pub mod codeclimate;
 mod nullable;
 pub mod toon;
 
@@ -36,6 +41,10 @@ pub enum OutputFormat {
     Toon,
     /// Whichever of json/toon is estimated to use fewer tokens for this output
     Auto,
+    /// Human-readable Markdown report for PR comments (review only)
+    Markdown,
+    /// Code Climate / GitLab Code Quality issue list (review, dead-code, lint)
+    Codeclimate,
 }
 
 impl OutputFormat {
@@ -44,8 +53,15 @@ impl OutputFormat {
             OutputFormat::Json => "json",
             OutputFormat::Toon => "toon",
             OutputFormat::Auto => "auto",
+            OutputFormat::Markdown => "markdown",
+            OutputFormat::Codeclimate => "codeclimate",
         }
     }
+
+    /// 汎用の serializer では描けず、対応コマンドが専用に描画するレポート形式か。
+    pub fn is_report(self) -> bool {
+        matches!(self, OutputFormat::Markdown | OutputFormat::Codeclimate)
+    }
 }
 
 /// 1 行あたりの追加コスト (文字数換算)。`SIZE_METRIC` の行罰則項。
@@ -189,17 +205,40 @@ impl OutputOptions {
     /// いずれも `OutputOptions` を経由せず JSON を直接書いており、
     /// `protocol_surfaces_stay_json_under_auto` がその挙動を固定している。
     pub fn ensure_json_protocol(&self, surface: &str) -> Result<()> {
-        if self.format == OutputFormat::Toon && self.explicit_format {
+        if (self.format == OutputFormat::Toon || self.format.is_report()) && self.explicit_format {
             return Err(AstroError::new(
                 ErrorCode::InvalidRequest,
                 format!(
-                    "--format toon is not supported for {surface}; it emits a line-oriented JSON protocol"
+                    "--format {} is not supported for {surface}; it emits a line-oriented JSON protocol",
+                    self.format.as_str()
                 ),
             )
             .into());
         }
         Ok(())
     }
+
+    /// レポート形式 (`markdown` 等) を `supported` に含まないコマンドの前で呼ぶ。
+    ///
+    /// CLI で明示された非対応のレポート形式はエラー、config 由来なら JSON に倒した
+    /// options を返す (`ensure_json_protocol` と同じ「どこから来た指定か」の扱い)。
+    /// 通過後の汎用経路 (`serialize_document` / バッチ描画) はレポート形式を見ない。
+    pub fn ensure_report_format(self, command: &str, supported: &[OutputFormat]) -> Result<Self> {
+        if !self.format.is_report() || supported.contains(&self.format) {
+            return Ok(self);
+        }
+        if self.explicit_format {
+            return Err(AstroError::new(
+                ErrorCode::InvalidRequest,
+                format!(
+                    "--format {} is not supported for {command}",
+                    self.format.as_str()
+                ),
+            )
+            .into());
+        }
+        Ok(self.with_format(OutputFormat::Json))
+    }
 }
 
 /// 1 ドキュメントを出力形式に従って直列化する。TOON は末尾改行を含まない。
@@ -236,6 +275,16 @@ fn serialize_document_with_format<T: serde::Serialize + ?Sized>(
             JsonStyle::Pretty => Ok((serde_json::to_string_pretty(value)?, OutputFormat::Json)),
         },
         OutputFormat::Toon => Ok((to_toon(value, toon::encode_value)?, OutputFormat::Toon)),
+        // レポート形式は対応コマンドが専用に描画する。ここへ来るのは
+        // `ensure_report_format` を通していない経路のバグ。
+        OutputFormat::Markdown | OutputFormat::Codeclimate => Err(AstroError::new(
+            ErrorCode::InvalidRequest,
+            format!(
+                "--format {} is not supported for this output",
+                output.format.as_str()
+            ),
+        )
+        .into()),
         OutputFormat::Auto => {
             // 比較は常に compact JSON と TOON で行う (どちらも「短く出す」形)。
             // JSON が勝った場合だけ `--pretty` を適用する = auto は形式を選び、
@@ -404,6 +453,36 @@ mod tests {
         assert!(explicit.ensure_json_protocol("session").is_err());
     }
 
+    #[test]
+    fn report_format_is_rejected_only_when_explicit_and_unsupported() {
+        let explicit =
+            OutputOptions::resolve(Some(OutputFormat::Markdown), OutputFormat::Json, false);
+        assert!(explicit.ensure_report_format("refs", &[]).is_err());
+        assert!(explicit.ensure_json_protocol("review --hook").is_err());
+        assert_eq!(
+            explicit
+                .ensure_report_format("review", &[OutputFormat::Markdown])
+                .unwrap()
+                .format(),
+            OutputFormat::Markdown
+        );
+
+        let from_config = OutputOptions::resolve(None, OutputFormat::Markdown, false);
+        assert_eq!(
+            from_config
+                .ensure_report_format("refs", &[])
+                .unwrap()
+                .format(),
+            OutputFormat::Json
+        );
+
+        let json = OutputOptions::resolve(Some(OutputFormat::Json), OutputFormat::Json, false);
+        assert_eq!(
+            json.ensure_report_format("refs", &[]).unwrap().format(),
+            OutputFormat::Json
+        );
+    }
+
     #[test]
     fn config_sourced_toon_silently_falls_back_on_protocol_surfaces() {
         // `format = "toon"` を設定しただけで session / hook が全滅しないこと。
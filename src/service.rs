@@ -1,10 +1,12 @@
 use anyhow::{Result, bail};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use tracing::{debug, warn};
 
 use crate::cache::store::CacheStore;
 use crate::engine::{
-    calls, extractor, impact, imports, lexer, lint, parser, refs, snippet, symbols,
+    calls, extractor, impact, imports, lexer, lint, parse_health, parser, refs, snippet, source,
+    symbols,
 };
 use crate::error::{AstroError, ErrorCode};
 use crate::language::DetectedLang;
@@ -13,9 +15,12 @@ use crate::models::cochange::{CoChangeOptions, CoChangeResult};
 use crate::models::impact::ContextResult;
 use crate::models::import::ImportsResult;
 use crate::models::location::LocationKey;
+use crate::models::parse_health::ParseWarning;
 use crate::models::reference::RefsResult;
 use crate::models::response::AstgenResponse;
 use crate::models::sequence::SequenceDiagramResult;
+use crate::models::source::{SourceResult, SymbolSource};
+use crate::models::truncation::TruncationInfo;
 
 // ---------------------------------------------------------------------------
 // AppService: CLI / Session / MCP で共有する中核ロジック
@@ -37,6 +42,17 @@ pub struct AstParams<'a> {
     pub context_lines: usize,
 }
 
+/// 定義ソース抽出パラメータ。`path` (単一ファイル) と `dir` (ワークスペース検索) はどちらか一方。
+pub struct SourceParams<'a> {
+    pub path: Option<&'a str>,
+    pub dir: Option<&'a str>,
+    /// `dir` 検索時のファイル絞り込み。
+    pub glob: Option<&'a str>,
+    pub name: &'a str,
+    pub container: Option<&'a str>,
+    pub max_bytes: usize,
+}
+
 impl Default for AppService {
     fn default() -> Self {
         Self::new()
@@ -469,6 +485,7 @@ impl AppService {
         let result = RefsResult {
             symbol: name.to_string(),
             references,
+            parse_warnings: Vec::new(),
         };
         debug!(
             name = name,
@@ -500,6 +517,7 @@ impl AppService {
                 RefsResult {
                     symbol: name.clone(),
                     references,
+                    parse_warnings: Vec::new(),
                 }
             })
             .collect();
@@ -513,6 +531,166 @@ impl AppService {
         Ok(results)
     }
 
+    /// refs の `--parse-warnings` 用: `names` の各名前について、その名前を含む (= refs が
+    /// parse する) ファイルのうち ERROR / MISSING ノードを持つものを返す。
+    ///
+    /// 戻り値は `names` と同じ順序。parse error のあるファイルでは参照を取りこぼし得るため、
+    /// 件数が少ない / 0 件の結果を「本当に参照が無い」と読み違えないための補助情報。
+    pub fn parse_warnings_for_names(
+        &self,
+        names: &[String],
+        dir: &str,
+        glob: Option<&str>,
+    ) -> Result<Vec<Vec<ParseWarning>>> {
+        use rayon::prelude::*;
+
+        let canonical_dir = self.validate_dir(dir)?;
+        let files = refs::collect_files(&canonical_dir, glob)?;
+        let finders: Vec<memchr::memmem::Finder<'_>> = names
+            .iter()
+            .map(|name| memchr::memmem::Finder::new(name.as_bytes()))
+            .collect();
+        let touched: Vec<(PathBuf, Vec<usize>)> = files
+            .par_iter()
+            .filter_map(|file| {
+                let utf8 = camino::Utf8Path::from_path(file)?;
+                let source = parser::read_file(utf8).ok()?;
+                let hits: Vec<usize> = finders
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, finder)| finder.find(&source).is_some())
+                    .map(|(i, _)| i)
+                    .collect();
+                (!hits.is_empty()).then(|| (file.clone(), hits))
+            })
+            .collect();
+
+        let paths: Vec<PathBuf> = touched.iter().map(|(path, _)| path.clone()).collect();
+        let unhealthy: HashMap<String, ParseWarning> =
+            parse_health::scan_parse_health(&canonical_dir, &paths)
+                .iter()
+                .filter(|health| health.is_unhealthy())
+                .map(|health| (health.path.clone(), ParseWarning::from(health)))
+                .collect();
+
+        let mut warnings = vec![Vec::new(); names.len()];
+        for (path, hits) in &touched {
+            let Some(relative) = crate::engine::paths::relative_path(&canonical_dir, path) else {
+                continue;
+            };
+            if let Some(warning) = unhealthy.get(&relative) {
+                for &i in hits {
+                    warnings[i].push(warning.clone());
+                }
+            }
+        }
+        for per_name in &mut warnings {
+            per_name.sort_by(|a, b| a.path.cmp(&b.path));
+        }
+        Ok(warnings)
+    }
+
+    /// 名前で指定したシンボル定義のソーステキスト (直前の doc / attribute を含む) を返す。
+    ///
+    /// `dir` 検索では名前のバイト列を含まないファイルを parse 前に弾く。パスは `dir` 相対。
+    pub fn extract_source(&self, p: &SourceParams<'_>) -> Result<SourceResult> {
+        debug!(
+            path = ?p.path,
+            dir = ?p.dir,
+            name = p.name,
+            container = ?p.container,
+            "extract_source called"
+        );
+        let name = p.name.trim();
+        if name.is_empty() {
+            bail!(AstroError::new(
+                ErrorCode::InvalidRequest,
+                "name must not be empty"
+            ));
+        }
+        if p.max_bytes == 0 {
+            bail!(AstroError::new(
+                ErrorCode::InvalidRequest,
+                "max_bytes must be at least 1"
+            ));
+        }
+        let query = source::SourceQuery {
+            name,
+            container: p.container,
+            max_bytes: p.max_bytes,
+        };
+
+        let found: Vec<(SymbolSource, Option<usize>)> = match (p.path, p.dir) {
+            (Some(path), None) => {
+                let utf8_path_buf = self.validate_path_utf8(path)?;
+                let source = parser::read_file(utf8_path_buf.as_path())?;
+                let lang_id = parser::detect_lang(utf8_path_buf.as_path(), &source)?;
+                if lang_id.is_lexer_only() {
+                    bail!(AstroError::new(
+                        ErrorCode::InvalidRequest,
+                        format!("source is not supported for lexer-only language: {lang_id}"),
+                    ));
+                }
+                file_symbol_sources(&source, lang_id, path, &query)?
+            }
+            (None, Some(dir)) => {
+                use rayon::prelude::*;
+
+                let canonical_dir = self.validate_dir(dir)?;
+                let files = refs::collect_files(&canonical_dir, p.glob)?;
+                let finder = memchr::memmem::Finder::new(name.as_bytes());
+                let mut found: Vec<(SymbolSource, Option<usize>)> = files
+                    .par_iter()
+                    .filter_map(|file| {
+                        let utf8 = camino::Utf8Path::from_path(file)?;
+                        let source = parser::read_file(utf8).ok()?;
+                        finder.find(&source)?;
+                        let lang_id = parser::detect_lang(utf8, &source).ok()?;
+                        if lang_id.is_lexer_only() {
+                            return None;
+                        }
+                        let relative = crate::engine::paths::relative_path(&canonical_dir, file)?;
+                        file_symbol_sources(&source, lang_id, &relative, &query).ok()
+                    })
+                    .flatten()
+                    .collect();
+                found.sort_by(|a, b| {
+                    a.0.path
+                        .cmp(&b.0.path)
+                        .then_with(|| a.0.range.start.line.cmp(&b.0.range.start.line))
+                });
+                found
+            }
+            _ => bail!(AstroError::new(
+                ErrorCode::InvalidRequest,
+                "exactly one of path or dir is required"
+            )),
+        };
+
+        let mut result = SourceResult {
+            name: name.to_string(),
+            container: p.container.map(str::to_string),
+            ..Default::default()
+        };
+        for (symbol_source, original_bytes) in found {
+            if let Some(actual) = original_bytes {
+                result.truncations.push(TruncationInfo::source_too_large(
+                    &symbol_source.path,
+                    actual,
+                    p.max_bytes,
+                ));
+            }
+            result.matches.push(symbol_source);
+        }
+        debug!(
+            name = name,
+            matches = result.matches.len(),
+            truncations = result.truncations.len(),
+            "extract_source completed"
+        );
+        Ok(result)
+    }
+
     /// unified diff がコードベースへ与える影響を解析する。
     ///
     /// `options.exclude_dirs` / `options.exclude_globs` は Pass2 cross-file 検索
@@ -584,11 +762,51 @@ impl AppService {
         let mut callers_count = 0usize;
         let mut affected_count = 0usize;
 
+        // caller ごとの owner 付与用。CODEOWNERS が無ければ空で、付与処理ごと省く。
+        let codeowners = crate::engine::codeowners::CodeOwners::discover(&canonical_dir);
+
+        // `--transitive-depth`: 参照元のワークスペース走査と関数一覧は変更ファイル間で共有する。
+        let mut transitive = if options.transitive_depth > 0 {
+            Some(impact::TransitiveCallers::new(&canonical_dir, options)?)
+        } else {
+            None
+        };
+
         impact::analyze_impact_streaming(diff, &canonical_dir, options, |mut impact| {
             // impacted_callers 内の絶対パスを相対パスへ変換する。
             for caller in &mut impact.impacted_callers {
-                if let Ok(rel) = std::path::Path::new(&caller.path).strip_prefix(&canonical_dir) {
-                    caller.path = rel.to_string_lossy().to_string();
+                if let Some(rel) =
+                    crate::engine::paths::relative_path(&canonical_dir, Path::new(&caller.path))
+                {
+                    caller.path = rel;
+                }
+            }
+            // `--transitive-depth`: 公開ラッパー経由の間接 caller。相対化済みの
+            // impacted_callers を起点にするため、パス変換の後で辿る。
+            if let Some(transitive) = &mut transitive {
+                impact.transitive_callers = transitive.collect(&impact)?;
+            }
+            if !codeowners.is_empty() {
+                for caller in impact
+                    .impacted_callers
+                    .iter_mut()
+                    .chain(&mut impact.low_confidence_callers)
+                    .chain(&mut impact.informational_callers)
+                {
+                    // low / informational は絶対パスのまま残るため、照合時だけ dir 相対に直す。
+                    // dir 外のパスは CODEOWNERS の対象外なので owner を付けない。
+                    let path = Path::new(&caller.path);
+                    let rel = if path.is_absolute() {
+                        crate::engine::paths::relative_path(&canonical_dir, path)
+                    } else {
+                        Some(caller.path.clone())
+                    };
+                    if let Some(rel) = rel {
+                        caller.owners = codeowners.owners_of(&rel).to_vec();
+                    }
+                }
+                for caller in &mut impact.transitive_callers {
+                    caller.owners = codeowners.owners_of(&caller.path).to_vec();
                 }
             }
             changes_count += 1;
@@ -762,6 +980,21 @@ fn relativize_paths(
     refs
 }
 
+/// 1 ファイルを parse して `query` に一致する定義ソースを集める。
+fn file_symbol_sources(
+    source: &[u8],
+    lang_id: crate::language::LangId,
+    path: &str,
+    query: &source::SourceQuery<'_>,
+) -> Result<Vec<(SymbolSource, Option<usize>)>> {
+    let tree = parser::parse_source(source, lang_id)?;
+    let root = tree.root_node();
+    let syms = symbols::extract_symbols(root, source, lang_id)?;
+    Ok(source::find_symbol_sources(
+        root, source, lang_id, &syms, path, query,
+    ))
+}
+
 // ---------------------------------------------------------------------------
 // 診断情報ヘルパー（AppService の全コード経路で共有）
 // ---------------------------------------------------------------------------
@@ -8,6 +8,8 @@
 mod integration {
     mod support;
 
+    mod affected_tests;
+    mod api_snapshot;
     mod ast_symbols;
     mod cli_basics;
     mod cochange;
@@ -15,6 +17,8 @@ mod integration {
     mod dead_code;
     mod dead_code_conventions;
     mod dead_code_languages;
+    mod doc_coverage;
+    mod dupes;
     mod git_non_ascii;
     mod hidden_and_angular_liveness;
     mod impact;
@@ -22,10 +26,16 @@ mod integration {
     mod languages;
     mod mcp;
     mod output_format;
+    mod parse_health;
     mod php_member_liveness;
+    mod project_config;
     mod refs;
     mod review;
     mod review_dead_scope;
     mod sandbox;
+    mod semver;
     mod session;
+    mod source;
+    mod symdiff;
+    mod unused_imports;
 }
@@ -0,0 +1,114 @@
+//! affected-tests サブコマンドの統合テスト。
+
+use super::support::{TestRepo, cargo_bin};
+
+fn rust_repo() -> TestRepo {
+    let repo = TestRepo::new();
+    repo.write("Cargo.toml", "[package]\nname = \"demo\"\n");
+    repo.create_dir_all("src");
+    repo.create_dir_all("tests");
+    repo.write(
+        "src/lib.rs",
+        "pub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n\npub fn double(x: i32) -> i32 {\n    add(x, x)\n}\n\npub fn negate(x: i32) -> i32 {\n    -x\n}\n",
+    );
+    repo.write(
+        "tests/math.rs",
+        "use demo::{double, negate};\n\n#[test]\nfn doubles() {\n    assert_eq!(double(2), 4);\n}\n\n#[test]\nfn negates() {\n    assert_eq!(negate(1), -1);\n}\n",
+    );
+    repo.init_git();
+    repo.commit_all("initial");
+    repo
+}
+
+#[test]
+fn affected_tests_follows_references_transitively() {
+    let repo = rust_repo();
+    repo.write(
+        "src/lib.rs",
+        "pub fn add(a: i32, b: i32) -> i32 {\n    b + a\n}\n\npub fn double(x: i32) -> i32 {\n    add(x, x)\n}\n\npub fn negate(x: i32) -> i32 {\n    -x\n}\n",
+    );
+
+    let json = repo.run_json("affected-tests", &["--git"]);
+
+    assert_eq!(
+        json["changed_symbols"],
+        serde_json::json!(["add"]),
+        "{json}"
+    );
+    let files = json["test_files"].as_array().expect("test_files");
+    assert_eq!(files.len(), 1, "{json}");
+    assert_eq!(files[0]["path"], "tests/math.rs");
+    assert_eq!(files[0]["tests"], serde_json::json!(["doubles"]));
+    assert_eq!(files[0]["depth"], 2);
+    assert!(json.get("truncations").is_none());
+}
+
+#[test]
+fn affected_tests_reports_depth_limit() {
+    let repo = rust_repo();
+    repo.write(
+        "src/lib.rs",
+        "pub fn add(a: i32, b: i32) -> i32 {\n    b + a\n}\n\npub fn double(x: i32) -> i32 {\n    add(x, x)\n}\n\npub fn negate(x: i32) -> i32 {\n    -x\n}\n",
+    );
+
+    let json = repo.run_json("affected-tests", &["--git", "--max-depth", "1"]);
+
+    assert_eq!(
+        json["test_files"].as_array().map(Vec::len),
+        Some(0),
+        "{json}"
+    );
+    assert_eq!(json["truncations"][0]["reason"], "depth_limit_reached");
+}
+
+#[test]
+fn affected_tests_runner_prints_filter_line() {
+    let repo = TestRepo::new();
+    repo.write(
+        "calc.go",
+        "package calc\n\nfunc Add(a, b int) int {\n\treturn a + b\n}\n",
+    );
+    repo.write(
+        "calc_test.go",
+        "package calc\n\nimport \"testing\"\n\nfunc TestAdd(t *testing.T) {\n\tif Add(1, 2) != 3 {\n\t\tt.Fatal()\n\t}\n}\n\nfunc TestOther(t *testing.T) {}\n",
+    );
+    repo.init_git();
+    repo.commit_all("initial");
+    repo.write(
+        "calc.go",
+        "package calc\n\nfunc Add(a, b int) int {\n\treturn b + a\n}\n",
+    );
+
+    let output = cargo_bin()
+        .args(["affected-tests", "--git", "--runner", "go", "--dir"])
+        .arg(repo.root())
+        .output()
+        .expect("failed to run astro-sight");
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "^(TestAdd)$\n");
+}
+
+#[test]
+fn affected_tests_names_changed_rust_test_functions() {
+    let repo = rust_repo();
+    repo.write(
+        "tests/math.rs",
+        "use demo::{double, negate};\n\n#[test]\nfn doubles() {\n    assert_eq!(double(3), 6);\n}\n\n#[test]\nfn negates() {\n    assert_eq!(negate(1), -1);\n}\n",
+    );
+
+    let json = repo.run_json("affected-tests", &["--git"]);
+
+    let files = json["test_files"].as_array().expect("test_files");
+    assert_eq!(files.len(), 1, "{json}");
+    assert_eq!(files[0]["path"], "tests/math.rs");
+    assert_eq!(files[0]["tests"], serde_json::json!(["doubles"]));
+    assert_eq!(files[0]["depth"], 0);
+
+    let output = cargo_bin()
+        .args(["affected-tests", "--git", "--runner", "cargo", "--dir"])
+        .arg(repo.root())
+        .output()
+        .expect("failed to run astro-sight");
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "doubles\n");
+}
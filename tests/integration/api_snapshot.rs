@@ -0,0 +1,81 @@
+//! api dump / api check サブコマンドの統合テスト。
+
+use super::support::{TestRepo, cargo_bin};
+
+const LIB_RS: &str = "\
+pub fn run(a: u8) -> u8 {
+    a
+}
+
+pub fn stop() {}
+
+fn helper() {}
+";
+
+fn dump(repo: &TestRepo) -> String {
+    let output = cargo_bin()
+        .args(["api", "dump", "--dir"])
+        .arg(repo.root())
+        .output()
+        .expect("failed to run astro-sight");
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).expect("snapshot is not UTF-8")
+}
+
+fn check(repo: &TestRepo) -> (bool, serde_json::Value) {
+    let output = cargo_bin()
+        .args(["api", "check", "--snapshot"])
+        .arg(repo.path("api.txt"))
+        .arg("--dir")
+        .arg(repo.root())
+        .output()
+        .expect("failed to run astro-sight");
+    let json = serde_json::from_slice(&output.stdout).expect("astro-sight returned invalid JSON");
+    (output.status.success(), json)
+}
+
+#[test]
+fn api_dump_is_sorted_and_deterministic_without_git() {
+    let repo = TestRepo::new();
+    repo.write(
+        "Cargo.toml",
+        "[package]\nname = \"demo\"\nversion = \"0.1.0\"\n",
+    );
+    repo.create_dir_all("src");
+    repo.write("src/lib.rs", LIB_RS);
+
+    let snapshot = dump(&repo);
+    assert_eq!(snapshot, dump(&repo));
+    let lines: Vec<&str> = snapshot.lines().collect();
+    assert_eq!(lines[0], "# astro-sight api snapshot v1");
+    assert_eq!(lines.len(), 3, "{snapshot}");
+    assert!(lines[1].starts_with("src/lib.rs\trun\tfunction\tpub fn run(a: u8) -> u8"));
+    assert!(lines[2].starts_with("src/lib.rs\tstop\tfunction\t"));
+}
+
+#[test]
+fn api_check_reports_drift_against_snapshot() {
+    let repo = TestRepo::new();
+    repo.write(
+        "Cargo.toml",
+        "[package]\nname = \"demo\"\nversion = \"0.1.0\"\n",
+    );
+    repo.create_dir_all("src");
+    repo.write("src/lib.rs", LIB_RS);
+    repo.write("api.txt", dump(&repo));
+
+    let (success, json) = check(&repo);
+    assert!(success, "{json}");
+    assert_eq!(json["ok"], true);
+
+    repo.write(
+        "src/lib.rs",
+        "pub fn run(a: u16) -> u16 {\n    a\n}\n\npub fn start() {}\n",
+    );
+    let (success, json) = check(&repo);
+    assert!(!success);
+    assert_eq!(json["added"][0]["name"], "start", "{json}");
+    assert_eq!(json["removed"][0]["name"], "stop");
+    assert_eq!(json["modified"][0]["name"], "run");
+    assert_eq!(json["ok"], false);
+}
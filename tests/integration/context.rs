@@ -480,3 +480,46 @@ fn context_php_generic_method_bare_call_routed_to_low_confidence() {
         "fallback 下では low_confidence_callers は空 (skip_serializing_if で省略) のはず: {impact:?}"
     );
 }
+
+#[test]
+fn context_transitive_depth_follows_exported_wrapper() {
+    let repo = TestRepo::new();
+    repo.create_dir_all("src");
+    repo.write(
+        "src/lib.rs",
+        "fn helper() -> i32 {\n    1\n}\n\npub fn run() -> i32 {\n    helper()\n}\n",
+    );
+    repo.write("src/app.rs", "pub fn start() -> i32 {\n    run()\n}\n");
+    repo.init_git();
+    repo.commit_all("init");
+    repo.write(
+        "src/lib.rs",
+        "fn helper() -> i64 {\n    2\n}\n\npub fn run() -> i64 {\n    helper()\n}\n",
+    );
+
+    // 既定 (depth 0) では transitive_callers を出さない。
+    let json = repo.run_json("context", &["--git"]);
+    let lib = json["changes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|c| c["path"] == "src/lib.rs")
+        .expect("lib.rs change");
+    assert!(lib.get("transitive_callers").is_none(), "{lib}");
+
+    let json = repo.run_json("context", &["--git", "--transitive-depth", "1"]);
+    let lib = json["changes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|c| c["path"] == "src/lib.rs")
+        .expect("lib.rs change");
+    let transitive = lib["transitive_callers"].as_array().expect("transitive");
+    let start = transitive
+        .iter()
+        .find(|c| c["path"] == "src/app.rs")
+        .unwrap_or_else(|| panic!("start should be reported: {lib}"));
+    assert_eq!(start["name"], "start");
+    assert_eq!(start["chain"], serde_json::json!(["helper", "run"]));
+    assert_eq!(start["depth"], 1);
+}
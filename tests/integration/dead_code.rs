@@ -703,3 +703,294 @@ fn dead_code_excludes_python_dynamic_protocol_callbacks() {
         "規約外の未参照メソッドは dead のままにする: {json}"
     );
 }
+
+#[test]
+fn dead_code_scope_private_and_all() {
+    let repo = TestRepo::new();
+    repo.write(
+        "util.js",
+        "function used() { return 1; }\n\nfunction forgotten() { return 2; }\n\nexport function api() { return used(); }\n\nexport function orphanApi() { return 3; }\n",
+    );
+    let names = |json: &serde_json::Value| -> Vec<(String, Option<String>)> {
+        let mut names: Vec<_> = json["dead_symbols"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|d| {
+                (
+                    d["name"].as_str().unwrap().to_string(),
+                    d["visibility"].as_str().map(str::to_string),
+                )
+            })
+            .collect();
+        names.sort();
+        names
+    };
+
+    // 既定 (exported) は従来どおり公開シンボルのみ。
+    let json = repo.run_json("dead-code", &[]);
+    assert_eq!(
+        names(&json),
+        vec![("api".to_string(), None), ("orphanApi".to_string(), None)]
+    );
+
+    let json = repo.run_json("dead-code", &["--scope", "private"]);
+    assert_eq!(
+        names(&json),
+        vec![("forgotten".to_string(), Some("private".to_string()))]
+    );
+
+    let json = repo.run_json("dead-code", &["--scope", "all"]);
+    assert_eq!(
+        names(&json),
+        vec![
+            ("api".to_string(), None),
+            ("forgotten".to_string(), Some("private".to_string())),
+            ("orphanApi".to_string(), None),
+        ]
+    );
+}
+
+#[test]
+fn dead_code_reachability_reports_clusters() {
+    let repo = TestRepo::new();
+    repo.write(
+        "util.js",
+        "function walk(n) { return n > 0 ? step(n - 1) : 0; }\n\nfunction step(n) { return walk(n); }\n\nfunction helper() { return 1; }\n\nexport function api() { return helper(); }\n",
+    );
+    repo.write("main.js", "import { api } from \"./util.js\";\n\napi();\n");
+
+    let json = repo.run_json("dead-code", &["--reachability"]);
+    let clusters = json["dead_clusters"].as_array().unwrap();
+    assert_eq!(clusters.len(), 1, "{json}");
+    let names: Vec<&str> = clusters[0]["symbols"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|d| d["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, ["walk", "step"]);
+    // 既存の消費者向けに dead_symbols にも平坦に載る。
+    assert_eq!(json["dead_symbols"].as_array().unwrap().len(), 2);
+
+    let json = repo.run_json("dead-code", &["--reachability", "--entry", "util.js:walk"]);
+    assert!(json.get("dead_clusters").is_none(), "{json}");
+    assert!(json["dead_symbols"].as_array().unwrap().is_empty());
+}
+
+#[test]
+fn dead_code_fix_diff_removes_declarations_and_orphaned_imports() {
+    let repo = TestRepo::new();
+    repo.create_dir_all("lib");
+    repo.create_dir_all("tests");
+    repo.write(
+        "lib/format.ts",
+        "import { pad } from \"./pad\";\nimport { trim } from \"./trim\";\n\n/** Formats a label. */\nexport function formatLabel(s: string): string {\n  return trim(s);\n}\n\n/**\n * Legacy formatter.\n */\nexport function legacyFormat(s: string): string {\n  return pad(s);\n}\n\nexport function onlyInTests(): number {\n  return 1;\n}\n",
+    );
+    repo.write(
+        "lib/pad.ts",
+        "export function pad(s: string): string {\n  return s;\n}\n",
+    );
+    repo.write(
+        "lib/trim.ts",
+        "export function trim(s: string): string {\n  return s.trim();\n}\n",
+    );
+    // 同名の未使用 export は、どちらを消すべきか名前だけでは決まらないため触らない。
+    repo.write(
+        "lib/a.ts",
+        "export function dup(): number {\n  return 1;\n}\n",
+    );
+    repo.write(
+        "lib/b.ts",
+        "export function dup(): number {\n  return 2;\n}\n",
+    );
+    repo.write(
+        "main.ts",
+        "import { formatLabel } from \"./lib/format\";\n\nconsole.log(formatLabel(\" x \"));\n",
+    );
+    repo.write(
+        "tests/format.test.ts",
+        "import { onlyInTests } from \"../lib/format\";\n\nonlyInTests();\n",
+    );
+
+    let output = cargo_bin()
+        .args(["dead-code", "--dir"])
+        .arg(repo.root())
+        .arg("--fix-diff")
+        .output()
+        .expect("failed to run astro-sight");
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let patch = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        patch.starts_with("--- a/lib/format.ts\n+++ b/lib/format.ts\n"),
+        "{patch}"
+    );
+    for removed in [
+        "-import { pad } from \"./pad\";\n",
+        "-/**\n- * Legacy formatter.\n- */\n-export function legacyFormat(s: string): string {\n",
+    ] {
+        assert!(patch.contains(removed), "missing {removed:?} in {patch}");
+    }
+    assert!(!patch.contains("-import { trim }"), "{patch}");
+    assert!(!patch.contains("-export function formatLabel"), "{patch}");
+    assert!(
+        !patch.contains("onlyInTests"),
+        "test-only は対象外: {patch}"
+    );
+    assert!(!patch.contains("dup"), "同名シンボルは対象外: {patch}");
+
+    // そのまま git apply できる patch であること。
+    repo.write("fix.patch", &patch);
+    let status = Command::new("git")
+        .args(["apply", "fix.patch"])
+        .current_dir(repo.root())
+        .status()
+        .expect("failed to run git apply");
+    assert!(status.success(), "git apply failed:\n{patch}");
+    assert_eq!(
+        std::fs::read_to_string(repo.path("lib/format.ts")).unwrap(),
+        "import { trim } from \"./trim\";\n\n/** Formats a label. */\nexport function formatLabel(s: string): string {\n  return trim(s);\n}\n\nexport function onlyInTests(): number {\n  return 1;\n}\n",
+    );
+}
+
+#[test]
+fn dead_code_fix_diff_conflicts_with_format() {
+    let repo = TestRepo::new();
+    repo.write(
+        "main.ts",
+        "export function unused(): number {\n  return 1;\n}\n",
+    );
+
+    let output = cargo_bin()
+        .args(["dead-code", "--dir"])
+        .arg(repo.root())
+        .args(["--fix-diff", "--format", "markdown"])
+        .output()
+        .expect("failed to run astro-sight");
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("--fix-diff"), "stderr: {stderr}");
+}
+
+#[test]
+fn dead_code_blame_annotates_last_commit_and_reference_removal() {
+    let repo = TestRepo::new();
+    repo.init_git();
+    let commit_at = |message: &str, date: &str| {
+        repo.stage_all();
+        let status = Command::new("git")
+            .args(["commit", "-q", "-m", message])
+            .env("GIT_AUTHOR_DATE", date)
+            .env("GIT_COMMITTER_DATE", date)
+            .current_dir(repo.root())
+            .status()
+            .expect("failed to run git commit");
+        assert!(status.success());
+        let output = Command::new("git")
+            .args(["rev-parse", "HEAD"])
+            .current_dir(repo.root())
+            .output()
+            .expect("failed to run git rev-parse");
+        String::from_utf8(output.stdout).unwrap().trim().to_string()
+    };
+
+    repo.write(
+        "util.ts",
+        "export function oldHelper(): number {\n  return 1;\n}\n",
+    );
+    repo.write(
+        "main.ts",
+        "import { oldHelper } from \"./util\";\n\nconsole.log(oldHelper());\n",
+    );
+    let first = commit_at("add helper", "2020-01-02T03:04:05Z");
+    repo.write(
+        "util.ts",
+        "export function newHelper(): number {\n  return 2;\n}\n\nexport function oldHelper(): number {\n  return 1;\n}\n",
+    );
+    repo.write("main.ts", "console.log(\"hi\");\n");
+    repo.write("notes.py", "newHelper = 1\nprint(newHelper)\n");
+    let second = commit_at("drop caller", "2024-05-06T07:08:09Z");
+    // 宣言の言語から参照し得ないファイル (Python) で同名が消えても参照の削除とみなさない。
+    std::fs::remove_file(repo.path("notes.py")).unwrap();
+    commit_at("drop notes", "2024-06-07T08:09:10Z");
+
+    let json = repo.run_json("dead-code", &["--blame", "--sort", "age"]);
+    let dead = json["dead_symbols"].as_array().unwrap();
+    let names: Vec<&str> = dead.iter().map(|d| d["name"].as_str().unwrap()).collect();
+    assert_eq!(names, ["oldHelper", "newHelper"], "{json}");
+
+    let old = &dead[0]["blame"];
+    assert_eq!(old["sha"], first.as_str());
+    assert_eq!(old["author"], "astro-sight");
+    assert_eq!(old["date"], "2020-01-02T03:04:05Z");
+    assert_eq!(old["reference_removed_by"]["sha"], second.as_str());
+
+    let new = &dead[1]["blame"];
+    assert_eq!(new["sha"], second.as_str());
+    assert!(new.get("reference_removed_by").is_none(), "{json}");
+
+    // --blame 無しでは付けず、並びも位置順のまま。
+    let json = repo.run_json("dead-code", &[]);
+    assert_eq!(json["dead_symbols"][0]["name"], "newHelper", "{json}");
+    assert!(json["dead_symbols"][0].get("blame").is_none(), "{json}");
+}
+
+#[test]
+fn dead_code_cargo_features_reports_unused_and_undeclared_features() {
+    let repo = TestRepo::new();
+    repo.write("Cargo.toml", "[workspace]\nmembers = [\"crates/*\"]\n");
+    repo.write(
+        "crates/core/Cargo.toml",
+        "[package]\nname = \"core\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[features]\ndefault = [\"std\"]\nstd = []\nsimd = []\njson = [\"dep:serde_json\"]\nlegacy = []\n\n[dependencies]\nserde_json = { version = \"1\", optional = true }\nlog = { version = \"0.4\", optional = true }\n",
+    );
+    // build script の `CARGO_FEATURE_<NAME>` も参照として数える。
+    repo.write(
+        "crates/core/build.rs",
+        "fn main() {\n    if std::env::var(\"CARGO_FEATURE_SIMD\").is_ok() {}\n}\n",
+    );
+    // doc comment 内の例は参照に数えず、optional 依存は暗黙の feature として宣言済み扱い。
+    repo.write(
+        "crates/core/src/lib.rs",
+        "/// Enable with `#[cfg(feature = \"legacy\")]`.\n#[cfg(feature = \"std\")]\npub fn a() {}\n\n#[cfg(any(feature = \"log\", feature = \"jsno\"))]\npub fn b() {}\n",
+    );
+    // vendor 配下の crate は報告しない。
+    repo.write(
+        "vendor/dep/Cargo.toml",
+        "[package]\nname = \"dep\"\nversion = \"0.1.0\"\n\n[features]\nx = []\n",
+    );
+    repo.write(
+        "vendor/dep/src/lib.rs",
+        "#[cfg(feature = \"y\")]\npub fn d() {}\n",
+    );
+
+    let json = repo.run_json("dead-code", &["--cargo-features"]);
+    assert_eq!(
+        json["cargo_features"],
+        serde_json::json!([
+            {
+                "kind": "unused",
+                "feature": "legacy",
+                "file": "crates/core/Cargo.toml",
+                "line": 10,
+                "manifest": "crates/core/Cargo.toml",
+            },
+            {
+                "kind": "undeclared",
+                "feature": "jsno",
+                "file": "crates/core/src/lib.rs",
+                "line": 4,
+                "manifest": "crates/core/Cargo.toml",
+            },
+        ]),
+        "{json}"
+    );
+
+    // 指定しなければ出さない。
+    let json = repo.run_json("dead-code", &[]);
+    assert!(json.get("cargo_features").is_none(), "{json}");
+}
@@ -1522,3 +1522,365 @@ class MainActivity : AppCompatActivity() {
         "AndroidManifest.xml で宣言された activity は dead 扱いすべきでない: {names:?}"
     );
 }
+
+/// Rails: routes.rb のルーティング先アクション・コールバックのシンボル引数・ERB ビューから
+/// 呼ばれるヘルパーは dead から除外される。Gemfile の rails gem でプリセットが自動適用される。
+#[test]
+fn dead_code_excludes_rails_routes_callbacks_and_view_helpers() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let root = dir.path();
+    for sub in [
+        "config",
+        "app/controllers",
+        "app/helpers",
+        "app/views/articles",
+    ] {
+        std::fs::create_dir_all(root.join(sub)).unwrap();
+    }
+    std::fs::write(root.join("Gemfile"), "gem 'rails', '~> 7.1'\n").unwrap();
+    std::fs::write(
+        root.join("config/routes.rb"),
+        "Rails.application.routes.draw do\n  resources :articles, only: [:index]\n  get 'articles/feed', to: 'articles#feed'\nend\n",
+    )
+    .unwrap();
+    let controller_src = "\
+class ArticlesController < ApplicationController
+  before_action :load_articles
+
+  def index
+  end
+
+  def feed
+  end
+
+  def orphan_action
+  end
+
+  def load_articles
+  end
+end
+";
+    std::fs::write(
+        root.join("app/controllers/articles_controller.rb"),
+        controller_src,
+    )
+    .unwrap();
+    let helper_src = "\
+module ArticlesHelper
+  def format_headline(article)
+  end
+
+  def unused_formatter(article)
+  end
+end
+";
+    std::fs::write(root.join("app/helpers/articles_helper.rb"), helper_src).unwrap();
+    std::fs::write(
+        root.join("app/views/articles/index.html.erb"),
+        "<h1>Articles</h1>\n<%= format_headline(@article) %>\n",
+    )
+    .unwrap();
+
+    let output = cargo_bin()
+        .args(["dead-code", "--dir", root.to_str().unwrap()])
+        .output()
+        .expect("failed to run");
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("invalid JSON");
+    let dead_names: Vec<String> = json["dead_symbols"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|s| s["name"].as_str().map(str::to_string))
+        .collect();
+    for name in [
+        "ArticlesController",
+        "index",
+        "feed",
+        "load_articles",
+        "format_headline",
+    ] {
+        assert!(
+            !dead_names.iter().any(|n| n.ends_with(name)),
+            "Rails 規約経由で呼ばれる `{name}` は dead から除外されるべき: {dead_names:?}"
+        );
+    }
+    for name in ["orphan_action", "unused_formatter"] {
+        assert!(
+            dead_names.iter().any(|n| n.ends_with(name)),
+            "どこからも呼ばれない `{name}` は dead として残るべき (回帰担保): {dead_names:?}"
+        );
+    }
+}
+
+/// Spring: `pom.xml` の Spring 依存で `spring` プリセットが自動適用され、ハンドラ /
+/// `@Bean` / `@Scheduled` / `@EventListener` と `@Autowired` で注入される型は dead から除外される。
+#[test]
+fn dead_code_excludes_spring_annotated_entry_points() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let root = dir.path();
+    let pkg = root.join("src/main/java/com/example");
+    std::fs::create_dir_all(&pkg).unwrap();
+    std::fs::write(
+        root.join("pom.xml"),
+        "<project><dependencies><dependency><groupId>org.springframework.boot</groupId></dependency></dependencies></project>\n",
+    )
+    .unwrap();
+    let controller_src = "\
+package com.example;
+
+@RestController
+public class OrderController {
+    @Autowired
+    private OrderRepository orders;
+
+    @GetMapping(\"/orders\")
+    public String list() { return \"\"; }
+
+    @Scheduled(fixedDelay = 1000)
+    public void purge() {}
+
+    @EventListener
+    public void onStartup(Object event) {}
+
+    @Bean
+    public Object clock() { return null; }
+
+    public void orphanHelper() {}
+}
+";
+    std::fs::write(pkg.join("OrderController.java"), controller_src).unwrap();
+    std::fs::write(
+        pkg.join("OrderRepository.java"),
+        "package com.example;\n\npublic interface OrderRepository {}\n",
+    )
+    .unwrap();
+
+    let output = cargo_bin()
+        .args(["dead-code", "--dir", root.to_str().unwrap()])
+        .output()
+        .expect("failed to run");
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("invalid JSON");
+    let dead_names: Vec<String> = json["dead_symbols"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|s| s["name"].as_str().map(str::to_string))
+        .collect();
+    for name in [
+        "OrderController",
+        "OrderRepository",
+        "list",
+        "purge",
+        "onStartup",
+        "clock",
+    ] {
+        assert!(
+            !dead_names.iter().any(|n| n.ends_with(name)),
+            "Spring がリフレクションで呼ぶ `{name}` は dead から除外されるべき: {dead_names:?}"
+        );
+    }
+    assert!(
+        dead_names.iter().any(|n| n.ends_with("orphanHelper")),
+        "annotation の無いメソッドは dead として残るべき (回帰担保): {dead_names:?}"
+    );
+}
+
+/// Spring の annotation は `--scope private` (非公開シンボル) と `--reachability` にも効く。
+#[test]
+fn dead_code_private_and_reachability_honor_spring_annotations() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let root = dir.path();
+    std::fs::write(
+        root.join("pom.xml"),
+        "<project><dependencies><dependency><groupId>org.springframework.boot</groupId></dependency></dependencies></project>\n",
+    )
+    .unwrap();
+    let jobs_src = "\
+@Component
+public class Jobs {
+    @PostConstruct
+    private void init() {}
+
+    @Scheduled(fixedRate = 1000)
+    private void sweep() {
+        cleanup();
+    }
+
+    private void cleanup() {}
+
+    private void stale() {}
+}
+";
+    std::fs::write(root.join("Jobs.java"), jobs_src).unwrap();
+
+    for flags in [&["--scope", "private"][..], &["--reachability"][..]] {
+        let output = cargo_bin()
+            .args(["dead-code", "--dir", root.to_str().unwrap()])
+            .args(flags)
+            .output()
+            .expect("failed to run");
+        assert!(
+            output.status.success(),
+            "stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("invalid JSON");
+        let dead_names: Vec<String> = json["dead_symbols"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|s| s["name"].as_str().map(str::to_string))
+            .collect();
+        for name in ["init", "sweep", "cleanup"] {
+            assert!(
+                !dead_names.iter().any(|n| n.ends_with(name)),
+                "{flags:?}: Spring が呼ぶ `{name}` (とその呼び出し先) は dead から除外されるべき: {dead_names:?}"
+            );
+        }
+        assert!(
+            dead_names.iter().any(|n| n.ends_with("stale")),
+            "{flags:?}: annotation の無い private メソッドは dead として残るべき: {dead_names:?}"
+        );
+    }
+}
+
+/// `--entry-annotation` で追加した annotation はプリセット無しのプロジェクトでも効く。
+#[test]
+fn dead_code_entry_annotation_flag_marks_declarations_live() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let root = dir.path();
+    let jobs_src = "\
+public class Jobs {
+    @com.example.Nightly
+    public void rebuildIndex() {}
+
+    public void orphanJob() {}
+}
+";
+    std::fs::write(root.join("Jobs.java"), jobs_src).unwrap();
+
+    let output = cargo_bin()
+        .args([
+            "dead-code",
+            "--dir",
+            root.to_str().unwrap(),
+            "--entry-annotation",
+            "Nightly",
+        ])
+        .output()
+        .expect("failed to run");
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("invalid JSON");
+    let dead_names: Vec<String> = json["dead_symbols"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|s| s["name"].as_str().map(str::to_string))
+        .collect();
+    assert!(
+        !dead_names.iter().any(|n| n.ends_with("rebuildIndex")),
+        "--entry-annotation の annotation が付いた宣言は dead から除外されるべき: {dead_names:?}"
+    );
+    assert!(
+        dead_names.iter().any(|n| n.ends_with("orphanJob")),
+        "annotation の無いメソッドは dead として残るべき (回帰担保): {dead_names:?}"
+    );
+}
+
+/// `.astro-sight/presets/<name>.yaml` のユーザー定義プリセットは marker で自動適用され、
+/// `base_classes` / `method_patterns` / `string_refs` に合うシンボルを dead から除外する。
+#[test]
+fn dead_code_applies_user_defined_yaml_preset() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let root = dir.path();
+    std::fs::create_dir_all(root.join(".git")).unwrap();
+    std::fs::create_dir_all(root.join(".astro-sight/presets")).unwrap();
+    std::fs::write(
+        root.join(".astro-sight/presets/acme.yaml"),
+        "markers: [acme.json]\n\
+         base_classes: [AcmeJob]\n\
+         method_patterns: ['^on_']\n\
+         string_refs: [\"route('{name}')\"]\n",
+    )
+    .unwrap();
+    std::fs::write(root.join("acme.json"), "{}\n").unwrap();
+    let jobs_src = "\
+class NightlySync(AcmeJob):
+    def perform(self):
+        pass
+
+
+def on_startup():
+    pass
+
+
+def export_report():
+    pass
+
+
+def orphan_helper():
+    pass
+";
+    std::fs::write(root.join("jobs.py"), jobs_src).unwrap();
+    std::fs::write(root.join("urls.py"), "url = route('export_report')\n").unwrap();
+
+    let dead_names = |framework: Option<&str>| -> Vec<String> {
+        let mut cmd = cargo_bin();
+        cmd.args(["dead-code", "--dir", root.to_str().unwrap()]);
+        if let Some(name) = framework {
+            cmd.args(["--framework", name]);
+        }
+        let output = cmd.output().expect("failed to run");
+        assert!(
+            output.status.success(),
+            "stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("invalid JSON");
+        json["dead_symbols"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|s| s["name"].as_str().map(str::to_string))
+            .collect()
+    };
+
+    for names in [dead_names(None), dead_names(Some("acme"))] {
+        for live in ["NightlySync", "perform", "on_startup", "export_report"] {
+            assert!(
+                !names.iter().any(|n| n.ends_with(live)),
+                "{live} はプリセット規約で生存扱いになるべき: {names:?}"
+            );
+        }
+        assert!(
+            names.iter().any(|n| n.ends_with("orphan_helper")),
+            "規約に合わない関数は dead として残るべき (回帰担保): {names:?}"
+        );
+    }
+
+    let output = cargo_bin()
+        .args([
+            "dead-code",
+            "--dir",
+            root.to_str().unwrap(),
+            "--framework",
+            "unknown-acme",
+        ])
+        .output()
+        .expect("failed to run");
+    assert!(!output.status.success());
+}
@@ -0,0 +1,73 @@
+//! doc-coverage サブコマンドの統合テスト。
+
+use super::support::TestRepo;
+
+const LIB_RS: &str = "\
+/// Adds two numbers.
+pub fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+pub fn sub(a: i32, b: i32) -> i32 {
+    a - b
+}
+
+pub(crate) fn internal() {}
+
+fn private_helper() {}
+";
+
+#[test]
+fn doc_coverage_reports_file_and_package_coverage() {
+    let repo = TestRepo::new();
+    repo.write("Cargo.toml", "[package]\nname = \"demo\"\n");
+    repo.create_dir_all("src");
+    repo.write("src/lib.rs", LIB_RS);
+
+    let json = repo.run_json("doc-coverage", &[]);
+
+    assert_eq!(json["total"], 2, "{json}");
+    assert_eq!(json["documented"], 1);
+    assert_eq!(json["coverage"], 50.0);
+    assert_eq!(json["packages"][0]["package"], ".");
+    assert_eq!(json["files"][0]["file"], "src/lib.rs");
+    let undocumented = json["undocumented"].as_array().expect("undocumented");
+    assert_eq!(undocumented.len(), 1, "{json}");
+    assert_eq!(undocumented[0]["name"], "sub");
+    assert_eq!(undocumented[0]["line"], 5);
+    assert!(json.get("violations").is_none());
+}
+
+#[test]
+fn doc_coverage_git_fails_only_for_touched_undocumented_symbols() {
+    let repo = TestRepo::new();
+    repo.create_dir_all("src");
+    repo.write("src/lib.rs", LIB_RS);
+    repo.init_git();
+    repo.commit_all("initial");
+
+    // 既存の undocumented `sub` に触れない変更 (doc 付き追加) は成功する。
+    repo.write(
+        "src/lib.rs",
+        format!("{LIB_RS}\n/// Multiplies two numbers.\npub fn mul(a: i32, b: i32) -> i32 {{\n    a * b\n}}\n"),
+    );
+    let json = repo.run_json("doc-coverage", &["--git"]);
+    assert!(json.get("violations").is_none(), "{json}");
+
+    // doc 無しの公開関数を追加すると exit 1 で violations に出る。
+    repo.write(
+        "src/lib.rs",
+        format!("{LIB_RS}\npub fn div(a: i32, b: i32) -> i32 {{\n    a / b\n}}\n"),
+    );
+    let output = super::support::cargo_bin()
+        .args(["doc-coverage", "--git", "--dir"])
+        .arg(repo.root())
+        .output()
+        .expect("failed to run astro-sight");
+    assert!(!output.status.success());
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("astro-sight returned invalid JSON");
+    let violations = json["violations"].as_array().expect("violations");
+    assert_eq!(violations.len(), 1, "{json}");
+    assert_eq!(violations[0]["name"], "div");
+}
@@ -0,0 +1,99 @@
+//! dupes サブコマンドと review の `duplicated_code` の統合テスト。
+
+use super::support::TestRepo;
+
+const TOTAL_PRICE: &str = "\
+def total_price(items):
+    result = 0
+    for item in items:
+        if item.active:
+            result += item.price * item.qty
+    return result
+";
+
+const SUM_WEIGHTS: &str = "\
+def sum_weights(rows):
+    acc = 0
+    for row in rows:
+        if row.enabled:
+            acc += row.weight * row.count
+    return acc
+";
+
+#[test]
+fn dupes_reports_renamed_copy_across_files() {
+    let repo = TestRepo::new();
+    repo.write("billing.py", TOTAL_PRICE);
+    repo.write("shipping.py", format!("import os\n\n{SUM_WEIGHTS}"));
+
+    let json = repo.run_json("dupes", &["--min-tokens", "20"]);
+
+    assert_eq!(json["min_tokens"], 20);
+    let groups = json["groups"]
+        .as_array()
+        .expect("groups should be an array");
+    assert_eq!(groups.len(), 1, "{json}");
+    let instances = groups[0]["instances"].as_array().expect("instances");
+    let files: Vec<&str> = instances
+        .iter()
+        .filter_map(|i| i["file"].as_str())
+        .collect();
+    assert_eq!(files, ["billing.py", "shipping.py"]);
+    assert_eq!(instances[1]["start_line"], 2);
+    let similarity = groups[0]["similarity"].as_f64().expect("similarity");
+    assert!(similarity < 1.0, "rename を伴うコピー: {similarity}");
+}
+
+#[test]
+fn dupes_rejects_zero_min_tokens() {
+    let repo = TestRepo::new();
+    repo.write("a.py", TOTAL_PRICE);
+
+    let output = super::support::cargo_bin()
+        .args(["dupes", "--dir"])
+        .arg(repo.root())
+        .args(["--min-tokens", "0"])
+        .output()
+        .expect("failed to run astro-sight");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn review_reports_added_code_duplicating_existing_code() {
+    // review は既定の最小トークン数 (50) を使うため、閾値を超える大きさの関数にする。
+    let existing = "\
+def summarize(orders):
+    total = 0
+    count = 0
+    for order in orders:
+        if order.status == 'paid' and order.amount > 0:
+            total += order.amount * order.rate
+            count += 1
+    average = total / count if count else 0
+    return {'total': total, 'count': count, 'average': average}
+";
+    let repo = TestRepo::new();
+    repo.write("orders.py", existing);
+    repo.init_git();
+    repo.commit_all("initial");
+
+    let copied = existing
+        .replace("summarize", "summarize_refunds")
+        .replace("orders", "refunds")
+        .replace("order", "refund");
+    repo.write("refunds.py", &copied);
+    repo.stage_all();
+
+    let json = repo.run_json("review", &["--git", "--staged"]);
+
+    let dupes = json["duplicated_code"]
+        .as_array()
+        .expect("duplicated_code should be present");
+    assert_eq!(dupes.len(), 1, "{json}");
+    assert_eq!(dupes[0]["file"], "refunds.py");
+    assert_eq!(dupes[0]["duplicates"][0]["file"], "orders.py");
+
+    // --no-dupes では検出しない。
+    let json = repo.run_json("review", &["--git", "--staged", "--no-dupes"]);
+    assert!(json.get("duplicated_code").is_none(), "{json}");
+}
@@ -70,6 +70,7 @@ fn mcp_tools_list() {
         "lint",
         "sequence_diagram",
         "cochange_analyze",
+        "source_extract",
         "doctor",
     ] {
         assert!(
@@ -1,4 +1,4 @@
-//! `--format json|toon` と `config.toml` の `format` の統合テスト。
+//! `--format json|toon|markdown|codeclimate` と `config.toml` の `format` の統合テスト。
 //!
 //! 検証の軸は 3 つ:
 //! 1. 既定 (JSON) の出力が **1 バイトも変わっていない** こと
@@ -457,6 +457,138 @@ fn errors_stay_json_even_when_toon_is_requested() {
     assert_eq!(value["error"]["code"], "FILE_NOT_FOUND");
 }
 
+// ---------------------------------------------------------------------------
+// レポート形式 (markdown)
+// ---------------------------------------------------------------------------
+
+#[test]
+fn review_renders_markdown_report() {
+    let repo = sample_repo();
+    repo.init_git();
+    repo.commit_all("init");
+    repo.write("b.rs", "pub fn gamma(y: usize) -> usize {\n    y\n}\n");
+
+    let stdout = stdout_of(&run(
+        &repo,
+        &["review", "--dir", ".", "--git", "--format", "markdown"],
+    ));
+    assert!(
+        stdout.starts_with("## astro-sight review\n"),
+        "unexpected output: {stdout}"
+    );
+    assert!(stdout.contains("impacted caller(s)"), "output: {stdout}");
+    assert!(serde_json::from_str::<serde_json::Value>(stdout.trim()).is_err());
+}
+
+#[test]
+fn explicit_markdown_is_rejected_outside_review() {
+    let repo = sample_repo();
+    repo.init_git();
+    repo.commit_all("init");
+    repo.write("b.rs", "pub fn gamma() -> usize {\n    3\n}\n");
+
+    for args in [
+        vec!["symbols", "--path", "a.rs", "--format", "markdown"],
+        vec![
+            "review", "--dir", ".", "--git", "--hook", "--format", "markdown",
+        ],
+    ] {
+        let output = run(&repo, &args);
+        assert!(!output.status.success(), "should reject: {args:?}");
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let value: serde_json::Value =
+            serde_json::from_str(stdout.trim()).expect("error output stays JSON");
+        assert_eq!(value["error"]["code"], "INVALID_REQUEST", "args: {args:?}");
+    }
+}
+
+#[test]
+fn config_sourced_markdown_falls_back_to_json_outside_review() {
+    let repo = sample_repo();
+    repo.write("astro-sight.toml", "format = \"markdown\"\n");
+    let config = repo.path("astro-sight.toml");
+    let config = config.to_str().expect("utf-8 path");
+
+    let stdout = stdout_of(&run(
+        &repo,
+        &["--config", config, "symbols", "--path", "a.rs"],
+    ));
+    serde_json::from_str::<serde_json::Value>(stdout.trim()).expect("falls back to JSON");
+}
+
+#[test]
+fn dead_code_renders_codeclimate_issues() {
+    let repo = sample_repo();
+    let stdout = stdout_of(&run(
+        &repo,
+        &["dead-code", "--dir", ".", "--format", "codeclimate"],
+    ));
+    let issues: Vec<serde_json::Value> =
+        serde_json::from_str(stdout.trim()).expect("codeclimate output is a JSON array");
+    let gamma = issues
+        .iter()
+        .find(|issue| issue["description"].as_str().unwrap().contains("`gamma`"))
+        .unwrap_or_else(|| panic!("gamma should be reported: {stdout}"));
+    assert_eq!(gamma["type"], "issue");
+    assert_eq!(gamma["check_name"], "astro-sight/dead-symbol");
+    assert_eq!(gamma["categories"][0], "Clarity");
+    assert_eq!(gamma["location"]["path"], "b.rs");
+    assert_eq!(gamma["location"]["lines"]["begin"], 1);
+    assert!(gamma["fingerprint"].is_string());
+}
+
+#[test]
+fn lint_batch_renders_one_codeclimate_array() {
+    let repo = sample_repo();
+    repo.write(
+        "rules.yaml",
+        "- id: no-max\n  language: rust\n  severity: error\n  message: avoid MAX\n  pattern: MAX\n",
+    );
+    let stdout = stdout_of(&run(
+        &repo,
+        &[
+            "lint",
+            "--paths",
+            "a.rs,b.rs",
+            "--rules",
+            "rules.yaml",
+            "--format",
+            "codeclimate",
+        ],
+    ));
+    let issues: Vec<serde_json::Value> =
+        serde_json::from_str(stdout.trim()).expect("single JSON array, not NDJSON");
+    assert!(!issues.is_empty(), "output: {stdout}");
+    for issue in &issues {
+        assert_eq!(issue["check_name"], "astro-sight/lint/no-max");
+        assert_eq!(issue["severity"], "major");
+        assert_eq!(issue["location"]["path"], "a.rs");
+    }
+    let fingerprints: std::collections::HashSet<&str> = issues
+        .iter()
+        .map(|issue| issue["fingerprint"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        fingerprints.len(),
+        issues.len(),
+        "fingerprints must be unique"
+    );
+}
+
+#[test]
+fn explicit_codeclimate_is_rejected_for_unsupported_commands() {
+    let repo = sample_repo();
+    let output = run(
+        &repo,
+        &["symbols", "--path", "a.rs", "--format", "codeclimate"],
+    );
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let value: serde_json::Value =
+        serde_json::from_str(stdout.trim()).expect("error output stays JSON");
+    assert_eq!(value["error"]["code"], "INVALID_REQUEST");
+}
+
 // ---------------------------------------------------------------------------
 // config.toml
 // ---------------------------------------------------------------------------
@@ -0,0 +1,70 @@
+//! parse-health サブコマンドと `--parse-warnings` の統合テスト。
+
+use super::support::TestRepo;
+
+fn repo_with_broken_python() -> TestRepo {
+    let repo = TestRepo::new();
+    repo.write("lib.rs", "pub fn helper() -> i32 {\n    1\n}\n");
+    repo.write("ok.py", "def helper():\n    return 1\n");
+    repo.write(
+        "broken.py",
+        "def caller():\n    return helper()\n\ndef oops(:\n    pass\n",
+    );
+    repo
+}
+
+#[test]
+fn parse_health_groups_broken_files_by_language() {
+    let repo = repo_with_broken_python();
+
+    let json = repo.run_json("parse-health", &[]);
+
+    assert_eq!(json["scanned_files"], 3, "{json}");
+    assert_eq!(json["files_with_errors"], 1);
+    let languages = json["languages"].as_array().expect("languages");
+    assert_eq!(languages.len(), 1, "{json}");
+    assert_eq!(languages[0]["lang"], "python");
+    assert_eq!(languages[0]["scanned_files"], 2);
+    let file = &languages[0]["files"][0];
+    assert_eq!(file["path"], "broken.py");
+    assert!(file["errors"].as_u64().unwrap() >= 1, "{json}");
+    assert_eq!(file["locations"][0]["line"], 3);
+}
+
+#[test]
+fn parse_health_clean_workspace_has_no_languages() {
+    let repo = TestRepo::new();
+    repo.write("lib.rs", "pub fn helper() {}\n");
+
+    let json = repo.run_json("parse-health", &[]);
+
+    assert_eq!(json["scanned_files"], 1);
+    assert_eq!(json["files_with_errors"], 0);
+    assert_eq!(json["languages"].as_array().map(Vec::len), Some(0));
+}
+
+#[test]
+fn refs_parse_warnings_is_opt_in() {
+    let repo = repo_with_broken_python();
+
+    let json = repo.run_json("refs", &["--name", "helper"]);
+    assert!(json.get("parse_warnings").is_none(), "{json}");
+
+    let json = repo.run_json("refs", &["--name", "helper", "--parse-warnings"]);
+    let warnings = json["parse_warnings"].as_array().expect("parse_warnings");
+    assert_eq!(warnings.len(), 1, "{json}");
+    assert_eq!(warnings[0]["path"], "broken.py");
+    assert_eq!(warnings[0]["lang"], "python");
+    assert_eq!(warnings[0]["line"], 3);
+}
+
+#[test]
+fn dead_code_parse_warnings_lists_broken_files() {
+    let repo = repo_with_broken_python();
+
+    let json = repo.run_json("dead-code", &["--parse-warnings"]);
+
+    let warnings = json["parse_warnings"].as_array().expect("parse_warnings");
+    assert_eq!(warnings.len(), 1, "{json}");
+    assert_eq!(warnings[0]["path"], "broken.py");
+}
@@ -0,0 +1,75 @@
+//! リポジトリ単位の `.astro-sight.toml` と `config show` の統合テスト。
+
+use super::support::{TestRepo, cargo_bin_with_explicit_config};
+
+#[test]
+fn project_config_excludes_apply_without_cli_flags() {
+    let repo = TestRepo::new();
+    repo.create_dir_all("src");
+    repo.create_dir_all("generated");
+    repo.write(
+        "src/lib.rs",
+        "pub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n",
+    );
+    repo.write("generated/api.rs", "pub fn stub() {}\n");
+
+    let json = repo.run_json("doc-coverage", &[]);
+    assert_eq!(json["total"], 2, "{json}");
+
+    repo.write(".astro-sight.toml", "exclude_dirs = [\"generated\"]\n");
+    let json = repo.run_json("doc-coverage", &[]);
+    assert_eq!(json["total"], 1, "{json}");
+    assert_eq!(json["files"][0]["file"], "src/lib.rs");
+}
+
+#[test]
+fn config_show_effective_reports_precedence() {
+    let repo = TestRepo::new();
+    repo.create_dir_all("src");
+    repo.write(
+        "user.toml",
+        "framework = \"laravel\"\n[cochange]\nmin_confidence = 0.2\n",
+    );
+    repo.write(".astro-sight.toml", "[cochange]\nmin_confidence = 0.5\n");
+
+    let output = cargo_bin_with_explicit_config()
+        .arg("--config")
+        .arg(repo.path("user.toml"))
+        .args(["config", "show", "--effective", "--dir"])
+        .arg(repo.path("src"))
+        .output()
+        .expect("failed to run astro-sight");
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("astro-sight returned invalid JSON");
+    assert!(
+        json["project_config"]
+            .as_str()
+            .is_some_and(|path| path.ends_with(".astro-sight.toml")),
+        "{json}"
+    );
+    assert_eq!(json["effective"]["framework"], "laravel");
+    assert_eq!(json["effective"]["cochange"]["min_confidence"], 0.5);
+    assert_eq!(json["effective"]["cochange"]["min_samples"], 2);
+    assert_eq!(json["effective"]["hook"]["dead_scope"], "touched-symbols");
+    assert_eq!(json["sources"]["framework"], "user");
+    assert_eq!(json["sources"]["cochange.min_confidence"], "project");
+    assert_eq!(json["sources"]["cochange.min_samples"], "default");
+}
+
+#[test]
+fn project_config_with_unknown_key_is_an_error() {
+    let repo = TestRepo::new();
+    repo.write(".astro-sight.toml", "exclude_dir = [\"generated\"]\n");
+    let output = super::support::cargo_bin()
+        .args(["doc-coverage", "--dir"])
+        .arg(repo.root())
+        .output()
+        .expect("failed to run astro-sight");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("exclude_dir"));
+}
@@ -1188,3 +1188,346 @@ fn review_hook_ts_shadowed_local_fn_unupdated_caller_exits_nonzero() {
         "対象 caller 未更新なら blocking (exit 1) のまま"
     );
 }
+
+#[test]
+fn review_range_per_commit_attributes_unresolved_issues() {
+    let repo = TestRepo::new();
+    // root 直下の単体スクリプトは API 面外扱いになるため package 配下に置く
+    repo.create_dir_all("pkg");
+    repo.write("pkg/__init__.py", "");
+    repo.write("pkg/api.py", "def greet():\n    return 1\n");
+    repo.write(
+        "pkg/caller.py",
+        "from pkg.api import greet\n\n\ndef use_it():\n    return greet()\n",
+    );
+    repo.init_git();
+    repo.commit_all("init");
+    repo.git(["tag", "base"]);
+
+    repo.write("pkg/api.py", "def greet(name):\n    return 1\n");
+    repo.commit_all("change greet");
+    repo.write("pkg/helper.py", "def helper():\n    return 2\n");
+    repo.commit_all("add helper");
+    // 作業ツリーの未コミット変更は範囲 review に混ざらない
+    repo.write("pkg/api.py", "def greet(name, extra):\n    return 1\n");
+
+    let output = cargo_bin()
+        .args([
+            "review",
+            "--dir",
+            ".",
+            "--range",
+            "base..HEAD",
+            "--per-commit",
+        ])
+        .current_dir(repo.root())
+        .output()
+        .expect("failed to run astro-sight");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let records: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("each line is JSON"))
+        .collect();
+    assert_eq!(records.len(), 3, "2 commits + summary: {stdout}");
+
+    let change_sha = String::from_utf8(
+        Command::new("git")
+            .args(["rev-parse", "HEAD~1"])
+            .current_dir(repo.root())
+            .output()
+            .expect("git rev-parse")
+            .stdout,
+    )
+    .unwrap();
+    let change_sha = change_sha.trim();
+
+    assert_eq!(records[0]["sha"], change_sha);
+    assert_eq!(records[0]["subject"], "change greet");
+    let modified = records[0]["api_changes"]["modified"].as_array().unwrap();
+    assert!(
+        modified.iter().any(|m| m["name"] == "greet"),
+        "greet signature change belongs to the first commit: {stdout}"
+    );
+    assert_eq!(records[1]["subject"], "add helper");
+    assert!(
+        records[1]["api_changes"]["modified"]
+            .as_array()
+            .unwrap()
+            .is_empty()
+    );
+
+    let summary = &records[2]["summary"];
+    assert_eq!(summary["range"], "base..HEAD");
+    assert_eq!(summary["commits"], 2);
+    let greet = summary["unresolved"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|issue| {
+            issue["check"] == "astro-sight/api-modified"
+                && issue["description"].as_str().unwrap().contains("`greet`")
+        })
+        .unwrap_or_else(|| panic!("greet change is still unresolved at HEAD: {stdout}"));
+    assert_eq!(greet["introduced_in"], change_sha);
+    assert!(
+        greet["description"]
+            .as_str()
+            .unwrap()
+            .contains("def greet(name)"),
+        "summary reviews HEAD, not the working tree: {stdout}"
+    );
+
+    // 一時 worktree は後片付けされる
+    let worktrees = Command::new("git")
+        .args(["worktree", "list", "--porcelain"])
+        .current_dir(repo.root())
+        .output()
+        .expect("git worktree list");
+    let listed = String::from_utf8_lossy(&worktrees.stdout);
+    assert_eq!(listed.matches("worktree ").count(), 1, "{listed}");
+}
+
+#[test]
+fn review_range_rejects_invalid_range() {
+    let repo = TestRepo::new();
+    repo.write("a.py", "def greet():\n    return 1\n");
+    repo.init_git();
+    repo.commit_all("init");
+
+    let output = cargo_bin()
+        .args([
+            "review",
+            "--dir",
+            ".",
+            "--range",
+            "HEAD...HEAD",
+            "--per-commit",
+        ])
+        .current_dir(repo.root())
+        .output()
+        .expect("failed to run astro-sight");
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let value: serde_json::Value = serde_json::from_str(stdout.trim()).expect("error JSON");
+    assert_eq!(value["error"]["code"], "INVALID_REQUEST");
+}
+
+#[test]
+fn review_reports_explainable_risk_score() {
+    let repo = TestRepo::new();
+    repo.write("lib.py", "def greet():\n    return 1\n");
+    repo.write("app.py", "from lib import greet\n\nprint(greet())\n");
+    repo.init_git();
+    repo.commit_all("init");
+    repo.write(
+        "lib.py",
+        "def greet(name):\n    if name:\n        return 1\n    return 2\n",
+    );
+
+    let json = repo.run_json("review", &["--git"]);
+    let risk = &json["risk"];
+    let file = &risk["files"][0];
+    assert_eq!(file["path"], "lib.py", "{risk}");
+    let factors = file["factors"].as_array().expect("factors");
+    let factor = |name: &str| {
+        factors
+            .iter()
+            .find(|f| f["factor"] == name)
+            .unwrap_or_else(|| panic!("{name} missing: {risk}"))
+    };
+    assert_eq!(factor("api_breaks")["value"], 1.0);
+    assert_eq!(factor("complexity_delta")["value"], 1.0);
+    let total: f64 = factors
+        .iter()
+        .map(|f| f["contribution"].as_f64().unwrap())
+        .sum();
+    assert_eq!(file["score"].as_f64(), Some(total), "{risk}");
+    assert_eq!(risk["score"].as_f64(), Some(total), "{risk}");
+
+    // --hook は閾値指定時だけスコアを算出し、到達すれば `risk` を載せて止める。
+    let output = cargo_bin()
+        .args([
+            "review",
+            "--dir",
+            ".",
+            "--git",
+            "--hook",
+            "--risk-threshold",
+            "1",
+        ])
+        .current_dir(repo.root())
+        .output()
+        .expect("failed to run astro-sight");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let hook: serde_json::Value = serde_json::from_str(stderr.trim()).expect("hook JSON");
+    assert_eq!(hook["risk"]["th"], 1.0, "{hook}");
+    assert_eq!(hook["risk"]["top"][0]["f"], "lib.py", "{hook}");
+}
+
+#[test]
+fn review_risk_does_not_credit_tests_of_a_same_named_symbol() {
+    let repo = TestRepo::new();
+    repo.write("lib.py", "def greet():\n    return 1\n");
+    repo.write("other.py", "def greet():\n    return 2\n");
+    repo.write(
+        "test_other.py",
+        "from other import greet\n\n\ndef test_greet():\n    assert greet() == 2\n",
+    );
+    repo.init_git();
+    repo.commit_all("init");
+    repo.write("lib.py", "def greet():\n    return 3\n");
+
+    let json = repo.run_json("review", &["--git"]);
+    let risk = &json["risk"];
+    let file = risk["files"]
+        .as_array()
+        .expect("files")
+        .iter()
+        .find(|f| f["path"] == "lib.py")
+        .unwrap_or_else(|| panic!("lib.py missing: {risk}"));
+    let untested = file["factors"]
+        .as_array()
+        .expect("factors")
+        .iter()
+        .find(|f| f["factor"] == "untested_symbols")
+        .unwrap_or_else(|| panic!("untested_symbols missing: {risk}"));
+    assert_eq!(untested["value"], 1.0, "{risk}");
+}
+
+#[test]
+fn review_risk_skips_complexity_without_git_base() {
+    let repo = TestRepo::new();
+    repo.write("lib.py", "def greet():\n    return 1\n");
+    repo.init_git();
+    repo.commit_all("init");
+    repo.write(
+        "lib.py",
+        "def greet(name):\n    if name:\n        return 1\n    return 2\n",
+    );
+    let diff = std::process::Command::new("git")
+        .args(["diff", "HEAD"])
+        .current_dir(repo.root())
+        .output()
+        .expect("git diff");
+    let diff_path = repo.root().join("changes.patch");
+    std::fs::write(&diff_path, &diff.stdout).unwrap();
+
+    let json = repo.run_json("review", &["--diff-file", diff_path.to_str().unwrap()]);
+    let risk = &json["risk"];
+    assert!(
+        risk["files"]
+            .as_array()
+            .expect("files")
+            .iter()
+            .flat_map(|f| f["factors"].as_array().unwrap())
+            .all(|f| f["factor"] != "complexity_delta"),
+        "{risk}"
+    );
+}
+
+#[test]
+fn review_annotates_callers_with_codeowners() {
+    let repo = TestRepo::new();
+    repo.create_dir_all(".github");
+    repo.create_dir_all("app");
+    repo.write(".github/CODEOWNERS", "* @org/core\n/app/ @org/app\n");
+    repo.write("lib.py", "def greet():\n    return 1\n");
+    repo.write("app/main.py", "from lib import greet\n\nprint(greet())\n");
+    repo.init_git();
+    repo.commit_all("init");
+    repo.write("lib.py", "def greet(name):\n    return name\n");
+
+    let json = repo.run_json("review", &["--git"]);
+    let callers = json["impact"]["changes"][0]["impacted_callers"]
+        .as_array()
+        .expect("impacted_callers");
+    let caller = callers
+        .iter()
+        .find(|c| c["path"] == "app/main.py")
+        .unwrap_or_else(|| panic!("app/main.py caller missing: {json}"));
+    assert_eq!(caller["owners"], serde_json::json!(["@org/app"]));
+    assert_eq!(
+        json["owners_to_notify"],
+        serde_json::json!([{ "owner": "@org/app", "files": ["app/main.py"] }]),
+        "{json}"
+    );
+}
+
+#[test]
+fn review_reports_parameters_added_but_unused() {
+    let repo = TestRepo::new();
+    repo.write(
+        "lib.py",
+        "def scale(value, legacy):\n    return value * 2\n\n\ndef total(items):\n    return sum(items)\n",
+    );
+    repo.init_git();
+    repo.commit_all("init");
+    // 既存の未使用引数 `legacy` は diff の責任ではないため報告しない。
+    repo.write(
+        "lib.py",
+        "def scale(value, legacy):\n    return value * 2\n\n\ndef total(items,\n          currency):\n    return sum(items)\n",
+    );
+
+    let json = repo.run_json("review", &["--git"]);
+    assert_eq!(
+        json["unused_parameters"],
+        serde_json::json!([{
+            "name": "currency",
+            "function": "total",
+            "file": "lib.py",
+            "line": 5,
+            "column": 10,
+        }]),
+        "{json}"
+    );
+}
+
+#[test]
+fn review_reports_cargo_feature_mismatches_for_touched_crates() {
+    let repo = TestRepo::new();
+    repo.write(
+        "Cargo.toml",
+        "[package]\nname = \"demo\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[features]\nstd = []\nstale = []\n",
+    );
+    repo.write("src/lib.rs", "#[cfg(feature = \"std\")]\npub fn a() {}\n");
+    // diff が触れない crate の食い違いは報告しない。
+    repo.write(
+        "other/Cargo.toml",
+        "[package]\nname = \"other\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[features]\nunused = []\n",
+    );
+    repo.write("other/src/lib.rs", "pub fn c() {}\n");
+    repo.init_git();
+    repo.commit_all("init");
+    repo.write(
+        "src/lib.rs",
+        "#[cfg(feature = \"std\")]\npub fn a() {}\n\n#[cfg(feature = \"sdt\")]\npub fn b() {}\n",
+    );
+
+    let json = repo.run_json("review", &["--git"]);
+    assert_eq!(
+        json["cargo_features"],
+        serde_json::json!([
+            {
+                "kind": "unused",
+                "feature": "stale",
+                "file": "Cargo.toml",
+                "line": 7,
+                "manifest": "Cargo.toml",
+            },
+            {
+                "kind": "undeclared",
+                "feature": "sdt",
+                "file": "src/lib.rs",
+                "line": 3,
+                "manifest": "Cargo.toml",
+            },
+        ]),
+        "{json}"
+    );
+}
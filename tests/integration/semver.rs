@@ -0,0 +1,85 @@
+//! semver サブコマンドの統合テスト。
+
+use super::support::{TestRepo, cargo_bin};
+
+fn cargo_toml(version: &str) -> String {
+    format!("[package]\nname = \"demo\"\nversion = \"{version}\"\nedition = \"2021\"\n")
+}
+
+fn release_repo() -> TestRepo {
+    let repo = TestRepo::new();
+    repo.write("Cargo.toml", cargo_toml("1.2.0"));
+    repo.create_dir_all("src");
+    repo.write("src/lib.rs", "pub fn run(a: u8) -> u8 {\n    a\n}\n");
+    repo.init_git();
+    repo.commit_all("release");
+    repo.git(["tag", "v1.2.0"]);
+    repo
+}
+
+#[test]
+fn semver_fails_when_breaking_change_ships_in_minor_bump() {
+    let repo = release_repo();
+    repo.write("Cargo.toml", cargo_toml("1.3.0"));
+    repo.write("src/lib.rs", "pub fn run(a: u16) -> u16 {\n    a\n}\n");
+
+    let output = cargo_bin()
+        .args(["semver", "--base", "v1.2.0", "--dir"])
+        .arg(repo.root())
+        .output()
+        .expect("failed to run astro-sight");
+    assert!(!output.status.success());
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("astro-sight returned invalid JSON");
+    assert_eq!(json["required"], "major", "{json}");
+    assert_eq!(json["reasons"][0]["change"], "modified");
+    assert_eq!(json["reasons"][0]["name"], "run");
+    assert_eq!(json["manifests"][0]["path"], "Cargo.toml");
+    assert_eq!(json["manifests"][0]["actual"], "minor");
+    assert_eq!(json["manifests"][0]["sufficient"], false);
+    assert_eq!(json["ok"], false);
+}
+
+#[test]
+fn semver_fails_when_manifest_version_cannot_be_read() {
+    let repo = release_repo();
+    repo.write(
+        "Cargo.toml",
+        "[package]\nname = \"demo\"\nversion.workspace = true\nedition = \"2021\"\n",
+    );
+    repo.write(
+        "src/lib.rs",
+        "pub fn run(a: u8) -> u8 {\n    a\n}\n\npub fn stop() {}\n",
+    );
+
+    let output = cargo_bin()
+        .args(["semver", "--base", "v1.2.0", "--dir"])
+        .arg(repo.root())
+        .output()
+        .expect("failed to run astro-sight");
+    assert!(!output.status.success());
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("astro-sight returned invalid JSON");
+    assert_eq!(json["required"], "minor", "{json}");
+    assert!(json["manifests"][0].get("actual").is_none(), "{json}");
+    assert_eq!(json["manifests"][0]["sufficient"], false);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Cargo.toml (unknown)"));
+}
+
+#[test]
+fn semver_accepts_minor_bump_for_additions() {
+    let repo = release_repo();
+    repo.write("Cargo.toml", cargo_toml("1.3.0"));
+    repo.write(
+        "src/lib.rs",
+        "pub fn run(a: u8) -> u8 {\n    a\n}\n\npub fn stop() {}\n",
+    );
+
+    let json = repo.run_json("semver", &["--base", "v1.2.0"]);
+    assert_eq!(json["required"], "minor", "{json}");
+    assert_eq!(json["reasons"][0]["change"], "added");
+    assert_eq!(json["reasons"][0]["name"], "stop");
+    assert_eq!(json["manifests"][0]["old_version"], "1.2.0");
+    assert_eq!(json["manifests"][0]["new_version"], "1.3.0");
+    assert_eq!(json["ok"], true);
+}
@@ -0,0 +1,120 @@
+//! source サブコマンドの統合テスト。
+
+use super::support::{TestRepo, cargo_bin};
+
+const SHAPES_PY: &str = "\
+class Circle:
+    # Area of the circle.
+    def area(self):
+        return 3.14
+
+class Square:
+    @property
+    def area(self):
+        return 1.0
+";
+
+#[test]
+fn source_path_returns_definition_with_leading_docs() {
+    let repo = TestRepo::new();
+    repo.write(
+        "lib.rs",
+        "use std::fmt;\n\n/// Greets someone.\n#[must_use]\npub fn greet(name: &str) -> String {\n    format!(\"hi {name}\")\n}\n",
+    );
+
+    let output = cargo_bin()
+        .args(["source", "--name", "greet", "--path"])
+        .arg(repo.root().join("lib.rs"))
+        .output()
+        .expect("failed to run astro-sight");
+    assert!(output.status.success());
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("astro-sight returned invalid JSON");
+
+    let matches = json["matches"].as_array().expect("matches");
+    assert_eq!(matches.len(), 1, "{json}");
+    assert_eq!(
+        matches[0]["source"],
+        "/// Greets someone.\n#[must_use]\npub fn greet(name: &str) -> String {\n    format!(\"hi {name}\")\n}"
+    );
+    assert_eq!(matches[0]["range"]["start"]["line"], 2);
+    assert!(json.get("truncations").is_none());
+}
+
+#[test]
+fn source_dir_searches_workspace_and_filters_by_container() {
+    let repo = TestRepo::new();
+    repo.create_dir_all("pkg");
+    repo.write("pkg/shapes.py", SHAPES_PY);
+    repo.write("pkg/other.py", "def unrelated():\n    pass\n");
+
+    let json = repo.run_json("source", &["--name", "area"]);
+    assert_eq!(json["matches"].as_array().expect("matches").len(), 2);
+
+    let json = repo.run_json("source", &["--name", "area", "--container", "Square"]);
+    let matches = json["matches"].as_array().expect("matches");
+    assert_eq!(matches.len(), 1, "{json}");
+    assert_eq!(matches[0]["path"], "pkg/shapes.py");
+    assert_eq!(matches[0]["container"], "Square");
+    assert!(
+        matches[0]["source"]
+            .as_str()
+            .unwrap()
+            .starts_with("@property")
+    );
+}
+
+#[test]
+fn source_reports_truncation_over_max_bytes() {
+    let repo = TestRepo::new();
+    repo.write("shapes.py", SHAPES_PY);
+
+    let json = repo.run_json(
+        "source",
+        &[
+            "--name",
+            "area",
+            "--container",
+            "Circle",
+            "--max-bytes",
+            "30",
+        ],
+    );
+    let matches = json["matches"].as_array().expect("matches");
+    assert_eq!(matches[0]["truncated"], true, "{json}");
+    assert_eq!(matches[0]["source"], "# Area of the circle.\n");
+    let truncations = json["truncations"].as_array().expect("truncations");
+    assert_eq!(truncations[0]["reason"], "source_too_large");
+}
+
+#[test]
+fn source_session_command() {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let repo = TestRepo::new();
+    repo.write("shapes.py", SHAPES_PY);
+
+    let mut child = cargo_bin()
+        .arg("session")
+        .env("ASTRO_SIGHT_WORKSPACE", repo.root())
+        .current_dir(repo.root())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("failed to run session");
+    {
+        let stdin = child.stdin.as_mut().expect("stdin should be available");
+        writeln!(
+            stdin,
+            r#"{{"command":"source","path":"shapes.py","name":"area","container":"Circle"}}"#
+        )
+        .unwrap();
+    }
+    let output = child.wait_with_output().expect("failed to wait session");
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("session output should be JSON");
+    let matches = json["matches"].as_array().expect("matches");
+    assert_eq!(matches.len(), 1, "{json}");
+    assert!(matches[0]["source"].as_str().unwrap().contains("3.14"));
+}
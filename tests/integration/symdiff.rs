@@ -0,0 +1,133 @@
+//! symdiff サブコマンドの統合テスト。
+
+use super::support::TestRepo;
+
+#[test]
+fn symdiff_classifies_changes_between_tags() {
+    let repo = TestRepo::new();
+    repo.write(
+        "src/lib.rs",
+        "\
+pub fn stable() -> i32 {
+    1
+}
+
+fn tweak(a: i32) -> i32 {
+    a
+}
+
+fn compute_total(items: &[u32]) -> u32 {
+    items.iter().sum()
+}
+
+fn obsolete() {}
+
+pub fn relocate(x: u32) -> u32 {
+    x + 1
+}
+",
+    );
+    repo.init_git();
+    repo.commit_all("v1");
+    repo.git(["tag", "v1"]);
+
+    repo.write(
+        "src/lib.rs",
+        "\
+pub fn stable() -> i32 {
+    2
+}
+
+fn tweak(a: i64) -> i64 {
+    a
+}
+
+fn sum_items(items: &[u32]) -> u32 {
+    items.iter().sum()
+}
+
+fn fresh() -> bool {
+    true
+}
+",
+    );
+    repo.write(
+        "src/util.rs",
+        "\
+pub fn relocate(x: u32) -> u32 {
+    x + 1
+}
+",
+    );
+    repo.commit_all("v2");
+
+    let json = repo.run_json("symdiff", &["--from", "v1"]);
+
+    assert_eq!(json["from"], "v1");
+    assert_eq!(json["to"], "HEAD");
+    assert_eq!(json["files_changed"], 2);
+    assert_eq!(json["body_changed"][0]["name"], "stable", "{json}");
+    assert_eq!(json["signature_changed"][0]["name"], "tweak", "{json}");
+    assert_eq!(json["renamed"][0]["old_name"], "compute_total", "{json}");
+    assert_eq!(json["renamed"][0]["new_name"], "sum_items", "{json}");
+    assert_eq!(json["moved"][0]["from"], "src/lib.rs", "{json}");
+    assert_eq!(json["moved"][0]["to"], "src/util.rs", "{json}");
+    assert_eq!(json["added"][0]["name"], "fresh", "{json}");
+    assert_eq!(json["removed"][0]["name"], "obsolete", "{json}");
+}
+
+#[test]
+fn symdiff_skips_outside_git_repository() {
+    let repo = TestRepo::new();
+    repo.write("lib.rs", "fn a() {}\n");
+
+    let json = repo.run_json("symdiff", &["--from", "HEAD~1"]);
+
+    assert!(json["skipped"].is_object(), "{json}");
+    assert_eq!(json["files_changed"], 0);
+}
+
+#[test]
+fn symdiff_rejects_option_like_revision() {
+    let repo = TestRepo::new();
+    repo.init_git();
+
+    let output = super::support::cargo_bin()
+        .args(["symdiff", "--dir"])
+        .arg(repo.root())
+        .args(["--from=--output=/tmp/x"])
+        .output()
+        .expect("failed to run astro-sight");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn symdiff_reports_symbols_of_renamed_file_as_moved() {
+    let repo = TestRepo::new();
+    repo.write(
+        "src/old_name.rs",
+        "pub fn alpha() -> u32 {\n    1\n}\n\npub fn beta() -> u32 {\n    2\n}\n",
+    );
+    repo.init_git();
+    repo.commit_all("v1");
+    repo.git(["tag", "v1"]);
+    repo.git(["mv", "src/old_name.rs", "src/new_name.rs"]);
+    repo.commit_all("rename");
+
+    let json = repo.run_json("symdiff", &["--from", "v1"]);
+
+    assert_eq!(json["files_changed"], 1, "{json}");
+    assert_eq!(
+        json["moved"],
+        serde_json::json!([
+            { "name": "alpha", "kind": "function", "from": "src/old_name.rs", "to": "src/new_name.rs" },
+            { "name": "beta", "kind": "function", "from": "src/old_name.rs", "to": "src/new_name.rs" },
+        ]),
+        "{json}"
+    );
+    assert!(json["added"].as_array().is_none_or(Vec::is_empty), "{json}");
+    assert!(
+        json["removed"].as_array().is_none_or(Vec::is_empty),
+        "{json}"
+    );
+}
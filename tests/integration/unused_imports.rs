@@ -0,0 +1,92 @@
+//! unused-imports サブコマンドの統合テスト。
+
+use super::support::{TestRepo, cargo_bin};
+
+const APP_TS: &str = "\
+import { readFile, writeFile } from \"fs\";
+import * as path from \"path\";
+import \"./polyfill\";
+
+export function load(name: string) {
+  return readFile(name);
+}
+";
+
+#[test]
+fn unused_imports_reports_names_with_spans() {
+    let repo = TestRepo::new();
+    repo.create_dir_all("src");
+    repo.write("src/app.ts", APP_TS);
+    repo.write("src/lib.rs", "use std::fmt::Display;\n\npub fn run() {}\n");
+
+    let json = repo.run_json("unused-imports", &[]);
+    assert_eq!(json["scanned_files"], 2, "{json}");
+    let unused = json["unused_imports"].as_array().unwrap();
+    let names: Vec<(&str, &str, bool)> = unused
+        .iter()
+        .map(|u| {
+            (
+                u["file"].as_str().unwrap(),
+                u["name"].as_str().unwrap(),
+                u["whole_statement"].as_bool().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        names,
+        [
+            ("src/app.ts", "writeFile", false),
+            ("src/app.ts", "path", true),
+            ("src/lib.rs", "Display", true),
+        ]
+    );
+    // span は import 指定子 `writeFile` そのもの。
+    assert_eq!(unused[0]["span"]["start"]["line"], 0);
+    assert_eq!(unused[0]["span"]["start"]["column"], 19);
+    assert_eq!(unused[0]["span"]["end"]["column"], 28);
+
+    // --path は 1 ファイルだけを見る。
+    let json = repo.run_json(
+        "unused-imports",
+        &["--path", repo.path("src/lib.rs").to_str().unwrap()],
+    );
+    assert_eq!(json["scanned_files"], 1, "{json}");
+    assert_eq!(json["unused_imports"][0]["name"], "Display");
+    assert_eq!(json["unused_imports"][0]["file"], "src/lib.rs");
+
+    // --dir の外のファイルは相対パスにできないためエラーにする。
+    let other = TestRepo::new();
+    let output = cargo_bin()
+        .args(["unused-imports", "--dir"])
+        .arg(other.root())
+        .arg("--path")
+        .arg(repo.path("src/lib.rs"))
+        .output()
+        .expect("failed to run astro-sight");
+    assert!(!output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("invalid JSON");
+    assert_eq!(json["error"]["code"], "INVALID_REQUEST");
+    assert!(
+        json["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("--path must be inside --dir"),
+        "{json}"
+    );
+}
+
+#[test]
+fn unused_imports_git_checks_only_changed_files() {
+    let repo = TestRepo::new();
+    repo.write("old.py", "import os\n");
+    repo.write("new.py", "import sys\n");
+    repo.init_git();
+    repo.commit_all("initial");
+
+    repo.write("new.py", "import sys\nimport json\n\nprint(sys.argv)\n");
+    let json = repo.run_json("unused-imports", &["--git"]);
+    let unused = json["unused_imports"].as_array().unwrap();
+    assert_eq!(unused.len(), 1, "{json}");
+    assert_eq!(unused[0]["file"], "new.py");
+    assert_eq!(unused[0]["name"], "json");
+}